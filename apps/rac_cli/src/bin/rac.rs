@@ -0,0 +1,9 @@
+use rac_cli::rac_lite::{parse_rac_args, run};
+
+fn main() {
+    let cli = parse_rac_args();
+    if let Err(e) = run(cli) {
+        eprintln!("Error: {e}");
+        std::process::exit(1);
+    }
+}
@@ -1,9 +1,8 @@
-use clap::Parser;
-use rac_cli::rac_lite::{run, Cli};
+use rac_cli::rac_lite::{parse_args, run};
 use rac_protocol::error::RacError;
 
 fn main() {
-    let cli = Cli::parse();
+    let cli = parse_args();
     if let Err(e) = run(cli) {
         eprintln!("Error: {e}");
         if let RacError::Io(err) = &e {
@@ -1,17 +1,107 @@
-use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use rac_protocol::client::ProtocolPreference;
+use rac_protocol::tabular::Format;
+use serde::Deserialize;
+
+use crate::rac_lite::console_output::Output;
+use crate::rac_lite::parse::{parse_interval, parse_method};
 
 #[derive(Parser, Debug)]
 #[command(name = "rac_lite", version, about = "Minimal RAC client")]
 pub struct Cli {
-    #[arg(long)]
+    /// Same as `--format json`.
+    #[arg(long, conflicts_with = "format")]
     pub json: bool,
+    /// Output format; human-readable text when omitted.
+    #[arg(long, value_enum)]
+    pub format: Option<OutputFormat>,
     #[arg(long)]
     pub debug_raw: bool,
+    /// Refuse commands that change cluster state.
+    #[arg(long)]
+    pub read_only: bool,
+    /// Named profile from the config file; supplies the address, cluster
+    /// and credentials that are not given on the command line.
+    #[arg(long)]
+    pub profile: Option<String>,
+    /// Config file with profiles [default: ~/.config/rac_lite/config.toml].
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+    /// Protocol version; `auto` tries 16.0, then 11.0.
+    #[arg(long, value_enum)]
+    pub protocol: Option<ProtocolArg>,
+    #[arg(long)]
+    pub connect_timeout_ms: Option<u64>,
+    #[arg(long)]
+    pub read_timeout_ms: Option<u64>,
+    #[arg(long)]
+    pub write_timeout_ms: Option<u64>,
+    /// Re-run a list command every interval (`2`, `2s`, `500ms`) over one
+    /// connection and highlight what changed.
+    #[arg(long, value_parser = parse_interval, conflicts_with_all = ["json", "format"])]
+    pub watch: Option<Duration>,
+    /// Print the RPCs a command that changes the cluster would send, as an
+    /// annotated hex dump, without connecting.
+    #[arg(long, conflicts_with = "watch")]
+    pub dry_run: bool,
+    /// Do not ask before ending sessions or removing objects; the policy
+    /// still applies.
+    #[arg(long, short = 'y')]
+    pub yes: bool,
+    /// Actions to refuse outright [default: policy.toml next to the config
+    /// file].
+    #[arg(long)]
+    pub policy: Option<PathBuf>,
     #[command(subcommand)]
     pub command: TopCommand,
 }
 
-#[derive(Subcommand, Debug)]
+impl Cli {
+    pub fn output(&self) -> Output {
+        match self.format {
+            Some(OutputFormat::Json) => Output::Data(Format::Json),
+            Some(OutputFormat::Csv) => Output::Data(Format::Csv),
+            Some(OutputFormat::Ndjson) => Output::Data(Format::Ndjson),
+            Some(OutputFormat::Table) => Output::Data(Format::Table),
+            Some(OutputFormat::Rac) => Output::Rac,
+            None if self.json => Output::Data(Format::Json),
+            None => Output::Text,
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum OutputFormat {
+    Json,
+    Csv,
+    Ndjson,
+    Table,
+    /// Same layout as the vendor `rac` utility.
+    Rac,
+}
+
+#[derive(ValueEnum, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ProtocolArg {
+    Auto,
+    V11,
+    V16,
+}
+
+impl From<ProtocolArg> for ProtocolPreference {
+    fn from(protocol: ProtocolArg) -> Self {
+        match protocol {
+            ProtocolArg::Auto => ProtocolPreference::Auto,
+            ProtocolArg::V11 => ProtocolPreference::V11_0,
+            ProtocolArg::V16 => ProtocolPreference::V16_0,
+        }
+    }
+}
+
+#[derive(Subcommand, Debug, Clone)]
 pub enum TopCommand {
     Agent {
         #[command(subcommand)]
@@ -69,9 +159,187 @@ pub enum TopCommand {
         #[command(subcommand)]
         command: ServiceSettingCmd,
     },
+    /// Exports a cluster's setup as TOML, or plans and applies a document.
+    Config {
+        #[command(subcommand)]
+        command: ConfigCmd,
+    },
+    /// Sends an RPC with any method ID and body and dumps the reply.
+    Raw(RawArgs),
+    /// Interactive session over one connection; commands omit the address.
+    Shell {
+        addr: String,
+        #[command(flatten)]
+        context: ContextArgs,
+    },
+    /// Runs the commands of a script over one connection.
+    Exec {
+        /// Commands in CLI syntax or JSON lines; `-` reads stdin.
+        script: PathBuf,
+        addr: String,
+        #[command(flatten)]
+        context: ContextArgs,
+        #[arg(long, value_enum, default_value = "stop")]
+        on_error: OnError,
+    },
+}
+
+impl TopCommand {
+    pub fn name(&self) -> &'static str {
+        match self {
+            TopCommand::Agent { .. } => "agent",
+            TopCommand::Cluster { .. } => "cluster",
+            TopCommand::Manager { .. } => "manager",
+            TopCommand::Server { .. } => "server",
+            TopCommand::Process { .. } => "process",
+            TopCommand::Infobase { .. } => "infobase",
+            TopCommand::Connection { .. } => "connection",
+            TopCommand::Session { .. } => "session",
+            TopCommand::Lock { .. } => "lock",
+            TopCommand::Profile { .. } => "profile",
+            TopCommand::Counter { .. } => "counter",
+            TopCommand::Limit { .. } => "limit",
+            TopCommand::Rule { .. } => "rule",
+            TopCommand::ServiceSetting { .. } => "service-setting",
+            TopCommand::Config { .. } => "config",
+            TopCommand::Raw(_) => "raw",
+            TopCommand::Shell { .. } => "shell",
+            TopCommand::Exec { .. } => "exec",
+        }
+    }
+
+    /// Commands that print a list of records, the ones `--watch` repeats.
+    pub fn is_list(&self) -> bool {
+        matches!(
+            self,
+            TopCommand::Agent {
+                command: AgentCmd::Admin {
+                    command: AgentAdminCmd::List { .. }
+                }
+            } | TopCommand::Cluster {
+                command: ClusterCmd::List { .. }
+                    | ClusterCmd::Admin {
+                        command: ClusterAdminCmd::List { .. }
+                    }
+            } | TopCommand::Manager {
+                command: ManagerCmd::List { .. }
+            } | TopCommand::Server {
+                command: ServerCmd::List { .. }
+            } | TopCommand::Process {
+                command: ProcessCmd::List { .. }
+            } | TopCommand::Infobase {
+                command: InfobaseCmd::SummaryList { .. }
+            } | TopCommand::Connection {
+                command: ConnectionCmd::List { .. }
+            } | TopCommand::Session {
+                command: SessionCmd::List { .. }
+            } | TopCommand::Lock {
+                command: LockCmd::List { .. }
+            } | TopCommand::Profile {
+                command: ProfileCmd::List { .. }
+            } | TopCommand::Counter {
+                command: CounterCmd::List { .. }
+                    | CounterCmd::Values { .. }
+                    | CounterCmd::AccumulatedValues { .. }
+            } | TopCommand::Limit {
+                command: LimitCmd::List { .. }
+            } | TopCommand::Rule {
+                command: RuleCmd::List { .. }
+            } | TopCommand::ServiceSetting {
+                command: ServiceSettingCmd::List { .. }
+            }
+        )
+    }
+
+    /// Commands that only read the cluster; `--dry-run` has nothing to show
+    /// for them.
+    pub fn is_read(&self) -> bool {
+        self.is_list()
+            || matches!(
+                self,
+                TopCommand::Agent {
+                    command: AgentCmd::Version { .. }
+                } | TopCommand::Cluster {
+                    command: ClusterCmd::Info { .. }
+                } | TopCommand::Manager {
+                    command: ManagerCmd::Info { .. }
+                } | TopCommand::Server {
+                    command: ServerCmd::Info { .. }
+                } | TopCommand::Process {
+                    command: ProcessCmd::Info { .. }
+                } | TopCommand::Infobase {
+                    command: InfobaseCmd::SummaryInfo { .. } | InfobaseCmd::Info { .. }
+                } | TopCommand::Connection {
+                    command: ConnectionCmd::Info { .. }
+                } | TopCommand::Session {
+                    command: SessionCmd::Info { .. }
+                } | TopCommand::Counter {
+                    command: CounterCmd::Info { .. }
+                } | TopCommand::Limit {
+                    command: LimitCmd::Info { .. }
+                } | TopCommand::Rule {
+                    command: RuleCmd::Info { .. }
+                } | TopCommand::ServiceSetting {
+                    command: ServiceSettingCmd::Info { .. }
+                        | ServiceSettingCmd::GetServiceDataDirsForTransfer { .. }
+                } | TopCommand::Config {
+                    command: ConfigCmd::Export { .. } | ConfigCmd::Plan { .. }
+                }
+            )
+    }
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct RawArgs {
+    pub addr: String,
+    /// Method ID, `0x..` or decimal.
+    #[arg(long, value_parser = parse_method)]
+    pub method: u8,
+    /// Reply method to wait for; otherwise the first reply is shown.
+    #[arg(long, value_parser = parse_method)]
+    pub expect: Option<u8>,
+    /// Cluster whose context is set, after authenticating, before the call.
+    #[arg(long)]
+    pub cluster: Option<String>,
+    #[arg(long)]
+    pub cluster_user: Option<String>,
+    #[arg(long)]
+    pub cluster_pwd: Option<String>,
+    /// Authenticate to the agent first.
+    #[arg(long)]
+    pub agent_user: Option<String>,
+    #[arg(long)]
+    pub agent_pwd: Option<String>,
+    /// Body after the method byte: hex, or `@path` for a binary file.
+    #[arg(long, default_value = "")]
+    pub body: String,
+}
+
+/// Starting context of `shell` and `exec`.
+#[derive(Args, Debug, Default, Clone)]
+pub struct ContextArgs {
+    /// Initial cluster, same as `use cluster <name|uuid>`.
+    #[arg(long)]
+    pub cluster: Option<String>,
+    #[arg(long)]
+    pub cluster_user: Option<String>,
+    #[arg(long)]
+    pub cluster_pwd: Option<String>,
+    #[arg(long)]
+    pub agent_user: Option<String>,
+    #[arg(long)]
+    pub agent_pwd: Option<String>,
 }
 
-#[derive(Subcommand, Debug)]
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OnError {
+    /// Skip the remaining steps.
+    Stop,
+    /// Run the remaining steps and fail at the end.
+    Continue,
+}
+
+#[derive(Subcommand, Debug, Clone)]
 pub enum AgentCmd {
     Version { addr: String },
     Admin {
@@ -80,7 +348,7 @@ pub enum AgentCmd {
     },
 }
 
-#[derive(Subcommand, Debug)]
+#[derive(Subcommand, Debug, Clone)]
 pub enum AgentAdminCmd {
     List {
         addr: String,
@@ -117,7 +385,7 @@ pub enum AgentAdminCmd {
     },
 }
 
-#[derive(Subcommand, Debug)]
+#[derive(Subcommand, Debug, Clone)]
 pub enum ClusterCmd {
     List {
         addr: String,
@@ -126,6 +394,10 @@ pub enum ClusterCmd {
         addr: String,
         #[arg(long)]
         cluster: String,
+        #[arg(long)]
+        cluster_user: Option<String>,
+        #[arg(long)]
+        cluster_pwd: Option<String>,
     },
     Admin {
         #[command(subcommand)]
@@ -133,7 +405,7 @@ pub enum ClusterCmd {
     },
 }
 
-#[derive(Subcommand, Debug)]
+#[derive(Subcommand, Debug, Clone)]
 pub enum ClusterAdminCmd {
     List {
         addr: String,
@@ -163,45 +435,65 @@ pub enum ClusterAdminCmd {
     },
 }
 
-#[derive(Subcommand, Debug)]
+#[derive(Subcommand, Debug, Clone)]
 pub enum ManagerCmd {
     List {
         addr: String,
         #[arg(long)]
         cluster: String,
+        #[arg(long)]
+        cluster_user: Option<String>,
+        #[arg(long)]
+        cluster_pwd: Option<String>,
     },
     Info {
         addr: String,
         #[arg(long)]
         cluster: String,
         #[arg(long)]
+        cluster_user: Option<String>,
+        #[arg(long)]
+        cluster_pwd: Option<String>,
+        #[arg(long)]
         manager: String,
     },
 }
 
-#[derive(Subcommand, Debug)]
+#[derive(Subcommand, Debug, Clone)]
 pub enum ServerCmd {
     List {
         addr: String,
         #[arg(long)]
         cluster: String,
+        #[arg(long)]
+        cluster_user: Option<String>,
+        #[arg(long)]
+        cluster_pwd: Option<String>,
     },
     Info {
         addr: String,
         #[arg(long)]
         cluster: String,
         #[arg(long)]
+        cluster_user: Option<String>,
+        #[arg(long)]
+        cluster_pwd: Option<String>,
+        #[arg(long)]
         server: String,
     },
 }
 
-#[derive(Subcommand, Debug)]
+#[derive(Subcommand, Debug, Clone)]
 pub enum ProcessCmd {
     List {
         addr: String,
         #[arg(long)]
         cluster: String,
         #[arg(long)]
+        cluster_user: Option<String>,
+        #[arg(long)]
+        cluster_pwd: Option<String>,
+        #[arg(long)]
         licenses: bool,
     },
     Info {
@@ -209,24 +501,36 @@ pub enum ProcessCmd {
         #[arg(long)]
         cluster: String,
         #[arg(long)]
+        cluster_user: Option<String>,
+        #[arg(long)]
+        cluster_pwd: Option<String>,
+        #[arg(long)]
         process: String,
         #[arg(long)]
         licenses: bool,
     },
 }
 
-#[derive(Subcommand, Debug)]
+#[derive(Subcommand, Debug, Clone)]
 pub enum InfobaseCmd {
     SummaryList {
         addr: String,
         #[arg(long)]
         cluster: String,
+        #[arg(long)]
+        cluster_user: Option<String>,
+        #[arg(long)]
+        cluster_pwd: Option<String>,
     },
     SummaryInfo {
         addr: String,
         #[arg(long)]
         cluster: String,
         #[arg(long)]
+        cluster_user: Option<String>,
+        #[arg(long)]
+        cluster_pwd: Option<String>,
+        #[arg(long)]
         infobase: String,
     },
     Info {
@@ -234,38 +538,61 @@ pub enum InfobaseCmd {
         #[arg(long)]
         cluster: String,
         #[arg(long)]
+        cluster_user: Option<String>,
+        #[arg(long)]
+        cluster_pwd: Option<String>,
+        #[arg(long)]
         infobase: String,
     },
 }
 
-#[derive(Subcommand, Debug)]
+#[derive(Subcommand, Debug, Clone)]
 pub enum ConnectionCmd {
     List {
         addr: String,
         #[arg(long)]
         cluster: String,
+        #[arg(long)]
+        cluster_user: Option<String>,
+        #[arg(long)]
+        cluster_pwd: Option<String>,
     },
     Info {
         addr: String,
         #[arg(long)]
         cluster: String,
         #[arg(long)]
+        cluster_user: Option<String>,
+        #[arg(long)]
+        cluster_pwd: Option<String>,
+        #[arg(long)]
         connection: String,
     },
 }
 
-#[derive(Subcommand, Debug)]
+#[derive(Subcommand, Debug, Clone)]
 pub enum SessionCmd {
     List {
         addr: String,
         #[arg(long)]
         cluster: String,
+        #[arg(long)]
+        cluster_user: Option<String>,
+        #[arg(long)]
+        cluster_pwd: Option<String>,
+        /// Print the licenses sessions hold instead of the sessions.
+        #[arg(long)]
+        licenses: bool,
     },
     Info {
         addr: String,
         #[arg(long)]
         cluster: String,
         #[arg(long)]
+        cluster_user: Option<String>,
+        #[arg(long)]
+        cluster_pwd: Option<String>,
+        #[arg(long)]
         session: String,
     },
     Terminate {
@@ -296,36 +623,53 @@ pub enum SessionCmd {
     },
 }
 
-#[derive(Subcommand, Debug)]
+#[derive(Subcommand, Debug, Clone)]
 pub enum LockCmd {
     List {
         addr: String,
         #[arg(long)]
         cluster: String,
+        #[arg(long)]
+        cluster_user: Option<String>,
+        #[arg(long)]
+        cluster_pwd: Option<String>,
     },
 }
 
-#[derive(Subcommand, Debug)]
+#[derive(Subcommand, Debug, Clone)]
 pub enum ProfileCmd {
     List {
         addr: String,
         #[arg(long)]
         cluster: String,
+        #[arg(long)]
+        cluster_user: Option<String>,
+        #[arg(long)]
+        cluster_pwd: Option<String>,
     },
 }
 
-#[derive(Subcommand, Debug)]
+#[derive(Subcommand, Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
 pub enum CounterCmd {
     List {
         addr: String,
         #[arg(long)]
         cluster: String,
+        #[arg(long)]
+        cluster_user: Option<String>,
+        #[arg(long)]
+        cluster_pwd: Option<String>,
     },
     Info {
         addr: String,
         #[arg(long)]
         cluster: String,
         #[arg(long)]
+        cluster_user: Option<String>,
+        #[arg(long)]
+        cluster_pwd: Option<String>,
+        #[arg(long)]
         counter: String,
     },
     Clear {
@@ -423,18 +767,26 @@ pub enum CounterCmd {
     },
 }
 
-#[derive(Subcommand, Debug)]
+#[derive(Subcommand, Debug, Clone)]
 pub enum LimitCmd {
     List {
         addr: String,
         #[arg(long)]
         cluster: String,
+        #[arg(long)]
+        cluster_user: Option<String>,
+        #[arg(long)]
+        cluster_pwd: Option<String>,
     },
     Info {
         addr: String,
         #[arg(long)]
         cluster: String,
         #[arg(long)]
+        cluster_user: Option<String>,
+        #[arg(long)]
+        cluster_pwd: Option<String>,
+        #[arg(long)]
         limit: String,
     },
     Update {
@@ -491,7 +843,7 @@ pub enum LimitCmd {
     },
 }
 
-#[derive(Subcommand, Debug)]
+#[derive(Subcommand, Debug, Clone)]
 pub enum RuleCmd {
     Apply {
         addr: String,
@@ -591,7 +943,60 @@ pub enum RuleCmd {
     },
 }
 
-#[derive(Subcommand, Debug)]
+#[derive(Subcommand, Debug, Clone)]
+pub enum ConfigCmd {
+    /// Prints admins, profiles, counters, limits, rules and service settings
+    /// as a TOML document; agent admins only with `--agent-user`.
+    Export {
+        addr: String,
+        #[arg(long)]
+        cluster: String,
+        #[arg(long)]
+        cluster_user: Option<String>,
+        #[arg(long)]
+        cluster_pwd: Option<String>,
+        #[arg(long)]
+        agent_user: Option<String>,
+        #[arg(long)]
+        agent_pwd: Option<String>,
+    },
+    /// Shows the steps that would bring the cluster to `file`.
+    Plan {
+        addr: String,
+        file: PathBuf,
+        #[arg(long)]
+        cluster: String,
+        #[arg(long)]
+        cluster_user: Option<String>,
+        #[arg(long)]
+        cluster_pwd: Option<String>,
+        #[arg(long)]
+        agent_user: Option<String>,
+        #[arg(long)]
+        agent_pwd: Option<String>,
+    },
+    /// Runs the steps of `plan`, then applies changed rules and service
+    /// settings.
+    Apply {
+        addr: String,
+        file: PathBuf,
+        #[arg(long)]
+        cluster: String,
+        #[arg(long)]
+        cluster_user: Option<String>,
+        #[arg(long)]
+        cluster_pwd: Option<String>,
+        #[arg(long)]
+        agent_user: Option<String>,
+        #[arg(long)]
+        agent_pwd: Option<String>,
+        /// Mode of the `rule apply` that follows rule changes.
+        #[arg(long, default_value = "full")]
+        rule_apply_mode: String,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
 pub enum ServiceSettingCmd {
     List {
         addr: String,
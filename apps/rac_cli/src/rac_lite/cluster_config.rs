@@ -0,0 +1,1146 @@
+//! Declarative cluster setup behind `config export`, `config plan` and
+//! `config apply`: a TOML document of admins, profiles, counters, limits,
+//! rules and service settings, and the steps that bring a cluster to it.
+//!
+//! A section left out of the document is not managed; an empty one removes
+//! everything of that kind.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use rac_protocol::client::RacClient;
+use rac_protocol::commands::agent::{
+    AgentAdminListRpc, AgentAdminRegisterRpc, AgentAdminRemoveRpc,
+};
+use rac_protocol::commands::cluster::{
+    ClusterAdminListRpc, ClusterAdminRegisterRpc, ClusterAdminRemoveRpc,
+};
+use rac_protocol::commands::profile::ProfileListRpc;
+use rac_protocol::commands::{
+    AgentAdminRecord, ClusterAdminRecord, CounterListRpc, CounterRecord, CounterRemoveRpc,
+    CounterUpdateRpc, LimitListRpc, LimitRecord, LimitRemoveRpc, LimitUpdateRpc, ProfileRecord,
+    ProfileUpdateRpc, RuleApplyRpc, RuleInsertRpc, RuleListRpc, RuleRecord, RuleRemoveRpc,
+    RuleUpdateRpc, ServerListRpc, ServerRecord, ServiceSettingApplyRpc, ServiceSettingInsertRpc,
+    ServiceSettingListRpc, ServiceSettingRecord, ServiceSettingRemoveRpc, ServiceSettingUpdateRpc,
+};
+use rac_protocol::error::{RacError, Result};
+use rac_protocol::policy;
+use rac_protocol::Uuid16;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::rac_lite::config::Secret;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClusterConfig {
+    /// Needs agent credentials; exported only when they are given.
+    pub agent_admins: Option<Vec<Admin>>,
+    pub cluster_admins: Option<Vec<Admin>>,
+    pub profiles: Option<Vec<ProfileRecord>>,
+    pub counters: Option<Vec<CounterRecord>>,
+    pub limits: Option<Vec<LimitRecord>>,
+    /// Working servers by name; servers not listed keep their rules and
+    /// service settings.
+    pub servers: Option<Vec<ServerConfig>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Admin {
+    pub name: String,
+    pub descr: String,
+    pub auth_pwd: u8,
+    pub auth_os: u8,
+    pub os_user: String,
+    /// Only read from the document; registering an admin with password
+    /// authentication needs it.
+    #[serde(default, skip_serializing)]
+    pub pwd: Option<Secret>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ServerConfig {
+    pub name: String,
+    /// In order of position.
+    pub rules: Option<Vec<Rule>>,
+    pub service_settings: Option<Vec<ServiceSetting>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Rule {
+    pub object_type: u32,
+    pub infobase_name: String,
+    pub rule_type: u8,
+    pub application_ext: String,
+    pub priority: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ServiceSetting {
+    pub service_name: String,
+    pub infobase_name: String,
+    pub service_data_dir: String,
+    pub active: bool,
+}
+
+impl ClusterConfig {
+    pub fn parse(raw: &str) -> std::result::Result<Self, String> {
+        toml::from_str(raw).map_err(|err| err.to_string())
+    }
+
+    pub fn to_toml(&self) -> std::result::Result<String, String> {
+        toml::to_string(self).map_err(|err| err.to_string())
+    }
+}
+
+macro_rules! admin_from {
+    ($record:ty) => {
+        impl From<&$record> for Admin {
+            fn from(record: &$record) -> Self {
+                Admin {
+                    name: record.name.clone(),
+                    descr: record.descr.clone(),
+                    auth_pwd: record.auth_pwd,
+                    auth_os: record.auth_os,
+                    os_user: record.os_user.clone(),
+                    pwd: None,
+                }
+            }
+        }
+    };
+}
+
+admin_from!(AgentAdminRecord);
+admin_from!(ClusterAdminRecord);
+
+impl From<&RuleRecord> for Rule {
+    fn from(record: &RuleRecord) -> Self {
+        Rule {
+            object_type: record.object_type,
+            infobase_name: record.infobase_name.clone(),
+            rule_type: record.rule_type,
+            application_ext: record.application_ext.clone(),
+            priority: record.priority,
+        }
+    }
+}
+
+impl From<&ServiceSettingRecord> for ServiceSetting {
+    fn from(record: &ServiceSettingRecord) -> Self {
+        ServiceSetting {
+            service_name: record.service_name.clone(),
+            infobase_name: record.infobase_name.clone(),
+            service_data_dir: record.service_data_dir.clone(),
+            active: record.active,
+        }
+    }
+}
+
+/// Objects as the cluster reports them, with the UUIDs steps refer to.
+#[derive(Debug, Default)]
+pub struct Current {
+    pub agent_admins: Option<Vec<Admin>>,
+    pub cluster_admins: Vec<Admin>,
+    pub profiles: Vec<ProfileRecord>,
+    pub counters: Vec<CounterRecord>,
+    pub limits: Vec<LimitRecord>,
+    pub servers: Vec<CurrentServer>,
+}
+
+#[derive(Debug)]
+pub struct CurrentServer {
+    pub server: Uuid16,
+    pub name: String,
+    pub rules: Vec<RuleRecord>,
+    pub service_settings: Vec<ServiceSettingRecord>,
+}
+
+/// Reads everything a document can describe. The caller has authenticated to
+/// the cluster, and to the agent when `agent_admins` is set.
+pub fn fetch(client: &mut RacClient, cluster: Uuid16, agent_admins: bool) -> Result<Current> {
+    let agent_admins = if agent_admins {
+        let admins = client.call_typed(AgentAdminListRpc)?.admins;
+        Some(admins.iter().map(Admin::from).collect())
+    } else {
+        None
+    };
+    let cluster_admins = client.call_typed(ClusterAdminListRpc { cluster })?.admins;
+    let mut current = Current {
+        agent_admins,
+        cluster_admins: cluster_admins.iter().map(Admin::from).collect(),
+        profiles: client.call_typed(ProfileListRpc { cluster })?.profiles,
+        counters: client.call_typed(CounterListRpc { cluster })?.records,
+        limits: client.call_typed(LimitListRpc { cluster })?.limits,
+        servers: Vec::new(),
+    };
+    let servers: Vec<ServerRecord> = client.call_typed(ServerListRpc { cluster })?.servers;
+    for server in servers {
+        let rules = client
+            .call_typed(RuleListRpc {
+                cluster,
+                server: server.server,
+            })?
+            .records;
+        let service_settings = client
+            .call_typed(ServiceSettingListRpc {
+                cluster,
+                server: server.server,
+            })?
+            .records;
+        current.servers.push(CurrentServer {
+            server: server.server,
+            name: server.name,
+            rules,
+            service_settings,
+        });
+    }
+    Ok(current)
+}
+
+impl Current {
+    pub fn export(&self) -> ClusterConfig {
+        ClusterConfig {
+            agent_admins: self.agent_admins.clone(),
+            cluster_admins: Some(self.cluster_admins.clone()),
+            profiles: Some(self.profiles.clone()),
+            counters: Some(self.counters.clone()),
+            limits: Some(self.limits.clone()),
+            servers: Some(
+                self.servers
+                    .iter()
+                    .map(|server| ServerConfig {
+                        name: server.name.clone(),
+                        rules: Some(server.rules.iter().map(Rule::from).collect()),
+                        service_settings: Some(
+                            server
+                                .service_settings
+                                .iter()
+                                .map(ServiceSetting::from)
+                                .collect(),
+                        ),
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Op {
+    Insert,
+    Update,
+    Remove,
+}
+
+impl Op {
+    fn sign(self) -> char {
+        match self {
+            Op::Insert => '+',
+            Op::Update => '~',
+            Op::Remove => '-',
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Step {
+    pub op: Op,
+    pub kind: &'static str,
+    pub name: String,
+    /// `field: old -> new` for updates.
+    pub changes: Vec<String>,
+    #[serde(skip)]
+    target: Target,
+}
+
+#[derive(Debug)]
+enum Target {
+    AgentAdmin(Admin),
+    ClusterAdmin(Admin),
+    Profile(ProfileRecord),
+    Counter(CounterRecord),
+    Limit(LimitRecord),
+    Rule {
+        server: Uuid16,
+        rule: Uuid16,
+        position: u32,
+        entry: Rule,
+    },
+    ServiceSetting {
+        server: Uuid16,
+        setting: Uuid16,
+        entry: ServiceSetting,
+    },
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct Plan {
+    pub steps: Vec<Step>,
+    /// Differences the plan cannot resolve.
+    pub notes: Vec<String>,
+}
+
+impl Step {
+    pub fn summary(&self) -> String {
+        format!("{} {} {}", self.op.sign(), self.kind, self.name)
+    }
+
+    /// What a removal hits, for the guard; named as the command that removes
+    /// one such object, e.g. `limit.remove`.
+    pub fn removes(&self) -> Option<policy::Target> {
+        if self.op != Op::Remove {
+            return None;
+        }
+        let action = match self.kind {
+            "agent admin" => "agent.admin_remove".to_string(),
+            "cluster admin" => "cluster.admin_remove".to_string(),
+            kind => format!("{}.remove", kind.replace(' ', "_")),
+        };
+        let target = policy::Target::new(action).with("name", &self.name);
+        Some(match &self.target {
+            Target::Rule { entry, .. } => target
+                .with("infobase", &entry.infobase_name)
+                .with("application_ext", &entry.application_ext),
+            Target::ServiceSetting { entry, .. } => target
+                .with("service", &entry.service_name)
+                .with("infobase", &entry.infobase_name),
+            _ => target,
+        })
+    }
+}
+
+impl Plan {
+    pub fn count(&self, op: Op) -> usize {
+        self.steps.iter().filter(|step| step.op == op).count()
+    }
+}
+
+impl std::fmt::Display for Plan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for step in &self.steps {
+            writeln!(f, "{}", step.summary())?;
+            for change in &step.changes {
+                writeln!(f, "    {change}")?;
+            }
+        }
+        for note in &self.notes {
+            writeln!(f, "note: {note}")?;
+        }
+        if self.steps.is_empty() {
+            write!(f, "no changes")
+        } else {
+            write!(
+                f,
+                "{} to add, {} to change, {} to remove",
+                self.count(Op::Insert),
+                self.count(Op::Update),
+                self.count(Op::Remove)
+            )
+        }
+    }
+}
+
+/// Steps that turn `current` into `desired`: inserts and updates first, in
+/// dependency order (counters before the limits that use them), then removes
+/// in reverse.
+pub fn plan(current: &Current, desired: &ClusterConfig) -> Result<Plan> {
+    let mut plan = Plan::default();
+    let mut removes = Vec::new();
+
+    if let Some(admins) = &desired.agent_admins {
+        let Some(existing) = &current.agent_admins else {
+            return Err(RacError::InvalidArgs(
+                "agent admins were not read from the server".to_string(),
+            ));
+        };
+        check_admin_passwords("agent admin", admins, existing)?;
+        diff_named(
+            &mut plan.steps,
+            &mut removes,
+            "agent admin",
+            existing,
+            admins,
+            |admin| admin.name.clone(),
+            Target::AgentAdmin,
+        )?;
+    }
+    if let Some(admins) = &desired.cluster_admins {
+        check_admin_passwords("cluster admin", admins, &current.cluster_admins)?;
+        diff_named(
+            &mut plan.steps,
+            &mut removes,
+            "cluster admin",
+            &current.cluster_admins,
+            admins,
+            |admin| admin.name.clone(),
+            Target::ClusterAdmin,
+        )?;
+    }
+    if let Some(profiles) = &desired.profiles {
+        let mut profile_removes = Vec::new();
+        diff_named(
+            &mut plan.steps,
+            &mut profile_removes,
+            "profile",
+            &current.profiles,
+            profiles,
+            |profile| profile.name.clone(),
+            Target::Profile,
+        )?;
+        for step in profile_removes {
+            plan.notes.push(format!(
+                "profile {:?} is not in the document; remove it by hand",
+                step.name
+            ));
+        }
+    }
+    if let Some(counters) = &desired.counters {
+        diff_named(
+            &mut plan.steps,
+            &mut removes,
+            "counter",
+            &current.counters,
+            counters,
+            |counter| counter.name.clone(),
+            Target::Counter,
+        )?;
+    }
+    if let Some(limits) = &desired.limits {
+        diff_named(
+            &mut plan.steps,
+            &mut removes,
+            "limit",
+            &current.limits,
+            limits,
+            |limit| limit.name.clone(),
+            Target::Limit,
+        )?;
+    }
+    for server in desired.servers.iter().flatten() {
+        let Some(existing) = current.servers.iter().find(|s| s.name == server.name) else {
+            return Err(RacError::InvalidArgs(format!(
+                "server {:?} is not in the cluster",
+                server.name
+            )));
+        };
+        if let Some(rules) = &server.rules {
+            diff_rules(&mut plan.steps, &mut removes, existing, rules)?;
+        }
+        if let Some(settings) = &server.service_settings {
+            let current_settings: Vec<ServiceSetting> = existing
+                .service_settings
+                .iter()
+                .map(ServiceSetting::from)
+                .collect();
+            diff_named(
+                &mut plan.steps,
+                &mut removes,
+                "service setting",
+                &current_settings,
+                settings,
+                |setting| format!("{}/{}", server.name, setting_key(setting)),
+                |entry| Target::ServiceSetting {
+                    server: existing.server,
+                    setting: existing
+                        .service_settings
+                        .iter()
+                        .find(|record| {
+                            setting_key(&ServiceSetting::from(*record)) == setting_key(&entry)
+                        })
+                        .map_or([0; 16], |record| record.setting),
+                    entry,
+                },
+            )?;
+        }
+    }
+
+    removes.reverse();
+    plan.steps.extend(removes);
+    Ok(plan)
+}
+
+/// `service@infobase`, or the service alone for settings of all infobases.
+fn setting_key(setting: &ServiceSetting) -> String {
+    if setting.infobase_name.is_empty() {
+        setting.service_name.clone()
+    } else {
+        format!("{}@{}", setting.service_name, setting.infobase_name)
+    }
+}
+
+fn check_admin_passwords(kind: &str, desired: &[Admin], current: &[Admin]) -> Result<()> {
+    for admin in desired {
+        let changed = match current.iter().find(|c| c.name == admin.name) {
+            Some(existing) => !changes(existing, admin).is_empty(),
+            None => true,
+        };
+        if changed && admin.auth_pwd != 0 && admin.pwd.is_none() {
+            return Err(RacError::InvalidArgs(format!(
+                "{kind} {:?} uses password authentication: set `pwd` to register it",
+                admin.name
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn diff_named<T: Serialize + Clone>(
+    steps: &mut Vec<Step>,
+    removes: &mut Vec<Step>,
+    kind: &'static str,
+    current: &[T],
+    desired: &[T],
+    key: impl Fn(&T) -> String,
+    target: impl Fn(T) -> Target,
+) -> Result<()> {
+    let mut seen = BTreeSet::new();
+    let existing: BTreeMap<String, &T> = current.iter().map(|item| (key(item), item)).collect();
+    for item in desired {
+        let name = key(item);
+        if !seen.insert(name.clone()) {
+            return Err(RacError::InvalidArgs(format!("duplicate {kind} {name:?}")));
+        }
+        let (op, changes) = match existing.get(&name) {
+            None => (Op::Insert, Vec::new()),
+            Some(old) => {
+                let changes = changes(*old, item);
+                if changes.is_empty() {
+                    continue;
+                }
+                (Op::Update, changes)
+            }
+        };
+        steps.push(Step {
+            op,
+            kind,
+            name,
+            changes,
+            target: target(item.clone()),
+        });
+    }
+    for item in current {
+        let name = key(item);
+        if !seen.contains(&name) {
+            removes.push(Step {
+                op: Op::Remove,
+                kind,
+                name,
+                changes: Vec::new(),
+                target: target(item.clone()),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Rules are matched by what they select (object type, infobase, rule type
+/// and application) along the longest common subsequence of the two lists,
+/// so moving or inserting one rule does not touch the others. Positions are
+/// those at the time each step runs: after the inserts and updates before it,
+/// with the rules to remove still in place.
+fn diff_rules(
+    steps: &mut Vec<Step>,
+    removes: &mut Vec<Step>,
+    server: &CurrentServer,
+    desired: &[Rule],
+) -> Result<()> {
+    let current: Vec<Rule> = server.rules.iter().map(Rule::from).collect();
+    let matched = match_rules(&current, desired);
+    let mut previous = None;
+    for (index, entry) in desired.iter().enumerate() {
+        let name = format!("{}/#{index}", server.name);
+        let anchor = matched[index].or(previous);
+        // Rules to remove that are still ahead of this one.
+        let stale = current
+            .iter()
+            .enumerate()
+            .take(anchor.map_or(0, |at| at + 1))
+            .filter(|(at, _)| !matched.contains(&Some(*at)))
+            .count();
+        let position = u32::try_from(index + stale)
+            .map_err(|_| RacError::InvalidArgs("too many rules".to_string()))?;
+        match matched[index] {
+            Some(at) => {
+                previous = Some(at);
+                let changes = changes(&current[at], entry);
+                if !changes.is_empty() {
+                    steps.push(Step {
+                        op: Op::Update,
+                        kind: "rule",
+                        name,
+                        changes,
+                        target: Target::Rule {
+                            server: server.server,
+                            rule: server.rules[at].rule,
+                            position,
+                            entry: entry.clone(),
+                        },
+                    });
+                }
+            }
+            None => steps.push(Step {
+                op: Op::Insert,
+                kind: "rule",
+                name,
+                changes: Vec::new(),
+                target: Target::Rule {
+                    server: server.server,
+                    rule: [0; 16],
+                    position,
+                    entry: entry.clone(),
+                },
+            }),
+        }
+    }
+    for (index, record) in server.rules.iter().enumerate() {
+        if matched.contains(&Some(index)) {
+            continue;
+        }
+        removes.push(Step {
+            op: Op::Remove,
+            kind: "rule",
+            name: format!("{}/#{index}", server.name),
+            changes: Vec::new(),
+            target: Target::Rule {
+                server: server.server,
+                rule: record.rule,
+                position: u32::try_from(index).unwrap_or(u32::MAX),
+                entry: current[index].clone(),
+            },
+        });
+    }
+    Ok(())
+}
+
+/// For each desired rule, the index of the current rule it keeps, if any.
+fn match_rules(current: &[Rule], desired: &[Rule]) -> Vec<Option<usize>> {
+    let same = |a: &Rule, b: &Rule| {
+        (
+            a.object_type,
+            &a.infobase_name,
+            a.rule_type,
+            &a.application_ext,
+        ) == (
+            b.object_type,
+            &b.infobase_name,
+            b.rule_type,
+            &b.application_ext,
+        )
+    };
+    // lengths[i][j]: longest common subsequence of current[i..] and desired[j..].
+    let mut lengths = vec![vec![0usize; desired.len() + 1]; current.len() + 1];
+    for i in (0..current.len()).rev() {
+        for j in (0..desired.len()).rev() {
+            lengths[i][j] = if same(&current[i], &desired[j]) {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+    let mut matched = vec![None; desired.len()];
+    let (mut i, mut j) = (0, 0);
+    while i < current.len() && j < desired.len() {
+        if same(&current[i], &desired[j]) {
+            matched[j] = Some(i);
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    matched
+}
+
+/// Fields whose serialized values differ, as `field: old -> new`.
+fn changes<T: Serialize>(old: &T, new: &T) -> Vec<String> {
+    let (Ok(Value::Object(old)), Ok(Value::Object(new))) =
+        (serde_json::to_value(old), serde_json::to_value(new))
+    else {
+        return Vec::new();
+    };
+    new.iter()
+        .filter_map(|(field, value)| {
+            let before = old.get(field)?;
+            (before != value).then(|| format!("{field}: {before} -> {value}"))
+        })
+        .collect()
+}
+
+/// Runs the steps of `plan` in order, calling `done` after each, then applies
+/// the rules and service settings of the servers it changed.
+pub fn apply(
+    client: &mut RacClient,
+    cluster: Uuid16,
+    plan: &Plan,
+    rule_apply_mode: u32,
+    mut done: impl FnMut(&Step),
+) -> Result<()> {
+    let mut rules_changed = false;
+    let mut settings_changed = Vec::new();
+    for step in &plan.steps {
+        apply_step(client, cluster, step)?;
+        match &step.target {
+            Target::Rule { .. } => rules_changed = true,
+            Target::ServiceSetting { server, .. } if !settings_changed.contains(server) => {
+                settings_changed.push(*server);
+            }
+            _ => {}
+        }
+        done(step);
+    }
+    if rules_changed {
+        client.call_typed(RuleApplyRpc {
+            cluster,
+            mode: rule_apply_mode,
+        })?;
+    }
+    for server in settings_changed {
+        client.call_typed(ServiceSettingApplyRpc { cluster, server })?;
+    }
+    Ok(())
+}
+
+fn admin_pwd(admin: &Admin) -> Result<String> {
+    match &admin.pwd {
+        Some(secret) => secret.resolve().map_err(RacError::InvalidArgs),
+        None => Ok(String::new()),
+    }
+}
+
+fn apply_step(client: &mut RacClient, cluster: Uuid16, step: &Step) -> Result<()> {
+    let remove = step.op == Op::Remove;
+    match &step.target {
+        Target::AgentAdmin(admin) if remove => {
+            client.call_typed(AgentAdminRemoveRpc {
+                name: admin.name.clone(),
+            })?;
+        }
+        Target::AgentAdmin(admin) => {
+            client.call_typed(AgentAdminRegisterRpc {
+                name: admin.name.clone(),
+                descr: admin.descr.clone(),
+                pwd: admin_pwd(admin)?,
+                auth_pwd: admin.auth_pwd,
+                auth_os: admin.auth_os,
+                os_user: admin.os_user.clone(),
+            })?;
+        }
+        Target::ClusterAdmin(admin) if remove => {
+            client.call_typed(ClusterAdminRemoveRpc {
+                cluster,
+                name: admin.name.clone(),
+            })?;
+        }
+        Target::ClusterAdmin(admin) => {
+            client.call_typed(ClusterAdminRegisterRpc {
+                cluster,
+                name: admin.name.clone(),
+                descr: admin.descr.clone(),
+                pwd: admin_pwd(admin)?,
+                auth_pwd: admin.auth_pwd,
+                auth_os: admin.auth_os,
+                os_user: admin.os_user.clone(),
+            })?;
+        }
+        Target::Profile(profile) => {
+            client.call_typed(ProfileUpdateRpc {
+                cluster,
+                name: profile.name.clone(),
+                descr: profile.descr.clone(),
+                directory_access: profile.directory_access,
+                com_access: profile.com_access,
+                addin_access: profile.addin_access,
+                module_access: profile.module_access,
+                app_access: profile.app_access,
+                config: profile.config,
+                privileged_mode: profile.privileged_mode,
+                inet_access: profile.inet_access,
+                crypto: profile.crypto,
+                right_extension: profile.right_extension,
+                right_extension_definition_roles: profile.right_extension_definition_roles.clone(),
+                all_modules_extension: profile.all_modules_extension,
+                modules_available_for_extension: profile.modules_available_for_extension.clone(),
+                modules_not_available_for_extension: profile
+                    .modules_not_available_for_extension
+                    .clone(),
+                privileged_mode_roles: profile.privileged_mode_roles.clone(),
+            })?;
+        }
+        Target::Counter(counter) if remove => {
+            client.call_typed(CounterRemoveRpc {
+                cluster,
+                name: counter.name.clone(),
+            })?;
+        }
+        Target::Counter(counter) => {
+            client.call_typed(CounterUpdateRpc {
+                cluster,
+                name: counter.name.clone(),
+                collection_time: counter.collection_time,
+                group: counter.group,
+                filter_type: counter.filter_type,
+                filter: counter.filter.clone(),
+                duration: counter.duration,
+                cpu_time: counter.cpu_time,
+                duration_dbms: counter.duration_dbms,
+                service: counter.service,
+                memory: counter.memory,
+                read: counter.read,
+                write: counter.write,
+                dbms_bytes: counter.dbms_bytes,
+                call: counter.call,
+                number_of_active_sessions: counter.number_of_active_sessions,
+                number_of_sessions: counter.number_of_sessions,
+                descr: counter.descr.clone(),
+            })?;
+        }
+        Target::Limit(limit) if remove => {
+            client.call_typed(LimitRemoveRpc {
+                cluster,
+                name: limit.name.clone(),
+            })?;
+        }
+        Target::Limit(limit) => {
+            client.call_typed(LimitUpdateRpc {
+                cluster,
+                name: limit.name.clone(),
+                counter: limit.counter.clone(),
+                action: limit.action,
+                duration: limit.duration,
+                cpu_time: limit.cpu_time,
+                memory: limit.memory,
+                read: limit.read,
+                write: limit.write,
+                duration_dbms: limit.duration_dbms,
+                dbms_bytes: limit.dbms_bytes,
+                service: limit.service,
+                call: limit.call,
+                number_of_active_sessions: limit.number_of_active_sessions,
+                number_of_sessions: limit.number_of_sessions,
+                error_message: limit.error_message.clone(),
+                descr: limit.descr.clone(),
+            })?;
+        }
+        Target::Rule { server, rule, .. } if remove => {
+            client.call_typed(RuleRemoveRpc {
+                cluster,
+                server: *server,
+                rule: *rule,
+            })?;
+        }
+        Target::Rule {
+            server,
+            position,
+            entry,
+            ..
+        } if step.op == Op::Insert => {
+            client.call_typed(RuleInsertRpc {
+                cluster,
+                server: *server,
+                rule: [0; 16],
+                position: *position,
+                object_type: entry.object_type,
+                infobase_name: entry.infobase_name.clone(),
+                rule_type: entry.rule_type,
+                application_ext: entry.application_ext.clone(),
+                priority: entry.priority,
+            })?;
+        }
+        Target::Rule {
+            server,
+            rule,
+            position,
+            entry,
+        } => {
+            client.call_typed(RuleUpdateRpc {
+                cluster,
+                server: *server,
+                rule: *rule,
+                position: *position,
+                object_type: entry.object_type,
+                infobase_name: entry.infobase_name.clone(),
+                rule_type: entry.rule_type,
+                application_ext: entry.application_ext.clone(),
+                priority: entry.priority,
+            })?;
+        }
+        Target::ServiceSetting {
+            server, setting, ..
+        } if remove => {
+            client.call_typed(ServiceSettingRemoveRpc {
+                cluster,
+                server: *server,
+                setting: *setting,
+            })?;
+        }
+        Target::ServiceSetting { server, entry, .. } if step.op == Op::Insert => {
+            client.call_typed(ServiceSettingInsertRpc {
+                cluster,
+                server: *server,
+                service_name: entry.service_name.clone(),
+                infobase_name: entry.infobase_name.clone(),
+                service_data_dir: entry.service_data_dir.clone(),
+                active: u16::from(entry.active),
+            })?;
+        }
+        Target::ServiceSetting {
+            server,
+            setting,
+            entry,
+        } => {
+            client.call_typed(ServiceSettingUpdateRpc {
+                cluster,
+                server: *server,
+                setting: *setting,
+                service_name: entry.service_name.clone(),
+                infobase_name: entry.infobase_name.clone(),
+                service_data_dir: entry.service_data_dir.clone(),
+                active: u16::from(entry.active),
+            })?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CURRENT: &str = r#"
+[[cluster_admins]]
+name = "admin"
+descr = ""
+auth_pwd = 1
+auth_os = 0
+os_user = ""
+
+[[profiles]]
+name = "restricted"
+descr = ""
+directory_access = 0
+com_access = 0
+addin_access = 0
+module_access = 0
+app_access = 0
+config = false
+privileged_mode = false
+inet_access = 0
+crypto = false
+right_extension = false
+right_extension_definition_roles = ""
+all_modules_extension = false
+modules_available_for_extension = ""
+modules_not_available_for_extension = ""
+privileged_mode_roles = ""
+
+[[limits]]
+name = "cpu"
+counter = "by-user"
+action = 2
+duration = 0
+cpu_time = 1000
+memory = 0
+read = 0
+write = 0
+duration_dbms = 0
+dbms_bytes = 0
+service = 0
+call = 0
+number_of_active_sessions = 0
+number_of_sessions = 0
+error_message = ""
+descr = ""
+
+[[limits]]
+name = "old"
+counter = "by-user"
+action = 0
+duration = 0
+cpu_time = 0
+memory = 0
+read = 0
+write = 0
+duration_dbms = 0
+dbms_bytes = 0
+service = 0
+call = 0
+number_of_active_sessions = 0
+number_of_sessions = 0
+error_message = ""
+descr = ""
+"#;
+
+    const COUNTER: &str = r#"
+[[counters]]
+name = "by-user"
+collection_time = 60
+group = 0
+filter_type = 2
+filter = ""
+duration = 0
+cpu_time = 1
+duration_dbms = 0
+service = 0
+memory = 0
+read = 0
+write = 0
+dbms_bytes = 0
+call = 0
+number_of_active_sessions = 0
+number_of_sessions = 0
+descr = ""
+"#;
+
+    fn rule(infobase: &str) -> RuleRecord {
+        RuleRecord {
+            rule: [infobase.len() as u8; 16],
+            object_type: 0,
+            infobase_name: infobase.to_string(),
+            rule_type: 1,
+            application_ext: String::new(),
+            priority: 0,
+        }
+    }
+
+    fn current() -> Current {
+        let doc = ClusterConfig::parse(CURRENT).expect("current");
+        Current {
+            agent_admins: None,
+            cluster_admins: doc.cluster_admins.unwrap_or_default(),
+            profiles: doc.profiles.unwrap_or_default(),
+            counters: Vec::new(),
+            limits: doc.limits.unwrap_or_default(),
+            servers: vec![CurrentServer {
+                server: [7; 16],
+                name: "central".to_string(),
+                rules: vec![rule("buh"), rule("trade")],
+                service_settings: Vec::new(),
+            }],
+        }
+    }
+
+    #[test]
+    fn export_round_trips_without_changes() {
+        let current = current();
+        let text = current.export().to_toml().expect("toml");
+        assert!(text.contains("[[servers.rules]]"), "{text}");
+        let doc = ClusterConfig::parse(&text).expect("parse");
+        let plan = plan(&current, &doc).expect("plan");
+        assert!(plan.steps.is_empty(), "{plan}");
+        assert_eq!(plan.to_string(), "no changes");
+    }
+
+    #[test]
+    fn plans_inserts_updates_then_removes() {
+        let mut current = current();
+        let mut doc = current.export();
+        doc.cluster_admins = None;
+        doc.profiles = Some(Vec::new());
+        doc.counters = Some(ClusterConfig::parse(COUNTER).unwrap().counters.unwrap());
+        let limits = doc.limits.as_mut().unwrap();
+        limits.retain(|limit| limit.name == "cpu");
+        limits[0].cpu_time = 2000;
+        let rules = doc.servers.as_mut().unwrap()[0].rules.as_mut().unwrap();
+        rules.truncate(1);
+        rules[0].priority = 5;
+        current.cluster_admins.clear();
+
+        let plan = plan(&current, &doc).expect("plan");
+        let steps: Vec<String> = plan.steps.iter().map(Step::summary).collect();
+        assert_eq!(
+            steps,
+            [
+                "+ counter by-user",
+                "~ limit cpu",
+                "~ rule central/#0",
+                "- rule central/#1",
+                "- limit old",
+            ]
+        );
+        assert_eq!(plan.steps[1].changes, ["cpu_time: 1000 -> 2000"]);
+        let removes: Vec<_> = plan.steps.iter().filter_map(Step::removes).collect();
+        assert_eq!(removes.len(), 2);
+        assert_eq!(removes[0].action, "rule.remove");
+        assert_eq!(removes[0].get("name"), Some("central/#1"));
+        assert_eq!(removes[1].to_string(), "limit.remove: name \"old\"");
+        assert_eq!(plan.notes.len(), 1, "{:?}", plan.notes);
+        assert!(plan
+            .to_string()
+            .ends_with("1 to add, 2 to change, 2 to remove"));
+    }
+
+    #[test]
+    fn plans_one_insert_for_a_rule_added_on_top() {
+        let current = current();
+        let mut doc = current.export();
+        let rules = doc.servers.as_mut().unwrap()[0].rules.as_mut().unwrap();
+        let mut first = Rule::from(&rule("hr"));
+        first.priority = 3;
+        rules.insert(0, first);
+
+        let plan = plan(&current, &doc).expect("plan");
+        let steps: Vec<String> = plan.steps.iter().map(Step::summary).collect();
+        assert_eq!(steps, ["+ rule central/#0"]);
+        assert!(matches!(
+            &plan.steps[0].target,
+            Target::Rule { position: 0, entry, .. } if entry.infobase_name == "hr"
+        ));
+
+        // Replacing the first rule inserts after the rule it replaces, which
+        // is removed last.
+        let rules = doc.servers.as_mut().unwrap()[0].rules.as_mut().unwrap();
+        rules.remove(1);
+        rules[1].priority = 9;
+        let replaced = super::plan(&current, &doc).expect("plan");
+        let steps: Vec<String> = replaced.steps.iter().map(Step::summary).collect();
+        assert_eq!(
+            steps,
+            [
+                "+ rule central/#0",
+                "~ rule central/#1",
+                "- rule central/#0"
+            ]
+        );
+        let positions: Vec<u32> = replaced
+            .steps
+            .iter()
+            .map(|step| match &step.target {
+                &Target::Rule { position, .. } => position,
+                other => panic!("unexpected target: {other:?}"),
+            })
+            .collect();
+        assert_eq!(positions[..2], [0, 2]);
+    }
+
+    #[test]
+    fn rejects_documents_it_cannot_apply() {
+        let current = current();
+
+        let mut doc = current.export();
+        doc.cluster_admins.as_mut().unwrap()[0].descr = "changed".to_string();
+        let err = plan(&current, &doc).expect_err("no password");
+        assert!(err.to_string().contains("pwd"), "{err}");
+
+        let mut doc = current.export();
+        let limits = doc.limits.as_mut().unwrap();
+        limits.push(limits[0].clone());
+        let err = plan(&current, &doc).expect_err("duplicate");
+        assert!(err.to_string().contains("duplicate limit"), "{err}");
+
+        let mut doc = current.export();
+        doc.servers.as_mut().unwrap()[0].name = "missing".to_string();
+        assert!(plan(&current, &doc).is_err());
+
+        let doc = ClusterConfig::parse("[[agent_admins]]\nname = \"a\"\ndescr = \"\"\nauth_pwd = 0\nauth_os = 1\nos_user = \"x\"\n")
+            .expect("agent admins");
+        assert!(plan(&current, &doc).is_err(), "agent admins were not read");
+        assert!(ClusterConfig::parse("limit = []").is_err());
+    }
+}
@@ -1,4 +1,3 @@
-use rac_protocol::client::{ClientConfig, RacClient};
 use rac_protocol::commands::{
     agent_admin_list,
     agent_admin_register,
@@ -7,18 +6,19 @@ use rac_protocol::commands::{
     agent_version,
 };
 use rac_protocol::error::Result;
+use rac_protocol::policy::Target;
 
 use crate::rac_lite::cli::{AgentAdminCmd, AgentCmd};
-use crate::rac_lite::console_output as console;
+use crate::rac_lite::connector::Connector;
+use crate::rac_lite::console_output::{self as console, Output};
 use crate::rac_lite::parse::parse_auth_flags;
 
-pub fn run(json: bool, cfg: &ClientConfig, command: AgentCmd) -> Result<()> {
+pub fn run(output: Output, conn: &mut Connector, command: AgentCmd) -> Result<()> {
     match command {
         AgentCmd::Version { addr } => {
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
-            let resp = agent_version(&mut client)?;
-            console::output(json, &resp, console::agent_version(&resp.version));
-            client.close()?;
+            let client = conn.client(&addr)?;
+            let resp = agent_version(client)?;
+            console::output(output, &resp, console::agent_version(&resp.version));
         }
         AgentCmd::Admin { command } => match command {
             AgentAdminCmd::List {
@@ -26,15 +26,14 @@ pub fn run(json: bool, cfg: &ClientConfig, command: AgentCmd) -> Result<()> {
                 agent_user,
                 agent_pwd,
             } => {
-                let mut client = RacClient::connect(&addr, cfg.clone())?;
+                let client = conn.client(&addr)?;
                 let creds = agent_auth_optional(
-                    &mut client,
+                    client,
                     agent_user.as_deref(),
                     agent_pwd.as_deref(),
                 )?;
-                let resp = agent_admin_list(&mut client, creds.user, creds.pwd)?;
-                console::output(json, &resp, console::agent_admin_list(&resp.admins));
-                client.close()?;
+                let resp = agent_admin_list(client, creds.user, creds.pwd)?;
+                console::output(output, &resp, console::agent_admin_list(&resp.admins));
             }
             AgentAdminCmd::Register {
                 addr,
@@ -48,14 +47,14 @@ pub fn run(json: bool, cfg: &ClientConfig, command: AgentCmd) -> Result<()> {
             } => {
                 let (auth_pwd, auth_os) = parse_auth_flags(&auth)?;
                 let pwd = pwd.unwrap_or_default();
-                let mut client = RacClient::connect(&addr, cfg.clone())?;
+                let client = conn.client(&addr)?;
                 let creds = agent_auth_optional(
-                    &mut client,
+                    client,
                     agent_user.as_deref(),
                     agent_pwd.as_deref(),
                 )?;
                 let resp = agent_admin_register(
-                    &mut client,
+                    client,
                     creds.user,
                     creds.pwd,
                     name,
@@ -65,8 +64,7 @@ pub fn run(json: bool, cfg: &ClientConfig, command: AgentCmd) -> Result<()> {
                     auth_os,
                     os_user,
                 )?;
-                console::output(json, &resp, console::agent_admin_register(&resp));
-                client.close()?;
+                console::output(output, &resp, console::agent_admin_register(&resp));
             }
             AgentAdminCmd::Remove {
                 addr,
@@ -74,15 +72,18 @@ pub fn run(json: bool, cfg: &ClientConfig, command: AgentCmd) -> Result<()> {
                 agent_pwd,
                 name,
             } => {
-                let mut client = RacClient::connect(&addr, cfg.clone())?;
+                let client = conn.client(&addr)?;
                 let creds = agent_auth_optional(
-                    &mut client,
+                    client,
                     agent_user.as_deref(),
                     agent_pwd.as_deref(),
                 )?;
-                let resp = agent_admin_remove(&mut client, creds.user, creds.pwd, &name)?;
-                console::output(json, &resp, console::agent_admin_remove(&resp));
-                client.close()?;
+                conn.guard(&addr, |_| {
+                    Ok(Target::new("agent.admin_remove").with("name", &name))
+                })?;
+                let client = conn.client(&addr)?;
+                let resp = agent_admin_remove(client, creds.user, creds.pwd, &name)?;
+                console::output(output, &resp, console::agent_admin_remove(&resp));
             }
         },
     }
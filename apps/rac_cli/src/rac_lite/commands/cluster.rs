@@ -1,27 +1,35 @@
-use rac_protocol::client::{ClientConfig, RacClient};
 use rac_protocol::commands::{
     cluster_admin_list, cluster_admin_register, cluster_auth_optional, cluster_info, cluster_list,
 };
 use rac_protocol::error::Result;
 
 use crate::rac_lite::cli::{ClusterAdminCmd, ClusterCmd};
-use crate::rac_lite::console_output as console;
-use crate::rac_lite::parse::{parse_auth_flags, parse_uuid_arg};
+use crate::rac_lite::connector::Connector;
+use crate::rac_lite::console_output::{self as console, Output};
+use crate::rac_lite::parse::{parse_auth_flags, resolve_cluster_arg, resolve_cluster_auth};
 
-pub fn run(json: bool, cfg: &ClientConfig, command: ClusterCmd) -> Result<()> {
+pub fn run(output: Output, conn: &mut Connector, command: ClusterCmd) -> Result<()> {
     match command {
         ClusterCmd::List { addr } => {
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
-            let resp = cluster_list(&mut client)?;
-            console::output(json, &resp, console::cluster_list(&resp));
-            client.close()?;
+            let client = conn.client(&addr)?;
+            let resp = cluster_list(client)?;
+            console::output(output, &resp, console::cluster_list(&resp));
         }
-        ClusterCmd::Info { addr, cluster } => {
-            let cluster = parse_uuid_arg(&cluster)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
-            let resp = cluster_info(&mut client, cluster)?;
-            console::output(json, &resp, console::cluster_info(&resp));
-            client.close()?;
+        ClusterCmd::Info {
+            addr,
+            cluster,
+            cluster_user,
+            cluster_pwd,
+        } => {
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_auth(
+                client,
+                &cluster,
+                cluster_user.as_deref(),
+                cluster_pwd.as_deref(),
+            )?;
+            let resp = cluster_info(client, cluster)?;
+            console::output(output, &resp, console::cluster_info(&resp));
         }
         ClusterCmd::Admin { command } => match command {
             ClusterAdminCmd::List {
@@ -30,17 +38,16 @@ pub fn run(json: bool, cfg: &ClientConfig, command: ClusterCmd) -> Result<()> {
                 cluster_user,
                 cluster_pwd,
             } => {
-                let cluster = parse_uuid_arg(&cluster)?;
-                let mut client = RacClient::connect(&addr, cfg.clone())?;
+                let client = conn.client(&addr)?;
+                let cluster = resolve_cluster_arg(client, &cluster)?;
                 let _creds = cluster_auth_optional(
-                    &mut client,
+                    client,
                     cluster,
                     cluster_user.as_deref(),
                     cluster_pwd.as_deref(),
                 )?;
-                let resp = cluster_admin_list(&mut client, cluster)?;
-                console::output(json, &resp, console::cluster_admin_list(&resp));
-                client.close()?;
+                let resp = cluster_admin_list(client, cluster)?;
+                console::output(output, &resp, console::cluster_admin_list(&resp));
             }
             ClusterAdminCmd::Register {
                 addr,
@@ -52,18 +59,18 @@ pub fn run(json: bool, cfg: &ClientConfig, command: ClusterCmd) -> Result<()> {
                 descr,
                 auth,
             } => {
-                let cluster = parse_uuid_arg(&cluster)?;
+                let client = conn.client(&addr)?;
+                let cluster = resolve_cluster_arg(client, &cluster)?;
                 let (auth_pwd, auth_os) = parse_auth_flags(&auth)?;
                 let pwd = pwd.unwrap_or_default();
-                let mut client = RacClient::connect(&addr, cfg.clone())?;
                 let _creds = cluster_auth_optional(
-                    &mut client,
+                    client,
                     cluster,
                     cluster_user.as_deref(),
                     cluster_pwd.as_deref(),
                 )?;
                 let resp = cluster_admin_register(
-                    &mut client,
+                    client,
                     cluster,
                     name,
                     descr,
@@ -71,8 +78,7 @@ pub fn run(json: bool, cfg: &ClientConfig, command: ClusterCmd) -> Result<()> {
                     auth_pwd,
                     auth_os,
                 )?;
-                console::output(json, &resp, console::cluster_admin_register(resp));
-                client.close()?;
+                console::output(output, &resp, console::cluster_admin_register(resp));
             }
         },
     }
@@ -0,0 +1,138 @@
+use std::fs;
+use std::path::Path;
+
+use rac_protocol::client::RacClient;
+use rac_protocol::commands::{agent_auth_optional, cluster_auth_optional};
+use rac_protocol::error::{RacError, Result};
+use rac_protocol::Uuid16;
+
+use crate::rac_lite::cli::ConfigCmd;
+use crate::rac_lite::cluster_config::{apply, fetch, plan, ClusterConfig, Current, Step};
+use crate::rac_lite::connector::Connector;
+use crate::rac_lite::console_output::{self as console, Output};
+use crate::rac_lite::parse::{parse_rule_apply_mode, resolve_cluster_arg};
+
+struct Creds {
+    cluster_user: Option<String>,
+    cluster_pwd: Option<String>,
+    agent_user: Option<String>,
+    agent_pwd: Option<String>,
+}
+
+pub fn run(output: Output, conn: &mut Connector, command: ConfigCmd) -> Result<()> {
+    match command {
+        ConfigCmd::Export {
+            addr,
+            cluster,
+            cluster_user,
+            cluster_pwd,
+            agent_user,
+            agent_pwd,
+        } => {
+            let creds = Creds {
+                cluster_user,
+                cluster_pwd,
+                agent_user,
+                agent_pwd,
+            };
+            let agent_admins = creds.agent_user.is_some();
+            let client = conn.client(&addr)?;
+            let (_, current) = read_cluster(client, &cluster, &creds, agent_admins)?;
+            let doc = current.export();
+            let text = doc.to_toml().map_err(RacError::InvalidArgs)?;
+            console::output(output, &doc, text.trim_end());
+        }
+        ConfigCmd::Plan {
+            addr,
+            file,
+            cluster,
+            cluster_user,
+            cluster_pwd,
+            agent_user,
+            agent_pwd,
+        } => {
+            let creds = Creds {
+                cluster_user,
+                cluster_pwd,
+                agent_user,
+                agent_pwd,
+            };
+            let desired = load(&file)?;
+            let client = conn.client(&addr)?;
+            let agent_admins = desired.agent_admins.is_some();
+            let (_, current) = read_cluster(client, &cluster, &creds, agent_admins)?;
+            let plan = plan(&current, &desired)?;
+            console::output(output, &plan, &plan);
+        }
+        ConfigCmd::Apply {
+            addr,
+            file,
+            cluster,
+            cluster_user,
+            cluster_pwd,
+            agent_user,
+            agent_pwd,
+            rule_apply_mode,
+        } => {
+            let creds = Creds {
+                cluster_user,
+                cluster_pwd,
+                agent_user,
+                agent_pwd,
+            };
+            let mode = parse_rule_apply_mode(&rule_apply_mode)?;
+            let desired = load(&file)?;
+            let client = conn.client(&addr)?;
+            let agent_admins = desired.agent_admins.is_some();
+            let (cluster, current) = read_cluster(client, &cluster, &creds, agent_admins)?;
+            let plan = plan(&current, &desired)?;
+            conn.guard_all(&addr, |_| {
+                Ok(plan.steps.iter().filter_map(Step::removes).collect())
+            })?;
+            let client = conn.client(&addr)?;
+            let progress = matches!(output, Output::Text | Output::Rac);
+            apply(client, cluster, &plan, mode, |step| {
+                if progress {
+                    println!("{}", step.summary());
+                }
+            })?;
+            let text = if plan.steps.is_empty() {
+                "no changes".to_string()
+            } else {
+                format!("applied {} changes", plan.steps.len())
+            };
+            console::output(output, &plan, text);
+        }
+    }
+    Ok(())
+}
+
+fn load(path: &Path) -> Result<ClusterConfig> {
+    let raw = fs::read_to_string(path)?;
+    ClusterConfig::parse(&raw)
+        .map_err(|err| RacError::InvalidArgs(format!("{}: {err}", path.display())))
+}
+
+/// Authenticates as `creds` and reads the current setup of `cluster`.
+fn read_cluster(
+    client: &mut RacClient,
+    cluster: &str,
+    creds: &Creds,
+    agent_admins: bool,
+) -> Result<(Uuid16, Current)> {
+    let cluster = resolve_cluster_arg(client, cluster)?;
+    if agent_admins {
+        agent_auth_optional(
+            client,
+            creds.agent_user.as_deref(),
+            creds.agent_pwd.as_deref(),
+        )?;
+    }
+    cluster_auth_optional(
+        client,
+        cluster,
+        creds.cluster_user.as_deref(),
+        creds.cluster_pwd.as_deref(),
+    )?;
+    Ok((cluster, fetch(client, cluster, agent_admins)?))
+}
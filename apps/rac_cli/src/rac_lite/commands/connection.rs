@@ -1,31 +1,46 @@
-use rac_protocol::client::{ClientConfig, RacClient};
 use rac_protocol::commands::{connection_info, connection_list};
 use rac_protocol::error::Result;
 
 use crate::rac_lite::cli::ConnectionCmd;
-use crate::rac_lite::console_output as console;
-use crate::rac_lite::parse::parse_uuid_arg;
+use crate::rac_lite::connector::Connector;
+use crate::rac_lite::console_output::{self as console, Output};
+use crate::rac_lite::parse::{parse_uuid_arg, resolve_cluster_auth};
 
-pub fn run(json: bool, cfg: &ClientConfig, command: ConnectionCmd) -> Result<()> {
+pub fn run(output: Output, conn: &mut Connector, command: ConnectionCmd) -> Result<()> {
     match command {
-        ConnectionCmd::List { addr, cluster } => {
-            let cluster = parse_uuid_arg(&cluster)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
-            let resp = connection_list(&mut client, cluster)?;
-            console::output(json, &resp, console::connection_list(&resp.records));
-            client.close()?;
+        ConnectionCmd::List {
+            addr,
+            cluster,
+            cluster_user,
+            cluster_pwd,
+        } => {
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_auth(
+                client,
+                &cluster,
+                cluster_user.as_deref(),
+                cluster_pwd.as_deref(),
+            )?;
+            let resp = connection_list(client, cluster)?;
+            console::output(output, &resp, console::connection_list(&resp.records));
         }
         ConnectionCmd::Info {
             addr,
             cluster,
+            cluster_user,
+            cluster_pwd,
             connection,
         } => {
-            let cluster = parse_uuid_arg(&cluster)?;
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_auth(
+                client,
+                &cluster,
+                cluster_user.as_deref(),
+                cluster_pwd.as_deref(),
+            )?;
             let connection = parse_uuid_arg(&connection)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
-            let resp = connection_info(&mut client, cluster, connection)?;
-            console::output(json, &resp, console::connection_info(&resp.record));
-            client.close()?;
+            let resp = connection_info(client, cluster, connection)?;
+            console::output(output, &resp, console::connection_info(&resp.record));
         }
     }
     Ok(())
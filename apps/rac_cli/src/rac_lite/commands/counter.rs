@@ -1,37 +1,54 @@
-use rac_protocol::client::{ClientConfig, RacClient};
 use rac_protocol::commands::{
     counter_accumulated_values, counter_clear, counter_info, counter_list, counter_remove,
     counter_update, counter_values, CounterAccumulatedValuesRpc, CounterClearRpc,
     CounterRemoveRpc, CounterUpdateRpc, CounterValuesRpc,
 };
 use rac_protocol::error::Result;
+use rac_protocol::policy::Target;
 
 use rac_protocol::commands::cluster_auth_optional;
 use crate::rac_lite::cli::CounterCmd;
-use crate::rac_lite::console_output as console;
+use crate::rac_lite::connector::Connector;
+use crate::rac_lite::console_output::{self as console, Output};
 use crate::rac_lite::parse::{
-    parse_counter_analyze_flag, parse_counter_filter_type, parse_counter_group, parse_uuid_arg,
+    parse_counter_analyze_flag, parse_counter_filter_type, parse_counter_group, resolve_cluster_arg,
+    resolve_cluster_auth,
 };
 
-pub fn run(json: bool, cfg: &ClientConfig, command: CounterCmd) -> Result<()> {
+pub fn run(output: Output, conn: &mut Connector, command: CounterCmd) -> Result<()> {
     match command {
-        CounterCmd::List { addr, cluster } => {
-            let cluster = parse_uuid_arg(&cluster)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
-            let resp = counter_list(&mut client, cluster)?;
-            console::output(json, &resp, console::counter_list(&resp.records));
-            client.close()?;
+        CounterCmd::List {
+            addr,
+            cluster,
+            cluster_user,
+            cluster_pwd,
+        } => {
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_auth(
+                client,
+                &cluster,
+                cluster_user.as_deref(),
+                cluster_pwd.as_deref(),
+            )?;
+            let resp = counter_list(client, cluster)?;
+            console::output(output, &resp, console::counter_list(&resp.records));
         }
         CounterCmd::Info {
             addr,
             cluster,
+            cluster_user,
+            cluster_pwd,
             counter,
         } => {
-            let cluster = parse_uuid_arg(&cluster)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
-            let resp = counter_info(&mut client, cluster, &counter)?;
-            console::output(json, &resp, console::counter_info(&resp.record));
-            client.close()?;
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_auth(
+                client,
+                &cluster,
+                cluster_user.as_deref(),
+                cluster_pwd.as_deref(),
+            )?;
+            let resp = counter_info(client, cluster, &counter)?;
+            console::output(output, &resp, console::counter_info(&resp.record));
         }
         CounterCmd::Clear {
             addr,
@@ -41,16 +58,22 @@ pub fn run(json: bool, cfg: &ClientConfig, command: CounterCmd) -> Result<()> {
             counter,
             object,
         } => {
-            let cluster = parse_uuid_arg(&cluster)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_arg(client, &cluster)?;
             let creds = cluster_auth_optional(
-                &mut client,
+                client,
                 cluster,
                 cluster_user.as_deref(),
                 cluster_pwd.as_deref(),
             )?;
+            conn.guard(&addr, |_| {
+                Ok(Target::new("counter.clear")
+                    .with("counter", &counter)
+                    .with("object", &object))
+            })?;
+            let client = conn.client(&addr)?;
             let resp = counter_clear(
-                &mut client,
+                client,
                 creds.user,
                 creds.pwd,
                 CounterClearRpc {
@@ -59,8 +82,7 @@ pub fn run(json: bool, cfg: &ClientConfig, command: CounterCmd) -> Result<()> {
                     object,
                 },
             )?;
-            console::output(json, &resp, console::counter_clear(&resp));
-            client.close()?;
+            console::output(output, &resp, console::counter_clear(&resp));
         }
         CounterCmd::Remove {
             addr,
@@ -69,22 +91,25 @@ pub fn run(json: bool, cfg: &ClientConfig, command: CounterCmd) -> Result<()> {
             cluster_pwd,
             name,
         } => {
-            let cluster = parse_uuid_arg(&cluster)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_arg(client, &cluster)?;
             let creds = cluster_auth_optional(
-                &mut client,
+                client,
                 cluster,
                 cluster_user.as_deref(),
                 cluster_pwd.as_deref(),
             )?;
+            conn.guard(&addr, |_| {
+                Ok(Target::new("counter.remove").with("name", &name))
+            })?;
+            let client = conn.client(&addr)?;
             let resp = counter_remove(
-                &mut client,
+                client,
                 creds.user,
                 creds.pwd,
                 CounterRemoveRpc { cluster, name },
             )?;
-            console::output(json, &resp, console::counter_remove(&resp));
-            client.close()?;
+            console::output(output, &resp, console::counter_remove(&resp));
         }
         CounterCmd::Values {
             addr,
@@ -94,16 +119,16 @@ pub fn run(json: bool, cfg: &ClientConfig, command: CounterCmd) -> Result<()> {
             counter,
             object,
         } => {
-            let cluster = parse_uuid_arg(&cluster)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_arg(client, &cluster)?;
             let creds = cluster_auth_optional(
-                &mut client,
+                client,
                 cluster,
                 cluster_user.as_deref(),
                 cluster_pwd.as_deref(),
             )?;
             let resp = counter_values(
-                &mut client,
+                client,
                 creds.user,
                 creds.pwd,
                 CounterValuesRpc {
@@ -112,8 +137,7 @@ pub fn run(json: bool, cfg: &ClientConfig, command: CounterCmd) -> Result<()> {
                     object,
                 },
             )?;
-            console::output(json, &resp, console::counter_values(&resp.records));
-            client.close()?;
+            console::output(output, &resp, console::counter_values(&resp.records));
         }
         CounterCmd::Update {
             addr,
@@ -138,7 +162,8 @@ pub fn run(json: bool, cfg: &ClientConfig, command: CounterCmd) -> Result<()> {
             number_of_sessions,
             descr,
         } => {
-            let cluster = parse_uuid_arg(&cluster)?;
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_arg(client, &cluster)?;
             let req = CounterUpdateRpc {
                 cluster,
                 name,
@@ -165,16 +190,14 @@ pub fn run(json: bool, cfg: &ClientConfig, command: CounterCmd) -> Result<()> {
                 )?,
                 descr,
             };
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
             let creds = cluster_auth_optional(
-                &mut client,
+                client,
                 cluster,
                 cluster_user.as_deref(),
                 cluster_pwd.as_deref(),
             )?;
-            let resp = counter_update(&mut client, creds.user, creds.pwd, req)?;
-            console::output(json, &resp, console::counter_update(&resp));
-            client.close()?;
+            let resp = counter_update(client, creds.user, creds.pwd, req)?;
+            console::output(output, &resp, console::counter_update(&resp));
         }
         CounterCmd::AccumulatedValues {
             addr,
@@ -184,16 +207,16 @@ pub fn run(json: bool, cfg: &ClientConfig, command: CounterCmd) -> Result<()> {
             counter,
             object,
         } => {
-            let cluster = parse_uuid_arg(&cluster)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_arg(client, &cluster)?;
             let creds = cluster_auth_optional(
-                &mut client,
+                client,
                 cluster,
                 cluster_user.as_deref(),
                 cluster_pwd.as_deref(),
             )?;
             let resp = counter_accumulated_values(
-                &mut client,
+                client,
                 creds.user,
                 creds.pwd,
                 CounterAccumulatedValuesRpc {
@@ -203,11 +226,10 @@ pub fn run(json: bool, cfg: &ClientConfig, command: CounterCmd) -> Result<()> {
                 },
             )?;
             console::output(
-                json,
+                output,
                 &resp,
                 console::counter_accumulated_values(&resp.records),
             );
-            client.close()?;
         }
     }
     Ok(())
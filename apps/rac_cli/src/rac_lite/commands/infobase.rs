@@ -1,47 +1,68 @@
-use rac_protocol::client::{ClientConfig, RacClient};
 use rac_protocol::commands::{infobase_info, infobase_summary_info, infobase_summary_list};
 use rac_protocol::error::Result;
 
 use crate::rac_lite::cli::InfobaseCmd;
-use crate::rac_lite::console_output as console;
-use crate::rac_lite::parse::parse_uuid_arg;
+use crate::rac_lite::connector::Connector;
+use crate::rac_lite::console_output::{self as console, Output};
+use crate::rac_lite::parse::{resolve_cluster_auth, resolve_infobase_arg};
 
-pub fn run(json: bool, cfg: &ClientConfig, command: InfobaseCmd) -> Result<()> {
+pub fn run(output: Output, conn: &mut Connector, command: InfobaseCmd) -> Result<()> {
     match command {
-        InfobaseCmd::SummaryList { addr, cluster } => {
-            let cluster = parse_uuid_arg(&cluster)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
-            let resp = infobase_summary_list(&mut client, cluster)?;
-            console::output(json, &resp, console::infobase_summary_list(&resp.summaries));
-            client.close()?;
+        InfobaseCmd::SummaryList {
+            addr,
+            cluster,
+            cluster_user,
+            cluster_pwd,
+        } => {
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_auth(
+                client,
+                &cluster,
+                cluster_user.as_deref(),
+                cluster_pwd.as_deref(),
+            )?;
+            let resp = infobase_summary_list(client, cluster)?;
+            console::output(output, &resp, console::infobase_summary_list(&resp.summaries));
         }
         InfobaseCmd::SummaryInfo {
             addr,
             cluster,
+            cluster_user,
+            cluster_pwd,
             infobase,
         } => {
-            let cluster = parse_uuid_arg(&cluster)?;
-            let infobase = parse_uuid_arg(&infobase)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
-            let resp = infobase_summary_info(&mut client, cluster, infobase)?;
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_auth(
+                client,
+                &cluster,
+                cluster_user.as_deref(),
+                cluster_pwd.as_deref(),
+            )?;
+            let infobase = resolve_infobase_arg(client, cluster, &infobase)?;
+            let resp = infobase_summary_info(client, cluster, infobase)?;
             let summary = &resp.summary;
             let fields = vec![summary.name.clone(), summary.descr.clone()];
-            console::output(json, &resp, console::info("infobase", &summary.infobase, &fields));
-            client.close()?;
+            console::output(output, &resp, console::info("infobase", &summary.infobase, &fields));
         }
         InfobaseCmd::Info {
             addr,
             cluster,
+            cluster_user,
+            cluster_pwd,
             infobase,
         } => {
-            let cluster = parse_uuid_arg(&cluster)?;
-            let infobase = parse_uuid_arg(&infobase)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
-            let resp = infobase_info(&mut client, cluster, infobase)?;
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_auth(
+                client,
+                &cluster,
+                cluster_user.as_deref(),
+                cluster_pwd.as_deref(),
+            )?;
+            let infobase = resolve_infobase_arg(client, cluster, &infobase)?;
+            let resp = infobase_info(client, cluster, infobase)?;
             let info = &resp.info;
             let fields = vec![info.name.clone(), info.descr.clone()];
-            console::output(json, &resp, console::info("infobase", &info.infobase, &fields));
-            client.close()?;
+            console::output(output, &resp, console::info("infobase", &info.infobase, &fields));
         }
     }
     Ok(())
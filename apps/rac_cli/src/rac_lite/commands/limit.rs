@@ -1,33 +1,49 @@
-use rac_protocol::client::{ClientConfig, RacClient};
 use rac_protocol::commands::{
     limit_info, limit_list, limit_remove, limit_update, LimitRemoveRpc, LimitUpdateRpc,
 };
 use rac_protocol::error::Result;
+use rac_protocol::policy::Target;
 
 use rac_protocol::commands::cluster_auth_optional;
 use crate::rac_lite::cli::LimitCmd;
-use crate::rac_lite::console_output as console;
-use crate::rac_lite::parse::{parse_limit_action, parse_uuid_arg};
+use crate::rac_lite::connector::Connector;
+use crate::rac_lite::console_output::{self as console, Output};
+use crate::rac_lite::parse::{parse_limit_action, resolve_cluster_arg, resolve_cluster_auth};
 
-pub fn run(json: bool, cfg: &ClientConfig, command: LimitCmd) -> Result<()> {
+pub fn run(output: Output, conn: &mut Connector, command: LimitCmd) -> Result<()> {
     match command {
-        LimitCmd::List { addr, cluster } => {
-            let cluster = parse_uuid_arg(&cluster)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
-            let resp = limit_list(&mut client, cluster)?;
-            console::output(json, &resp, console::limit_list(&resp.limits));
-            client.close()?;
+        LimitCmd::List {
+            addr,
+            cluster,
+            cluster_user,
+            cluster_pwd,
+        } => {
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_auth(
+                client,
+                &cluster,
+                cluster_user.as_deref(),
+                cluster_pwd.as_deref(),
+            )?;
+            let resp = limit_list(client, cluster)?;
+            console::output(output, &resp, console::limit_list(&resp.limits));
         }
         LimitCmd::Info {
             addr,
             cluster,
+            cluster_user,
+            cluster_pwd,
             limit,
         } => {
-            let cluster = parse_uuid_arg(&cluster)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
-            let resp = limit_info(&mut client, cluster, &limit)?;
-            console::output(json, &resp, console::limit_info(&resp.record));
-            client.close()?;
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_auth(
+                client,
+                &cluster,
+                cluster_user.as_deref(),
+                cluster_pwd.as_deref(),
+            )?;
+            let resp = limit_info(client, cluster, &limit)?;
+            console::output(output, &resp, console::limit_info(&resp.record));
         }
         LimitCmd::Update {
             addr,
@@ -51,7 +67,8 @@ pub fn run(json: bool, cfg: &ClientConfig, command: LimitCmd) -> Result<()> {
             error_message,
             descr,
         } => {
-            let cluster = parse_uuid_arg(&cluster)?;
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_arg(client, &cluster)?;
             let req = LimitUpdateRpc {
                 cluster,
                 name,
@@ -71,16 +88,14 @@ pub fn run(json: bool, cfg: &ClientConfig, command: LimitCmd) -> Result<()> {
                 error_message,
                 descr,
             };
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
             let creds = cluster_auth_optional(
-                &mut client,
+                client,
                 cluster,
                 cluster_user.as_deref(),
                 cluster_pwd.as_deref(),
             )?;
-            let resp = limit_update(&mut client, creds.user, creds.pwd, req)?;
-            console::output(json, &resp, console::limit_update(&resp));
-            client.close()?;
+            let resp = limit_update(client, creds.user, creds.pwd, req)?;
+            console::output(output, &resp, console::limit_update(&resp));
         }
         LimitCmd::Remove {
             addr,
@@ -89,22 +104,25 @@ pub fn run(json: bool, cfg: &ClientConfig, command: LimitCmd) -> Result<()> {
             cluster_pwd,
             name,
         } => {
-            let cluster = parse_uuid_arg(&cluster)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_arg(client, &cluster)?;
             let creds = cluster_auth_optional(
-                &mut client,
+                client,
                 cluster,
                 cluster_user.as_deref(),
                 cluster_pwd.as_deref(),
             )?;
+            conn.guard(&addr, |_| {
+                Ok(Target::new("limit.remove").with("name", &name))
+            })?;
+            let client = conn.client(&addr)?;
             let resp = limit_remove(
-                &mut client,
+                client,
                 creds.user,
                 creds.pwd,
                 LimitRemoveRpc { cluster, name },
             )?;
-            console::output(json, &resp, console::limit_remove(&resp));
-            client.close()?;
+            console::output(output, &resp, console::limit_remove(&resp));
         }
     }
     Ok(())
@@ -1,19 +1,28 @@
-use rac_protocol::client::{ClientConfig, RacClient};
 use rac_protocol::commands::lock_list;
 use rac_protocol::error::Result;
 
 use crate::rac_lite::cli::LockCmd;
-use crate::rac_lite::console_output as console;
-use crate::rac_lite::parse::parse_uuid_arg;
+use crate::rac_lite::connector::Connector;
+use crate::rac_lite::console_output::{self as console, Output};
+use crate::rac_lite::parse::resolve_cluster_auth;
 
-pub fn run(json: bool, cfg: &ClientConfig, command: LockCmd) -> Result<()> {
+pub fn run(output: Output, conn: &mut Connector, command: LockCmd) -> Result<()> {
     match command {
-        LockCmd::List { addr, cluster } => {
-            let cluster = parse_uuid_arg(&cluster)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
-            let resp = lock_list(&mut client, cluster)?;
-            console::output(json, &resp, console::lock_list(&resp.records));
-            client.close()?;
+        LockCmd::List {
+            addr,
+            cluster,
+            cluster_user,
+            cluster_pwd,
+        } => {
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_auth(
+                client,
+                &cluster,
+                cluster_user.as_deref(),
+                cluster_pwd.as_deref(),
+            )?;
+            let resp = lock_list(client, cluster)?;
+            console::output(output, &resp, console::lock_list(&resp.records));
         }
     }
     Ok(())
@@ -1,31 +1,46 @@
-use rac_protocol::client::{ClientConfig, RacClient};
 use rac_protocol::commands::{manager_info, manager_list};
 use rac_protocol::error::Result;
 
 use crate::rac_lite::cli::ManagerCmd;
-use crate::rac_lite::console_output as console;
-use crate::rac_lite::parse::parse_uuid_arg;
+use crate::rac_lite::connector::Connector;
+use crate::rac_lite::console_output::{self as console, Output};
+use crate::rac_lite::parse::{parse_uuid_arg, resolve_cluster_auth};
 
-pub fn run(json: bool, cfg: &ClientConfig, command: ManagerCmd) -> Result<()> {
+pub fn run(output: Output, conn: &mut Connector, command: ManagerCmd) -> Result<()> {
     match command {
-        ManagerCmd::List { addr, cluster } => {
-            let cluster = parse_uuid_arg(&cluster)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
-            let resp = manager_list(&mut client, cluster)?;
-            console::output(json, &resp, console::manager_list(&resp.managers));
-            client.close()?;
+        ManagerCmd::List {
+            addr,
+            cluster,
+            cluster_user,
+            cluster_pwd,
+        } => {
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_auth(
+                client,
+                &cluster,
+                cluster_user.as_deref(),
+                cluster_pwd.as_deref(),
+            )?;
+            let resp = manager_list(client, cluster)?;
+            console::output(output, &resp, console::manager_list(&resp.managers));
         }
         ManagerCmd::Info {
             addr,
             cluster,
+            cluster_user,
+            cluster_pwd,
             manager,
         } => {
-            let cluster = parse_uuid_arg(&cluster)?;
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_auth(
+                client,
+                &cluster,
+                cluster_user.as_deref(),
+                cluster_pwd.as_deref(),
+            )?;
             let manager = parse_uuid_arg(&manager)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
-            let resp = manager_info(&mut client, cluster, manager)?;
-            console::output(json, &resp, console::manager_info(&resp.record));
-            client.close()?;
+            let resp = manager_info(client, cluster, manager)?;
+            console::output(output, &resp, console::manager_info(&resp.record));
         }
     }
     Ok(())
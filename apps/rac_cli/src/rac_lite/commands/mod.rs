@@ -1,5 +1,6 @@
 pub mod agent;
 pub mod cluster;
+pub mod config;
 pub mod connection;
 pub mod counter;
 pub mod infobase;
@@ -8,6 +9,7 @@ pub mod lock;
 pub mod manager;
 pub mod process;
 pub mod profile;
+pub mod raw;
 pub mod rule;
 pub mod server;
 pub mod service_setting;
@@ -1,44 +1,56 @@
-use rac_protocol::client::{ClientConfig, RacClient};
 use rac_protocol::commands::{process_info, process_list};
 use rac_protocol::error::Result;
 
 use crate::rac_lite::cli::ProcessCmd;
-use crate::rac_lite::console_output as console;
-use crate::rac_lite::parse::parse_uuid_arg;
+use crate::rac_lite::connector::Connector;
+use crate::rac_lite::console_output::{self as console, Output};
+use crate::rac_lite::parse::{parse_uuid_arg, resolve_cluster_auth};
 
-pub fn run(json: bool, cfg: &ClientConfig, command: ProcessCmd) -> Result<()> {
+pub fn run(output: Output, conn: &mut Connector, command: ProcessCmd) -> Result<()> {
     match command {
         ProcessCmd::List {
             addr,
             cluster,
+            cluster_user,
+            cluster_pwd,
             licenses,
         } => {
-            let cluster = parse_uuid_arg(&cluster)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
-            let resp = process_list(&mut client, cluster)?;
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_auth(
+                client,
+                &cluster,
+                cluster_user.as_deref(),
+                cluster_pwd.as_deref(),
+            )?;
+            let resp = process_list(client, cluster)?;
             if licenses {
-                console::output(json, &resp, console::process_list_licenses(&resp.records));
+                console::output(output, &resp, console::process_list_licenses(&resp.records));
             } else {
-                console::output(json, &resp, console::process_list(&resp.records));
+                console::output(output, &resp, console::process_list(&resp.records));
             }
-            client.close()?;
         }
         ProcessCmd::Info {
             addr,
             cluster,
+            cluster_user,
+            cluster_pwd,
             process,
             licenses,
         } => {
-            let cluster = parse_uuid_arg(&cluster)?;
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_auth(
+                client,
+                &cluster,
+                cluster_user.as_deref(),
+                cluster_pwd.as_deref(),
+            )?;
             let process = parse_uuid_arg(&process)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
-            let resp = process_info(&mut client, cluster, process)?;
+            let resp = process_info(client, cluster, process)?;
             if licenses {
-                console::output(json, &resp, console::process_info_licenses(&resp.record));
+                console::output(output, &resp, console::process_info_licenses(&resp.record));
             } else {
-                console::output(json, &resp, console::process_info(&resp.record));
+                console::output(output, &resp, console::process_info(&resp.record));
             }
-            client.close()?;
         }
     }
     Ok(())
@@ -0,0 +1,79 @@
+use rac_protocol::commands::{agent_auth_optional, cluster_auth_optional};
+use rac_protocol::dry_run::{dump, scan, Span};
+use rac_protocol::error::Result;
+use rac_protocol::policy::Target;
+use rac_protocol::rac_wire::{encode_rpc, format_uuid};
+use rac_protocol::rpc::RawRpc;
+use serde::Serialize;
+
+use crate::rac_lite::cli::RawArgs;
+use crate::rac_lite::connector::Connector;
+use crate::rac_lite::console_output::{self as console, Output};
+use crate::rac_lite::parse::{parse_hex_body, resolve_cluster_arg};
+
+#[derive(Serialize)]
+struct RawExchange {
+    request: String,
+    reply: String,
+    /// Guessed fields of the reply.
+    fields: Vec<Span>,
+}
+
+pub fn run(output: Output, conn: &mut Connector, args: RawArgs) -> Result<()> {
+    let body = parse_hex_body(&args.body)?;
+    let client = conn.client(&args.addr)?;
+    if args.agent_user.is_some() || args.agent_pwd.is_some() {
+        agent_auth_optional(
+            client,
+            args.agent_user.as_deref(),
+            args.agent_pwd.as_deref(),
+        )?;
+    }
+    let cluster = match &args.cluster {
+        Some(cluster) => {
+            let cluster = resolve_cluster_arg(client, cluster)?;
+            cluster_auth_optional(
+                client,
+                cluster,
+                args.cluster_user.as_deref(),
+                args.cluster_pwd.as_deref(),
+            )?;
+            Some(cluster)
+        }
+        None => None,
+    };
+    // Nothing tells what an arbitrary request changes, so raw is guarded
+    // like the destructive commands.
+    let mut target = Target::new("raw").with("method", args.method.to_string());
+    if let Some(cluster) = &cluster {
+        target = target.with("cluster", format_uuid(cluster));
+    }
+    conn.guard(&args.addr, |_| Ok(target))?;
+
+    let request = encode_rpc(args.method, &body);
+    let reply = conn.client(&args.addr)?.call(RawRpc {
+        method: args.method,
+        expect: args.expect,
+        cluster,
+        body,
+    })?;
+
+    let text = format!(
+        "request ({} bytes)\n{}reply ({} bytes)\n{}",
+        request.len(),
+        dump(&request, &scan(&request)),
+        reply.len(),
+        dump(&reply, &scan(&reply)),
+    );
+    let resp = RawExchange {
+        request: hex(&request),
+        reply: hex(&reply),
+        fields: scan(&reply),
+    };
+    console::output(output, &resp, text.trim_end());
+    Ok(())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
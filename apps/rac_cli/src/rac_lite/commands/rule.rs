@@ -1,4 +1,3 @@
-use rac_protocol::client::{ClientConfig, RacClient};
 use rac_protocol::commands::{
     rule_apply,
     rule_info,
@@ -14,13 +13,16 @@ use rac_protocol::commands::{
     RuleUpdateRpc,
 };
 use rac_protocol::error::Result;
+use rac_protocol::policy::Target;
+use rac_protocol::rac_wire::format_uuid;
 
 use rac_protocol::commands::cluster_auth_optional;
 use crate::rac_lite::cli::RuleCmd;
-use crate::rac_lite::console_output as console;
-use crate::rac_lite::parse::{parse_rule_apply_mode, parse_uuid_arg};
+use crate::rac_lite::connector::Connector;
+use crate::rac_lite::console_output::{self as console, Output};
+use crate::rac_lite::parse::{parse_rule_apply_mode, parse_uuid_arg, resolve_cluster_arg};
 
-pub fn run(json: bool, cfg: &ClientConfig, command: RuleCmd) -> Result<()> {
+pub fn run(output: Output, conn: &mut Connector, command: RuleCmd) -> Result<()> {
     match command {
         RuleCmd::Apply {
             addr,
@@ -29,19 +31,18 @@ pub fn run(json: bool, cfg: &ClientConfig, command: RuleCmd) -> Result<()> {
             cluster_pwd,
             mode,
         } => {
-            let cluster = parse_uuid_arg(&cluster)?;
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_arg(client, &cluster)?;
             let mode = parse_rule_apply_mode(&mode)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
             let creds = cluster_auth_optional(
-                &mut client,
+                client,
                 cluster,
                 cluster_user.as_deref(),
                 cluster_pwd.as_deref(),
             )?;
             let req = RuleApplyRpc { cluster, mode };
-            let resp = rule_apply(&mut client, creds.user, creds.pwd, req)?;
-            console::output(json, &resp, console::rule_apply(&resp));
-            client.close()?;
+            let resp = rule_apply(client, creds.user, creds.pwd, req)?;
+            console::output(output, &resp, console::rule_apply(&resp));
         }
         RuleCmd::List {
             addr,
@@ -50,19 +51,18 @@ pub fn run(json: bool, cfg: &ClientConfig, command: RuleCmd) -> Result<()> {
             cluster_pwd,
             server,
         } => {
-            let cluster = parse_uuid_arg(&cluster)?;
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_arg(client, &cluster)?;
             let server = parse_uuid_arg(&server)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
             let creds = cluster_auth_optional(
-                &mut client,
+                client,
                 cluster,
                 cluster_user.as_deref(),
                 cluster_pwd.as_deref(),
             )?;
             let req = RuleListRpc { cluster, server };
-            let resp = rule_list(&mut client, creds.user, creds.pwd, req)?;
-            console::output(json, &resp, console::rule_list(&resp.records));
-            client.close()?;
+            let resp = rule_list(client, creds.user, creds.pwd, req)?;
+            console::output(output, &resp, console::rule_list(&resp.records));
         }
         RuleCmd::Info {
             addr,
@@ -72,12 +72,12 @@ pub fn run(json: bool, cfg: &ClientConfig, command: RuleCmd) -> Result<()> {
             server,
             rule,
         } => {
-            let cluster = parse_uuid_arg(&cluster)?;
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_arg(client, &cluster)?;
             let server = parse_uuid_arg(&server)?;
             let rule = parse_uuid_arg(&rule)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
             let creds = cluster_auth_optional(
-                &mut client,
+                client,
                 cluster,
                 cluster_user.as_deref(),
                 cluster_pwd.as_deref(),
@@ -87,9 +87,8 @@ pub fn run(json: bool, cfg: &ClientConfig, command: RuleCmd) -> Result<()> {
                 server,
                 rule,
             };
-            let resp = rule_info(&mut client, creds.user, creds.pwd, req)?;
-            console::output(json, &resp, console::rule_info(&resp.record));
-            client.close()?;
+            let resp = rule_info(client, creds.user, creds.pwd, req)?;
+            console::output(output, &resp, console::rule_info(&resp.record));
         }
         RuleCmd::Insert {
             addr,
@@ -104,9 +103,9 @@ pub fn run(json: bool, cfg: &ClientConfig, command: RuleCmd) -> Result<()> {
             application_ext,
             priority,
         } => {
-            let cluster = parse_uuid_arg(&cluster)?;
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_arg(client, &cluster)?;
             let server = parse_uuid_arg(&server)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
             let req = RuleInsertRpc {
                 cluster,
                 server,
@@ -119,14 +118,13 @@ pub fn run(json: bool, cfg: &ClientConfig, command: RuleCmd) -> Result<()> {
                 priority,
             };
             let creds = cluster_auth_optional(
-                &mut client,
+                client,
                 cluster,
                 cluster_user.as_deref(),
                 cluster_pwd.as_deref(),
             )?;
-            let resp = rule_insert(&mut client, creds.user, creds.pwd, req)?;
-            console::output(json, &resp, console::rule_insert(&resp));
-            client.close()?;
+            let resp = rule_insert(client, creds.user, creds.pwd, req)?;
+            console::output(output, &resp, console::rule_insert(&resp));
         }
         RuleCmd::Update {
             addr,
@@ -142,10 +140,10 @@ pub fn run(json: bool, cfg: &ClientConfig, command: RuleCmd) -> Result<()> {
             application_ext,
             priority,
         } => {
-            let cluster = parse_uuid_arg(&cluster)?;
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_arg(client, &cluster)?;
             let server = parse_uuid_arg(&server)?;
             let rule = parse_uuid_arg(&rule)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
             let req = RuleUpdateRpc {
                 cluster,
                 server,
@@ -158,14 +156,13 @@ pub fn run(json: bool, cfg: &ClientConfig, command: RuleCmd) -> Result<()> {
                 priority,
             };
             let creds = cluster_auth_optional(
-                &mut client,
+                client,
                 cluster,
                 cluster_user.as_deref(),
                 cluster_pwd.as_deref(),
             )?;
-            let resp = rule_update(&mut client, creds.user, creds.pwd, req)?;
-            console::output(json, &resp, console::rule_update(&resp));
-            client.close()?;
+            let resp = rule_update(client, creds.user, creds.pwd, req)?;
+            console::output(output, &resp, console::rule_update(&resp));
         }
         RuleCmd::Remove {
             addr,
@@ -175,24 +172,36 @@ pub fn run(json: bool, cfg: &ClientConfig, command: RuleCmd) -> Result<()> {
             server,
             rule,
         } => {
-            let cluster = parse_uuid_arg(&cluster)?;
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_arg(client, &cluster)?;
             let server = parse_uuid_arg(&server)?;
             let rule = parse_uuid_arg(&rule)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
             let creds = cluster_auth_optional(
-                &mut client,
+                client,
                 cluster,
                 cluster_user.as_deref(),
                 cluster_pwd.as_deref(),
             )?;
+            conn.guard(&addr, |client| {
+                let req = RuleInfoRpc {
+                    cluster,
+                    server,
+                    rule,
+                };
+                let record = rule_info(client, creds.user, creds.pwd, req)?.record;
+                Ok(Target::new("rule.remove")
+                    .with("rule", format_uuid(&rule))
+                    .with("infobase", record.infobase_name)
+                    .with("application_ext", record.application_ext))
+            })?;
+            let client = conn.client(&addr)?;
             let req = RuleRemoveRpc {
                 cluster,
                 server,
                 rule,
             };
-            let resp = rule_remove(&mut client, creds.user, creds.pwd, req)?;
-            console::output(json, &resp, console::rule_remove(&resp));
-            client.close()?;
+            let resp = rule_remove(client, creds.user, creds.pwd, req)?;
+            console::output(output, &resp, console::rule_remove(&resp));
         }
     }
     Ok(())
@@ -1,31 +1,46 @@
-use rac_protocol::client::{ClientConfig, RacClient};
 use rac_protocol::commands::{server_info, server_list};
 use rac_protocol::error::Result;
 
 use crate::rac_lite::cli::ServerCmd;
-use crate::rac_lite::console_output as console;
-use crate::rac_lite::parse::parse_uuid_arg;
+use crate::rac_lite::connector::Connector;
+use crate::rac_lite::console_output::{self as console, Output};
+use crate::rac_lite::parse::{parse_uuid_arg, resolve_cluster_auth};
 
-pub fn run(json: bool, cfg: &ClientConfig, command: ServerCmd) -> Result<()> {
+pub fn run(output: Output, conn: &mut Connector, command: ServerCmd) -> Result<()> {
     match command {
-        ServerCmd::List { addr, cluster } => {
-            let cluster = parse_uuid_arg(&cluster)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
-            let resp = server_list(&mut client, cluster)?;
-            console::output(json, &resp, console::server_list(&resp.servers));
-            client.close()?;
+        ServerCmd::List {
+            addr,
+            cluster,
+            cluster_user,
+            cluster_pwd,
+        } => {
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_auth(
+                client,
+                &cluster,
+                cluster_user.as_deref(),
+                cluster_pwd.as_deref(),
+            )?;
+            let resp = server_list(client, cluster)?;
+            console::output(output, &resp, console::server_list(&resp.servers));
         }
         ServerCmd::Info {
             addr,
             cluster,
+            cluster_user,
+            cluster_pwd,
             server,
         } => {
-            let cluster = parse_uuid_arg(&cluster)?;
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_auth(
+                client,
+                &cluster,
+                cluster_user.as_deref(),
+                cluster_pwd.as_deref(),
+            )?;
             let server = parse_uuid_arg(&server)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
-            let resp = server_info(&mut client, cluster, server)?;
-            console::output(json, &resp, console::server_info(&resp.record));
-            client.close()?;
+            let resp = server_info(client, cluster, server)?;
+            console::output(output, &resp, console::server_info(&resp.record));
         }
     }
     Ok(())
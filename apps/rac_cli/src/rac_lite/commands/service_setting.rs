@@ -1,4 +1,3 @@
-use rac_protocol::client::{ClientConfig, RacClient};
 use rac_protocol::commands::{
     service_setting_apply,
     service_setting_get_service_data_dirs_for_transfer,
@@ -17,13 +16,15 @@ use rac_protocol::commands::{
     ServiceSettingUpdateRpc,
 };
 use rac_protocol::error::Result;
+use rac_protocol::policy::Target;
 
 use rac_protocol::commands::cluster_auth_optional;
 use crate::rac_lite::cli::ServiceSettingCmd;
-use crate::rac_lite::console_output as console;
-use crate::rac_lite::parse::parse_uuid_arg;
+use crate::rac_lite::connector::Connector;
+use crate::rac_lite::console_output::{self as console, Output};
+use crate::rac_lite::parse::{parse_uuid_arg, resolve_cluster_arg};
 
-pub fn run(json: bool, cfg: &ClientConfig, command: ServiceSettingCmd) -> Result<()> {
+pub fn run(output: Output, conn: &mut Connector, command: ServiceSettingCmd) -> Result<()> {
     match command {
         ServiceSettingCmd::List {
             addr,
@@ -32,19 +33,18 @@ pub fn run(json: bool, cfg: &ClientConfig, command: ServiceSettingCmd) -> Result
             cluster_pwd,
             server,
         } => {
-            let cluster = parse_uuid_arg(&cluster)?;
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_arg(client, &cluster)?;
             let server = parse_uuid_arg(&server)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
             let creds = cluster_auth_optional(
-                &mut client,
+                client,
                 cluster,
                 cluster_user.as_deref(),
                 cluster_pwd.as_deref(),
             )?;
             let req = ServiceSettingListRpc { cluster, server };
-            let resp = service_setting_list(&mut client, creds.user, creds.pwd, req)?;
-            console::output(json, &resp, console::service_setting_list(&resp.records));
-            client.close()?;
+            let resp = service_setting_list(client, creds.user, creds.pwd, req)?;
+            console::output(output, &resp, console::service_setting_list(&resp.records));
         }
         ServiceSettingCmd::Info {
             addr,
@@ -54,12 +54,12 @@ pub fn run(json: bool, cfg: &ClientConfig, command: ServiceSettingCmd) -> Result
             server,
             setting,
         } => {
-            let cluster = parse_uuid_arg(&cluster)?;
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_arg(client, &cluster)?;
             let server = parse_uuid_arg(&server)?;
             let setting = parse_uuid_arg(&setting)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
             let creds = cluster_auth_optional(
-                &mut client,
+                client,
                 cluster,
                 cluster_user.as_deref(),
                 cluster_pwd.as_deref(),
@@ -69,9 +69,8 @@ pub fn run(json: bool, cfg: &ClientConfig, command: ServiceSettingCmd) -> Result
                 server,
                 setting,
             };
-            let resp = service_setting_info(&mut client, creds.user, creds.pwd, req)?;
-            console::output(json, &resp, console::service_setting_info(&resp.record));
-            client.close()?;
+            let resp = service_setting_info(client, creds.user, creds.pwd, req)?;
+            console::output(output, &resp, console::service_setting_info(&resp.record));
         }
         ServiceSettingCmd::Insert {
             addr,
@@ -85,11 +84,11 @@ pub fn run(json: bool, cfg: &ClientConfig, command: ServiceSettingCmd) -> Result
             active,
             no_active,
         } => {
-            let cluster = parse_uuid_arg(&cluster)?;
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_arg(client, &cluster)?;
             let server = parse_uuid_arg(&server)?;
             let active = if no_active { false } else { active };
             let active = if active { 1u16 } else { 0u16 };
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
             let req = ServiceSettingInsertRpc {
                 cluster,
                 server,
@@ -99,14 +98,13 @@ pub fn run(json: bool, cfg: &ClientConfig, command: ServiceSettingCmd) -> Result
                 active,
             };
             let creds = cluster_auth_optional(
-                &mut client,
+                client,
                 cluster,
                 cluster_user.as_deref(),
                 cluster_pwd.as_deref(),
             )?;
-            let resp = service_setting_insert(&mut client, creds.user, creds.pwd, req)?;
-            console::output(json, &resp, console::service_setting_insert(&resp));
-            client.close()?;
+            let resp = service_setting_insert(client, creds.user, creds.pwd, req)?;
+            console::output(output, &resp, console::service_setting_insert(&resp));
         }
         ServiceSettingCmd::Update {
             addr,
@@ -117,12 +115,12 @@ pub fn run(json: bool, cfg: &ClientConfig, command: ServiceSettingCmd) -> Result
             setting,
             service_data_dir,
         } => {
-            let cluster = parse_uuid_arg(&cluster)?;
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_arg(client, &cluster)?;
             let server = parse_uuid_arg(&server)?;
             let setting = parse_uuid_arg(&setting)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
             let _creds = cluster_auth_optional(
-                &mut client,
+                client,
                 cluster,
                 cluster_user.as_deref(),
                 cluster_pwd.as_deref(),
@@ -132,7 +130,7 @@ pub fn run(json: bool, cfg: &ClientConfig, command: ServiceSettingCmd) -> Result
                 server,
                 setting,
             };
-            let info = service_setting_info_no_auth(&mut client, info_req)?;
+            let info = service_setting_info_no_auth(client, info_req)?;
             let active = if info.record.active { 1u16 } else { 0u16 };
             let req = ServiceSettingUpdateRpc {
                 cluster,
@@ -143,9 +141,8 @@ pub fn run(json: bool, cfg: &ClientConfig, command: ServiceSettingCmd) -> Result
                 service_data_dir,
                 active,
             };
-            let resp = service_setting_update_no_auth(&mut client, req)?;
-            console::output(json, &resp, console::service_setting_update(&resp));
-            client.close()?;
+            let resp = service_setting_update_no_auth(client, req)?;
+            console::output(output, &resp, console::service_setting_update(&resp));
         }
         ServiceSettingCmd::Remove {
             addr,
@@ -155,24 +152,35 @@ pub fn run(json: bool, cfg: &ClientConfig, command: ServiceSettingCmd) -> Result
             server,
             setting,
         } => {
-            let cluster = parse_uuid_arg(&cluster)?;
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_arg(client, &cluster)?;
             let server = parse_uuid_arg(&server)?;
             let setting = parse_uuid_arg(&setting)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
             let creds = cluster_auth_optional(
-                &mut client,
+                client,
                 cluster,
                 cluster_user.as_deref(),
                 cluster_pwd.as_deref(),
             )?;
+            conn.guard(&addr, |client| {
+                let req = ServiceSettingInfoRpc {
+                    cluster,
+                    server,
+                    setting,
+                };
+                let record = service_setting_info(client, creds.user, creds.pwd, req)?.record;
+                Ok(Target::new("service_setting.remove")
+                    .with("service", record.service_name)
+                    .with("infobase", record.infobase_name))
+            })?;
+            let client = conn.client(&addr)?;
             let req = ServiceSettingRemoveRpc {
                 cluster,
                 server,
                 setting,
             };
-            let resp = service_setting_remove(&mut client, creds.user, creds.pwd, req)?;
-            console::output(json, &resp, console::service_setting_remove(&resp));
-            client.close()?;
+            let resp = service_setting_remove(client, creds.user, creds.pwd, req)?;
+            console::output(output, &resp, console::service_setting_remove(&resp));
         }
         ServiceSettingCmd::Apply {
             addr,
@@ -181,19 +189,18 @@ pub fn run(json: bool, cfg: &ClientConfig, command: ServiceSettingCmd) -> Result
             cluster_pwd,
             server,
         } => {
-            let cluster = parse_uuid_arg(&cluster)?;
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_arg(client, &cluster)?;
             let server = parse_uuid_arg(&server)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
             let creds = cluster_auth_optional(
-                &mut client,
+                client,
                 cluster,
                 cluster_user.as_deref(),
                 cluster_pwd.as_deref(),
             )?;
             let req = ServiceSettingApplyRpc { cluster, server };
-            let resp = service_setting_apply(&mut client, creds.user, creds.pwd, req)?;
-            console::output(json, &resp, console::service_setting_apply(&resp));
-            client.close()?;
+            let resp = service_setting_apply(client, creds.user, creds.pwd, req)?;
+            console::output(output, &resp, console::service_setting_apply(&resp));
         }
         ServiceSettingCmd::GetServiceDataDirsForTransfer {
             addr,
@@ -203,11 +210,11 @@ pub fn run(json: bool, cfg: &ClientConfig, command: ServiceSettingCmd) -> Result
             server,
             service_name,
         } => {
-            let cluster = parse_uuid_arg(&cluster)?;
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_arg(client, &cluster)?;
             let server = parse_uuid_arg(&server)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
             let creds = cluster_auth_optional(
-                &mut client,
+                client,
                 cluster,
                 cluster_user.as_deref(),
                 cluster_pwd.as_deref(),
@@ -218,17 +225,16 @@ pub fn run(json: bool, cfg: &ClientConfig, command: ServiceSettingCmd) -> Result
                 service_name,
             };
             let resp = service_setting_get_service_data_dirs_for_transfer(
-                &mut client,
+                client,
                 creds.user,
                 creds.pwd,
                 req,
             )?;
             console::output(
-                json,
+                output,
                 &resp,
                 console::service_setting_get_data_dirs_for_transfer(&resp.records),
             );
-            client.close()?;
         }
     }
     Ok(())
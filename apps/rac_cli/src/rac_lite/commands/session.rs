@@ -1,4 +1,3 @@
-use rac_protocol::client::{ClientConfig, RacClient};
 use rac_protocol::commands::{
     cluster_auth_optional,
     session_info,
@@ -7,31 +6,53 @@ use rac_protocol::commands::{
     session_terminate,
 };
 use rac_protocol::error::Result;
+use rac_protocol::policy::session_target;
 
 use crate::rac_lite::cli::SessionCmd;
-use crate::rac_lite::console_output as console;
-use crate::rac_lite::parse::parse_uuid_arg;
+use crate::rac_lite::connector::Connector;
+use crate::rac_lite::console_output::{self as console, Output};
+use crate::rac_lite::parse::{parse_uuid_arg, resolve_cluster_arg, resolve_cluster_auth};
 
-pub fn run(json: bool, cfg: &ClientConfig, command: SessionCmd) -> Result<()> {
+pub fn run(output: Output, conn: &mut Connector, command: SessionCmd) -> Result<()> {
     match command {
-        SessionCmd::List { addr, cluster } => {
-            let cluster = parse_uuid_arg(&cluster)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
-            let resp = session_list(&mut client, cluster)?;
-            console::output(json, &resp, console::session_list(&resp.records));
-            client.close()?;
+        SessionCmd::List {
+            addr,
+            cluster,
+            cluster_user,
+            cluster_pwd,
+            licenses,
+        } => {
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_auth(
+                client,
+                &cluster,
+                cluster_user.as_deref(),
+                cluster_pwd.as_deref(),
+            )?;
+            let resp = session_list(client, cluster)?;
+            if licenses {
+                console::output(output, &resp, console::session_list_licenses(&resp.records));
+            } else {
+                console::output(output, &resp, console::session_list(&resp.records));
+            }
         }
         SessionCmd::Info {
             addr,
             cluster,
+            cluster_user,
+            cluster_pwd,
             session,
         } => {
-            let cluster = parse_uuid_arg(&cluster)?;
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_auth(
+                client,
+                &cluster,
+                cluster_user.as_deref(),
+                cluster_pwd.as_deref(),
+            )?;
             let session = parse_uuid_arg(&session)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
-            let resp = session_info(&mut client, cluster, session)?;
-            console::output(json, &resp, console::session_info(&resp.record));
-            client.close()?;
+            let resp = session_info(client, cluster, session)?;
+            console::output(output, &resp, console::session_info(&resp.record));
         }
         SessionCmd::Terminate {
             addr,
@@ -41,25 +62,28 @@ pub fn run(json: bool, cfg: &ClientConfig, command: SessionCmd) -> Result<()> {
             session,
             error_message,
         } => {
-            let cluster = parse_uuid_arg(&cluster)?;
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_arg(client, &cluster)?;
             let session = parse_uuid_arg(&session)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
             let creds = cluster_auth_optional(
-                &mut client,
+                client,
                 cluster,
                 cluster_user.as_deref(),
                 cluster_pwd.as_deref(),
             )?;
+            conn.guard(&addr, |client| {
+                session_target(client, "session.terminate", cluster, session)
+            })?;
+            let client = conn.client(&addr)?;
             let resp = session_terminate(
-                &mut client,
+                client,
                 creds.user,
                 creds.pwd,
                 cluster,
                 session,
                 error_message,
             )?;
-            console::output(json, &resp, console::session_terminate(&resp));
-            client.close()?;
+            console::output(output, &resp, console::session_terminate(&resp));
         }
         SessionCmd::InterruptCurrentServerCall {
             addr,
@@ -69,25 +93,33 @@ pub fn run(json: bool, cfg: &ClientConfig, command: SessionCmd) -> Result<()> {
             session,
             error_message,
         } => {
-            let cluster = parse_uuid_arg(&cluster)?;
+            let client = conn.client(&addr)?;
+            let cluster = resolve_cluster_arg(client, &cluster)?;
             let session = parse_uuid_arg(&session)?;
-            let mut client = RacClient::connect(&addr, cfg.clone())?;
             let creds = cluster_auth_optional(
-                &mut client,
+                client,
                 cluster,
                 cluster_user.as_deref(),
                 cluster_pwd.as_deref(),
             )?;
+            conn.guard(&addr, |client| {
+                session_target(
+                    client,
+                    "session.interrupt_current_server_call",
+                    cluster,
+                    session,
+                )
+            })?;
+            let client = conn.client(&addr)?;
             let resp = session_interrupt_current_server_call(
-                &mut client,
+                client,
                 creds.user,
                 creds.pwd,
                 cluster,
                 session,
                 error_message,
             )?;
-            console::output(json, &resp, console::session_interrupt_current_server_call(&resp));
-            client.close()?;
+            console::output(output, &resp, console::session_interrupt_current_server_call(&resp));
         }
     }
     Ok(())
@@ -0,0 +1,181 @@
+//! Command line of the vendor `rac` utility:
+//! `rac <mode> [<command>] [--option=value ...] [<host>[:<port>]]`.
+//!
+//! The arguments are rewritten into `rac_lite --format rac <mode> <command> <addr> ...`
+//! and parsed with the regular [`Cli`].
+
+use clap::{Command, CommandFactory, Parser};
+
+use crate::rac_lite::cli::Cli;
+
+const DEFAULT_HOST: &str = "localhost";
+const DEFAULT_PORT: &str = "1545";
+
+pub fn parse_rac_args() -> Cli {
+    Cli::parse_from(translate(std::env::args()))
+}
+
+pub fn translate<I>(args: I) -> Vec<String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+    let bin = args.next().unwrap_or_else(|| "rac".to_string());
+    let (options, words): (Vec<String>, Vec<String>) = args.partition(|arg| arg.starts_with('-'));
+    let mut cmd = Cli::command();
+    // Connection-level flags such as `--protocol` belong before the mode.
+    let (global, options): (Vec<String>, Vec<String>) =
+        options.into_iter().partition(|arg| is_top_level(&cmd, arg));
+
+    // The vendor utility never asks for confirmation; the policy still applies.
+    let mut out = vec![
+        bin,
+        "--format".to_string(),
+        "rac".to_string(),
+        "--yes".to_string(),
+    ];
+    out.extend(global);
+    let mut rest = words.as_slice();
+    while cmd.has_subcommands() {
+        let Some((sub, used)) = find_subcommand(&cmd, rest) else {
+            break;
+        };
+        out.push(sub.get_name().to_string());
+        cmd = sub.clone();
+        rest = &rest[used..];
+    }
+
+    let takes_addr = cmd.get_positionals().any(|arg| arg.get_id() == "addr");
+    if takes_addr {
+        match rest.split_first() {
+            Some((addr, extra)) => {
+                out.push(with_default_port(addr));
+                rest = extra;
+            }
+            None => out.push(format!("{DEFAULT_HOST}:{DEFAULT_PORT}")),
+        }
+    }
+    out.extend(rest.iter().cloned());
+    out.extend(options);
+    out
+}
+
+/// Matches `summary list` against `summary-list`, preferring the longest run of words.
+fn find_subcommand<'a>(cmd: &'a Command, words: &[String]) -> Option<(&'a Command, usize)> {
+    (1..=words.len()).rev().find_map(|len| {
+        let name = words[..len].join("-");
+        cmd.find_subcommand(&name).map(|sub| (sub, len))
+    })
+}
+
+fn is_top_level(cmd: &Command, option: &str) -> bool {
+    let name = option.trim_start_matches('-');
+    let name = name.split_once('=').map_or(name, |(name, _)| name);
+    name == "version" || cmd.get_arguments().any(|arg| arg.get_long() == Some(name))
+}
+
+fn with_default_port(addr: &str) -> String {
+    if addr.contains(':') {
+        addr.to_string()
+    } else {
+        format!("{addr}:{DEFAULT_PORT}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rac_lite::cli::{SessionCmd, TopCommand};
+
+    fn rac(line: &str) -> Vec<String> {
+        translate(line.split_whitespace().map(str::to_string))
+    }
+
+    #[test]
+    fn rewrites_official_syntax() {
+        assert_eq!(
+            rac("rac session list --cluster=ab12 srv:1545"),
+            [
+                "rac",
+                "--format",
+                "rac",
+                "--yes",
+                "session",
+                "list",
+                "srv:1545",
+                "--cluster=ab12"
+            ]
+        );
+        assert_eq!(
+            rac("rac infobase --cluster=ab12 summary list srv"),
+            [
+                "rac",
+                "--format",
+                "rac",
+                "--yes",
+                "infobase",
+                "summary-list",
+                "srv:1545",
+                "--cluster=ab12"
+            ]
+        );
+        assert_eq!(
+            rac("rac cluster admin list --cluster=ab12 --cluster-user=admin"),
+            [
+                "rac",
+                "--format",
+                "rac",
+                "--yes",
+                "cluster",
+                "admin",
+                "list",
+                "localhost:1545",
+                "--cluster=ab12",
+                "--cluster-user=admin",
+            ]
+        );
+    }
+
+    #[test]
+    fn translated_arguments_parse() {
+        let args = rac("rac process list --cluster=ab12 --licenses srv:1541");
+        assert!(Cli::try_parse_from(args).is_ok());
+        let args = rac("rac cluster list");
+        assert!(Cli::try_parse_from(args).is_ok());
+
+        for line in [
+            "rac session list --cluster=ab12 --cluster-user=admin --cluster-pwd=x host:1545",
+            "rac session list --cluster=ab12 --licenses host",
+            "rac session info --cluster=ab12 --cluster-user=admin --session=cd34",
+            "rac connection list --cluster=ab12 --cluster-user=admin --cluster-pwd=x",
+            "rac infobase summary list --cluster=ab12 --cluster-user=admin",
+            "rac infobase info --cluster=ab12 --cluster-user=admin --infobase=ef56",
+            "rac process list --cluster=ab12 --cluster-user=admin --licenses",
+            "rac server list --cluster=ab12 --cluster-user=admin",
+            "rac lock list --cluster=ab12 --cluster-user=admin",
+            "rac limit list --cluster=ab12 --cluster-user=admin",
+        ] {
+            if let Err(err) = Cli::try_parse_from(rac(line)) {
+                panic!("{line}: {err}");
+            }
+        }
+        let cli = Cli::try_parse_from(rac(
+            "rac session list --cluster=ab12 --cluster-user=admin --licenses srv",
+        ))
+        .expect("session list");
+        match cli.command {
+            TopCommand::Session {
+                command:
+                    SessionCmd::List {
+                        cluster_user,
+                        licenses,
+                        ..
+                    },
+            } => {
+                assert_eq!(cluster_user.as_deref(), Some("admin"));
+                assert!(licenses);
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,445 @@
+//! Named connection profiles from `~/.config/rac_lite/config.toml`.
+//!
+//! A profile fills in the server address, cluster and credentials of the
+//! invoked command when they are not given on the command line, and the
+//! protocol and timeouts when the global flags are omitted:
+//!
+//! ```toml
+//! default_profile = "prod"
+//!
+//! [profiles.prod]
+//! addr = "ras.example.org:1545"
+//! cluster = "Main"
+//! protocol = "v16"
+//! read_timeout_ms = 10000
+//! cluster_user = "admin"
+//! cluster_pwd = { env = "RAC_PROD_PWD" }   # or { file = "..." }, { command = "..." }
+//! ```
+
+use std::collections::BTreeMap;
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command as Process;
+
+use clap::error::ErrorKind;
+use clap::{ArgMatches, Command, CommandFactory, FromArgMatches, Parser};
+use serde::Deserialize;
+
+use crate::rac_lite::cli::{Cli, ProtocolArg};
+
+const CONFIG_ENV: &str = "RAC_LITE_CONFIG";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigFile {
+    pub default_profile: Option<String>,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    pub addr: Option<String>,
+    pub cluster: Option<String>,
+    pub protocol: Option<ProtocolArg>,
+    pub connect_timeout_ms: Option<u64>,
+    pub read_timeout_ms: Option<u64>,
+    pub write_timeout_ms: Option<u64>,
+    pub cluster_user: Option<String>,
+    pub cluster_pwd: Option<Secret>,
+    pub agent_user: Option<String>,
+    pub agent_pwd: Option<Secret>,
+}
+
+/// A password kept out of the command line.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Secret {
+    Plain(String),
+    Env {
+        env: String,
+    },
+    File {
+        file: PathBuf,
+    },
+    /// Shell command printing the password, e.g. `pass show rac/prod`.
+    Command {
+        command: String,
+    },
+}
+
+impl Secret {
+    pub fn resolve(&self) -> Result<String, String> {
+        match self {
+            Secret::Plain(value) => Ok(value.clone()),
+            Secret::Env { env: name } => {
+                env::var(name).map_err(|err| format!("password variable {name}: {err}"))
+            }
+            Secret::File { file } => fs::read_to_string(expand_home(file))
+                .map(|text| trim_line_end(&text).to_string())
+                .map_err(|err| format!("password file {}: {err}", file.display())),
+            Secret::Command { command } => {
+                let output = shell(command)
+                    .output()
+                    .map_err(|err| format!("password command `{command}`: {err}"))?;
+                if !output.status.success() {
+                    return Err(format!(
+                        "password command `{command}` failed: {}",
+                        output.status
+                    ));
+                }
+                let text = String::from_utf8(output.stdout)
+                    .map_err(|_| format!("password command `{command}` printed non-UTF-8"))?;
+                Ok(trim_line_end(&text).to_string())
+            }
+        }
+    }
+}
+
+impl ConfigFile {
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        toml::from_str(raw).map_err(|err| err.to_string())
+    }
+
+    /// `Ok(None)` when the file does not exist.
+    pub fn load(path: &Path) -> Result<Option<Self>, String> {
+        match fs::read_to_string(path) {
+            Ok(raw) => Self::parse(&raw)
+                .map(Some)
+                .map_err(|err| format!("{}: {err}", path.display())),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(format!("{}: {err}", path.display())),
+        }
+    }
+
+    /// The profile named `name`, or the default one when `name` is `None`.
+    pub fn profile(&self, name: Option<&str>) -> Result<Option<&Profile>, String> {
+        match name.or(self.default_profile.as_deref()) {
+            Some(name) => self
+                .profiles
+                .get(name)
+                .map(Some)
+                .ok_or_else(|| format!("unknown profile: {name}")),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Parses the process arguments, filling in values from the selected
+/// profile. Exits with a usage error when the config or profile is invalid.
+pub fn parse_args() -> Cli {
+    let args: Vec<OsString> = env::args_os().collect();
+    match parse_from(args) {
+        Ok(cli) => cli,
+        Err(err) => err.exit(),
+    }
+}
+
+pub fn parse_from(args: Vec<OsString>) -> Result<Cli, clap::Error> {
+    let config_error = |msg: String| Cli::command().error(ErrorKind::InvalidValue, msg);
+    // Only the profile flags and the subcommand path matter here; usage
+    // errors are reported by the full parse below.
+    let pre = Cli::command()
+        .ignore_errors(true)
+        .try_get_matches_from(&args)
+        .unwrap_or_default();
+    let requested = pre.get_one::<String>("profile").cloned();
+    let path = pre
+        .get_one::<PathBuf>("config")
+        .cloned()
+        .or_else(default_config_path);
+    let config = match path {
+        Some(path) => ConfigFile::load(&path).map_err(config_error)?,
+        None => None,
+    };
+    let profile = match (&config, &requested) {
+        (Some(config), _) => config
+            .profile(requested.as_deref())
+            .map_err(config_error)?
+            .cloned(),
+        (None, Some(name)) => {
+            return Err(config_error(format!(
+                "profile {name} requested but no config file found"
+            )))
+        }
+        (None, None) => None,
+    };
+    let Some(profile) = profile else {
+        return Cli::try_parse_from(args);
+    };
+    apply_profile(args, &pre, &profile).map(|(cli, _)| cli)
+}
+
+/// Parses `args` with `profile` filling in what they omit; also returns the
+/// matches so callers can tell given values from defaults.
+pub fn parse_with_profile(
+    args: Vec<OsString>,
+    profile: &Profile,
+) -> Result<(Cli, ArgMatches), clap::Error> {
+    let pre = Cli::command()
+        .ignore_errors(true)
+        .try_get_matches_from(&args)
+        .unwrap_or_default();
+    apply_profile(args, &pre, profile)
+}
+
+fn apply_profile(
+    args: Vec<OsString>,
+    pre: &ArgMatches,
+    profile: &Profile,
+) -> Result<(Cli, ArgMatches), clap::Error> {
+    let config_error = |msg: String| Cli::command().error(ErrorKind::InvalidValue, msg);
+    let (subcommands, leaf) = subcommand_path(pre);
+    let defaults =
+        profile_defaults(profile, &Cli::command(), &subcommands, leaf).map_err(config_error)?;
+    let matches =
+        with_defaults(Cli::command(), &subcommands, &defaults).try_get_matches_from(args)?;
+    let mut cli = Cli::from_arg_matches(&matches)?;
+    cli.protocol = cli.protocol.or(profile.protocol);
+    cli.connect_timeout_ms = cli.connect_timeout_ms.or(profile.connect_timeout_ms);
+    cli.read_timeout_ms = cli.read_timeout_ms.or(profile.read_timeout_ms);
+    cli.write_timeout_ms = cli.write_timeout_ms.or(profile.write_timeout_ms);
+    Ok((cli, matches))
+}
+
+/// `$RAC_LITE_CONFIG`, else `rac_lite/config.toml` under `$XDG_CONFIG_HOME`
+/// or `~/.config`.
+pub fn default_config_path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os(CONFIG_ENV) {
+        return Some(PathBuf::from(path));
+    }
+    let base = match env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => home_dir()?.join(".config"),
+    };
+    Some(base.join("rac_lite").join("config.toml"))
+}
+
+/// `policy.toml` in the directory of [`default_config_path`].
+pub fn default_policy_path() -> Option<PathBuf> {
+    default_config_path().map(|path| path.with_file_name("policy.toml"))
+}
+
+fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+fn trim_line_end(text: &str) -> &str {
+    text.trim_end_matches(['\r', '\n'])
+}
+
+#[cfg(unix)]
+fn shell(command: &str) -> Process {
+    let mut process = Process::new("sh");
+    process.arg("-c").arg(command);
+    process
+}
+
+#[cfg(not(unix))]
+fn shell(command: &str) -> Process {
+    let mut process = Process::new("cmd");
+    process.arg("/C").arg(command);
+    process
+}
+
+/// Names of the invoked subcommands and the matches of the innermost one.
+pub fn subcommand_path(matches: &ArgMatches) -> (Vec<String>, &ArgMatches) {
+    let mut names = Vec::new();
+    let mut leaf = matches;
+    while let Some((name, sub)) = leaf.subcommand() {
+        names.push(name.to_string());
+        leaf = sub;
+    }
+    (names, leaf)
+}
+
+/// A default for one argument of the invoked subcommand.
+struct ArgDefault {
+    id: &'static str,
+    value: String,
+    secret: bool,
+}
+
+/// Profile values for the arguments the invoked subcommand has. Passwords
+/// are resolved only when the subcommand takes one and it was not given.
+fn profile_defaults(
+    profile: &Profile,
+    root: &Command,
+    subcommands: &[String],
+    leaf: &ArgMatches,
+) -> Result<Vec<ArgDefault>, String> {
+    let mut command = root;
+    for name in subcommands {
+        match command.find_subcommand(name) {
+            Some(sub) => command = sub,
+            None => return Ok(Vec::new()),
+        }
+    }
+    let has_arg = |id: &str| command.get_arguments().any(|arg| arg.get_id() == id);
+    let given = |id: &str| leaf.try_contains_id(id).unwrap_or(false);
+    let plain = [
+        ("addr", &profile.addr),
+        ("cluster", &profile.cluster),
+        ("cluster_user", &profile.cluster_user),
+        ("agent_user", &profile.agent_user),
+    ];
+    let mut defaults: Vec<ArgDefault> = plain
+        .into_iter()
+        .filter(|(id, _)| has_arg(id))
+        .filter_map(|(id, value)| {
+            value.clone().map(|value| ArgDefault {
+                id,
+                value,
+                secret: false,
+            })
+        })
+        .collect();
+    for (id, secret) in [
+        ("cluster_pwd", &profile.cluster_pwd),
+        ("agent_pwd", &profile.agent_pwd),
+    ] {
+        if let Some(secret) = secret.as_ref().filter(|_| has_arg(id) && !given(id)) {
+            defaults.push(ArgDefault {
+                id,
+                value: secret.resolve()?,
+                secret: true,
+            });
+        }
+    }
+    Ok(defaults)
+}
+
+fn with_defaults(command: Command, subcommands: &[String], defaults: &[ArgDefault]) -> Command {
+    match subcommands.split_first() {
+        Some((name, rest)) => {
+            command.mut_subcommand(name, |sub| with_defaults(sub, rest, defaults))
+        }
+        None => defaults.iter().fold(command, |command, default| {
+            command.mut_arg(default.id, |arg| {
+                arg.default_value(default.value.clone())
+                    .required(false)
+                    .hide_default_value(default.secret)
+            })
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rac_lite::cli::{ClusterCmd, SessionCmd, TopCommand};
+
+    const CONFIG: &str = r#"
+default_profile = "prod"
+
+[profiles.prod]
+addr = "ras.example.org:1545"
+cluster = "Main"
+protocol = "v11"
+read_timeout_ms = 10000
+cluster_user = "admin"
+cluster_pwd = { env = "RAC_LITE_TEST_PWD" }
+
+[profiles.dev]
+addr = "127.0.0.1:1545"
+cluster_pwd = { command = "echo dev-secret" }
+"#;
+
+    fn parse(config: &Path, args: &[&str]) -> Result<Cli, clap::Error> {
+        let mut argv: Vec<OsString> = vec!["rac_lite".into(), "--config".into()];
+        argv.push(config.into());
+        argv.extend(args.iter().map(OsString::from));
+        parse_from(argv)
+    }
+
+    fn write_config(name: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("rac_lite_{name}_{}.toml", std::process::id()));
+        fs::write(&path, CONFIG).expect("write config");
+        path
+    }
+
+    #[test]
+    fn parses_profiles_and_secrets() {
+        let config = ConfigFile::parse(CONFIG).expect("config");
+        let prod = config.profile(None).expect("profile").expect("default");
+        assert_eq!(prod.protocol, Some(ProtocolArg::V11));
+        assert!(
+            matches!(&prod.cluster_pwd, Some(Secret::Env { env }) if env == "RAC_LITE_TEST_PWD")
+        );
+        let err = config.profile(Some("nope")).expect_err("unknown");
+        assert!(err.contains("nope"), "{err}");
+        assert!(ConfigFile::parse("[profiles.x]\nadress = \"a\"\n").is_err());
+
+        let dev = config.profile(Some("dev")).expect("dev").expect("dev");
+        let pwd = dev.cluster_pwd.as_ref().expect("pwd").resolve();
+        assert_eq!(pwd.as_deref(), Ok("dev-secret"));
+        assert_eq!(Secret::Plain("x".into()).resolve().as_deref(), Ok("x"));
+    }
+
+    #[test]
+    fn profile_fills_missing_arguments() {
+        let path = write_config("fill");
+        let cli = parse(&path, &["--profile", "dev", "cluster", "list"]).expect("parse");
+        assert_eq!(cli.protocol, None);
+        match cli.command {
+            TopCommand::Cluster {
+                command: ClusterCmd::List { addr },
+            } => assert_eq!(addr, "127.0.0.1:1545"),
+            other => panic!("unexpected command: {other:?}"),
+        }
+
+        // The default profile applies; explicit arguments win.
+        let cli = parse(
+            &path,
+            &[
+                "--read-timeout-ms",
+                "500",
+                "session",
+                "list",
+                "10.0.0.1:1545",
+                "--cluster",
+                "Other",
+                "--cluster-pwd",
+                "given",
+            ],
+        )
+        .expect("parse");
+        assert_eq!(cli.protocol, Some(ProtocolArg::V11));
+        assert_eq!(cli.read_timeout_ms, Some(500));
+        match cli.command {
+            TopCommand::Session {
+                command:
+                    SessionCmd::List {
+                        addr,
+                        cluster,
+                        cluster_user,
+                        cluster_pwd,
+                        ..
+                    },
+            } => {
+                assert_eq!(addr, "10.0.0.1:1545");
+                assert_eq!(cluster, "Other");
+                assert_eq!(cluster_user.as_deref(), Some("admin"));
+                assert_eq!(cluster_pwd.as_deref(), Some("given"));
+            }
+            other => panic!("unexpected command: {other:?}"),
+        }
+
+        let err = parse(&path, &["--profile", "qa", "agent", "version"]).expect_err("unknown");
+        assert!(err.to_string().contains("unknown profile: qa"), "{err}");
+        let _ = fs::remove_file(path);
+    }
+}
@@ -0,0 +1,121 @@
+use rac_protocol::client::{ClientConfig, RacClient};
+use rac_protocol::dry_run::PlannedRpc;
+use rac_protocol::error::Result;
+use rac_protocol::policy::{self, Target};
+use rac_protocol::protocol::ProtocolVersion;
+
+use crate::rac_lite::guard::Guard;
+
+/// Connection shared by the commands of one run. Opened on first use and
+/// reopened when a command targets another address.
+pub struct Connector {
+    cfg: ClientConfig,
+    addr: String,
+    client: Option<RacClient>,
+    /// Set for `--dry-run`: clients record RPCs instead of connecting.
+    dry_run: Option<ProtocolVersion>,
+    guard: Guard,
+}
+
+impl Connector {
+    pub fn new(cfg: ClientConfig) -> Self {
+        Self {
+            cfg,
+            addr: String::new(),
+            client: None,
+            dry_run: None,
+            guard: Guard::default(),
+        }
+    }
+
+    pub fn with_guard(self, guard: Guard) -> Self {
+        Self { guard, ..self }
+    }
+
+    /// A connector that records RPCs encoded for the protocol this one would
+    /// try first.
+    pub fn recorder(&self) -> Self {
+        let cfg = ClientConfig {
+            read_only: false,
+            ..self.cfg.clone()
+        };
+        Self {
+            dry_run: Some(self.cfg.protocol.candidates()[0]),
+            ..Self::new(cfg)
+        }
+    }
+
+    pub fn read_only(&self) -> bool {
+        self.cfg.read_only
+    }
+
+    /// Applies to the open connection too, so one `shell` or `exec` line can
+    /// be made read-only.
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.cfg.read_only = read_only;
+        if let Some(client) = self.client.as_mut() {
+            client.set_read_only(read_only);
+        }
+    }
+
+    /// What a recording connector would have sent.
+    pub fn planned(&self) -> &[PlannedRpc] {
+        self.client.as_ref().map_or(&[], RacClient::planned)
+    }
+
+    pub fn protocol_name(&self) -> Option<&'static str> {
+        self.client.as_ref().map(RacClient::protocol_name)
+    }
+
+    pub fn client(&mut self, addr: &str) -> Result<&mut RacClient> {
+        if self.addr != addr {
+            self.close()?;
+        }
+        let client = match self.client.take() {
+            Some(client) => client,
+            None => match self.dry_run {
+                Some(protocol) => {
+                    let mut client = RacClient::dry_run(protocol);
+                    client.set_read_only(self.cfg.read_only);
+                    client
+                }
+                None => RacClient::connect(addr, self.cfg.clone())?,
+            },
+        };
+        self.addr = addr.to_string();
+        Ok(self.client.insert(client))
+    }
+
+    /// Resolves what a destructive command is about to hit and lets the
+    /// guard refuse it. Skipped when the RPC would not be sent anyway.
+    pub fn guard(
+        &mut self,
+        addr: &str,
+        target: impl FnOnce(&mut RacClient) -> Result<Target>,
+    ) -> Result<()> {
+        self.guard_all(addr, |client| Ok(vec![target(client)?]))
+    }
+
+    pub fn guard_all(
+        &mut self,
+        addr: &str,
+        targets: impl FnOnce(&mut RacClient) -> Result<Vec<Target>>,
+    ) -> Result<()> {
+        self.client(addr)?;
+        let client = self.client.as_mut().expect("connected above");
+        let targets = policy::enforce(client, self.guard.policy()?, targets)?;
+        self.guard.confirm(&targets)
+    }
+
+    /// Drops the connection without the close handshake, e.g. after an I/O error.
+    pub fn reset(&mut self) {
+        self.client = None;
+    }
+
+    pub fn close(&mut self) -> Result<()> {
+        match self.client.take() {
+            Some(client) => client.close(),
+            None => Ok(()),
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use std::cell::RefCell;
 use std::fmt::{self, Display, Write as _};
 
 use serde::Serialize;
@@ -12,9 +13,12 @@ use rac_protocol::commands::{
 };
 use rac_protocol::rpc::AckResponse;
 use rac_protocol::rac_wire::format_uuid;
+use rac_protocol::tabular::{self, Format};
 use rac_protocol::Uuid16;
 
-use super::format::{info_display_to_string, list_to_string, write_trimmed, MoreLabel};
+use super::format::{
+    info_display_to_string, list_to_string, write_rac_blocks, write_trimmed, MoreLabel, RacFields,
+};
 
 macro_rules! outln {
     ($out:expr, $($arg:tt)*) => {
@@ -33,25 +37,78 @@ fn display_str(value: &str) -> &str {
 }
 
 fn render_ack(f: &mut fmt::Formatter<'_>, label: &str, acknowledged: bool) -> fmt::Result {
-    if acknowledged {
+    if f.alternate() && acknowledged {
+        // `rac` is silent on success.
+        Ok(())
+    } else if acknowledged {
         write!(f, "{label}: ok")
     } else {
         write!(f, "{label}: failed")
     }
 }
 
-pub fn output<T, D>(json: bool, resp: &T, text: D)
+#[derive(Clone, Copy, Debug)]
+pub enum Output {
+    Text,
+    /// Same bytes as the vendor `rac` utility; rendered via `{:#}`.
+    Rac,
+    Data(Format),
+    /// Nothing is printed; the response is still kept by [`keep_printed`].
+    Quiet,
+}
+
+thread_local! {
+    /// Responses printed on this thread, kept only after [`keep_printed`].
+    static PRINTED: RefCell<Option<Vec<serde_json::Value>>> = const { RefCell::new(None) };
+}
+
+/// Starts keeping printed responses for [`take_printed`]; the shell builds
+/// its completions from them.
+pub fn keep_printed() {
+    PRINTED.with(|printed| *printed.borrow_mut() = Some(Vec::new()));
+}
+
+pub fn take_printed() -> Vec<serde_json::Value> {
+    PRINTED.with(|printed| {
+        printed
+            .borrow_mut()
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    })
+}
+
+pub fn output<T, D>(mode: Output, resp: &T, text: D)
 where
     T: Serialize,
     D: Display,
 {
-    if json {
-        match serde_json::to_string_pretty(resp) {
+    PRINTED.with(|printed| {
+        if let Some(printed) = printed.borrow_mut().as_mut() {
+            if let Ok(value) = serde_json::to_value(resp) {
+                printed.push(value);
+            }
+        }
+    });
+    match mode {
+        Output::Quiet => {}
+        Output::Text => println!("{text}"),
+        Output::Rac => {
+            let out = format!("{text:#}");
+            if out.is_empty() || out.ends_with('\n') {
+                print!("{out}");
+            } else {
+                println!("{out}");
+            }
+        }
+        Output::Data(Format::Json) => match serde_json::to_string_pretty(resp) {
             Ok(payload) => println!("{payload}"),
             Err(err) => eprintln!("json error: {err}"),
-        }
-    } else {
-        println!("{text}");
+        },
+        Output::Data(format) => match serde_json::to_value(resp) {
+            Ok(value) => print!("{}", tabular::render(format, &value)),
+            Err(err) => eprintln!("json error: {err}"),
+        },
     }
 }
 
@@ -92,6 +149,16 @@ pub fn process_info_licenses(item: &ProcessRecord) -> ProcessInfoLicensesDisplay
     ProcessInfoLicensesDisplay { item }
 }
 
+pub struct SessionListLicensesDisplay<'a> {
+    items: &'a [SessionRecord],
+}
+
+/// Sessions holding a license, one block per license as `rac session list
+/// --licenses` prints them.
+pub fn session_list_licenses(items: &[SessionRecord]) -> SessionListLicensesDisplay<'_> {
+    SessionListLicensesDisplay { items }
+}
+
 pub struct ProfileListDisplay<'a> {
     items: &'a [ProfileRecord],
 }
@@ -364,6 +431,9 @@ impl Display for ServiceSettingApplyDisplay<'_> {
 
 impl Display for ProcessListLicensesDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, self.items.iter().flat_map(rac_process_license_blocks));
+        }
         let out = list_to_string("processes", self.items, 5, MoreLabel::Default, |out, _idx, item| {
             outln!(out, "{}", process_info_licenses(item));
         });
@@ -373,6 +443,9 @@ impl Display for ProcessListLicensesDisplay<'_> {
 
 impl Display for ProcessInfoLicensesDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, rac_process_license_blocks(self.item));
+        }
         let mut out = String::new();
         let item = self.item;
         outln!(&mut out, "process: {}", format_uuid(&item.process));
@@ -386,8 +459,36 @@ impl Display for ProcessInfoLicensesDisplay<'_> {
     }
 }
 
+impl Display for SessionListLicensesDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let licensed: Vec<&SessionRecord> = self
+            .items
+            .iter()
+            .filter(|item| has_license(&item.license))
+            .collect();
+        if f.alternate() {
+            return write_rac_blocks(f, licensed.into_iter().map(rac_session_license_fields));
+        }
+        let out = list_to_string("licenses", &licensed, 5, MoreLabel::Default, |out, _idx, item| {
+            outln!(
+                out,
+                "- session {} {} ({}, {})",
+                format_uuid(&item.session),
+                display_str(&item.user_name),
+                display_str(&item.host),
+                display_str(&item.app_id)
+            );
+            append_license_prefixed(out, &item.license, "  ");
+        });
+        write_trimmed(f, &out)
+    }
+}
+
 impl Display for ProfileListDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, self.items.iter().map(rac_profile_fields));
+        }
         let out = list_to_string("profiles", self.items, 5, MoreLabel::Default, |out, _idx, item| {
             outln!(
                 out,
@@ -530,6 +631,107 @@ fn dedicate_managers_label(value: u32) -> String {
     }
 }
 
+fn limit_action_label(value: u8) -> String {
+    match value {
+        0 => "none".to_string(),
+        1 => "set-low-priority-thread".to_string(),
+        2 => "interrupt-current-call".to_string(),
+        3 => "interrupt-session".to_string(),
+        _ => value.to_string(),
+    }
+}
+
+fn counter_collection_time_label(value: u64) -> String {
+    match value {
+        0 => "current-call".to_string(),
+        _ => value.to_string(),
+    }
+}
+
+fn counter_group_label(value: u8) -> String {
+    match value {
+        0 => "users".to_string(),
+        1 => "data-separation".to_string(),
+        _ => value.to_string(),
+    }
+}
+
+fn counter_filter_type_label(value: u8) -> String {
+    match value {
+        0 => "all-selected".to_string(),
+        1 => "all-but-selected".to_string(),
+        2 => "all".to_string(),
+        _ => value.to_string(),
+    }
+}
+
+fn analyze_label(value: u8) -> &'static str {
+    if value != 0 { "analyze" } else { "not-analyze" }
+}
+
+fn profile_access_label(value: u8) -> String {
+    match value {
+        0 => "list".to_string(),
+        1 => "full".to_string(),
+        _ => value.to_string(),
+    }
+}
+
+fn rac_process_license_blocks(item: &ProcessRecord) -> Vec<RacFields> {
+    item.licenses
+        .iter()
+        .map(|license| {
+            vec![
+                ("process", format_uuid(&item.process)),
+                ("host", item.host.clone()),
+                ("port", item.port.to_string()),
+                ("pid", item.pid.clone()),
+                ("full-name", format!("\"{}\"", license.file_name)),
+                ("series", format!("\"{}\"", license.key_series)),
+                ("issued-by-server", yes_no(license.issued_by_server).to_string()),
+                ("license-type", process_license_type_label(license.license_type)),
+                ("net", yes_no(license.network_key).to_string()),
+                ("max-users-all", license.max_users_all.to_string()),
+                ("max-users-cur", license.max_users_current.to_string()),
+                ("rmngr-address", format!("\"{}\"", license.server_address)),
+                ("rmngr-port", license.server_port.to_string()),
+                ("rmngr-pid", license.process_id.clone()),
+                ("short-presentation", format!("\"{}\"", license.brief_presentation)),
+                ("full-presentation", format!("\"{}\"", license.full_presentation)),
+            ]
+        })
+        .collect()
+}
+
+/// Sessions without a license decode to an empty one.
+fn has_license(license: &SessionLicense) -> bool {
+    !(license.file_name.is_empty()
+        && license.key_series.is_empty()
+        && license.brief_presentation.is_empty())
+}
+
+fn rac_session_license_fields(item: &SessionRecord) -> RacFields {
+    let license = &item.license;
+    vec![
+        ("session", format_uuid(&item.session)),
+        ("user-name", item.user_name.clone()),
+        ("host", item.host.clone()),
+        ("app-id", item.app_id.clone()),
+        ("full-name", format!("\"{}\"", license.file_name)),
+        ("series", format!("\"{}\"", license.key_series)),
+        ("issued-by-server", yes_no(license.issued_by_server).to_string()),
+        ("license-type", process_license_type_label(license.license_type)),
+        ("net", yes_no(license.network_key).to_string()),
+        ("max-users-all", license.max_users_all.to_string()),
+        ("max-users-cur", license.max_users_current.to_string()),
+        ("rmngr-address", format!("\"{}\"", license.server_address)),
+        ("rmngr-port", license.server_port.to_string()),
+        ("rmngr-pid", license.process_id.clone()),
+        ("short-presentation", format!("\"{}\"", license.brief_presentation)),
+        ("full-presentation", format!("\"{}\"", license.full_presentation)),
+    ]
+}
+
 fn append_process_license_plain(out: &mut String, license: &ProcessLicense) {
     append_process_license_fields(out, |name| name.to_string(), license);
 }
@@ -674,3 +876,196 @@ fn append_counters_prefixed(out: &mut String, record: &SessionRecord, prefix: &s
 
 // Generated console output helpers.
 include!("console_output_generated.rs");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rac_protocol::commands::cluster::{ClusterAdminListResp, ClusterInfoResp};
+    use rac_protocol::commands::{
+        AgentAdminListResp, ConnectionListResp, CounterAccumulatedValuesResp, CounterInfoResp,
+        CounterListResp, CounterValuesResp, InfobaseSummaryListResp, LimitInfoResp, LimitListResp,
+        LockListResp, ProcessListResp, ProfileListResp, RuleListResp, SessionListResp,
+    };
+    use rac_protocol::protocol::ProtocolVersion;
+    use rac_protocol::rac_wire::{parse_frames, OPCODE_RPC};
+    use rac_protocol::rpc::Response;
+
+    fn decode<R: Response>(hex: &str, protocol: ProtocolVersion) -> R {
+        let hex = hex.trim().as_bytes();
+        let bytes: Vec<u8> = hex
+            .chunks(2)
+            .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).unwrap(), 16).unwrap())
+            .collect();
+        // Captures hold either the whole server stream or just the RPC payload.
+        let payload = parse_frames(&bytes)
+            .ok()
+            .and_then(|frames| frames.into_iter().rev().find(|frame| frame.opcode == OPCODE_RPC))
+            .map(|frame| frame.payload)
+            .unwrap_or(bytes);
+        R::decode(&payload, protocol.boxed().as_ref()).expect("decode response")
+    }
+
+    macro_rules! rac_reference {
+        ($name:ident, $capture:literal, $protocol:ident, $resp:ty, |$r:ident| $display:expr) => {
+            #[test]
+            fn $name() {
+                let $r: $resp = decode(
+                    include_str!(concat!("../../../../artifacts/rac/", $capture, "_response.hex")),
+                    ProtocolVersion::$protocol,
+                );
+                let expected =
+                    include_str!(concat!("../../../../artifacts/rac/", $capture, "_rac.out"));
+                assert_eq!(format!("{:#}", $display), expected);
+            }
+        };
+    }
+
+    rac_reference!(
+        rac_agent_admin_list,
+        "v11/v11_agent_admin_list_ro",
+        V11_0,
+        AgentAdminListResp,
+        |r| agent_admin_list(&r.admins)
+    );
+    rac_reference!(
+        rac_cluster_admin_list,
+        "v11/v11_cluster_admin_list_ro",
+        V11_0,
+        ClusterAdminListResp,
+        |r| cluster_admin_list(&r.admins)
+    );
+    rac_reference!(
+        rac_cluster_info,
+        "v16/v16_20260226_053425_cluster_info",
+        V16_0,
+        ClusterInfoResp,
+        |r| cluster_info(&r.cluster)
+    );
+    rac_reference!(
+        rac_connection_list,
+        "v11/v11_connection_list_ro",
+        V11_0,
+        ConnectionListResp,
+        |r| connection_list(&r.records)
+    );
+    rac_reference!(
+        rac_counter_list,
+        "v11/v11_counter_list_ro",
+        V11_0,
+        CounterListResp,
+        |r| counter_list(&r.records)
+    );
+    rac_reference!(
+        rac_counter_info,
+        "v11/v11_counter_info_ro",
+        V11_0,
+        CounterInfoResp,
+        |r| counter_info(&r.record)
+    );
+    rac_reference!(
+        rac_counter_values,
+        "v11/v11_counter_values_ro",
+        V11_0,
+        CounterValuesResp,
+        |r| counter_values(&r.records)
+    );
+    rac_reference!(
+        rac_counter_accumulated_values,
+        "v11/v11_counter_accumulated_values_ro",
+        V11_0,
+        CounterAccumulatedValuesResp,
+        |r| counter_accumulated_values(&r.records)
+    );
+    rac_reference!(
+        rac_infobase_summary_list,
+        "v11/v11_infobase_summary_list_ro",
+        V11_0,
+        InfobaseSummaryListResp,
+        |r| infobase_summary_list(&r.summaries)
+    );
+    rac_reference!(
+        rac_limit_list,
+        "v11/v11_limit_list_ro",
+        V11_0,
+        LimitListResp,
+        |r| limit_list(&r.limits)
+    );
+    rac_reference!(
+        rac_limit_info,
+        "v11/v11_limit_info_ro",
+        V11_0,
+        LimitInfoResp,
+        |r| limit_info(&r.record)
+    );
+    rac_reference!(
+        rac_lock_list,
+        "v11/v11_lock_list_ro",
+        V11_0,
+        LockListResp,
+        |r| lock_list(&r.records)
+    );
+    rac_reference!(
+        rac_lock_list_session,
+        "v11/v11_lock_list_session_ro",
+        V11_0,
+        LockListResp,
+        |r| lock_list(&r.records)
+    );
+    rac_reference!(
+        rac_process_list_licenses,
+        "v16/process_list_licenses",
+        V16_0,
+        ProcessListResp,
+        |r| process_list_licenses(&r.records)
+    );
+    rac_reference!(
+        rac_profile_list,
+        "v11/v11_profile_list_nonempty2",
+        V11_0,
+        ProfileListResp,
+        |r| profile_list(&r.profiles)
+    );
+    rac_reference!(
+        rac_rule_list,
+        "v11/v11_rule_list_ro",
+        V11_0,
+        RuleListResp,
+        |r| rule_list(&r.records)
+    );
+    rac_reference!(
+        rac_session_list,
+        "v11/v11_session_list_ro",
+        V11_0,
+        SessionListResp,
+        |r| session_list(&r.records)
+    );
+
+    #[test]
+    fn session_licenses_list_only_licensed_sessions() {
+        let mut resp: SessionListResp = decode(
+            include_str!("../../../../artifacts/rac/v11/v11_session_list_ro_response.hex"),
+            ProtocolVersion::V11_0,
+        );
+        let out = format!("{:#}", session_list_licenses(&resp.records));
+        let blocks: Vec<&str> = out.trim_end().split("\n\n").collect();
+        assert_eq!(blocks.len(), 2, "{out}");
+        let first: Vec<&str> = blocks[0].lines().collect();
+        assert_eq!(first.len(), 16);
+        assert_eq!(first[3], "app-id             : Designer");
+        assert_eq!(first[5], "series             : \"ORGL8\"");
+        assert_eq!(first[13], "rmngr-pid          : 1256250");
+
+        resp.records[1].license = Default::default();
+        let out = format!("{:#}", session_list_licenses(&resp.records));
+        assert_eq!(out.trim_end().split("\n\n").count(), 1);
+        assert!(!out.contains("1CV8C"), "{out}");
+    }
+
+    #[test]
+    fn rac_mode_is_silent_on_acknowledged_changes() {
+        let resp = AckResponse { acknowledged: true };
+        assert_eq!(format!("{:#}", limit_remove(&resp)), "");
+        assert_eq!(format!("{}", limit_remove(&resp)), "limit-remove: ok");
+    }
+}
@@ -1,6 +1,15 @@
 // @generated by scripts/rac/console_output_codegen.py. DO NOT EDIT.
 // source: /home/alko/develop/open-source/v8_protocols/schemas/rac/console_output.toml
 
+fn rac_infobase_summary_fields(item: &InfobaseSummary) -> RacFields {
+    let fields: RacFields = vec![
+        ("infobase", format_uuid(&item.infobase).to_string()),
+        ("name", display_str(&item.name).to_string()),
+        ("descr", format!("\"{}\"", item.descr)),
+    ];
+    fields
+}
+
 pub struct InfobaseSummaryInfoDisplay<'a> {
     item: &'a InfobaseSummary,
 }
@@ -17,6 +26,9 @@ fn render_infobase_summary_info(out: &mut String, item: &InfobaseSummary) {
 
 impl Display for InfobaseSummaryInfoDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, [rac_infobase_summary_fields(self.item)]);
+        }
         let mut out = String::new();
         render_infobase_summary_info(&mut out, self.item);
         write_trimmed(f, &out)
@@ -33,6 +45,9 @@ pub fn infobase_summary_list(items: &[InfobaseSummary]) -> InfobaseSummaryListDi
 
 impl Display for InfobaseSummaryListDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, self.items.iter().map(rac_infobase_summary_fields));
+        }
         let out = list_to_string("infobases", self.items, 5, MoreLabel::Default, |out, _idx, item| {
             outln!(out, "{}", infobase_summary_info(item));
         });
@@ -40,6 +55,21 @@ impl Display for InfobaseSummaryListDisplay<'_> {
     }
 }
 
+fn rac_connection_fields(item: &ConnectionRecord) -> RacFields {
+    let fields: RacFields = vec![
+        ("connection", format_uuid(&item.connection).to_string()),
+        ("conn-id", item.conn_id.to_string()),
+        ("host", display_str(&item.host).to_string()),
+        ("process", format_uuid(&item.process).to_string()),
+        ("infobase", format_uuid(&item.infobase).to_string()),
+        ("application", format!("\"{}\"", item.application)),
+        ("connected-at", display_str(&item.connected_at).to_string()),
+        ("session-number", item.session_number.to_string()),
+        ("blocked-by-ls", item.blocked_by_ls.to_string()),
+    ];
+    fields
+}
+
 pub struct ConnectionInfoDisplay<'a> {
     item: &'a ConnectionRecord,
 }
@@ -62,6 +92,9 @@ fn render_connection_info(out: &mut String, item: &ConnectionRecord) {
 
 impl Display for ConnectionInfoDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, [rac_connection_fields(self.item)]);
+        }
         let mut out = String::new();
         render_connection_info(&mut out, self.item);
         write_trimmed(f, &out)
@@ -78,6 +111,9 @@ pub fn connection_list(items: &[ConnectionRecord]) -> ConnectionListDisplay<'_>
 
 impl Display for ConnectionListDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, self.items.iter().map(rac_connection_fields));
+        }
         let out = list_to_string("connections", self.items, 5, MoreLabel::Default, |out, _idx, item| {
             outln!(out, "{}", connection_info(item));
         });
@@ -85,6 +121,18 @@ impl Display for ConnectionListDisplay<'_> {
     }
 }
 
+fn rac_manager_fields(item: &ManagerRecord) -> RacFields {
+    let fields: RacFields = vec![
+        ("manager", format_uuid(&item.manager).to_string()),
+        ("pid", display_str(&item.pid).to_string()),
+        ("using", manager_using_label(item.using).to_string()),
+        ("host", display_str(&item.host).to_string()),
+        ("port", item.port.to_string()),
+        ("descr", format!("\"{}\"", display_str(&item.descr))),
+    ];
+    fields
+}
+
 pub struct ManagerInfoDisplay<'a> {
     item: &'a ManagerRecord,
 }
@@ -104,6 +152,9 @@ fn render_manager_info(out: &mut String, item: &ManagerRecord) {
 
 impl Display for ManagerInfoDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, [rac_manager_fields(self.item)]);
+        }
         let mut out = String::new();
         render_manager_info(&mut out, self.item);
         write_trimmed(f, &out)
@@ -120,6 +171,9 @@ pub fn manager_list(items: &[ManagerRecord]) -> ManagerListDisplay<'_> {
 
 impl Display for ManagerListDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, self.items.iter().map(rac_manager_fields));
+        }
         let out = list_to_string("managers", self.items, 5, MoreLabel::Default, |out, _idx, item| {
             outln!(out, "{}", manager_info(item));
         });
@@ -127,6 +181,29 @@ impl Display for ManagerListDisplay<'_> {
     }
 }
 
+fn rac_server_fields(item: &ServerRecord) -> RacFields {
+    let fields: RacFields = vec![
+        ("server", format_uuid(&item.server).to_string()),
+        ("agent-host", display_str(&item.agent_host).to_string()),
+        ("agent-port", item.agent_port.to_string()),
+        ("port-range", format!("{}:{}", item.port_range_start, item.port_range_end)),
+        ("name", format!("\"{}\"", display_str(&item.name))),
+        ("using", server_using_label(item.using).to_string()),
+        ("dedicate-managers", dedicate_managers_label(item.dedicate_managers).to_string()),
+        ("infobases-limit", item.infobases_limit.to_string()),
+        ("memory-limit", item.gap_1.to_string()),
+        ("connections-limit", item.connections_limit.to_string()),
+        ("safe-working-processes-memory-limit", item.gap_2.to_string()),
+        ("safe-call-memory-limit", item.safe_call_memory_limit.to_string()),
+        ("cluster-port", item.cluster_port.to_string()),
+        ("critical-total-memory", item.critical_total_memory.to_string()),
+        ("temporary-allowed-total-memory", item.temporary_allowed_total_memory.to_string()),
+        ("temporary-allowed-total-memory-time-limit", item.temporary_allowed_total_memory_time_limit.to_string()),
+        ("service-principal-name", format!("\"{}\"", display_str(&item.service_principal_name))),
+    ];
+    fields
+}
+
 pub struct ServerInfoDisplay<'a> {
     item: &'a ServerRecord,
 }
@@ -156,6 +233,9 @@ fn render_server_info(out: &mut String, item: &ServerRecord) {
 
 impl Display for ServerInfoDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, [rac_server_fields(self.item)]);
+        }
         let mut out = String::new();
         render_server_info(&mut out, self.item);
         write_trimmed(f, &out)
@@ -172,6 +252,9 @@ pub fn server_list(items: &[ServerRecord]) -> ServerListDisplay<'_> {
 
 impl Display for ServerListDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, self.items.iter().map(rac_server_fields));
+        }
         let out = list_to_string("servers", self.items, 5, MoreLabel::Default, |out, _idx, item| {
             outln!(out, "{}", server_info(item));
         });
@@ -179,6 +262,32 @@ impl Display for ServerListDisplay<'_> {
     }
 }
 
+fn rac_process_fields(item: &ProcessRecord) -> RacFields {
+    let fields: RacFields = vec![
+        ("process", format_uuid(&item.process).to_string()),
+        ("host", display_str(&item.host).to_string()),
+        ("port", item.port.to_string()),
+        ("pid", display_str(&item.pid).to_string()),
+        ("is-enable", yes_no(item.turned_on).to_string()),
+        ("running", yes_no(item.running).to_string()),
+        ("started-at", display_str(&item.started_at).to_string()),
+        ("use", process_use_label(item.use_status).to_string()),
+        ("available-perfomance", item.available_performance.to_string()),
+        ("capacity", item.capacity.to_string()),
+        ("connections", item.connections.to_string()),
+        ("memory-size", item.memory_size.to_string()),
+        ("memory-excess-time", item.memory_excess_time.to_string()),
+        ("selection-size", item.selection_size.to_string()),
+        ("avg-call-time", format!("{:.3}", item.avg_call_time)),
+        ("avg-db-call-time", format!("{:.3}", item.avg_db_call_time)),
+        ("avg-lock-call-time", format!("{:.3}", item.avg_lock_call_time)),
+        ("avg-server-call-time", format!("{:.3}", item.avg_server_call_time)),
+        ("avg-threads", format!("{:.3}", item.avg_threads)),
+        ("reserve", yes_no(item.reserve).to_string()),
+    ];
+    fields
+}
+
 pub struct ProcessInfoDisplay<'a> {
     item: &'a ProcessRecord,
 }
@@ -212,6 +321,9 @@ fn render_process_info(out: &mut String, item: &ProcessRecord) {
 
 impl Display for ProcessInfoDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, [rac_process_fields(self.item)]);
+        }
         let mut out = String::new();
         render_process_info(&mut out, self.item);
         write_trimmed(f, &out)
@@ -228,6 +340,9 @@ pub fn process_list(items: &[ProcessRecord]) -> ProcessListDisplay<'_> {
 
 impl Display for ProcessListDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, self.items.iter().map(rac_process_fields));
+        }
         let out = list_to_string("processes", self.items, 5, MoreLabel::Default, |out, _idx, item| {
             outln!(out, "{}", process_info(item));
         });
@@ -235,6 +350,17 @@ impl Display for ProcessListDisplay<'_> {
     }
 }
 
+fn rac_lock_fields(item: &LockRecordRaw) -> RacFields {
+    let fields: RacFields = vec![
+        ("connection", format_uuid(&item.connection).to_string()),
+        ("session", format_uuid(&item.session).to_string()),
+        ("object", format_uuid(&item.object).to_string()),
+        ("locked", display_str(&item.locked_at).to_string()),
+        ("descr", format!("\"{}\"", item.descr.descr)),
+    ];
+    fields
+}
+
 pub struct LockInfoDisplay<'a> {
     item: &'a LockRecordRaw,
 }
@@ -256,6 +382,9 @@ fn render_lock_info(out: &mut String, item: &LockRecordRaw) {
 
 impl Display for LockInfoDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, [rac_lock_fields(self.item)]);
+        }
         let mut out = String::new();
         render_lock_info(&mut out, self.item);
         write_trimmed(f, &out)
@@ -272,6 +401,9 @@ pub fn lock_list(items: &[LockRecordRaw]) -> LockListDisplay<'_> {
 
 impl Display for LockListDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, self.items.iter().map(rac_lock_fields));
+        }
         let out = list_to_string("locks", self.items, 5, MoreLabel::Default, |out, _idx, item| {
             outln!(out, "{}", lock_info(item));
         });
@@ -279,6 +411,18 @@ impl Display for LockListDisplay<'_> {
     }
 }
 
+fn rac_rule_fields(item: &RuleRecord) -> RacFields {
+    let fields: RacFields = vec![
+        ("rule", format_uuid(&item.rule).to_string()),
+        ("object-type", item.object_type.to_string()),
+        ("infobase-name", display_str(&item.infobase_name).to_string()),
+        ("rule-type", rule_type_label(item.rule_type).to_string()),
+        ("application-ext", display_str(&item.application_ext).to_string()),
+        ("priority", item.priority.to_string()),
+    ];
+    fields
+}
+
 pub struct RuleInfoDisplay<'a> {
     item: &'a RuleRecord,
 }
@@ -298,6 +442,9 @@ fn render_rule_info(out: &mut String, item: &RuleRecord) {
 
 impl Display for RuleInfoDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, [rac_rule_fields(self.item)]);
+        }
         let mut out = String::new();
         render_rule_info(&mut out, self.item);
         write_trimmed(f, &out)
@@ -314,6 +461,9 @@ pub fn rule_list(items: &[RuleRecord]) -> RuleListDisplay<'_> {
 
 impl Display for RuleListDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, self.items.iter().map(rac_rule_fields));
+        }
         let out = list_to_string("rules", self.items, 5, MoreLabel::Default, |out, _idx, item| {
             outln!(out, "{}", rule_info(item));
         });
@@ -321,6 +471,28 @@ impl Display for RuleListDisplay<'_> {
     }
 }
 
+fn rac_limit_fields(item: &LimitRecord) -> RacFields {
+    let fields: RacFields = vec![
+        ("name", display_str(&item.name).to_string()),
+        ("counter", display_str(&item.counter).to_string()),
+        ("action", limit_action_label(item.action).to_string()),
+        ("duration", item.duration.to_string()),
+        ("cpu-time", item.cpu_time.to_string()),
+        ("memory", item.memory.to_string()),
+        ("read", item.read.to_string()),
+        ("write", item.write.to_string()),
+        ("duration-dbms", item.duration_dbms.to_string()),
+        ("dbms-bytes", item.dbms_bytes.to_string()),
+        ("service", item.service.to_string()),
+        ("call", item.call.to_string()),
+        ("number-of-active-sessions", item.number_of_active_sessions.to_string()),
+        ("number-of-sessions", item.number_of_sessions.to_string()),
+        ("error-message", display_str(&item.error_message).to_string()),
+        ("descr", display_str(&item.descr).to_string()),
+    ];
+    fields
+}
+
 pub struct LimitInfoDisplay<'a> {
     item: &'a LimitRecord,
 }
@@ -350,6 +522,9 @@ fn render_limit_info(out: &mut String, item: &LimitRecord) {
 
 impl Display for LimitInfoDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, [rac_limit_fields(self.item)]);
+        }
         let mut out = String::new();
         render_limit_info(&mut out, self.item);
         write_trimmed(f, &out)
@@ -366,6 +541,9 @@ pub fn limit_list(items: &[LimitRecord]) -> LimitListDisplay<'_> {
 
 impl Display for LimitListDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, self.items.iter().map(rac_limit_fields));
+        }
         let out = list_to_string("limits", self.items, 5, MoreLabel::Default, |out, _idx, item| {
             outln!(out, "{}", limit_info(item));
         });
@@ -373,6 +551,17 @@ impl Display for LimitListDisplay<'_> {
     }
 }
 
+fn rac_service_setting_fields(item: &ServiceSettingRecord) -> RacFields {
+    let fields: RacFields = vec![
+        ("setting", format_uuid(&item.setting).to_string()),
+        ("service-name", display_str(&item.service_name).to_string()),
+        ("infobase-name", display_str(&item.infobase_name).to_string()),
+        ("service-data-dir", display_str(&item.service_data_dir).to_string()),
+        ("active", yes_no(item.active).to_string()),
+    ];
+    fields
+}
+
 pub struct ServiceSettingInfoDisplay<'a> {
     item: &'a ServiceSettingRecord,
 }
@@ -391,6 +580,9 @@ fn render_service_setting_info(out: &mut String, item: &ServiceSettingRecord) {
 
 impl Display for ServiceSettingInfoDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, [rac_service_setting_fields(self.item)]);
+        }
         let mut out = String::new();
         render_service_setting_info(&mut out, self.item);
         write_trimmed(f, &out)
@@ -407,6 +599,9 @@ pub fn service_setting_list(items: &[ServiceSettingRecord]) -> ServiceSettingLis
 
 impl Display for ServiceSettingListDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, self.items.iter().map(rac_service_setting_fields));
+        }
         let out = list_to_string("service-settings", self.items, 5, MoreLabel::Default, |out, _idx, item| {
             outln!(out, "{}", service_setting_info(item));
         });
@@ -454,6 +649,29 @@ impl Display for ServiceSettingTransferDataDirsDisplay<'_> {
     }
 }
 
+fn rac_counter_fields(item: &CounterRecord) -> RacFields {
+    let fields: RacFields = vec![
+        ("name", display_str(&item.name).to_string()),
+        ("collection-time", counter_collection_time_label(item.collection_time).to_string()),
+        ("group", counter_group_label(item.group).to_string()),
+        ("filter-type", counter_filter_type_label(item.filter_type).to_string()),
+        ("filter", display_str(&item.filter).to_string()),
+        ("duration", analyze_label(item.duration).to_string()),
+        ("cpu-time", analyze_label(item.cpu_time).to_string()),
+        ("memory", analyze_label(item.memory).to_string()),
+        ("read", analyze_label(item.read).to_string()),
+        ("write", analyze_label(item.write).to_string()),
+        ("duration-dbms", analyze_label(item.duration_dbms).to_string()),
+        ("dbms-bytes", analyze_label(item.dbms_bytes).to_string()),
+        ("service", analyze_label(item.service).to_string()),
+        ("call", analyze_label(item.call).to_string()),
+        ("number-of-active-sessions", analyze_label(item.number_of_active_sessions).to_string()),
+        ("number-of-sessions", analyze_label(item.number_of_sessions).to_string()),
+        ("descr", display_str(&item.descr).to_string()),
+    ];
+    fields
+}
+
 pub struct CounterInfoDisplay<'a> {
     item: &'a CounterRecord,
 }
@@ -484,6 +702,9 @@ fn render_counter_info(out: &mut String, item: &CounterRecord) {
 
 impl Display for CounterInfoDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, [rac_counter_fields(self.item)]);
+        }
         let mut out = String::new();
         render_counter_info(&mut out, self.item);
         write_trimmed(f, &out)
@@ -500,6 +721,9 @@ pub fn counter_list(items: &[CounterRecord]) -> CounterListDisplay<'_> {
 
 impl Display for CounterListDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, self.items.iter().map(rac_counter_fields));
+        }
         let out = list_to_string("counters", self.items, 5, MoreLabel::Default, |out, _idx, item| {
             outln!(out, "{}", counter_info(item));
         });
@@ -507,6 +731,26 @@ impl Display for CounterListDisplay<'_> {
     }
 }
 
+fn rac_counter_values_fields(item: &CounterValuesRecord) -> RacFields {
+    let fields: RacFields = vec![
+        ("object", display_str(&item.object).to_string()),
+        ("collection-time", item.collection_time.to_string()),
+        ("duration", item.duration.to_string()),
+        ("cpu-time", item.cpu_time.to_string()),
+        ("memory", item.memory.to_string()),
+        ("read", item.read.to_string()),
+        ("write", item.write.to_string()),
+        ("duration-dbms", item.duration_dbms.to_string()),
+        ("dbms-bytes", item.dbms_bytes.to_string()),
+        ("service", item.service.to_string()),
+        ("call", item.call.to_string()),
+        ("number-of-active-sessions", item.number_of_active_sessions.to_string()),
+        ("number-of-sessions", item.number_of_sessions.to_string()),
+        ("time", display_str(&item.time).to_string()),
+    ];
+    fields
+}
+
 pub struct CounterValuesInfoDisplay<'a> {
     item: &'a CounterValuesRecord,
 }
@@ -534,6 +778,9 @@ fn render_counter_values_info(out: &mut String, item: &CounterValuesRecord) {
 
 impl Display for CounterValuesInfoDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, [rac_counter_values_fields(self.item)]);
+        }
         let mut out = String::new();
         render_counter_values_info(&mut out, self.item);
         write_trimmed(f, &out)
@@ -550,6 +797,9 @@ pub fn counter_values(items: &[CounterValuesRecord]) -> CounterValuesDisplay<'_>
 
 impl Display for CounterValuesDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, self.items.iter().map(rac_counter_values_fields));
+        }
         let out = list_to_string("counter-values", self.items, 5, MoreLabel::Default, |out, _idx, item| {
             outln!(out, "{}", counter_values_info(item));
         });
@@ -567,6 +817,9 @@ pub fn counter_accumulated_values(items: &[CounterValuesRecord]) -> CounterAccum
 
 impl Display for CounterAccumulatedValuesDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, self.items.iter().map(rac_counter_values_fields));
+        }
         let out = list_to_string("counter-accumulated-values", self.items, 5, MoreLabel::Default, |out, _idx, item| {
             outln!(out, "{}", counter_values_info(item));
         });
@@ -574,6 +827,16 @@ impl Display for CounterAccumulatedValuesDisplay<'_> {
     }
 }
 
+fn rac_agent_admin_fields(item: &AgentAdminRecord) -> RacFields {
+    let fields: RacFields = vec![
+        ("name", display_str(&item.name).to_string()),
+        ("auth", auth_name(item.auth_pwd, item.auth_os).to_string()),
+        ("os-user", display_str(&item.os_user).to_string()),
+        ("descr", display_str(&item.descr).to_string()),
+    ];
+    fields
+}
+
 pub struct AgentAdminInfoDisplay<'a> {
     item: &'a AgentAdminRecord,
 }
@@ -591,6 +854,9 @@ fn render_agent_admin_info(out: &mut String, item: &AgentAdminRecord) {
 
 impl Display for AgentAdminInfoDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, [rac_agent_admin_fields(self.item)]);
+        }
         let mut out = String::new();
         render_agent_admin_info(&mut out, self.item);
         write_trimmed(f, &out)
@@ -607,6 +873,9 @@ pub fn agent_admin_list(items: &[AgentAdminRecord]) -> AgentAdminListDisplay<'_>
 
 impl Display for AgentAdminListDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, self.items.iter().map(rac_agent_admin_fields));
+        }
         let out = list_to_string("agent-admins", self.items, self.items.len(), MoreLabel::Default, |out, _idx, item| {
             outln!(out, "{}", agent_admin_info(item));
         });
@@ -614,6 +883,16 @@ impl Display for AgentAdminListDisplay<'_> {
     }
 }
 
+fn rac_cluster_admin_fields(item: &ClusterAdminRecord) -> RacFields {
+    let fields: RacFields = vec![
+        ("name", display_str(&item.name).to_string()),
+        ("auth", auth_name(item.auth_pwd, item.auth_os).to_string()),
+        ("os-user", display_str(&item.os_user).to_string()),
+        ("descr", display_str(&item.descr).to_string()),
+    ];
+    fields
+}
+
 pub struct ClusterAdminInfoDisplay<'a> {
     item: &'a ClusterAdminRecord,
 }
@@ -632,6 +911,9 @@ fn render_cluster_admin_info(out: &mut String, item: &ClusterAdminRecord) {
 
 impl Display for ClusterAdminInfoDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, [rac_cluster_admin_fields(self.item)]);
+        }
         let mut out = String::new();
         render_cluster_admin_info(&mut out, self.item);
         write_trimmed(f, &out)
@@ -648,6 +930,9 @@ pub fn cluster_admin_list(items: &[ClusterAdminRecord]) -> ClusterAdminListDispl
 
 impl Display for ClusterAdminListDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, self.items.iter().map(rac_cluster_admin_fields));
+        }
         let out = list_to_string("cluster-admins", self.items, 5, MoreLabel::Default, |out, _idx, item| {
             outln!(out, "{}", cluster_admin_info(item));
         });
@@ -655,6 +940,38 @@ impl Display for ClusterAdminListDisplay<'_> {
     }
 }
 
+fn rac_cluster_fields(item: &ClusterRecord) -> RacFields {
+    let mut fields: RacFields = vec![
+        ("cluster", format_uuid(&item.uuid).to_string()),
+        ("host", display_str(&item.host).to_string()),
+        ("port", item.port.to_string()),
+        ("name", format!("\"{}\"", display_str(&item.display_name))),
+        ("expiration-timeout", item.expiration_timeout.to_string()),
+        ("lifetime-limit", item.lifetime_limit.to_string()),
+        ("max-memory-size", item.max_memory_size.to_string()),
+        ("max-memory-time-limit", item.max_memory_time_limit.to_string()),
+        ("security-level", item.security_level.to_string()),
+        ("session-fault-tolerance-level", item.session_fault_tolerance_level.to_string()),
+        ("load-balancing-mode", load_balancing_mode_name(item.load_balancing_mode).to_string()),
+        ("errors-count-threshold", item.errors_count_threshold.to_string()),
+        ("kill-problem-processes", u8::from(item.kill_problem_processes).to_string()),
+        ("kill-by-memory-with-dump", u8::from(item.kill_by_memory_with_dump).to_string()),
+    ];
+    if let Some(value) = item.allow_access_right_audit_events_recording {
+        fields.push(("allow-access-right-audit-events-recording", u8::from(value).to_string()));
+    }
+    if let Some(value) = item.ping_period {
+        fields.push(("ping-period", value.to_string()));
+    }
+    if let Some(value) = item.ping_timeout {
+        fields.push(("ping-timeout", value.to_string()));
+    }
+    if let Some(value) = item.restart_schedule_cron.as_ref() {
+        fields.push(("restart-schedule", value.to_string()));
+    }
+    fields
+}
+
 pub struct ClusterInfoDisplay<'a> {
     item: &'a ClusterRecord,
 }
@@ -694,6 +1011,9 @@ fn render_cluster_info(out: &mut String, item: &ClusterRecord) {
 
 impl Display for ClusterInfoDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, [rac_cluster_fields(self.item)]);
+        }
         let mut out = String::new();
         render_cluster_info(&mut out, self.item);
         write_trimmed(f, &out)
@@ -710,6 +1030,9 @@ pub fn cluster_list(items: &[ClusterRecord]) -> ClusterListDisplay<'_> {
 
 impl Display for ClusterListDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, self.items.iter().map(rac_cluster_fields));
+        }
         let mut out = String::new();
         for (idx, item) in self.items.iter().enumerate() {
             if idx > 0 {
@@ -741,6 +1064,61 @@ impl Display for AgentVersionDisplay<'_> {
     }
 }
 
+fn rac_session_fields(item: &SessionRecord) -> RacFields {
+    let fields: RacFields = vec![
+        ("session", format_uuid(&item.session).to_string()),
+        ("session-id", item.session_id.to_string()),
+        ("infobase", format_uuid(&item.infobase).to_string()),
+        ("connection", format_uuid(&item.connection).to_string()),
+        ("process", format_uuid(&item.process).to_string()),
+        ("user-name", display_str(&item.user_name).to_string()),
+        ("host", display_str(&item.host).to_string()),
+        ("app-id", display_str(&item.app_id).to_string()),
+        ("locale", display_str(&item.locale).to_string()),
+        ("started-at", display_str(&item.started_at).to_string()),
+        ("last-active-at", display_str(&item.last_active_at).to_string()),
+        ("hibernate", yes_no(item.hibernate).to_string()),
+        ("passive-session-hibernate-time", item.passive_session_hibernate_time.to_string()),
+        ("hibernate-session-terminate-time", item.hibernate_session_terminate_time.to_string()),
+        ("blocked-by-dbms", item.blocked_by_dbms.to_string()),
+        ("blocked-by-ls", item.blocked_by_ls.to_string()),
+        ("bytes-all", item.bytes_all.to_string()),
+        ("bytes-last-5min", item.bytes_last_5min.to_string()),
+        ("calls-all", item.calls_all.to_string()),
+        ("calls-last-5min", item.calls_last_5min.to_string()),
+        ("dbms-bytes-all", item.dbms_bytes_all.to_string()),
+        ("dbms-bytes-last-5min", item.dbms_bytes_last_5min.to_string()),
+        ("db-proc-info", display_str(&item.db_proc_info).to_string()),
+        ("db-proc-took", item.db_proc_took.to_string()),
+        ("db-proc-took-at", display_str(&item.db_proc_took_at).to_string()),
+        ("duration-all", item.duration_all.to_string()),
+        ("duration-all-dbms", item.duration_all_dbms.to_string()),
+        ("duration-current", item.duration_current.to_string()),
+        ("duration current-dbms", item.duration_current_dbms.to_string()),
+        ("duration-last-5min", item.duration_last_5min.to_string()),
+        ("duration-last-5min-dbms", item.duration_last_5min_dbms.to_string()),
+        ("memory-current", item.memory_current.to_string()),
+        ("memory-last-5min", item.memory_last_5min.to_string()),
+        ("memory-total", item.memory_total.to_string()),
+        ("read-current", item.read_current.to_string()),
+        ("read-last-5min", item.read_last_5min.to_string()),
+        ("read-total", item.read_total.to_string()),
+        ("write-current", item.write_current.to_string()),
+        ("write-last-5min", item.write_last_5min.to_string()),
+        ("write-total", item.write_total.to_string()),
+        ("duration-current-service", item.duration_current_service.to_string()),
+        ("duration-last-5min-service", item.duration_last_5min_service.to_string()),
+        ("duration-all-service", item.duration_all_service.to_string()),
+        ("current-service-name", display_str(&item.current_service_name).to_string()),
+        ("cpu-time-current", item.cpu_time_current.to_string()),
+        ("cpu-time-last-5min", item.cpu_time_last_5min.to_string()),
+        ("cpu-time-total", item.cpu_time_total.to_string()),
+        ("data-separation", display_str(&item.data_separation).to_string()),
+        ("client-ip", display_str(&item.client_ip).to_string()),
+    ];
+    fields
+}
+
 pub struct SessionInfoDisplay<'a> {
     item: &'a SessionRecord,
 }
@@ -773,6 +1151,9 @@ fn render_session_info(out: &mut String, item: &SessionRecord) {
 
 impl Display for SessionInfoDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, [rac_session_fields(self.item)]);
+        }
         let mut out = String::new();
         render_session_info(&mut out, self.item);
         write_trimmed(f, &out)
@@ -789,9 +1170,35 @@ pub fn session_list(items: &[SessionRecord]) -> SessionListDisplay<'_> {
 
 impl Display for SessionListDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_rac_blocks(f, self.items.iter().map(rac_session_fields));
+        }
         let out = list_to_string("sessions", self.items, 5, MoreLabel::Default, |out, _idx, item| {
             outln!(out, "{}", session_info(item));
         });
         write_trimmed(f, &out)
     }
 }
+
+fn rac_profile_fields(item: &ProfileRecord) -> RacFields {
+    let fields: RacFields = vec![
+        ("name", display_str(&item.name).to_string()),
+        ("descr", display_str(&item.descr).to_string()),
+        ("config", yes_no(item.config).to_string()),
+        ("priv", yes_no(item.privileged_mode).to_string()),
+        ("directory", profile_access_label(item.directory_access).to_string()),
+        ("com", profile_access_label(item.com_access).to_string()),
+        ("addin", profile_access_label(item.addin_access).to_string()),
+        ("module", profile_access_label(item.module_access).to_string()),
+        ("app", profile_access_label(item.app_access).to_string()),
+        ("inet", profile_access_label(item.inet_access).to_string()),
+        ("crypto", yes_no(item.crypto).to_string()),
+        ("right-extension", yes_no(item.right_extension).to_string()),
+        ("right-extension-definition-roles", display_str(&item.right_extension_definition_roles).to_string()),
+        ("all-modules-extension", yes_no(item.all_modules_extension).to_string()),
+        ("modules-available-for-extension", display_str(&item.modules_available_for_extension).to_string()),
+        ("modules-not-available-for-extension", display_str(&item.modules_not_available_for_extension).to_string()),
+        ("privileged-mode-roles", display_str(&item.privileged_mode_roles).to_string()),
+    ];
+    fields
+}
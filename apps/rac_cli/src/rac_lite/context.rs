@@ -0,0 +1,326 @@
+//! Address, cluster and credentials shared by the commands of a `shell` or
+//! `exec` session.
+
+use std::ffi::OsString;
+use std::fmt;
+
+use clap::parser::ValueSource;
+use clap::ArgMatches;
+
+use rac_protocol::error::{RacError, Result};
+use rac_protocol::rac_wire::format_uuid;
+
+use crate::rac_lite::cli::ContextArgs;
+use crate::rac_lite::config::{parse_with_profile, subcommand_path, Profile, Secret};
+use crate::rac_lite::connector::Connector;
+use crate::rac_lite::console_output::Output;
+use crate::rac_lite::parse::resolve_cluster_arg;
+use crate::rac_lite::runner;
+
+const CREDENTIALS: [&str; 4] = ["cluster_user", "cluster_pwd", "agent_user", "agent_pwd"];
+
+/// Options of the connection or the whole run, which a single command cannot
+/// change once `shell` or `exec` has started.
+const SESSION_OPTIONS: [&str; 10] = [
+    "debug_raw",
+    "profile",
+    "config",
+    "protocol",
+    "connect_timeout_ms",
+    "read_timeout_ms",
+    "write_timeout_ms",
+    "watch",
+    "yes",
+    "policy",
+];
+
+pub struct Context {
+    output: Output,
+    conn: Connector,
+    /// `--read-only` of the whole session; a command can only add to it.
+    read_only: bool,
+    addr: String,
+    /// Fills in the address, cluster and credentials each command omits.
+    profile: Profile,
+    /// The cluster as the user named it, for the prompt.
+    cluster_label: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum CommandError {
+    Usage(clap::Error),
+    Rac(RacError),
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandError::Usage(err) => write!(f, "{}", err.to_string().trim_end()),
+            CommandError::Rac(err) => write!(f, "Error: {err}"),
+        }
+    }
+}
+
+impl From<RacError> for CommandError {
+    fn from(err: RacError) -> Self {
+        CommandError::Rac(err)
+    }
+}
+
+impl Context {
+    pub fn new(output: Output, conn: Connector, addr: String, args: ContextArgs) -> Self {
+        let profile = Profile {
+            addr: Some(addr.clone()),
+            cluster: args.cluster,
+            cluster_user: args.cluster_user,
+            cluster_pwd: args.cluster_pwd.map(Secret::Plain),
+            agent_user: args.agent_user,
+            agent_pwd: args.agent_pwd.map(Secret::Plain),
+            ..Profile::default()
+        };
+        Self {
+            output,
+            read_only: conn.read_only(),
+            conn,
+            addr,
+            cluster_label: profile.cluster.clone(),
+            profile,
+        }
+    }
+
+    /// Opens the connection up front; returns the negotiated protocol.
+    pub fn connect(&mut self) -> Result<&'static str> {
+        Ok(self.conn.client(&self.addr)?.protocol_name())
+    }
+
+    pub fn addr(&self) -> &str {
+        &self.addr
+    }
+
+    pub fn prompt(&self) -> String {
+        match &self.cluster_label {
+            Some(cluster) => format!("{} {cluster}> ", self.addr),
+            None => format!("{}> ", self.addr),
+        }
+    }
+
+    /// `use cluster <name|uuid>`, `use none` or `use` to show the context.
+    pub fn use_context(&mut self, words: &[String]) -> Result<()> {
+        match words {
+            [] => {
+                let cluster = self.profile.cluster.as_deref().unwrap_or("none");
+                println!("server  : {}", self.addr);
+                println!("cluster : {cluster}");
+            }
+            [none] if none == "none" => {
+                self.profile.cluster = None;
+                self.cluster_label = None;
+                self.profile.cluster_user = None;
+                self.profile.cluster_pwd = None;
+            }
+            [kind, name] if kind == "cluster" => {
+                let client = self.conn.client(&self.addr)?;
+                let cluster = format_uuid(&resolve_cluster_arg(client, name)?);
+                if self.profile.cluster.as_ref() != Some(&cluster) {
+                    self.profile.cluster_user = None;
+                    self.profile.cluster_pwd = None;
+                }
+                self.profile.cluster = Some(cluster);
+                self.cluster_label = Some(name.clone());
+            }
+            _ => {
+                return Err(RacError::InvalidArgs(
+                    "usage: use cluster <name|uuid> | use none".to_string(),
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs one command given without the program name.
+    pub fn run(&mut self, words: Vec<String>) -> std::result::Result<(), CommandError> {
+        let mut args = vec![OsString::from("rac_lite")];
+        args.extend(words.into_iter().map(OsString::from));
+        let (cli, matches) =
+            parse_with_profile(args, &self.profile).map_err(CommandError::Usage)?;
+        if let Some(id) = SESSION_OPTIONS
+            .into_iter()
+            .find(|id| matches.value_source(id) == Some(ValueSource::CommandLine))
+        {
+            return Err(RacError::InvalidArgs(format!(
+                "--{} applies to the whole session: pass it to shell or exec",
+                id.replace('_', "-")
+            ))
+            .into());
+        }
+        let output = if cli.format.is_some() || cli.json {
+            cli.output()
+        } else {
+            self.output
+        };
+        if cli.dry_run {
+            return Ok(runner::run_dry(output, &self.conn, cli.command)?);
+        }
+        self.conn.set_read_only(self.read_only || cli.read_only);
+        let result = runner::dispatch(output, &mut self.conn, cli.command);
+        self.conn.set_read_only(self.read_only);
+        match result {
+            Ok(()) => {
+                self.remember(&matches);
+                Ok(())
+            }
+            Err(err) => {
+                // The stream may have stopped mid-frame; reconnect next time.
+                if matches!(
+                    err,
+                    RacError::Io(_)
+                        | RacError::Wire(_)
+                        | RacError::Decode(_)
+                        | RacError::DecodeMessage(_)
+                        | RacError::UnexpectedMethod { .. }
+                ) {
+                    self.conn.reset();
+                }
+                Err(err.into())
+            }
+        }
+    }
+
+    pub fn close(mut self) -> Result<()> {
+        self.conn.close()
+    }
+
+    /// Keeps the credentials typed on the command line for later commands.
+    fn remember(&mut self, matches: &ArgMatches) {
+        let (_, leaf) = subcommand_path(matches);
+        for id in CREDENTIALS {
+            if !leaf.try_contains_id(id).unwrap_or(false)
+                || leaf.value_source(id) != Some(ValueSource::CommandLine)
+            {
+                continue;
+            }
+            let Some(value) = leaf.get_one::<String>(id).cloned() else {
+                continue;
+            };
+            match id {
+                "cluster_user" => self.profile.cluster_user = Some(value),
+                "cluster_pwd" => self.profile.cluster_pwd = Some(Secret::Plain(value)),
+                "agent_user" => self.profile.agent_user = Some(value),
+                _ => self.profile.agent_pwd = Some(Secret::Plain(value)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rac_lite::cli::{RuleCmd, TopCommand};
+    use rac_protocol::client::ClientConfig;
+    use rac_protocol::rpc::Mutation;
+
+    fn parse(context: &Context, args: &[&str]) -> (crate::rac_lite::Cli, ArgMatches) {
+        let mut argv = vec![OsString::from("rac_lite")];
+        argv.extend(args.iter().map(OsString::from));
+        parse_with_profile(argv, &context.profile).expect("parse")
+    }
+
+    #[test]
+    fn fills_arguments_and_keeps_credentials() {
+        let args = ContextArgs {
+            cluster: Some("Main".to_string()),
+            ..ContextArgs::default()
+        };
+        let conn = Connector::new(ClientConfig::default());
+        let mut context = Context::new(Output::Text, conn, "ras:1545".to_string(), args);
+        assert_eq!(context.prompt(), "ras:1545 Main> ");
+
+        let (cli, matches) = parse(
+            &context,
+            &[
+                "rule",
+                "list",
+                "--server",
+                "ab",
+                "--cluster-user",
+                "admin",
+                "--cluster-pwd",
+                "x",
+            ],
+        );
+        match cli.command {
+            TopCommand::Rule {
+                command: RuleCmd::List { addr, cluster, .. },
+            } => {
+                assert_eq!(addr, "ras:1545");
+                assert_eq!(cluster, "Main");
+            }
+            other => panic!("unexpected command: {other:?}"),
+        }
+        context.remember(&matches);
+        assert_eq!(context.profile.cluster_user.as_deref(), Some("admin"));
+        assert!(matches!(&context.profile.cluster_pwd, Some(Secret::Plain(pwd)) if pwd == "x"));
+
+        // Remembered values are defaults now, not command-line values.
+        let (_, matches) = parse(&context, &["rule", "list", "--server", "ab"]);
+        let (_, leaf) = subcommand_path(&matches);
+        assert_eq!(
+            leaf.value_source("cluster_user"),
+            Some(ValueSource::DefaultValue)
+        );
+
+        context
+            .use_context(&["none".to_string()])
+            .expect("use none");
+        assert_eq!(context.prompt(), "ras:1545> ");
+        assert!(context.profile.cluster_pwd.is_none());
+        let err = context.run(vec!["rule".to_string(), "list".to_string()]);
+        assert!(matches!(err, Err(CommandError::Usage(_))));
+    }
+
+    #[test]
+    fn read_only_line_does_not_run_and_session_options_are_refused() {
+        let args = ContextArgs {
+            cluster: Some("00000000-0000-0000-0000-000000000001".to_string()),
+            ..ContextArgs::default()
+        };
+        let conn = Connector::new(ClientConfig::default()).recorder();
+        let mut context = Context::new(Output::Quiet, conn, "ras:1545".to_string(), args);
+        let line = |words: &[&str]| words.iter().map(|word| word.to_string()).collect();
+
+        let err = context.run(line(&[
+            "--read-only",
+            "session",
+            "terminate",
+            "--session",
+            "00000000-0000-0000-0000-000000000002",
+        ]));
+        assert!(matches!(
+            err,
+            Err(CommandError::Rac(RacError::ReadOnly { .. }))
+        ));
+        assert!(context
+            .conn
+            .planned()
+            .iter()
+            .all(|rpc| rpc.mutation == Mutation::Read));
+        assert!(!context.conn.read_only());
+        // The same line without the flag gets as far as the terminate RPC.
+        let err = context.run(line(&[
+            "session",
+            "terminate",
+            "--session",
+            "00000000-0000-0000-0000-000000000002",
+        ]));
+        assert!(matches!(err, Err(CommandError::Rac(RacError::DryRun))));
+
+        let err = context.run(line(&["--yes", "session", "list"]));
+        assert!(
+            matches!(err, Err(CommandError::Rac(RacError::InvalidArgs(message))) if message.starts_with("--yes "))
+        );
+        let err = context.run(line(&["--connect-timeout-ms", "10", "session", "list"]));
+        assert!(
+            matches!(err, Err(CommandError::Rac(RacError::InvalidArgs(message))) if message.starts_with("--connect-timeout-ms "))
+        );
+    }
+}
@@ -50,3 +50,26 @@ where
 pub fn write_trimmed(f: &mut fmt::Formatter<'_>, out: &str) -> fmt::Result {
     write!(f, "{}", out.trim_end())
 }
+
+pub type RacFields = Vec<(&'static str, String)>;
+
+/// Writes records the way the vendor `rac` utility does: one `label : value`
+/// block per record, labels padded to the widest one, each block followed by
+/// an empty line.
+pub fn write_rac_blocks<I>(f: &mut fmt::Formatter<'_>, blocks: I) -> fmt::Result
+where
+    I: IntoIterator<Item = RacFields>,
+{
+    for fields in blocks {
+        let width = fields
+            .iter()
+            .map(|(label, _)| label.chars().count())
+            .max()
+            .unwrap_or(0);
+        for (label, value) in &fields {
+            writeln!(f, "{label:<width$} : {value}")?;
+        }
+        writeln!(f)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use std::io::{self, BufRead as _, IsTerminal, Write as _};
+use std::path::PathBuf;
+
+use rac_protocol::error::{RacError, Result};
+use rac_protocol::policy::{Policy, Target};
+
+/// Checks run before a command ends sessions or removes objects: the
+/// policy always, then a confirmation prompt unless `--yes` was given.
+#[derive(Debug, Default)]
+pub struct Guard {
+    assume_yes: bool,
+    /// Read on the first guarded command, so a broken policy file does not
+    /// fail commands that change nothing.
+    policy_path: Option<PathBuf>,
+    policy: Option<Policy>,
+}
+
+impl Guard {
+    pub fn new(assume_yes: bool, policy_path: Option<PathBuf>) -> Self {
+        Self {
+            assume_yes,
+            policy_path,
+            policy: None,
+        }
+    }
+
+    pub fn policy(&mut self) -> Result<&Policy> {
+        let policy = match (self.policy.take(), &self.policy_path) {
+            (Some(policy), _) => policy,
+            (None, Some(path)) => Policy::load(path)?,
+            (None, None) => Policy::default(),
+        };
+        Ok(self.policy.insert(policy))
+    }
+
+    /// One prompt covers all of `targets`, which the policy already allowed.
+    pub fn confirm(&self, targets: &[Target]) -> Result<()> {
+        if self.assume_yes || targets.is_empty() {
+            return Ok(());
+        }
+        let lines: Vec<String> = targets.iter().map(Target::to_string).collect();
+        confirm(&lines.join("\n"))
+    }
+}
+
+fn confirm(what: &str) -> Result<()> {
+    let stdin = io::stdin();
+    if !stdin.is_terminal() {
+        return Err(RacError::InvalidArgs(format!(
+            "{what}\nneeds confirmation but stdin is not a terminal; pass --yes"
+        )));
+    }
+    let mut stderr = io::stderr();
+    write!(stderr, "{what}\nProceed? [y/N] ")?;
+    stderr.flush()?;
+    let mut answer = String::new();
+    stdin.lock().read_line(&mut answer)?;
+    if accepted(&answer) {
+        Ok(())
+    } else {
+        Err(RacError::Cancelled)
+    }
+}
+
+fn accepted(answer: &str) -> bool {
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn policy_loads_on_first_guarded_command() {
+        let path = env::temp_dir().join(format!("rac_lite_policy_{}.toml", std::process::id()));
+        fs::write(&path, "[[deny]]\nactoin = \"session.*\"\n").expect("write policy");
+        let mut guard = Guard::new(true, Some(path.clone()));
+        assert!(guard.confirm(&[Target::new("limit.remove")]).is_ok());
+        assert!(guard.policy().is_err());
+
+        fs::write(&path, "[[deny]]\naction = \"session.*\"\n").expect("write policy");
+        let policy = guard.policy().expect("policy");
+        assert!(policy.check(&Target::new("session.terminate")).is_err());
+        let _ = fs::remove_file(path);
+
+        assert!(accepted("y\n"));
+        assert!(accepted(" Yes "));
+        assert!(!accepted("\n"));
+        assert!(!accepted("no"));
+    }
+}
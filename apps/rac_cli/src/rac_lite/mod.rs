@@ -1,9 +1,22 @@
 mod cli;
+mod cluster_config;
 mod commands;
+mod compat;
+mod config;
+mod connector;
+mod context;
 mod console_output;
 mod format;
+mod guard;
 mod parse;
 mod runner;
+mod script;
+mod shell;
+mod watch;
 
-pub use cli::Cli;
+pub use cli::{Cli, ProtocolArg};
+pub use compat::parse_rac_args;
+pub use config::{
+    default_config_path, default_policy_path, parse_args, ConfigFile, Profile, Secret,
+};
 pub use runner::run;
@@ -1,11 +1,104 @@
+use std::time::Duration;
+
+use rac_protocol::client::RacClient;
+use rac_protocol::commands::cluster_auth_optional;
 use rac_protocol::error::{RacError, Result};
-use rac_protocol::rac_wire::parse_uuid;
+use rac_protocol::rac_wire::{format_uuid, parse_uuid};
+use rac_protocol::resolve::Resolver;
 use rac_protocol::Uuid16;
+use serde_json::Value;
 
 pub fn parse_uuid_arg(input: &str) -> Result<Uuid16> {
     Ok(parse_uuid(input)?)
 }
 
+/// A UUID as responses serialize it: an array of 16 byte values.
+pub fn json_uuid(value: &Value) -> Option<Uuid16> {
+    let items = value.as_array()?;
+    let mut uuid = [0u8; 16];
+    if items.len() != uuid.len() {
+        return None;
+    }
+    for (byte, item) in uuid.iter_mut().zip(items) {
+        *byte = u8::try_from(item.as_u64()?).ok()?;
+    }
+    Some(uuid)
+}
+
+/// Text of a response value: UUIDs in their usual form, strings as is, and
+/// an object with a single field as that field.
+pub fn json_scalar(value: &Value) -> Option<String> {
+    if let Some(uuid) = json_uuid(value) {
+        return Some(format_uuid(&uuid));
+    }
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(flag) => Some(flag.to_string()),
+        Value::Object(fields) if fields.len() == 1 => fields.values().next().and_then(json_scalar),
+        _ => None,
+    }
+}
+
+/// `2` or `2s` for seconds, `500ms` for milliseconds.
+pub fn parse_interval(input: &str) -> std::result::Result<Duration, String> {
+    let input = input.trim();
+    let (number, millis) = match input.strip_suffix("ms") {
+        Some(number) => (number, true),
+        None => (input.strip_suffix('s').unwrap_or(input), false),
+    };
+    let value: f64 = number
+        .parse()
+        .map_err(|_| format!("invalid interval: {input}"))?;
+    if !value.is_finite() || value <= 0.0 {
+        return Err(format!("interval must be positive: {input}"));
+    }
+    Ok(Duration::from_secs_f64(if millis {
+        value / 1000.0
+    } else {
+        value
+    }))
+}
+
+/// An RPC method ID, `0x4a` or `74`.
+pub fn parse_method(input: &str) -> std::result::Result<u8, String> {
+    let input = input.trim();
+    let parsed = match input.strip_prefix("0x") {
+        Some(hex) => u8::from_str_radix(hex, 16),
+        None => input.parse(),
+    };
+    parsed.map_err(|_| format!("invalid method id: {input}"))
+}
+
+/// Hex bytes, optionally prefixed with `0x` and separated by spaces or
+/// colons, or `@path` to read them raw from a file.
+pub fn parse_hex_body(input: &str) -> Result<Vec<u8>> {
+    if let Some(path) = input.strip_prefix('@') {
+        return Ok(std::fs::read(path)?);
+    }
+    let input = input.trim();
+    let digits: Vec<u8> = input
+        .strip_prefix("0x")
+        .unwrap_or(input)
+        .bytes()
+        .filter(|byte| !byte.is_ascii_whitespace() && *byte != b':')
+        .collect();
+    if !digits.len().is_multiple_of(2) {
+        return Err(RacError::InvalidArgs(
+            "hex body has an odd number of digits".to_string(),
+        ));
+    }
+    digits
+        .chunks(2)
+        .map(|pair| {
+            std::str::from_utf8(pair)
+                .ok()
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .ok_or_else(|| RacError::InvalidArgs(format!("invalid hex body: {input}")))
+        })
+        .collect()
+}
+
 pub fn parse_auth_flags(input: &str) -> Result<(u8, u8)> {
     let trimmed = input.trim();
     if trimmed.is_empty() {
@@ -74,3 +167,59 @@ pub fn parse_limit_action(input: &str) -> Result<u8> {
         _ => Err(RacError::Unsupported("unknown limit action")),
     }
 }
+
+/// Accepts a cluster UUID, display name or `host:port`.
+pub fn resolve_cluster_arg(client: &mut RacClient, input: &str) -> Result<Uuid16> {
+    Resolver::default().cluster(client, input)
+}
+
+/// As [`resolve_cluster_arg`], then authenticates to the cluster when
+/// credentials are given; lists and infos work without them on clusters that
+/// have no administrators.
+pub fn resolve_cluster_auth(
+    client: &mut RacClient,
+    input: &str,
+    user: Option<&str>,
+    pwd: Option<&str>,
+) -> Result<Uuid16> {
+    let cluster = resolve_cluster_arg(client, input)?;
+    if user.is_some() || pwd.is_some() {
+        cluster_auth_optional(client, cluster, user, pwd)?;
+    }
+    Ok(cluster)
+}
+
+/// Accepts an infobase UUID or name.
+pub fn resolve_infobase_arg(
+    client: &mut RacClient,
+    cluster: Uuid16,
+    input: &str,
+) -> Result<Uuid16> {
+    Resolver::default().infobase(client, cluster, input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_methods_and_hex_bodies() {
+        assert_eq!(parse_method("0x4a"), Ok(0x4a));
+        assert_eq!(parse_method("74"), Ok(74));
+        assert!(parse_method("0x100").is_err());
+
+        assert_eq!(parse_hex_body("").expect("empty"), Vec::<u8>::new());
+        assert_eq!(
+            parse_hex_body("0x01 0a:ff").expect("hex"),
+            vec![0x01, 0x0a, 0xff]
+        );
+        assert!(parse_hex_body("abc").is_err());
+        assert!(parse_hex_body("zz").is_err());
+
+        let path = std::env::temp_dir().join(format!("rac_lite_body_{}", std::process::id()));
+        std::fs::write(&path, [0u8, 0x20, 0xff]).expect("write");
+        let body = parse_hex_body(&format!("@{}", path.display())).expect("file");
+        std::fs::remove_file(&path).ok();
+        assert_eq!(body, vec![0, 0x20, 0xff]);
+    }
+}
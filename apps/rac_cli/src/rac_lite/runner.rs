@@ -1,37 +1,207 @@
+use std::time::Duration;
+
 use rac_protocol::client::ClientConfig;
-use rac_protocol::error::Result;
+use rac_protocol::dry_run::{self, Span};
+use rac_protocol::error::{RacError, Result};
+use rac_protocol::rpc::Mutation;
+use serde::Serialize;
 
-use crate::rac_lite::cli::{Cli, TopCommand};
+use crate::rac_lite::cli::{Cli, ConfigCmd, TopCommand};
 use crate::rac_lite::commands;
+use crate::rac_lite::config::default_policy_path;
+use crate::rac_lite::connector::Connector;
+use crate::rac_lite::console_output::{self as console, Output};
+use crate::rac_lite::context::Context;
+use crate::rac_lite::guard::Guard;
+use crate::rac_lite::{script, shell, watch};
 
 pub fn run(cli: Cli) -> Result<()> {
-    let json = cli.json;
-    let cfg = client_cfg(&cli);
-
+    let output = cli.output();
+    if let Some(interval) = cli.watch {
+        if !cli.command.is_list() {
+            return Err(RacError::InvalidArgs(
+                "--watch needs a list command".to_string(),
+            ));
+        }
+        let mut cfg = client_cfg(&cli);
+        cfg.read_only = true;
+        return watch::run(Connector::new(cfg), cli.command, interval);
+    }
+    let mut conn = Connector::new(client_cfg(&cli)).with_guard(guard(&cli));
+    if cli.dry_run {
+        return run_dry(output, &conn, cli.command);
+    }
     match cli.command {
-        TopCommand::Agent { command } => commands::agent::run(json, &cfg, command)?,
-        TopCommand::Cluster { command } => commands::cluster::run(json, &cfg, command)?,
-        TopCommand::Manager { command } => commands::manager::run(json, &cfg, command)?,
-        TopCommand::Server { command } => commands::server::run(json, &cfg, command)?,
-        TopCommand::Process { command } => commands::process::run(json, &cfg, command)?,
-        TopCommand::Infobase { command } => commands::infobase::run(json, &cfg, command)?,
-        TopCommand::Connection { command } => commands::connection::run(json, &cfg, command)?,
-        TopCommand::Session { command } => commands::session::run(json, &cfg, command)?,
-        TopCommand::Lock { command } => commands::lock::run(json, &cfg, command)?,
-        TopCommand::Profile { command } => commands::profile::run(json, &cfg, command)?,
-        TopCommand::Counter { command } => commands::counter::run(json, &cfg, command)?,
-        TopCommand::Limit { command } => commands::limit::run(json, &cfg, command)?,
-        TopCommand::Rule { command } => commands::rule::run(json, &cfg, command)?,
+        TopCommand::Shell { addr, context } => {
+            shell::run(Context::new(output, conn, addr, context))
+        }
+        TopCommand::Exec {
+            script,
+            addr,
+            context,
+            on_error,
+        } => script::run(Context::new(output, conn, addr, context), &script, on_error),
+        command => {
+            dispatch(output, &mut conn, command)?;
+            conn.close()
+        }
+    }
+}
+
+pub fn dispatch(output: Output, conn: &mut Connector, command: TopCommand) -> Result<()> {
+    match command {
+        TopCommand::Agent { command } => commands::agent::run(output, conn, command)?,
+        TopCommand::Cluster { command } => commands::cluster::run(output, conn, command)?,
+        TopCommand::Manager { command } => commands::manager::run(output, conn, command)?,
+        TopCommand::Server { command } => commands::server::run(output, conn, command)?,
+        TopCommand::Process { command } => commands::process::run(output, conn, command)?,
+        TopCommand::Infobase { command } => commands::infobase::run(output, conn, command)?,
+        TopCommand::Connection { command } => commands::connection::run(output, conn, command)?,
+        TopCommand::Session { command } => commands::session::run(output, conn, command)?,
+        TopCommand::Lock { command } => commands::lock::run(output, conn, command)?,
+        TopCommand::Profile { command } => commands::profile::run(output, conn, command)?,
+        TopCommand::Counter { command } => commands::counter::run(output, conn, command)?,
+        TopCommand::Limit { command } => commands::limit::run(output, conn, command)?,
+        TopCommand::Rule { command } => commands::rule::run(output, conn, command)?,
         TopCommand::ServiceSetting { command } => {
-            commands::service_setting::run(json, &cfg, command)?
+            commands::service_setting::run(output, conn, command)?
+        }
+        TopCommand::Config { command } => commands::config::run(output, conn, command)?,
+        TopCommand::Raw(args) => commands::raw::run(output, conn, args)?,
+        TopCommand::Shell { .. } | TopCommand::Exec { .. } => {
+            return Err(RacError::InvalidArgs(
+                "shell and exec cannot be nested".to_string(),
+            ))
         }
     }
 
     Ok(())
 }
 
+/// One RPC of a dry run, for `--format`.
+#[derive(Serialize)]
+struct PlannedRow {
+    protocol: &'static str,
+    name: &'static str,
+    mutation: Mutation,
+    payload: String,
+    fields: Vec<Span>,
+}
+
+/// Runs `command` against a recording connector and prints the RPCs it
+/// would send, up to the first one that changes the cluster.
+pub fn run_dry(output: Output, conn: &Connector, command: TopCommand) -> Result<()> {
+    if let TopCommand::Config {
+        command: ConfigCmd::Apply { .. },
+    } = command
+    {
+        // Planning reads the cluster, which a recording connector cannot.
+        return Err(RacError::InvalidArgs(
+            "config apply has no --dry-run: run config plan to see its steps".to_string(),
+        ));
+    }
+    if command.is_read() {
+        return Err(RacError::InvalidArgs(
+            "--dry-run needs a command that changes the cluster".to_string(),
+        ));
+    }
+    let mut recorder = conn.recorder();
+    match dispatch(Output::Quiet, &mut recorder, command) {
+        Err(RacError::DryRun) => {}
+        Err(err) => return Err(err),
+        Ok(()) => {
+            return Err(RacError::InvalidArgs(
+                "--dry-run needs a command that changes the cluster".to_string(),
+            ))
+        }
+    }
+    let protocol = recorder.protocol_name().unwrap_or("?");
+    let rows: Vec<PlannedRow> = recorder
+        .planned()
+        .iter()
+        .map(|rpc| PlannedRow {
+            protocol,
+            name: rpc.name,
+            mutation: rpc.mutation,
+            payload: rpc
+                .payload
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect(),
+            fields: dry_run::annotate(&rpc.payload, rpc.wire),
+        })
+        .collect();
+    let text = format!(
+        "# protocol {protocol}, not sent\n{}",
+        dry_run::render(recorder.planned())
+    );
+    console::output(output, &rows, text.trim_end());
+    Ok(())
+}
+
+fn guard(cli: &Cli) -> Guard {
+    Guard::new(cli.yes, cli.policy.clone().or_else(default_policy_path))
+}
+
 fn client_cfg(cli: &Cli) -> ClientConfig {
-    let mut cfg = ClientConfig::default();
-    cfg.debug_raw = cli.debug_raw;
+    let mut cfg = ClientConfig {
+        debug_raw: cli.debug_raw,
+        read_only: cli.read_only,
+        ..ClientConfig::default()
+    };
+    if let Some(protocol) = cli.protocol {
+        cfg.protocol = protocol.into();
+    }
+    if let Some(ms) = cli.connect_timeout_ms {
+        cfg.connect_timeout = Duration::from_millis(ms);
+    }
+    if let Some(ms) = cli.read_timeout_ms {
+        cfg.read_timeout = Duration::from_millis(ms);
+    }
+    if let Some(ms) = cli.write_timeout_ms {
+        cfg.write_timeout = Duration::from_millis(ms);
+    }
     cfg
 }
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    #[test]
+    fn config_apply_refuses_dry_run() {
+        let cli = Cli::try_parse_from([
+            "rac_lite",
+            "--dry-run",
+            "config",
+            "apply",
+            "127.0.0.1:1545",
+            "cluster.toml",
+            "--cluster",
+            "Main",
+        ])
+        .expect("parse");
+        let conn = Connector::new(ClientConfig::default());
+        let err = run_dry(Output::Quiet, &conn, cli.command).expect_err("dry run");
+        assert!(matches!(err, RacError::InvalidArgs(msg) if msg.contains("config plan")));
+    }
+
+    #[test]
+    fn read_commands_refuse_dry_run() {
+        let cli = Cli::try_parse_from([
+            "rac_lite",
+            "--dry-run",
+            "session",
+            "list",
+            "127.0.0.1:1545",
+            "--cluster",
+            "550e8400-e29b-41d4-a716-446655440000",
+        ])
+        .expect("parse");
+        let conn = Connector::new(ClientConfig::default());
+        let err = run_dry(Output::Quiet, &conn, cli.command).expect_err("dry run");
+        assert!(matches!(err, RacError::InvalidArgs(msg) if msg.contains("changes the cluster")));
+    }
+}
@@ -0,0 +1,291 @@
+//! `rac_lite exec <script> <addr>`: runs a list of commands over one
+//! connection.
+//!
+//! Each line is a command in `shell` syntax, optionally prefixed with
+//! `name =` to capture its response, or a JSON object:
+//!
+//! ```text
+//! # comments and blank lines are skipped
+//! use cluster Main
+//! srv = server list
+//! ib = infobase summary-list
+//! new = rule insert --server ${srv.servers.0.server} --position 0 --object-type 0 --rule-type 1
+//! {"command": ["rule", "remove", "--server", "${srv.servers.0.server}", "--rule", "${new}"]}
+//! {"command": "session list --format json", "capture": "sessions"}
+//! infobase info --infobase ${ib.summaries[name=trade].infobase}
+//! ```
+//!
+//! `${name.path}` walks the captured JSON: `.field`, `.0` for an index and
+//! `[field=value]` for the first array item with that field. UUIDs expand to
+//! their text form, and an object with a single field stands for that field.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use rac_protocol::error::{RacError, Result};
+
+use crate::rac_lite::cli::OnError;
+use crate::rac_lite::console_output as console;
+use crate::rac_lite::context::Context;
+use crate::rac_lite::parse::json_scalar;
+use crate::rac_lite::shell::split_line;
+
+pub fn run(mut context: Context, script: &Path, on_error: OnError) -> Result<()> {
+    let (source, text) = if script == Path::new("-") {
+        let mut text = String::new();
+        io::stdin().read_to_string(&mut text)?;
+        ("stdin".to_string(), text)
+    } else {
+        (script.display().to_string(), fs::read_to_string(script)?)
+    };
+    let lines: Vec<(usize, &str)> = text
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .collect();
+
+    context.connect()?;
+    console::keep_printed();
+    let mut vars = BTreeMap::new();
+    let mut failed = 0;
+    let mut done = 0;
+    for (number, line) in &lines {
+        done += 1;
+        if let Err(err) = run_step(&mut context, &mut vars, line) {
+            eprintln!("{source}:{number}: {err}");
+            failed += 1;
+            if on_error == OnError::Stop {
+                break;
+            }
+        }
+    }
+    context.close()?;
+
+    if failed == 0 {
+        return Ok(());
+    }
+    let skipped = lines.len() - done;
+    let mut msg = format!("{source}: {failed} of {} steps failed", lines.len());
+    if skipped > 0 {
+        msg.push_str(&format!(", {skipped} skipped"));
+    }
+    Err(RacError::InvalidArgs(msg))
+}
+
+#[derive(Debug, PartialEq)]
+struct Step {
+    words: Vec<String>,
+    capture: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonStep {
+    command: JsonCommand,
+    capture: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonCommand {
+    Line(String),
+    Words(Vec<String>),
+}
+
+fn run_step(
+    context: &mut Context,
+    vars: &mut BTreeMap<String, Value>,
+    line: &str,
+) -> std::result::Result<(), String> {
+    let step = parse_step(line)?;
+    let words = step
+        .words
+        .iter()
+        .map(|word| substitute(word, vars))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    if words.first().map(String::as_str) == Some("use") {
+        return context
+            .use_context(&words[1..])
+            .map_err(|err| format!("Error: {err}"));
+    }
+    console::take_printed();
+    context.run(words).map_err(|err| err.to_string())?;
+    if let Some(name) = step.capture {
+        let value = console::take_printed()
+            .pop()
+            .ok_or_else(|| format!("nothing to capture into {name}"))?;
+        vars.insert(name, value);
+    }
+    Ok(())
+}
+
+fn parse_step(line: &str) -> std::result::Result<Step, String> {
+    if line.starts_with('{') {
+        let step: JsonStep = serde_json::from_str(line).map_err(|err| err.to_string())?;
+        let words = match step.command {
+            JsonCommand::Line(line) => split_line(&line)?,
+            JsonCommand::Words(words) => words,
+        };
+        return Ok(Step {
+            words,
+            capture: step.capture,
+        });
+    }
+    let mut words = split_line(line)?;
+    let capture = match words.as_slice() {
+        [name, eq, _, ..] if eq == "=" && is_name(name) => Some(name.clone()),
+        [_, eq, ..] if eq == "=" => return Err("invalid variable name".to_string()),
+        _ => None,
+    };
+    if capture.is_some() {
+        words.drain(..2);
+    }
+    Ok(Step { words, capture })
+}
+
+fn is_name(word: &str) -> bool {
+    word.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && word.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Expands every `${name.path}` in `word`.
+fn substitute(word: &str, vars: &BTreeMap<String, Value>) -> std::result::Result<String, String> {
+    let mut out = String::new();
+    let mut rest = word;
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| format!("unterminated reference in {word}"))?;
+        out.push_str(&lookup(&rest[start + 2..start + end], vars)?);
+        rest = &rest[start + end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+fn lookup(path: &str, vars: &BTreeMap<String, Value>) -> std::result::Result<String, String> {
+    let name_end = path.find(['.', '[']).unwrap_or(path.len());
+    let name = &path[..name_end];
+    let mut value = vars
+        .get(name)
+        .ok_or_else(|| format!("unknown variable {name}"))?;
+    let mut rest = &path[name_end..];
+    while !rest.is_empty() {
+        if let Some(filter) = rest.strip_prefix('[') {
+            let end = filter
+                .find(']')
+                .ok_or_else(|| format!("unterminated filter in ${{{path}}}"))?;
+            let (key, expected) = filter[..end]
+                .split_once('=')
+                .ok_or_else(|| format!("filter needs field=value in ${{{path}}}"))?;
+            value = value
+                .as_array()
+                .and_then(|items| {
+                    items.iter().find(|item| {
+                        item.get(key).and_then(json_scalar).as_deref() == Some(expected)
+                    })
+                })
+                .ok_or_else(|| format!("no item with {key}={expected} in ${{{path}}}"))?;
+            rest = &filter[end + 1..];
+        } else {
+            let segment = rest.strip_prefix('.').unwrap_or(rest);
+            let end = segment.find(['.', '[']).unwrap_or(segment.len());
+            let field = &segment[..end];
+            value = match (value, field.parse::<usize>()) {
+                (Value::Array(items), Ok(index)) => items.get(index),
+                (value, _) => value.get(field),
+            }
+            .ok_or_else(|| format!("no {field} in ${{{path}}}"))?;
+            rest = &segment[end..];
+        }
+    }
+    json_scalar(value).ok_or_else(|| format!("${{{path}}} is not a single value"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn parses_cli_and_json_steps() {
+        assert_eq!(
+            parse_step("new = rule insert --server ${srv}"),
+            Ok(Step {
+                words: words("rule insert --server ${srv}"),
+                capture: Some("new".to_string()),
+            })
+        );
+        assert_eq!(
+            parse_step("session list"),
+            Ok(Step {
+                words: words("session list"),
+                capture: None,
+            })
+        );
+        assert_eq!(
+            parse_step(r#"{"command": "lock list --cluster 'a b'", "capture": "locks"}"#),
+            Ok(Step {
+                words: vec![
+                    "lock".to_string(),
+                    "list".to_string(),
+                    "--cluster".to_string(),
+                    "a b".to_string()
+                ],
+                capture: Some("locks".to_string()),
+            })
+        );
+        assert_eq!(
+            parse_step(r#"{"command": ["cluster", "list"]}"#),
+            Ok(Step {
+                words: words("cluster list"),
+                capture: None,
+            })
+        );
+        assert!(parse_step("1x = cluster list").is_err());
+        assert!(parse_step(r#"{"cmd": "cluster list"}"#).is_err());
+    }
+
+    #[test]
+    fn substitutes_captured_values() {
+        let nil = vec![0u8; 16];
+        let mut uuid = nil.clone();
+        uuid[15] = 7;
+        let mut vars = BTreeMap::new();
+        vars.insert("new".to_string(), serde_json::json!({ "rule": uuid }));
+        vars.insert(
+            "ib".to_string(),
+            serde_json::json!({
+                "summaries": [
+                    {"infobase": nil, "name": "hr"},
+                    {"infobase": uuid, "name": "trade"},
+                ]
+            }),
+        );
+        let text = "00000000-0000-0000-0000-000000000007";
+        assert_eq!(substitute("${new}", &vars).as_deref(), Ok(text));
+        assert_eq!(substitute("${new.rule}", &vars).as_deref(), Ok(text));
+        assert_eq!(
+            substitute("--infobase=${ib.summaries[name=trade].infobase}", &vars),
+            Ok(format!("--infobase={text}"))
+        );
+        assert_eq!(
+            substitute("${ib.summaries.0.name}", &vars).as_deref(),
+            Ok("hr")
+        );
+        assert_eq!(substitute("plain", &vars).as_deref(), Ok("plain"));
+        assert!(substitute("${missing}", &vars).is_err());
+        assert!(substitute("${ib.summaries}", &vars).is_err());
+        assert!(substitute("${ib", &vars).is_err());
+    }
+}
@@ -0,0 +1,423 @@
+//! `rac_lite shell <addr>`: commands read from a prompt and run over one
+//! connection.
+//!
+//! Lines use the one-shot grammar without the address (`session list`,
+//! `--format json infobase summary-list`). `use cluster <name|uuid>` supplies
+//! `--cluster`, and credentials are remembered once a command given them
+//! succeeds. Tab completes subcommands, flags and the UUIDs and names of
+//! records listed earlier in the session.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use clap::{Command, CommandFactory};
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::FileHistory;
+use rustyline::validate::Validator;
+use rustyline::{Editor, Helper};
+use serde_json::Value;
+
+use rac_protocol::error::{RacError, Result};
+use rac_protocol::rac_wire::format_uuid;
+
+use crate::rac_lite::cli::Cli;
+use crate::rac_lite::config::default_config_path;
+use crate::rac_lite::console_output as console;
+use crate::rac_lite::context::{CommandError, Context};
+use crate::rac_lite::parse::json_uuid;
+
+const BUILTINS: [&str; 4] = ["exit", "help", "quit", "use"];
+
+const HELP: &str = "\
+Shell commands:
+  use cluster <name|uuid>  run the following commands against this cluster
+  use none                 forget the cluster and its credentials
+  use                      show the current context
+  exit, quit               leave the shell (also Ctrl-D)
+
+Other lines are rac_lite commands without the address, e.g. `session list`;
+`<command> --help` describes one.";
+
+pub fn run(mut context: Context) -> Result<()> {
+    let protocol = context.connect()?;
+    println!(
+        "connected to {} ({protocol}); `help` lists commands",
+        context.addr()
+    );
+
+    let mut editor: Editor<ShellHelper, FileHistory> = Editor::new().map_err(editor_error)?;
+    editor.set_helper(Some(ShellHelper::default()));
+    let history = history_path();
+    if let Some(path) = &history {
+        let _ = editor.load_history(path);
+    }
+    console::keep_printed();
+
+    loop {
+        let line = match editor.readline(&context.prompt()) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(err) => return Err(editor_error(err)),
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let _ = editor.add_history_entry(history_entry(line));
+        if !execute(&mut context, line) {
+            break;
+        }
+        if let Some(helper) = editor.helper_mut() {
+            for value in console::take_printed() {
+                harvest(&value, &mut helper.values);
+            }
+        }
+    }
+
+    if let Some(path) = &history {
+        if let Some(dir) = path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        let _ = editor.save_history(path);
+    }
+    context.close()
+}
+
+/// Runs one line; `false` when the shell should exit.
+fn execute(context: &mut Context, line: &str) -> bool {
+    let words = match split_line(line) {
+        Ok(words) => words,
+        Err(msg) => {
+            eprintln!("Error: {msg}");
+            return true;
+        }
+    };
+    match words.first().map(String::as_str) {
+        Some("exit" | "quit") => return false,
+        Some("help") if words.len() == 1 => println!("{HELP}"),
+        Some("use") => {
+            if let Err(err) = context.use_context(&words[1..]) {
+                eprintln!("Error: {err}");
+            }
+        }
+        _ => match context.run(words) {
+            Ok(()) => {}
+            Err(CommandError::Usage(err)) => {
+                let _ = err.print();
+            }
+            Err(err) => eprintln!("{err}"),
+        },
+    }
+    true
+}
+
+/// `~/.config/rac_lite/history`, next to the config file.
+fn history_path() -> Option<PathBuf> {
+    Some(default_config_path()?.parent()?.join("history"))
+}
+
+/// `line` without password flags and their values, so the history file
+/// never holds a password; remembered credentials fill them in on reuse.
+fn history_entry(line: &str) -> String {
+    let Ok(words) = split_line(line) else {
+        return line.to_string();
+    };
+    let is_password = |word: &str| {
+        word.strip_prefix("--")
+            .is_some_and(|flag| flag.split('=').next().unwrap_or(flag).ends_with("pwd"))
+    };
+    if !words.iter().any(|word| is_password(word)) {
+        return line.to_string();
+    }
+    let mut kept = Vec::new();
+    let mut words = words.iter();
+    while let Some(word) = words.next() {
+        if is_password(word) {
+            if !word.contains('=') {
+                words.next();
+            }
+            continue;
+        }
+        kept.push(quote_word(word));
+    }
+    kept.join(" ")
+}
+
+fn editor_error(err: ReadlineError) -> RacError {
+    match err {
+        ReadlineError::Io(err) => RacError::Io(err),
+        other => RacError::Io(io::Error::other(other.to_string())),
+    }
+}
+
+/// Splits a line into words the way a POSIX shell would, without expansions.
+pub fn split_line(line: &str) -> std::result::Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => words.extend(word.take()),
+            '\'' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => return Err("unterminated quote".to_string()),
+                    }
+                }
+            }
+            '"' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c) => word.push(c),
+                            None => return Err("unterminated quote".to_string()),
+                        },
+                        Some(c) => word.push(c),
+                        None => return Err("unterminated quote".to_string()),
+                    }
+                }
+            }
+            '\\' => {
+                if let Some(c) = chars.next() {
+                    word.get_or_insert_with(String::new).push(c);
+                }
+            }
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    words.extend(word);
+    Ok(words)
+}
+
+/// Adds the UUIDs and names found in a printed response.
+fn harvest(value: &Value, values: &mut BTreeSet<String>) {
+    if let Some(uuid) = json_uuid(value) {
+        if uuid != [0u8; 16] {
+            values.insert(format_uuid(&uuid));
+        }
+        return;
+    }
+    match value {
+        Value::Array(items) => {
+            for item in items {
+                harvest(item, values);
+            }
+        }
+        Value::Object(fields) => {
+            for (key, field) in fields {
+                match field {
+                    Value::String(name) if is_name_key(key) && !name.is_empty() => {
+                        values.insert(quote_word(name));
+                    }
+                    _ => harvest(field, values),
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_name_key(key: &str) -> bool {
+    key == "name" || key.ends_with("_name")
+}
+
+fn quote_word(word: &str) -> String {
+    if word
+        .chars()
+        .any(|c| c.is_whitespace() || c == '"' || c == '\'')
+    {
+        format!("'{}'", word.replace('\'', r"'\''"))
+    } else {
+        word.to_string()
+    }
+}
+
+#[derive(Default)]
+struct ShellHelper {
+    values: BTreeSet<String>,
+}
+
+impl Completer for ShellHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &rustyline::Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        Ok(complete(&line[..pos], &self.values))
+    }
+}
+
+impl Hinter for ShellHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ShellHelper {}
+
+impl Validator for ShellHelper {}
+
+impl Helper for ShellHelper {}
+
+/// Start of the word before the cursor and its candidates.
+fn complete(line: &str, values: &BTreeSet<String>) -> (usize, Vec<String>) {
+    let start = line.rfind(char::is_whitespace).map_or(0, |pos| {
+        pos + line[pos..].chars().next().map_or(1, char::len_utf8)
+    });
+    let partial = &line[start..];
+    let words: Vec<&str> = line[..start].split_whitespace().collect();
+
+    let candidates: Vec<String> = match words.as_slice() {
+        [] => {
+            let root = Cli::command();
+            BUILTINS
+                .iter()
+                .map(|name| name.to_string())
+                .chain(subcommand_names(&root))
+                .collect()
+        }
+        ["use"] => vec!["cluster".to_string(), "none".to_string()],
+        ["use", "cluster"] => values.iter().cloned().collect(),
+        ["use", ..] => Vec::new(),
+        _ => {
+            let mut command = Cli::command();
+            for word in &words {
+                if let Some(sub) = command.find_subcommand(word) {
+                    command = sub.clone();
+                }
+            }
+            let expects_value = words.last().is_some_and(|word| takes_value(&command, word));
+            if partial.starts_with('-') {
+                command
+                    .get_arguments()
+                    .filter(|arg| !arg.is_hide_set())
+                    .filter_map(|arg| arg.get_long())
+                    .map(|long| format!("--{long}"))
+                    .collect()
+            } else if command.has_subcommands() && !expects_value {
+                subcommand_names(&command).collect()
+            } else {
+                values.iter().cloned().collect()
+            }
+        }
+    };
+    let mut candidates: Vec<String> = candidates
+        .into_iter()
+        .filter(|candidate| candidate.starts_with(partial))
+        .collect();
+    candidates.sort();
+    candidates.dedup();
+    (start, candidates)
+}
+
+fn subcommand_names(command: &Command) -> impl Iterator<Item = String> + '_ {
+    command
+        .get_subcommands()
+        .filter(|sub| !sub.is_hide_set())
+        .map(|sub| sub.get_name().to_string())
+}
+
+/// Whether `word` is a `--flag` whose value comes next.
+fn takes_value(command: &Command, word: &str) -> bool {
+    let Some(long) = word.strip_prefix("--").filter(|long| !long.contains('=')) else {
+        return false;
+    };
+    command
+        .get_arguments()
+        .any(|arg| arg.get_long() == Some(long) && arg.get_action().takes_values())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(items: &[&str]) -> BTreeSet<String> {
+        items.iter().map(|item| item.to_string()).collect()
+    }
+
+    #[test]
+    fn splits_quoted_words() {
+        assert_eq!(
+            split_line(r#"session list --cluster "Main cluster" --cluster-pwd 'a b'\ c"#),
+            Ok(vec![
+                "session".to_string(),
+                "list".to_string(),
+                "--cluster".to_string(),
+                "Main cluster".to_string(),
+                "--cluster-pwd".to_string(),
+                "a b c".to_string(),
+            ])
+        );
+        assert_eq!(split_line("  ''  "), Ok(vec![String::new()]));
+        assert!(split_line("use cluster \"Main").is_err());
+    }
+
+    #[test]
+    fn history_drops_passwords() {
+        assert_eq!(
+            history_entry(
+                r#"session list --cluster "Main cluster" --cluster-user admin --cluster-pwd 's e'"#
+            ),
+            "session list --cluster 'Main cluster' --cluster-user admin"
+        );
+        assert_eq!(
+            history_entry("agent admin register --agent-pwd=x --name ops --pwd secret"),
+            "agent admin register --name ops"
+        );
+        let plain = r#"session list --cluster "Main cluster""#;
+        assert_eq!(history_entry(plain), plain);
+    }
+
+    #[test]
+    fn completes_commands_flags_and_listed_values() {
+        let seen = values(&["Main", "6e3a3bd4-0b5d-4b55-9e26-3f4bd3e0a5a1"]);
+        assert_eq!(complete("ses", &seen), (0, vec!["session".to_string()]));
+        assert_eq!(complete("u", &seen), (0, vec!["use".to_string()]));
+        assert_eq!(complete("session li", &seen), (8, vec!["list".to_string()]));
+        let (_, flags) = complete("rule list --clu", &seen);
+        assert_eq!(flags, ["--cluster", "--cluster-pwd", "--cluster-user"]);
+        assert_eq!(
+            complete("session list --cluster M", &seen),
+            (23, vec!["Main".to_string()])
+        );
+        assert_eq!(
+            complete("use cluster 6e", &seen),
+            (12, vec!["6e3a3bd4-0b5d-4b55-9e26-3f4bd3e0a5a1".to_string()])
+        );
+    }
+
+    #[test]
+    fn harvests_uuids_and_names() {
+        let nil: Vec<u8> = vec![0; 16];
+        let mut uuid = nil.clone();
+        uuid[15] = 1;
+        let value = serde_json::json!({
+            "records": [
+                {"uuid": uuid, "name": "Main", "port": 1541},
+                {"uuid": nil, "infobase_name": "trade base", "descr": "ignored"},
+            ]
+        });
+        let mut seen = BTreeSet::new();
+        harvest(&value, &mut seen);
+        assert_eq!(
+            seen,
+            values(&[
+                "'trade base'",
+                "00000000-0000-0000-0000-000000000001",
+                "Main"
+            ])
+        );
+    }
+}
@@ -0,0 +1,320 @@
+//! `--watch <interval>`: re-runs a list command over one connection and
+//! redraws it, marking records added (`+`), removed (`-`) and changed (`~`)
+//! since the previous run. Changed records list their fields as
+//! `old -> new`, with the difference for numbers such as `memory_current`.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::io::{self, IsTerminal, Write as _};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde_json::{Map, Value};
+
+use rac_protocol::error::{RacError, Result};
+use rac_protocol::rac_wire::format_uuid;
+
+use crate::rac_lite::cli::TopCommand;
+use crate::rac_lite::connector::Connector;
+use crate::rac_lite::console_output::{self as console, Output};
+use crate::rac_lite::parse::{json_scalar, json_uuid};
+use crate::rac_lite::runner;
+
+/// Fields shown next to the key to tell records apart.
+const LABEL_FIELDS: [&str; 5] = ["name", "user_name", "app_id", "application", "host"];
+
+const GREEN: &str = "\x1b[32m";
+const RED: &str = "\x1b[31m";
+const YELLOW: &str = "\x1b[33m";
+const RESET: &str = "\x1b[0m";
+
+pub fn run(mut conn: Connector, command: TopCommand, interval: Duration) -> Result<()> {
+    let kind = command.name();
+    let tty = io::stdout().is_terminal();
+    console::keep_printed();
+    let mut previous: Option<List> = None;
+    loop {
+        let started = Instant::now();
+        let body = match runner::dispatch(Output::Quiet, &mut conn, command.clone()) {
+            Ok(()) => {
+                let resp = console::take_printed().pop().unwrap_or_default();
+                let list = List::from_response(kind, &resp).ok_or_else(|| {
+                    RacError::InvalidArgs("--watch needs a list command".to_string())
+                })?;
+                let body = render(&list, previous.as_ref(), tty);
+                previous = Some(list);
+                body
+            }
+            // Keep watching through outages once the first run succeeded.
+            Err(err) if previous.is_some() => {
+                conn.reset();
+                format!("Error: {err}\n")
+            }
+            Err(err) => return Err(err),
+        };
+        let header = format!("every {interval:?}, {} UTC", clock());
+        let mut stdout = io::stdout().lock();
+        if tty {
+            write!(stdout, "\x1b[H\x1b[2J{header}\n{body}")?;
+        } else {
+            writeln!(stdout, "{header}\n{body}")?;
+        }
+        stdout.flush()?;
+        drop(stdout);
+        thread::sleep(interval.saturating_sub(started.elapsed()));
+    }
+}
+
+/// Records of one run, keyed for comparison with the next.
+struct List {
+    label: String,
+    records: Vec<(String, Map<String, Value>)>,
+}
+
+impl List {
+    /// `None` unless the response is a single array of records.
+    fn from_response(kind: &str, resp: &Value) -> Option<Self> {
+        let fields = resp.as_object()?;
+        let (label, items) = match fields.iter().next() {
+            Some((label, Value::Array(items))) if fields.len() == 1 => (label, items),
+            _ => return None,
+        };
+        let records: Vec<Map<String, Value>> = items
+            .iter()
+            .map(|item| item.as_object().cloned())
+            .collect::<Option<_>>()?;
+        let keys = record_keys(kind, &records);
+        Some(Self {
+            label: label.clone(),
+            records: keys.into_iter().zip(records).collect(),
+        })
+    }
+}
+
+/// Keys from the first strategy that tells all records apart: the field
+/// named after the command (`session`, `process`), `uuid`, `name`, all UUID
+/// fields together (locks), and finally the position.
+fn record_keys(kind: &str, records: &[Map<String, Value>]) -> Vec<String> {
+    let own = kind.replace('-', "_");
+    let by_field = |field: &str| -> Option<Vec<String>> {
+        records
+            .iter()
+            .map(|record| record.get(field).and_then(json_scalar))
+            .collect()
+    };
+    let by_uuids = || -> Vec<String> {
+        records
+            .iter()
+            .map(|record| {
+                record
+                    .values()
+                    .filter_map(json_uuid)
+                    .map(|uuid| format_uuid(&uuid))
+                    .collect::<Vec<_>>()
+                    .join("/")
+            })
+            .collect()
+    };
+    let unique = |keys: &Vec<String>| {
+        !keys.iter().any(String::is_empty)
+            && keys.iter().collect::<BTreeSet<_>>().len() == keys.len()
+    };
+    let keys = [own.as_str(), "uuid", "name"]
+        .into_iter()
+        .filter_map(by_field)
+        .chain(std::iter::once(by_uuids()))
+        .find(unique);
+    keys.unwrap_or_else(|| (1..=records.len()).map(|n| format!("#{n}")).collect())
+}
+
+fn render(list: &List, previous: Option<&List>, color: bool) -> String {
+    let before: BTreeMap<&str, &Map<String, Value>> = previous
+        .map(|previous| {
+            previous
+                .records
+                .iter()
+                .map(|(key, record)| (key.as_str(), record))
+                .collect()
+        })
+        .unwrap_or_default();
+    let current: BTreeSet<&str> = list.records.iter().map(|(key, _)| key.as_str()).collect();
+    let paint = |code: &'static str| if color { code } else { "" };
+    let reset = if color { RESET } else { "" };
+
+    let mut lines = String::new();
+    let (mut added, mut changed) = (0, 0);
+    for (key, record) in &list.records {
+        let label = record_label(record);
+        match before.get(key.as_str()) {
+            None if previous.is_some() => {
+                added += 1;
+                let _ = writeln!(lines, "{}+ {key}{label}{reset}", paint(GREEN));
+            }
+            Some(old) if *old != record => {
+                changed += 1;
+                let _ = writeln!(lines, "{}~ {key}{label}{reset}", paint(YELLOW));
+                for (field, value) in record {
+                    let old = old.get(field).unwrap_or(&Value::Null);
+                    if old != value {
+                        let _ = writeln!(lines, "    {field}: {}", field_delta(old, value));
+                    }
+                }
+            }
+            _ => {
+                let _ = writeln!(lines, "  {key}{label}");
+            }
+        }
+    }
+    let mut removed = 0;
+    if let Some(previous) = previous {
+        for (key, record) in &previous.records {
+            if !current.contains(key.as_str()) {
+                removed += 1;
+                let label = record_label(record);
+                let _ = writeln!(lines, "{}- {key}{label}{reset}", paint(RED));
+            }
+        }
+    }
+    format!(
+        "{} {}: +{added} -{removed} ~{changed}\n{lines}",
+        list.records.len(),
+        list.label
+    )
+}
+
+fn record_label(record: &Map<String, Value>) -> String {
+    LABEL_FIELDS
+        .iter()
+        .filter_map(|field| {
+            let value = record.get(*field).and_then(json_scalar)?;
+            (!value.is_empty()).then(|| format!(" {field}={value}"))
+        })
+        .take(3)
+        .collect()
+}
+
+/// `old -> new`, plus the signed difference for numbers.
+fn field_delta(old: &Value, new: &Value) -> String {
+    let text = |value: &Value| json_scalar(value).unwrap_or_else(|| value.to_string());
+    let delta = match (old, new) {
+        (Value::Number(old), Value::Number(new)) => match (old.as_i64(), new.as_i64()) {
+            (Some(old), Some(new)) => format!(" ({:+})", new - old),
+            _ => match (old.as_f64(), new.as_f64()) {
+                (Some(old), Some(new)) => format!(" ({:+})", new - old),
+                _ => String::new(),
+            },
+        },
+        _ => String::new(),
+    };
+    format!("{} -> {}{delta}", text(old), text(new))
+}
+
+/// Wall-clock time of day, `HH:MM:SS` in UTC.
+fn clock() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let day = secs % 86_400;
+    format!("{:02}:{:02}:{:02}", day / 3600, day % 3600 / 60, day % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+    use crate::rac_lite::cli::Cli;
+    use crate::rac_lite::parse::parse_interval;
+
+    fn uuid(last: u8) -> Value {
+        let mut bytes = vec![0u8; 16];
+        bytes[15] = last;
+        serde_json::json!(bytes)
+    }
+
+    fn sessions(records: Value) -> List {
+        List::from_response("session", &serde_json::json!({ "records": records })).expect("list")
+    }
+
+    #[test]
+    fn marks_added_removed_and_changed_records() {
+        let first = sessions(serde_json::json!([
+            {"session": uuid(1), "user_name": "ivanov", "memory_current": 1000, "app_id": "1CV8C"},
+            {"session": uuid(2), "user_name": "petrov", "memory_current": 10, "app_id": ""},
+        ]));
+        let second = sessions(serde_json::json!([
+            {"session": uuid(1), "user_name": "ivanov", "memory_current": 1500, "app_id": "1CV8C"},
+            {"session": uuid(3), "user_name": "sidorov", "memory_current": 0, "app_id": ""},
+        ]));
+        assert_eq!(
+            render(&first, None, false),
+            "2 records: +0 -0 ~0\n\
+             \x20 00000000-0000-0000-0000-000000000001 user_name=ivanov app_id=1CV8C\n\
+             \x20 00000000-0000-0000-0000-000000000002 user_name=petrov\n"
+        );
+        assert_eq!(
+            render(&second, Some(&first), false),
+            "2 records: +1 -1 ~1\n\
+             ~ 00000000-0000-0000-0000-000000000001 user_name=ivanov app_id=1CV8C\n\
+             \x20   memory_current: 1000 -> 1500 (+500)\n\
+             + 00000000-0000-0000-0000-000000000003 user_name=sidorov\n\
+             - 00000000-0000-0000-0000-000000000002 user_name=petrov\n"
+        );
+        assert!(render(&second, Some(&first), true).contains("\x1b[32m+ "));
+    }
+
+    #[test]
+    fn keys_records_by_the_best_identifying_field() {
+        let records = |value: Value| -> Vec<Map<String, Value>> {
+            serde_json::from_value(value).expect("records")
+        };
+        let clusters = records(serde_json::json!([{"uuid": uuid(1), "name": "Main"}]));
+        assert_eq!(
+            record_keys("cluster", &clusters),
+            ["00000000-0000-0000-0000-000000000001"]
+        );
+        let counters = records(serde_json::json!([{"name": "cpu"}, {"name": "mem"}]));
+        assert_eq!(record_keys("counter", &counters), ["cpu", "mem"]);
+        let locks = records(serde_json::json!([
+            {"connection": uuid(1), "object": uuid(2), "descr": "a"},
+            {"connection": uuid(1), "object": uuid(3), "descr": "b"},
+        ]));
+        assert_eq!(
+            record_keys("lock", &locks)[1],
+            "00000000-0000-0000-0000-000000000001/00000000-0000-0000-0000-000000000003"
+        );
+        let rows = records(serde_json::json!([{"descr": "a"}, {"descr": "a"}]));
+        assert_eq!(record_keys("limit", &rows), ["#1", "#2"]);
+        assert!(List::from_response("cluster", &serde_json::json!({"record": {}})).is_none());
+    }
+
+    #[test]
+    fn only_list_commands_are_watched() {
+        let command = |line: &str| {
+            let args = std::iter::once("rac_lite").chain(line.split_whitespace());
+            Cli::try_parse_from(args).expect("parse").command
+        };
+        assert!(command("session list 127.0.0.1:1545 --cluster Main").is_list());
+        assert!(command("cluster admin list 127.0.0.1:1545 --cluster Main").is_list());
+        assert!(command("counter values 127.0.0.1:1545 --cluster Main --counter cpu").is_list());
+        assert!(!command("cluster info 127.0.0.1:1545 --cluster Main").is_list());
+        assert!(!command(
+            "session terminate 127.0.0.1:1545 --cluster Main \
+             --session 00000000-0000-0000-0000-000000000001"
+        )
+        .is_list());
+    }
+
+    #[test]
+    fn parses_intervals_and_deltas() {
+        assert_eq!(parse_interval("2"), Ok(Duration::from_secs(2)));
+        assert_eq!(parse_interval("1.5s"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_interval("500ms"), Ok(Duration::from_millis(500)));
+        assert!(parse_interval("0").is_err());
+        assert!(parse_interval("soon").is_err());
+        let delta = field_delta(&serde_json::json!(0.5), &serde_json::json!(0.25));
+        assert_eq!(delta, "0.5 -> 0.25 (-0.25)");
+        let delta = field_delta(&serde_json::json!("a"), &serde_json::json!("b"));
+        assert_eq!(delta, "a -> b");
+    }
+}
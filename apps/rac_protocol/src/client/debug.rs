@@ -19,6 +19,11 @@ pub(crate) fn format_payload_head(payload: &[u8], max_len: usize) -> String {
 }
 
 pub(crate) fn log_frame(label: &str, frame: &Frame) {
+    log_frame_for(None, label, frame);
+}
+
+/// As [`log_frame`], tagged with the request the frame belongs to.
+pub(crate) fn log_frame_for(request_id: Option<&str>, label: &str, frame: &Frame) {
     let mut hex = String::new();
     for (idx, b) in frame.payload.iter().enumerate() {
         if idx > 0 {
@@ -26,8 +31,11 @@ pub(crate) fn log_frame(label: &str, frame: &Frame) {
         }
         hex.push_str(&format!("{b:02x}"));
     }
+    let request = request_id
+        .map(|id| format!(" request_id={id}"))
+        .unwrap_or_default();
     eprintln!(
-        "rac_lite debug: {label}: opcode=0x{:02x} len={} payload_hex={}",
+        "rac_lite debug: {label}:{request} opcode=0x{:02x} len={} payload_hex={}",
         frame.opcode,
         frame.payload.len(),
         hex
@@ -5,27 +5,25 @@ mod transport;
 use std::io;
 use std::time::Duration;
 
-use crate::client::debug::{format_payload_head, log_frame};
+use crate::client::debug::{format_payload_head, log_frame_for};
 use crate::client::handshake::negotiate;
 use crate::protocol::{ProtocolCodec, ProtocolVersion};
 use crate::client::transport::RacTransport;
 use crate::codec::RecordCursor;
+use crate::dry_run::{PlannedRpc, CONTEXT_WIRE};
 use crate::error::{RacError, Result};
-use crate::rpc::{Request, Response};
+use crate::protocol::SerializedRpc;
+use crate::registry;
+use crate::rpc::{Mutation, Request, Response};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Default)]
 pub enum ProtocolPreference {
+    #[default]
     Auto,
     V11_0,
     V16_0,
 }
 
-impl Default for ProtocolPreference {
-    fn default() -> Self {
-        Self::Auto
-    }
-}
-
 impl ProtocolPreference {
     pub fn candidates(self) -> &'static [ProtocolVersion] {
         const AUTO: [ProtocolVersion; 2] = [ProtocolVersion::V16_0, ProtocolVersion::V11_0];
@@ -46,6 +44,8 @@ pub struct ClientConfig {
     pub write_timeout: Duration,
     pub debug_raw: bool,
     pub protocol: ProtocolPreference,
+    /// Refuse RPCs that are not [`Mutation::Read`] before they are sent.
+    pub read_only: bool,
 }
 
 impl Default for ClientConfig {
@@ -56,17 +56,26 @@ impl Default for ClientConfig {
             write_timeout: Duration::from_secs(5),
             debug_raw: false,
             protocol: ProtocolPreference::default(),
+            read_only: false,
         }
     }
 }
 
+/// Reply to an RPC without a response body.
+pub(crate) const ACK: [u8; 4] = [0x01, 0x00, 0x00, 0x00];
+
 pub struct RacClient {
-    transport: RacTransport,
+    /// `None` for a dry-run client, which records RPCs in `planned`.
+    transport: Option<RacTransport>,
     protocol: Box<dyn ProtocolCodec>,
     protocol_version: ProtocolVersion,
     current_cluster: Option<crate::Uuid16>,
     current_infobase: Option<crate::Uuid16>,
     debug_raw: bool,
+    read_only: bool,
+    /// Tags debug output with the caller's request (see [`Self::set_request_id`]).
+    request_id: Option<String>,
+    planned: Vec<PlannedRpc>,
 }
 
 impl RacClient {
@@ -92,37 +101,76 @@ impl RacClient {
         protocol: Box<dyn ProtocolCodec>,
         protocol_version: ProtocolVersion,
     ) -> Result<Self> {
-        let transport = RacTransport::connect(
+        let mut transport = RacTransport::connect(
             addr,
             cfg.connect_timeout,
             cfg.read_timeout,
             cfg.write_timeout,
         )?;
+        negotiate(&mut transport, protocol.as_ref(), cfg.debug_raw)?;
 
-        let mut client = Self {
-            transport,
+        Ok(Self {
+            transport: Some(transport),
             protocol,
             protocol_version,
             current_cluster: None,
             current_infobase: None,
             debug_raw: cfg.debug_raw,
-        };
+            read_only: cfg.read_only,
+            request_id: None,
+            planned: Vec::new(),
+        })
+    }
 
-        negotiate(
-            &mut client.transport,
-            client.protocol.as_ref(),
-            client.debug_raw,
-        )?;
-        Ok(client)
+    /// A client that encodes RPCs without connecting. Authentication and
+    /// context RPCs are recorded and acknowledged; the first RPC that changes
+    /// cluster state is recorded and fails with [`RacError::DryRun`]. RPCs
+    /// that need a reply, such as name lookups, are refused.
+    pub fn dry_run(protocol_version: ProtocolVersion) -> Self {
+        Self {
+            transport: None,
+            protocol: protocol_version.boxed(),
+            protocol_version,
+            current_cluster: None,
+            current_infobase: None,
+            debug_raw: false,
+            read_only: false,
+            request_id: None,
+            planned: Vec::new(),
+        }
+    }
+
+    /// RPCs a dry-run client would have sent, in order.
+    pub fn planned(&self) -> &[PlannedRpc] {
+        &self.planned
     }
 
-    pub fn close(mut self) -> Result<()> {
-        self.transport
-            .write_frame(self.protocol.opcode_close(), self.protocol.close_payload())?;
-        self.transport.flush()?;
+    pub fn close(self) -> Result<()> {
+        let Some(mut transport) = self.transport else {
+            return Ok(());
+        };
+        transport.write_frame(self.protocol.opcode_close(), self.protocol.close_payload())?;
+        transport.flush()?;
         Ok(())
     }
 
+    /// Request the following calls are made for; shown in `debug_raw` output
+    /// so frames of pooled connections can be matched to gateway requests.
+    pub fn set_request_id(&mut self, request_id: Option<String>) {
+        self.request_id = request_id;
+    }
+
+    /// Turns [`ClientConfig::read_only`] on or off for the following calls.
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
+    /// False for dry-run and read-only clients, which never send an RPC that
+    /// changes the cluster.
+    pub fn sends_changes(&self) -> bool {
+        self.transport.is_some() && !self.read_only
+    }
+
     pub fn protocol_name(&self) -> &'static str {
         self.protocol.name()
     }
@@ -132,6 +180,13 @@ impl RacClient {
     }
 
     pub fn call<R: Request>(&mut self, request: R) -> Result<Vec<u8>> {
+        let meta = request.meta();
+        if self.read_only && meta.mutation != Mutation::Read {
+            return Err(RacError::ReadOnly {
+                method: meta.method_req,
+                mutation: meta.mutation,
+            });
+        }
         let required = request.required_context();
         if let Some(cluster) = required.cluster {
             self.ensure_cluster_context(cluster)?;
@@ -141,6 +196,43 @@ impl RacClient {
         }
 
         let serialized = request.encode(self.protocol.as_ref())?;
+        if self.transport.is_none() {
+            let spec = registry::find(meta.name);
+            let planned = PlannedRpc {
+                name: meta.name,
+                mutation: meta.mutation,
+                payload: serialized.payload,
+                wire: spec.map_or(&[], |spec| spec.wire),
+            };
+            return self.record(planned, serialized.expect_method);
+        }
+        self.send_rpc_raw(&serialized.payload, serialized.expect_method)
+    }
+
+    fn record(&mut self, rpc: PlannedRpc, expect_method: Option<u8>) -> Result<Vec<u8>> {
+        if rpc.mutation == Mutation::Read && expect_method.is_some() {
+            return Err(RacError::Unsupported(
+                "a dry run gets no replies: pass UUIDs, not names, to a command that changes the cluster",
+            ));
+        }
+        let mutation = rpc.mutation;
+        self.planned.push(rpc);
+        if mutation != Mutation::Read {
+            return Err(RacError::DryRun);
+        }
+        Ok(ACK.to_vec())
+    }
+
+    fn send_context(&mut self, name: &'static str, serialized: SerializedRpc) -> Result<Vec<u8>> {
+        if self.transport.is_none() {
+            let planned = PlannedRpc {
+                name,
+                mutation: Mutation::Read,
+                payload: serialized.payload,
+                wire: CONTEXT_WIRE,
+            };
+            return self.record(planned, serialized.expect_method);
+        }
         self.send_rpc_raw(&serialized.payload, serialized.expect_method)
     }
 
@@ -167,7 +259,7 @@ impl RacClient {
 
         let serialized = self.protocol.serialize_set_cluster_context(cluster)?;
 
-        if let Err(err) = self.send_rpc_raw(&serialized.payload, serialized.expect_method) {
+        if let Err(err) = self.send_context("cluster.context", serialized) {
             if let RacError::Io(io_err) = &err {
                 if io_err.kind() == io::ErrorKind::WouldBlock {
                     self.current_cluster = Some(cluster);
@@ -190,7 +282,7 @@ impl RacClient {
 
         let serialized = self.protocol.serialize_set_infobase_context(cluster)?;
 
-        if let Err(err) = self.send_rpc_raw(&serialized.payload, serialized.expect_method) {
+        if let Err(err) = self.send_context("infobase.context", serialized) {
             if let RacError::Io(io_err) = &err {
                 if io_err.kind() == io::ErrorKind::WouldBlock {
                     self.current_infobase = Some(cluster);
@@ -205,8 +297,12 @@ impl RacClient {
     }
 
     fn send_rpc_raw(&mut self, payload: &[u8], expect_method: Option<u8>) -> Result<Vec<u8>> {
+        let Some(transport) = self.transport.as_mut() else {
+            return Err(RacError::Protocol("dry-run client has no connection"));
+        };
         if self.debug_raw {
-            log_frame(
+            log_frame_for(
+                self.request_id.as_deref(),
                 "rpc-send",
                 &crate::rac_wire::Frame {
                     opcode: self.protocol.opcode_rpc(),
@@ -215,12 +311,11 @@ impl RacClient {
                 },
             );
         }
-        self.transport
-            .write_frame(self.protocol.opcode_rpc(), payload)?;
-        self.transport.flush()?;
+        transport.write_frame(self.protocol.opcode_rpc(), payload)?;
+        transport.flush()?;
 
         for _ in 0..6 {
-            let reply = match self.transport.read_frame() {
+            let reply = match transport.read_frame() {
                 Ok(reply) => reply,
                 Err(err) => {
                     if matches!(err.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) {
@@ -230,14 +325,18 @@ impl RacClient {
                 }
             };
             if self.debug_raw {
-                log_frame("rpc-recv", &reply);
+                log_frame_for(self.request_id.as_deref(), "rpc-recv", &reply);
             }
             if reply.opcode == 0x0f {
                 continue;
             }
             if reply.opcode != self.protocol.opcode_rpc() {
                 if self.debug_raw {
-                    log_frame("rpc-unexpected-opcode", &reply);
+                    log_frame_for(
+                        self.request_id.as_deref(),
+                        "rpc-unexpected-opcode",
+                        &reply,
+                    );
                 }
                 let head = format_payload_head(&reply.payload, 24);
                 return Err(RacError::ProtocolMessage(format!(
@@ -249,7 +348,7 @@ impl RacClient {
                 )));
             }
 
-            if reply.payload == [0x01, 0x00, 0x00, 0x00] {
+            if reply.payload == ACK {
                 if expect_method.is_some() {
                     continue;
                 }
@@ -264,7 +363,11 @@ impl RacClient {
                             continue;
                         }
                         if self.debug_raw {
-                            log_frame("rpc-missing-header", &reply);
+                            log_frame_for(
+                                self.request_id.as_deref(),
+                                "rpc-missing-header",
+                                &reply,
+                            );
                         }
                         let head = format_payload_head(&reply.payload, 24);
                         return Err(RacError::ProtocolMessage(format!(
@@ -276,7 +379,11 @@ impl RacClient {
                 };
                 if got != expect {
                     if self.debug_raw {
-                        log_frame("rpc-unexpected-method", &reply);
+                        log_frame_for(
+                            self.request_id.as_deref(),
+                            "rpc-unexpected-method",
+                            &reply,
+                        );
                     }
                     return Err(RacError::UnexpectedMethod {
                         got,
@@ -303,3 +410,50 @@ fn is_service_notice(payload: &[u8]) -> bool {
     };
     head == [0x01, 0x00, 0x00, 0xff]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::session::{SessionListRpc, SessionTerminateRpc};
+    use crate::commands::RuleApplyRpc;
+
+    #[test]
+    fn read_only_client_refuses_changes_before_sending() {
+        let cluster = [1u8; 16];
+        let mut client = RacClient::dry_run(ProtocolVersion::V11_0);
+        client.read_only = true;
+
+        let err = client
+            .call(RuleApplyRpc { cluster, mode: 0 })
+            .expect_err("modify");
+        assert!(matches!(
+            err,
+            RacError::ReadOnly {
+                mutation: Mutation::Modify,
+                ..
+            }
+        ));
+        let err = client
+            .call(SessionTerminateRpc {
+                cluster,
+                session: [2; 16],
+                error_message: String::new(),
+            })
+            .expect_err("destructive");
+        assert!(matches!(
+            err,
+            RacError::ReadOnly {
+                mutation: Mutation::Destructive,
+                ..
+            }
+        ));
+        // Refused before the cluster context, let alone the request itself.
+        assert!(client.planned().is_empty());
+        assert_eq!(client.current_cluster, None);
+
+        let err = client
+            .call(SessionListRpc { cluster })
+            .expect_err("needs a reply");
+        assert!(matches!(err, RacError::Unsupported(_)));
+    }
+}
@@ -516,5 +516,5 @@ fn civil_from_days(days_since_unix_epoch: i64) -> (i64, i64, i64) {
     let d = doy - (153 * mp + 2).div_euclid(5) + 1;
     let m = mp + if mp < 10 { 3 } else { -9 };
     let year = y + if m <= 2 { 1 } else { 0 };
-    (year, i64::from(m), i64::from(d))
+    (year, m, d)
 }
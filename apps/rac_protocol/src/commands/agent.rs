@@ -6,6 +6,7 @@ mod generated {
     include!("agent_generated.rs");
 }
 
+pub(crate) use generated::COMMANDS;
 pub use generated::{
     AgentAdminListRpc,
     AgentAdminListResp,
@@ -29,6 +30,7 @@ pub fn agent_admin_list(
     client.call_typed(AgentAdminListRpc)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn agent_admin_register(
     client: &mut RacClient,
     agent_user: &str,
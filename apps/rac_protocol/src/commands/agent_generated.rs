@@ -3,6 +3,7 @@ use crate::protocol::ProtocolVersion;
 use crate::codec::RecordCursor;
 use crate::error::Result;
 use serde::Serialize;
+use serde::Deserialize;
 use crate::rac_wire::encode_with_len_u14;
 use crate::rac_wire::encode_with_len_u8;
 
@@ -67,6 +68,7 @@ impl AgentVersionRecord {
     }
 }
 
+#[derive(Deserialize)]
 pub struct AgentAuthRpc {
     pub user: String,
     pub pwd: String,
@@ -85,7 +87,7 @@ impl crate::rpc::Request for AgentAuthRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc AgentAuth unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 1 + self.user.len() } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.pwd.len() } else { 0 });
@@ -99,6 +101,7 @@ impl crate::rpc::Request for AgentAuthRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct AgentAdminListRpc;
 
 impl crate::rpc::Request for AgentAdminListRpc {
@@ -114,13 +117,14 @@ impl crate::rpc::Request for AgentAdminListRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc AgentAdminList unsupported for protocol"));
         }
         Ok(Vec::new())
     }
 }
 
+#[derive(Deserialize)]
 pub struct AgentAdminRegisterRpc {
     pub name: String,
     pub descr: String,
@@ -143,7 +147,7 @@ impl crate::rpc::Request for AgentAdminRegisterRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc AgentAdminRegister unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 1 + self.name.len() } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { if self.descr.len() < 0x40 { 1 + self.descr.len() } else { 2 + self.descr.len() } } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.pwd.len() } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.os_user.len() } else { 0 });
@@ -169,6 +173,7 @@ impl crate::rpc::Request for AgentAdminRegisterRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct AgentAdminRemoveRpc {
     pub name: String,
 }
@@ -186,7 +191,7 @@ impl crate::rpc::Request for AgentAdminRemoveRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc AgentAdminRemove unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 1 + self.name.len() } else { 0 });
@@ -197,6 +202,7 @@ impl crate::rpc::Request for AgentAdminRemoveRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct AgentVersionRpc;
 
 impl crate::rpc::Request for AgentVersionRpc {
@@ -212,7 +218,7 @@ impl crate::rpc::Request for AgentVersionRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc AgentVersion unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(0);
@@ -264,40 +270,127 @@ pub fn parse_agent_version_body(body: &[u8], protocol_version: ProtocolVersion)
 
 
 pub const RPC_AGENT_AUTH_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "agent.auth",
     method_req: METHOD_AGENT_AUTH_REQ,
     method_resp: None,
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: false,
     requires_infobase_context: false,
 };
 
 pub const RPC_AGENT_ADMIN_LIST_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "agent.admin_list",
     method_req: METHOD_AGENT_ADMIN_LIST_REQ,
     method_resp: Some(METHOD_AGENT_ADMIN_LIST_RESP),
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: false,
     requires_infobase_context: false,
 };
 
 pub const RPC_AGENT_ADMIN_REGISTER_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "agent.admin_register",
     method_req: METHOD_AGENT_ADMIN_REGISTER_REQ,
     method_resp: None,
+    mutation: crate::rpc::Mutation::Modify,
     requires_cluster_context: false,
     requires_infobase_context: false,
 };
 
 pub const RPC_AGENT_ADMIN_REMOVE_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "agent.admin_remove",
     method_req: METHOD_AGENT_ADMIN_REMOVE_REQ,
     method_resp: None,
+    mutation: crate::rpc::Mutation::Destructive,
     requires_cluster_context: false,
     requires_infobase_context: false,
 };
 
 pub const RPC_AGENT_VERSION_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "agent.version",
     method_req: METHOD_AGENT_VERSION_REQ,
     method_resp: Some(METHOD_AGENT_VERSION_RESP),
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: false,
     requires_infobase_context: false,
 };
 
+pub const COMMANDS: &[crate::registry::CommandSpec] = &[
+    crate::registry::CommandSpec {
+        name: "agent.auth",
+        request: "AgentAuthRpc",
+        response: "AckResponse",
+        args: &[
+            crate::registry::ArgSpec { name: "user", kind: crate::registry::ArgKind::String },
+            crate::registry::ArgSpec { name: "pwd", kind: crate::registry::ArgKind::String },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "user", ty: crate::registry::WireType::Str8 },
+            crate::registry::WireField { name: "pwd", ty: crate::registry::WireType::Str8 },
+        ],
+        meta: RPC_AGENT_AUTH_META,
+        validate: crate::registry::validate::<AgentAuthRpc>,
+        call: crate::registry::call::<AgentAuthRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "agent.admin_list",
+        request: "AgentAdminListRpc",
+        response: "AgentAdminListResp",
+        args: &[],
+        wire: &[],
+        meta: RPC_AGENT_ADMIN_LIST_META,
+        validate: crate::registry::validate::<AgentAdminListRpc>,
+        call: crate::registry::call::<AgentAdminListRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "agent.admin_register",
+        request: "AgentAdminRegisterRpc",
+        response: "AckResponse",
+        args: &[
+            crate::registry::ArgSpec { name: "name", kind: crate::registry::ArgKind::String },
+            crate::registry::ArgSpec { name: "descr", kind: crate::registry::ArgKind::String },
+            crate::registry::ArgSpec { name: "pwd", kind: crate::registry::ArgKind::String },
+            crate::registry::ArgSpec { name: "auth_pwd", kind: crate::registry::ArgKind::U8 },
+            crate::registry::ArgSpec { name: "auth_os", kind: crate::registry::ArgKind::U8 },
+            crate::registry::ArgSpec { name: "os_user", kind: crate::registry::ArgKind::String },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "name", ty: crate::registry::WireType::Str8 },
+            crate::registry::WireField { name: "descr", ty: crate::registry::WireType::StrU14 },
+            crate::registry::WireField { name: "pwd", ty: crate::registry::WireType::Str8 },
+            crate::registry::WireField { name: "auth_pwd", ty: crate::registry::WireType::U8 },
+            crate::registry::WireField { name: "auth_os", ty: crate::registry::WireType::U8 },
+            crate::registry::WireField { name: "os_user", ty: crate::registry::WireType::Str8 },
+        ],
+        meta: RPC_AGENT_ADMIN_REGISTER_META,
+        validate: crate::registry::validate::<AgentAdminRegisterRpc>,
+        call: crate::registry::call::<AgentAdminRegisterRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "agent.admin_remove",
+        request: "AgentAdminRemoveRpc",
+        response: "AckResponse",
+        args: &[
+            crate::registry::ArgSpec { name: "name", kind: crate::registry::ArgKind::String },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "name", ty: crate::registry::WireType::Str8 },
+        ],
+        meta: RPC_AGENT_ADMIN_REMOVE_META,
+        validate: crate::registry::validate::<AgentAdminRemoveRpc>,
+        call: crate::registry::call::<AgentAdminRemoveRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "agent.version",
+        request: "AgentVersionRpc",
+        response: "AgentVersionResp",
+        args: &[],
+        wire: &[],
+        meta: RPC_AGENT_VERSION_META,
+        validate: crate::registry::validate::<AgentVersionRpc>,
+        call: crate::registry::call::<AgentVersionRpc>,
+    },
+];
+
 #[cfg(all(test, feature = "artifacts"))]
 mod tests {
     use super::*;
@@ -5,6 +5,7 @@ use crate::Uuid16;
 
 use super::agent::AgentAuthRpc;
 use super::cluster::ClusterAuthRpc;
+use super::infobase::InfobaseAuthRpc;
 
 pub struct AuthPair<'a> {
     pub user: &'a str,
@@ -83,6 +84,36 @@ pub fn cluster_auth_optional<'a>(
     Ok(creds)
 }
 
+/// Authenticates to the infobases of `cluster`; the RPC names no infobase,
+/// the following infobase call does.
+pub fn infobase_auth_optional<'a>(
+    client: &mut RacClient,
+    cluster: Uuid16,
+    user: Option<&'a str>,
+    pwd: Option<&'a str>,
+) -> Result<AuthPair<'a>> {
+    let creds = resolve_auth_pair(
+        user,
+        pwd,
+        "infobase-user and infobase-pwd must be provided together",
+    )?;
+    let reply = client.call(InfobaseAuthRpc {
+        cluster,
+        user: creds.user.to_string(),
+        pwd: creds.pwd.to_string(),
+    })?;
+    let auth_reply = decode_auth_reply(&reply);
+    if !auth_reply.acknowledged {
+        let detail = auth_reply
+            .detail
+            .unwrap_or_else(|| format!("payload_hex={}", payload_hex(&reply, 96)));
+        return Err(RacError::ProtocolMessage(format!(
+            "infobase auth rejected: {detail}"
+        )));
+    }
+    Ok(creds)
+}
+
 fn decode_auth_reply(payload: &[u8]) -> AuthReply {
     let mut cursor = RecordCursor::new(payload);
     if cursor.remaining_len() < 4 {
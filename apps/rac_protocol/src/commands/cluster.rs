@@ -6,6 +6,7 @@ mod generated {
     include!("cluster_generated.rs");
 }
 
+pub(crate) use generated::COMMANDS;
 pub use generated::{
     ClusterAdminRecord,
     ClusterAdminListResp,
@@ -4,6 +4,7 @@ use crate::protocol::ProtocolVersion;
 use crate::codec::RecordCursor;
 use crate::error::Result;
 use serde::Serialize;
+use serde::Deserialize;
 use crate::rac_wire::encode_with_len_u14;
 use crate::rac_wire::encode_with_len_u8;
 
@@ -158,7 +159,9 @@ impl ClusterRecord {
     }
 }
 
+#[derive(Deserialize)]
 pub struct ClusterAuthRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
     pub user: String,
     pub pwd: String,
@@ -177,7 +180,7 @@ impl crate::rpc::Request for ClusterAuthRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc ClusterAuth unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.user.len() } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.pwd.len() } else { 0 });
@@ -194,7 +197,9 @@ impl crate::rpc::Request for ClusterAuthRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct ClusterAdminListRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
 }
 
@@ -211,7 +216,7 @@ impl crate::rpc::Request for ClusterAdminListRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc ClusterAdminList unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 });
@@ -222,7 +227,9 @@ impl crate::rpc::Request for ClusterAdminListRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct ClusterAdminRegisterRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
     pub name: String,
     pub descr: String,
@@ -245,7 +252,7 @@ impl crate::rpc::Request for ClusterAdminRegisterRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc ClusterAdminRegister unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.name.len() } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { if self.descr.len() < 0x40 { 1 + self.descr.len() } else { 2 + self.descr.len() } } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.pwd.len() } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.os_user.len() } else { 0 });
@@ -274,7 +281,9 @@ impl crate::rpc::Request for ClusterAdminRegisterRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct ClusterAdminRemoveRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
     pub name: String,
 }
@@ -292,7 +301,7 @@ impl crate::rpc::Request for ClusterAdminRemoveRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc ClusterAdminRemove unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.name.len() } else { 0 });
@@ -306,6 +315,7 @@ impl crate::rpc::Request for ClusterAdminRemoveRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct ClusterListRpc;
 
 impl crate::rpc::Request for ClusterListRpc {
@@ -321,14 +331,16 @@ impl crate::rpc::Request for ClusterListRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc ClusterList unsupported for protocol"));
         }
         Ok(Vec::new())
     }
 }
 
+#[derive(Deserialize)]
 pub struct ClusterInfoRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
 }
 
@@ -345,7 +357,7 @@ impl crate::rpc::Request for ClusterInfoRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc ClusterInfo unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 });
@@ -414,47 +426,160 @@ pub fn parse_cluster_info_body(body: &[u8], protocol_version: ProtocolVersion) -
 
 
 pub const RPC_CLUSTER_AUTH_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "cluster.auth",
     method_req: METHOD_CLUSTER_AUTH_REQ,
     method_resp: None,
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: false,
     requires_infobase_context: false,
 };
 
 pub const RPC_CLUSTER_ADMIN_LIST_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "cluster.admin_list",
     method_req: METHOD_CLUSTER_ADMIN_LIST_REQ,
     method_resp: Some(METHOD_CLUSTER_ADMIN_LIST_RESP),
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: false,
     requires_infobase_context: false,
 };
 
 pub const RPC_CLUSTER_ADMIN_REGISTER_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "cluster.admin_register",
     method_req: METHOD_CLUSTER_ADMIN_REGISTER_REQ,
     method_resp: None,
+    mutation: crate::rpc::Mutation::Modify,
     requires_cluster_context: false,
     requires_infobase_context: false,
 };
 
 pub const RPC_CLUSTER_ADMIN_REMOVE_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "cluster.admin_remove",
     method_req: METHOD_CLUSTER_ADMIN_REMOVE_REQ,
     method_resp: None,
+    mutation: crate::rpc::Mutation::Destructive,
     requires_cluster_context: false,
     requires_infobase_context: false,
 };
 
 pub const RPC_CLUSTER_LIST_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "cluster.list",
     method_req: METHOD_CLUSTER_LIST_REQ,
     method_resp: Some(METHOD_CLUSTER_LIST_RESP),
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: false,
     requires_infobase_context: false,
 };
 
 pub const RPC_CLUSTER_INFO_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "cluster.info",
     method_req: METHOD_CLUSTER_INFO_REQ,
     method_resp: Some(METHOD_CLUSTER_INFO_RESP),
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: false,
     requires_infobase_context: false,
 };
 
+pub const COMMANDS: &[crate::registry::CommandSpec] = &[
+    crate::registry::CommandSpec {
+        name: "cluster.auth",
+        request: "ClusterAuthRpc",
+        response: "AckResponse",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "user", kind: crate::registry::ArgKind::String },
+            crate::registry::ArgSpec { name: "pwd", kind: crate::registry::ArgKind::String },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "user", ty: crate::registry::WireType::Str8 },
+            crate::registry::WireField { name: "pwd", ty: crate::registry::WireType::Str8 },
+        ],
+        meta: RPC_CLUSTER_AUTH_META,
+        validate: crate::registry::validate::<ClusterAuthRpc>,
+        call: crate::registry::call::<ClusterAuthRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "cluster.admin_list",
+        request: "ClusterAdminListRpc",
+        response: "ClusterAdminListResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+        ],
+        meta: RPC_CLUSTER_ADMIN_LIST_META,
+        validate: crate::registry::validate::<ClusterAdminListRpc>,
+        call: crate::registry::call::<ClusterAdminListRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "cluster.admin_register",
+        request: "ClusterAdminRegisterRpc",
+        response: "AckResponse",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "name", kind: crate::registry::ArgKind::String },
+            crate::registry::ArgSpec { name: "descr", kind: crate::registry::ArgKind::String },
+            crate::registry::ArgSpec { name: "pwd", kind: crate::registry::ArgKind::String },
+            crate::registry::ArgSpec { name: "auth_pwd", kind: crate::registry::ArgKind::U8 },
+            crate::registry::ArgSpec { name: "auth_os", kind: crate::registry::ArgKind::U8 },
+            crate::registry::ArgSpec { name: "os_user", kind: crate::registry::ArgKind::String },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "name", ty: crate::registry::WireType::Str8 },
+            crate::registry::WireField { name: "descr", ty: crate::registry::WireType::StrU14 },
+            crate::registry::WireField { name: "pwd", ty: crate::registry::WireType::Str8 },
+            crate::registry::WireField { name: "auth_pwd", ty: crate::registry::WireType::U8 },
+            crate::registry::WireField { name: "auth_os", ty: crate::registry::WireType::U8 },
+            crate::registry::WireField { name: "os_user", ty: crate::registry::WireType::Str8 },
+        ],
+        meta: RPC_CLUSTER_ADMIN_REGISTER_META,
+        validate: crate::registry::validate::<ClusterAdminRegisterRpc>,
+        call: crate::registry::call::<ClusterAdminRegisterRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "cluster.admin_remove",
+        request: "ClusterAdminRemoveRpc",
+        response: "AckResponse",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "name", kind: crate::registry::ArgKind::String },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "name", ty: crate::registry::WireType::Str8 },
+        ],
+        meta: RPC_CLUSTER_ADMIN_REMOVE_META,
+        validate: crate::registry::validate::<ClusterAdminRemoveRpc>,
+        call: crate::registry::call::<ClusterAdminRemoveRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "cluster.list",
+        request: "ClusterListRpc",
+        response: "ClusterListResp",
+        args: &[],
+        wire: &[],
+        meta: RPC_CLUSTER_LIST_META,
+        validate: crate::registry::validate::<ClusterListRpc>,
+        call: crate::registry::call::<ClusterListRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "cluster.info",
+        request: "ClusterInfoRpc",
+        response: "ClusterInfoResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+        ],
+        meta: RPC_CLUSTER_INFO_META,
+        validate: crate::registry::validate::<ClusterInfoRpc>,
+        call: crate::registry::call::<ClusterInfoRpc>,
+    },
+];
+
 #[cfg(all(test, feature = "artifacts"))]
 mod tests {
     use super::*;
@@ -6,6 +6,7 @@ mod generated {
     include!("connection_generated.rs");
 }
 
+pub(crate) use generated::COMMANDS;
 pub use generated::{
     ConnectionDisconnectRpc,
     ConnectionInfoResp,
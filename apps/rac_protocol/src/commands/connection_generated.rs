@@ -5,6 +5,7 @@ use crate::protocol::ProtocolVersion;
 use crate::codec::RecordCursor;
 use crate::error::Result;
 use serde::Serialize;
+use serde::Deserialize;
 
 pub const METHOD_CONNECTION_LIST_REQ: u8 = 0x32;
 pub const METHOD_CONNECTION_LIST_RESP: u8 = 0x33;
@@ -52,7 +53,9 @@ impl ConnectionRecord {
     }
 }
 
+#[derive(Deserialize)]
 pub struct ConnectionListRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
 }
 
@@ -69,7 +72,7 @@ impl crate::rpc::Request for ConnectionListRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc ConnectionList unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 });
@@ -80,8 +83,11 @@ impl crate::rpc::Request for ConnectionListRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct ConnectionListByInfobaseRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub infobase: Uuid16,
 }
 
@@ -98,7 +104,7 @@ impl crate::rpc::Request for ConnectionListByInfobaseRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc ConnectionListByInfobase unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 });
@@ -112,8 +118,11 @@ impl crate::rpc::Request for ConnectionListByInfobaseRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct ConnectionInfoRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub connection: Uuid16,
 }
 
@@ -130,7 +139,7 @@ impl crate::rpc::Request for ConnectionInfoRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc ConnectionInfo unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 });
@@ -144,9 +153,13 @@ impl crate::rpc::Request for ConnectionInfoRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct ConnectionDisconnectRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub connection: Uuid16,
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub process: Uuid16,
 }
 
@@ -163,7 +176,7 @@ impl crate::rpc::Request for ConnectionDisconnectRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc ConnectionDisconnect unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 });
@@ -207,7 +220,7 @@ impl crate::rpc::Response for ConnectionInfoResp {
         let protocol_version = _codec.protocol_version();
         let record = parse_connection_info_body(body, protocol_version)?;
         Ok(Self {
-            record: record,
+            record,
         })
     }
 }
@@ -223,31 +236,106 @@ pub fn parse_connection_info_body(body: &[u8], protocol_version: ProtocolVersion
 
 
 pub const RPC_CONNECTION_LIST_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "connection.list",
     method_req: METHOD_CONNECTION_LIST_REQ,
     method_resp: Some(METHOD_CONNECTION_LIST_RESP),
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
 pub const RPC_CONNECTION_LIST_BY_INFOBASE_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "connection.list_by_infobase",
     method_req: METHOD_CONNECTION_LIST_BY_INFOBASE_REQ,
     method_resp: Some(METHOD_CONNECTION_LIST_BY_INFOBASE_RESP),
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
 pub const RPC_CONNECTION_INFO_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "connection.info",
     method_req: METHOD_CONNECTION_INFO_REQ,
     method_resp: Some(METHOD_CONNECTION_INFO_RESP),
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
 pub const RPC_CONNECTION_DISCONNECT_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "connection.disconnect",
     method_req: METHOD_CONNECTION_DISCONNECT_REQ,
     method_resp: None,
+    mutation: crate::rpc::Mutation::Destructive,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
+pub const COMMANDS: &[crate::registry::CommandSpec] = &[
+    crate::registry::CommandSpec {
+        name: "connection.list",
+        request: "ConnectionListRpc",
+        response: "ConnectionListResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+        ],
+        meta: RPC_CONNECTION_LIST_META,
+        validate: crate::registry::validate::<ConnectionListRpc>,
+        call: crate::registry::call::<ConnectionListRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "connection.list_by_infobase",
+        request: "ConnectionListByInfobaseRpc",
+        response: "ConnectionListResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "infobase", kind: crate::registry::ArgKind::Uuid },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "infobase", ty: crate::registry::WireType::Uuid },
+        ],
+        meta: RPC_CONNECTION_LIST_BY_INFOBASE_META,
+        validate: crate::registry::validate::<ConnectionListByInfobaseRpc>,
+        call: crate::registry::call::<ConnectionListByInfobaseRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "connection.info",
+        request: "ConnectionInfoRpc",
+        response: "ConnectionInfoResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "connection", kind: crate::registry::ArgKind::Uuid },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "connection", ty: crate::registry::WireType::Uuid },
+        ],
+        meta: RPC_CONNECTION_INFO_META,
+        validate: crate::registry::validate::<ConnectionInfoRpc>,
+        call: crate::registry::call::<ConnectionInfoRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "connection.disconnect",
+        request: "ConnectionDisconnectRpc",
+        response: "AckResponse",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "connection", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "process", kind: crate::registry::ArgKind::Uuid },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "connection", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "process", ty: crate::registry::WireType::Uuid },
+        ],
+        meta: RPC_CONNECTION_DISCONNECT_META,
+        validate: crate::registry::validate::<ConnectionDisconnectRpc>,
+        call: crate::registry::call::<ConnectionDisconnectRpc>,
+    },
+];
+
 
@@ -8,6 +8,7 @@ mod generated {
     include!("counter_generated.rs");
 }
 
+pub(crate) use generated::COMMANDS;
 pub use generated::{
     CounterAccumulatedValuesResp,
     CounterAccumulatedValuesRpc,
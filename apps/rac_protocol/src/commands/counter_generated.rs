@@ -4,6 +4,7 @@ use crate::protocol::ProtocolVersion;
 use crate::codec::RecordCursor;
 use crate::error::Result;
 use serde::Serialize;
+use serde::Deserialize;
 use crate::Uuid16;
 use crate::rac_wire::encode_with_len_u8;
 
@@ -19,7 +20,7 @@ pub const METHOD_COUNTER_VALUES_RESP: u8 = 0x83;
 pub const METHOD_COUNTER_ACCUMULATED_VALUES_REQ: u8 = 0x85;
 pub const METHOD_COUNTER_ACCUMULATED_VALUES_RESP: u8 = 0x86;
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CounterRecord {
     pub name: String,
     pub collection_time: u64,
@@ -134,7 +135,9 @@ impl CounterValuesRecord {
     }
 }
 
+#[derive(Deserialize)]
 pub struct CounterListRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
 }
 
@@ -151,7 +154,7 @@ impl crate::rpc::Request for CounterListRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc CounterList unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 });
@@ -162,7 +165,9 @@ impl crate::rpc::Request for CounterListRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct CounterInfoRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
     pub counter: String,
 }
@@ -180,7 +185,7 @@ impl crate::rpc::Request for CounterInfoRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc CounterInfo unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.counter.len() } else { 0 });
@@ -194,7 +199,9 @@ impl crate::rpc::Request for CounterInfoRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct CounterUpdateRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
     pub name: String,
     pub collection_time: u64,
@@ -228,7 +235,7 @@ impl crate::rpc::Request for CounterUpdateRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc CounterUpdate unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.name.len() } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 8 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.filter.len() } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.descr.len() } else { 0 });
@@ -290,7 +297,9 @@ impl crate::rpc::Request for CounterUpdateRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct CounterRemoveRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
     pub name: String,
 }
@@ -308,7 +317,7 @@ impl crate::rpc::Request for CounterRemoveRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc CounterRemove unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.name.len() } else { 0 });
@@ -322,7 +331,9 @@ impl crate::rpc::Request for CounterRemoveRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct CounterClearRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
     pub counter: String,
     pub object: String,
@@ -341,7 +352,7 @@ impl crate::rpc::Request for CounterClearRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc CounterClear unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.counter.len() } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.object.len() } else { 0 });
@@ -358,7 +369,9 @@ impl crate::rpc::Request for CounterClearRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct CounterValuesRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
     pub counter: String,
     pub object: String,
@@ -377,7 +390,7 @@ impl crate::rpc::Request for CounterValuesRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc CounterValues unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.counter.len() } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.object.len() } else { 0 });
@@ -394,7 +407,9 @@ impl crate::rpc::Request for CounterValuesRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct CounterAccumulatedValuesRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
     pub counter: String,
     pub object: String,
@@ -413,7 +428,7 @@ impl crate::rpc::Request for CounterAccumulatedValuesRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc CounterAccumulatedValues unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.counter.len() } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.object.len() } else { 0 });
@@ -457,7 +472,7 @@ impl crate::rpc::Response for CounterInfoResp {
         let protocol_version = _codec.protocol_version();
         let record = parse_counter_info_body(body, protocol_version)?;
         Ok(Self {
-            record: record,
+            record,
         })
     }
 }
@@ -503,52 +518,217 @@ pub fn parse_counter_info_body(body: &[u8], protocol_version: ProtocolVersion) -
 
 
 pub const RPC_COUNTER_LIST_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "counter.list",
     method_req: METHOD_COUNTER_LIST_REQ,
     method_resp: Some(METHOD_COUNTER_LIST_RESP),
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
 pub const RPC_COUNTER_INFO_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "counter.info",
     method_req: METHOD_COUNTER_INFO_REQ,
     method_resp: Some(METHOD_COUNTER_INFO_RESP),
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
 pub const RPC_COUNTER_UPDATE_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "counter.update",
     method_req: METHOD_COUNTER_UPDATE_REQ,
     method_resp: None,
+    mutation: crate::rpc::Mutation::Modify,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
 pub const RPC_COUNTER_REMOVE_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "counter.remove",
     method_req: METHOD_COUNTER_REMOVE_REQ,
     method_resp: None,
+    mutation: crate::rpc::Mutation::Destructive,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
 pub const RPC_COUNTER_CLEAR_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "counter.clear",
     method_req: METHOD_COUNTER_CLEAR_REQ,
     method_resp: None,
+    mutation: crate::rpc::Mutation::Destructive,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
 pub const RPC_COUNTER_VALUES_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "counter.values",
     method_req: METHOD_COUNTER_VALUES_REQ,
     method_resp: Some(METHOD_COUNTER_VALUES_RESP),
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
 pub const RPC_COUNTER_ACCUMULATED_VALUES_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "counter.accumulated_values",
     method_req: METHOD_COUNTER_ACCUMULATED_VALUES_REQ,
     method_resp: Some(METHOD_COUNTER_ACCUMULATED_VALUES_RESP),
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
+pub const COMMANDS: &[crate::registry::CommandSpec] = &[
+    crate::registry::CommandSpec {
+        name: "counter.list",
+        request: "CounterListRpc",
+        response: "CounterListResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+        ],
+        meta: RPC_COUNTER_LIST_META,
+        validate: crate::registry::validate::<CounterListRpc>,
+        call: crate::registry::call::<CounterListRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "counter.info",
+        request: "CounterInfoRpc",
+        response: "CounterInfoResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "counter", kind: crate::registry::ArgKind::String },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "counter", ty: crate::registry::WireType::Str8 },
+        ],
+        meta: RPC_COUNTER_INFO_META,
+        validate: crate::registry::validate::<CounterInfoRpc>,
+        call: crate::registry::call::<CounterInfoRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "counter.update",
+        request: "CounterUpdateRpc",
+        response: "AckResponse",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "name", kind: crate::registry::ArgKind::String },
+            crate::registry::ArgSpec { name: "collection_time", kind: crate::registry::ArgKind::U64 },
+            crate::registry::ArgSpec { name: "group", kind: crate::registry::ArgKind::U8 },
+            crate::registry::ArgSpec { name: "filter_type", kind: crate::registry::ArgKind::U8 },
+            crate::registry::ArgSpec { name: "filter", kind: crate::registry::ArgKind::String },
+            crate::registry::ArgSpec { name: "duration", kind: crate::registry::ArgKind::U8 },
+            crate::registry::ArgSpec { name: "cpu_time", kind: crate::registry::ArgKind::U8 },
+            crate::registry::ArgSpec { name: "duration_dbms", kind: crate::registry::ArgKind::U8 },
+            crate::registry::ArgSpec { name: "service", kind: crate::registry::ArgKind::U8 },
+            crate::registry::ArgSpec { name: "memory", kind: crate::registry::ArgKind::U8 },
+            crate::registry::ArgSpec { name: "read", kind: crate::registry::ArgKind::U8 },
+            crate::registry::ArgSpec { name: "write", kind: crate::registry::ArgKind::U8 },
+            crate::registry::ArgSpec { name: "dbms_bytes", kind: crate::registry::ArgKind::U8 },
+            crate::registry::ArgSpec { name: "call", kind: crate::registry::ArgKind::U8 },
+            crate::registry::ArgSpec { name: "number_of_active_sessions", kind: crate::registry::ArgKind::U8 },
+            crate::registry::ArgSpec { name: "number_of_sessions", kind: crate::registry::ArgKind::U8 },
+            crate::registry::ArgSpec { name: "descr", kind: crate::registry::ArgKind::String },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "name", ty: crate::registry::WireType::Str8 },
+            crate::registry::WireField { name: "collection_time", ty: crate::registry::WireType::U64Be },
+            crate::registry::WireField { name: "group", ty: crate::registry::WireType::U8 },
+            crate::registry::WireField { name: "filter_type", ty: crate::registry::WireType::U8 },
+            crate::registry::WireField { name: "filter", ty: crate::registry::WireType::Str8 },
+            crate::registry::WireField { name: "duration", ty: crate::registry::WireType::U8 },
+            crate::registry::WireField { name: "cpu_time", ty: crate::registry::WireType::U8 },
+            crate::registry::WireField { name: "duration_dbms", ty: crate::registry::WireType::U8 },
+            crate::registry::WireField { name: "service", ty: crate::registry::WireType::U8 },
+            crate::registry::WireField { name: "memory", ty: crate::registry::WireType::U8 },
+            crate::registry::WireField { name: "read", ty: crate::registry::WireType::U8 },
+            crate::registry::WireField { name: "write", ty: crate::registry::WireType::U8 },
+            crate::registry::WireField { name: "dbms_bytes", ty: crate::registry::WireType::U8 },
+            crate::registry::WireField { name: "call", ty: crate::registry::WireType::U8 },
+            crate::registry::WireField { name: "number_of_active_sessions", ty: crate::registry::WireType::U8 },
+            crate::registry::WireField { name: "number_of_sessions", ty: crate::registry::WireType::U8 },
+            crate::registry::WireField { name: "descr", ty: crate::registry::WireType::Str8 },
+        ],
+        meta: RPC_COUNTER_UPDATE_META,
+        validate: crate::registry::validate::<CounterUpdateRpc>,
+        call: crate::registry::call::<CounterUpdateRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "counter.remove",
+        request: "CounterRemoveRpc",
+        response: "AckResponse",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "name", kind: crate::registry::ArgKind::String },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "name", ty: crate::registry::WireType::Str8 },
+        ],
+        meta: RPC_COUNTER_REMOVE_META,
+        validate: crate::registry::validate::<CounterRemoveRpc>,
+        call: crate::registry::call::<CounterRemoveRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "counter.clear",
+        request: "CounterClearRpc",
+        response: "AckResponse",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "counter", kind: crate::registry::ArgKind::String },
+            crate::registry::ArgSpec { name: "object", kind: crate::registry::ArgKind::String },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "counter", ty: crate::registry::WireType::Str8 },
+            crate::registry::WireField { name: "object", ty: crate::registry::WireType::Str8 },
+        ],
+        meta: RPC_COUNTER_CLEAR_META,
+        validate: crate::registry::validate::<CounterClearRpc>,
+        call: crate::registry::call::<CounterClearRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "counter.values",
+        request: "CounterValuesRpc",
+        response: "CounterValuesResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "counter", kind: crate::registry::ArgKind::String },
+            crate::registry::ArgSpec { name: "object", kind: crate::registry::ArgKind::String },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "counter", ty: crate::registry::WireType::Str8 },
+            crate::registry::WireField { name: "object", ty: crate::registry::WireType::Str8 },
+        ],
+        meta: RPC_COUNTER_VALUES_META,
+        validate: crate::registry::validate::<CounterValuesRpc>,
+        call: crate::registry::call::<CounterValuesRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "counter.accumulated_values",
+        request: "CounterAccumulatedValuesRpc",
+        response: "CounterAccumulatedValuesResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "counter", kind: crate::registry::ArgKind::String },
+            crate::registry::ArgSpec { name: "object", kind: crate::registry::ArgKind::String },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "counter", ty: crate::registry::WireType::Str8 },
+            crate::registry::WireField { name: "object", ty: crate::registry::WireType::Str8 },
+        ],
+        meta: RPC_COUNTER_ACCUMULATED_VALUES_META,
+        validate: crate::registry::validate::<CounterAccumulatedValuesRpc>,
+        call: crate::registry::call::<CounterAccumulatedValuesRpc>,
+    },
+];
+
 
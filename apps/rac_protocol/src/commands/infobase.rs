@@ -6,7 +6,9 @@ mod generated {
     include!("infobase_generated.rs");
 }
 
+pub(crate) use generated::COMMANDS;
 pub use generated::{
+    InfobaseAuthRpc,
     InfobaseInfoRecord,
     InfobaseInfoResp,
     InfobaseInfoRpc,
@@ -4,8 +4,10 @@ use crate::protocol::ProtocolVersion;
 use crate::codec::RecordCursor;
 use crate::error::Result;
 use serde::Serialize;
+use serde::Deserialize;
 use crate::rac_wire::encode_with_len_u8;
 
+pub const METHOD_INFOBASE_AUTH_REQ: u8 = 0x0a;
 pub const METHOD_INFOBASE_SUMMARY_LIST_REQ: u8 = 0x2a;
 pub const METHOD_INFOBASE_SUMMARY_LIST_RESP: u8 = 0x2b;
 pub const METHOD_INFOBASE_SUMMARY_INFO_REQ: u8 = 0x2e;
@@ -125,7 +127,47 @@ impl InfobaseInfoRecord {
     }
 }
 
+#[derive(Deserialize)]
+pub struct InfobaseAuthRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
+    pub cluster: Uuid16,
+    pub user: String,
+    pub pwd: String,
+}
+
+impl crate::rpc::Request for InfobaseAuthRpc {
+    type Response = crate::rpc::AckResponse;
+
+    fn meta(&self) -> crate::rpc::Meta {
+        RPC_INFOBASE_AUTH_META
+    }
+
+    fn cluster(&self) -> Option<crate::Uuid16> {
+        None
+    }
+
+    fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
+        let protocol_version = _codec.protocol_version();
+        if protocol_version < ProtocolVersion::V11_0 {
+            return Err(RacError::Unsupported("rpc InfobaseAuth unsupported for protocol"));
+        }
+        let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.user.len() } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.pwd.len() } else { 0 });
+        if protocol_version >= ProtocolVersion::V11_0 {
+            out.extend_from_slice(&self.cluster);
+        }
+        if protocol_version >= ProtocolVersion::V11_0 {
+            out.extend_from_slice(&encode_with_len_u8(self.user.as_bytes())?);
+        }
+        if protocol_version >= ProtocolVersion::V11_0 {
+            out.extend_from_slice(&encode_with_len_u8(self.pwd.as_bytes())?);
+        }
+        Ok(out)
+    }
+}
+
+#[derive(Deserialize)]
 pub struct InfobaseSummaryListRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
 }
 
@@ -142,7 +184,7 @@ impl crate::rpc::Request for InfobaseSummaryListRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc InfobaseSummaryList unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 });
@@ -153,8 +195,11 @@ impl crate::rpc::Request for InfobaseSummaryListRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct InfobaseSummaryInfoRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub infobase: Uuid16,
 }
 
@@ -171,7 +216,7 @@ impl crate::rpc::Request for InfobaseSummaryInfoRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc InfobaseSummaryInfo unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 });
@@ -185,8 +230,11 @@ impl crate::rpc::Request for InfobaseSummaryInfoRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct InfobaseInfoRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub infobase: Uuid16,
 }
 
@@ -203,7 +251,7 @@ impl crate::rpc::Request for InfobaseInfoRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc InfobaseInfo unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 });
@@ -217,8 +265,11 @@ impl crate::rpc::Request for InfobaseInfoRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct InfobaseSummaryUpdateRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub infobase: Uuid16,
     pub descr: String,
 }
@@ -236,7 +287,7 @@ impl crate::rpc::Request for InfobaseSummaryUpdateRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc InfobaseSummaryUpdate unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.descr.len() } else { 0 });
@@ -319,32 +370,134 @@ pub fn parse_infobase_info_body(body: &[u8], protocol_version: ProtocolVersion)
 }
 
 
+pub const RPC_INFOBASE_AUTH_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "infobase.auth",
+    method_req: METHOD_INFOBASE_AUTH_REQ,
+    method_resp: None,
+    mutation: crate::rpc::Mutation::Read,
+    requires_cluster_context: false,
+    requires_infobase_context: false,
+};
+
 pub const RPC_INFOBASE_SUMMARY_LIST_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "infobase.summary_list",
     method_req: METHOD_INFOBASE_SUMMARY_LIST_REQ,
     method_resp: Some(METHOD_INFOBASE_SUMMARY_LIST_RESP),
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
 pub const RPC_INFOBASE_SUMMARY_INFO_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "infobase.summary_info",
     method_req: METHOD_INFOBASE_SUMMARY_INFO_REQ,
     method_resp: Some(METHOD_INFOBASE_SUMMARY_INFO_RESP),
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: true,
     requires_infobase_context: true,
 };
 
 pub const RPC_INFOBASE_INFO_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "infobase.info",
     method_req: METHOD_INFOBASE_INFO_REQ,
     method_resp: Some(METHOD_INFOBASE_INFO_RESP),
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: true,
     requires_infobase_context: true,
 };
 
 pub const RPC_INFOBASE_SUMMARY_UPDATE_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "infobase.summary_update",
     method_req: METHOD_INFOBASE_SUMMARY_UPDATE_REQ,
     method_resp: None,
+    mutation: crate::rpc::Mutation::Modify,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
+pub const COMMANDS: &[crate::registry::CommandSpec] = &[
+    crate::registry::CommandSpec {
+        name: "infobase.auth",
+        request: "InfobaseAuthRpc",
+        response: "AckResponse",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "user", kind: crate::registry::ArgKind::String },
+            crate::registry::ArgSpec { name: "pwd", kind: crate::registry::ArgKind::String },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "user", ty: crate::registry::WireType::Str8 },
+            crate::registry::WireField { name: "pwd", ty: crate::registry::WireType::Str8 },
+        ],
+        meta: RPC_INFOBASE_AUTH_META,
+        validate: crate::registry::validate::<InfobaseAuthRpc>,
+        call: crate::registry::call::<InfobaseAuthRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "infobase.summary_list",
+        request: "InfobaseSummaryListRpc",
+        response: "InfobaseSummaryListResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+        ],
+        meta: RPC_INFOBASE_SUMMARY_LIST_META,
+        validate: crate::registry::validate::<InfobaseSummaryListRpc>,
+        call: crate::registry::call::<InfobaseSummaryListRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "infobase.summary_info",
+        request: "InfobaseSummaryInfoRpc",
+        response: "InfobaseSummaryInfoResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "infobase", kind: crate::registry::ArgKind::Uuid },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "infobase", ty: crate::registry::WireType::Uuid },
+        ],
+        meta: RPC_INFOBASE_SUMMARY_INFO_META,
+        validate: crate::registry::validate::<InfobaseSummaryInfoRpc>,
+        call: crate::registry::call::<InfobaseSummaryInfoRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "infobase.info",
+        request: "InfobaseInfoRpc",
+        response: "InfobaseInfoResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "infobase", kind: crate::registry::ArgKind::Uuid },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "infobase", ty: crate::registry::WireType::Uuid },
+        ],
+        meta: RPC_INFOBASE_INFO_META,
+        validate: crate::registry::validate::<InfobaseInfoRpc>,
+        call: crate::registry::call::<InfobaseInfoRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "infobase.summary_update",
+        request: "InfobaseSummaryUpdateRpc",
+        response: "AckResponse",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "infobase", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "descr", kind: crate::registry::ArgKind::String },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "infobase", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "descr", ty: crate::registry::WireType::Str8 },
+        ],
+        meta: RPC_INFOBASE_SUMMARY_UPDATE_META,
+        validate: crate::registry::validate::<InfobaseSummaryUpdateRpc>,
+        call: crate::registry::call::<InfobaseSummaryUpdateRpc>,
+    },
+];
+
 
@@ -8,6 +8,7 @@ mod generated {
     include!("limit_generated.rs");
 }
 
+pub(crate) use generated::COMMANDS;
 pub use generated::{
     LimitInfoResp,
     LimitInfoRpc,
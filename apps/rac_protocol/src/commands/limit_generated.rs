@@ -3,6 +3,7 @@ use crate::protocol::ProtocolVersion;
 use crate::codec::RecordCursor;
 use crate::error::Result;
 use serde::Serialize;
+use serde::Deserialize;
 use crate::Uuid16;
 use crate::rac_wire::encode_with_len_u8;
 
@@ -13,7 +14,7 @@ pub const METHOD_LIMIT_INFO_RESP: u8 = 0x7f;
 pub const METHOD_LIMIT_UPDATE_REQ: u8 = 0x80;
 pub const METHOD_LIMIT_REMOVE_REQ: u8 = 0x81;
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct LimitRecord {
     pub name: String,
     pub counter: String,
@@ -72,7 +73,9 @@ impl LimitRecord {
     }
 }
 
+#[derive(Deserialize)]
 pub struct LimitListRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
 }
 
@@ -89,7 +92,7 @@ impl crate::rpc::Request for LimitListRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc LimitList unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 });
@@ -100,7 +103,9 @@ impl crate::rpc::Request for LimitListRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct LimitInfoRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
     pub name: String,
 }
@@ -118,7 +123,7 @@ impl crate::rpc::Request for LimitInfoRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc LimitInfo unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.name.len() } else { 0 });
@@ -132,7 +137,9 @@ impl crate::rpc::Request for LimitInfoRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct LimitUpdateRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
     pub name: String,
     pub counter: String,
@@ -165,7 +172,7 @@ impl crate::rpc::Request for LimitUpdateRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc LimitUpdate unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.name.len() } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.counter.len() } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 8 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 8 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 8 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 8 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 8 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 8 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 8 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 8 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 8 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 8 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 8 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.error_message.len() } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.descr.len() } else { 0 });
@@ -224,7 +231,9 @@ impl crate::rpc::Request for LimitUpdateRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct LimitRemoveRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
     pub name: String,
 }
@@ -242,7 +251,7 @@ impl crate::rpc::Request for LimitRemoveRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc LimitRemove unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.name.len() } else { 0 });
@@ -283,7 +292,7 @@ impl crate::rpc::Response for LimitInfoResp {
         let protocol_version = _codec.protocol_version();
         let record = parse_limit_info_body(body, protocol_version)?;
         Ok(Self {
-            record: record,
+            record,
         })
     }
 }
@@ -299,31 +308,134 @@ pub fn parse_limit_info_body(body: &[u8], protocol_version: ProtocolVersion) ->
 
 
 pub const RPC_LIMIT_LIST_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "limit.list",
     method_req: METHOD_LIMIT_LIST_REQ,
     method_resp: Some(METHOD_LIMIT_LIST_RESP),
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
 pub const RPC_LIMIT_INFO_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "limit.info",
     method_req: METHOD_LIMIT_INFO_REQ,
     method_resp: Some(METHOD_LIMIT_INFO_RESP),
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
 pub const RPC_LIMIT_UPDATE_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "limit.update",
     method_req: METHOD_LIMIT_UPDATE_REQ,
     method_resp: None,
+    mutation: crate::rpc::Mutation::Modify,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
 pub const RPC_LIMIT_REMOVE_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "limit.remove",
     method_req: METHOD_LIMIT_REMOVE_REQ,
     method_resp: None,
+    mutation: crate::rpc::Mutation::Destructive,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
+pub const COMMANDS: &[crate::registry::CommandSpec] = &[
+    crate::registry::CommandSpec {
+        name: "limit.list",
+        request: "LimitListRpc",
+        response: "LimitListResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+        ],
+        meta: RPC_LIMIT_LIST_META,
+        validate: crate::registry::validate::<LimitListRpc>,
+        call: crate::registry::call::<LimitListRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "limit.info",
+        request: "LimitInfoRpc",
+        response: "LimitInfoResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "name", kind: crate::registry::ArgKind::String },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "name", ty: crate::registry::WireType::Str8 },
+        ],
+        meta: RPC_LIMIT_INFO_META,
+        validate: crate::registry::validate::<LimitInfoRpc>,
+        call: crate::registry::call::<LimitInfoRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "limit.update",
+        request: "LimitUpdateRpc",
+        response: "AckResponse",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "name", kind: crate::registry::ArgKind::String },
+            crate::registry::ArgSpec { name: "counter", kind: crate::registry::ArgKind::String },
+            crate::registry::ArgSpec { name: "action", kind: crate::registry::ArgKind::U8 },
+            crate::registry::ArgSpec { name: "duration", kind: crate::registry::ArgKind::U64 },
+            crate::registry::ArgSpec { name: "cpu_time", kind: crate::registry::ArgKind::U64 },
+            crate::registry::ArgSpec { name: "memory", kind: crate::registry::ArgKind::U64 },
+            crate::registry::ArgSpec { name: "read", kind: crate::registry::ArgKind::U64 },
+            crate::registry::ArgSpec { name: "write", kind: crate::registry::ArgKind::U64 },
+            crate::registry::ArgSpec { name: "duration_dbms", kind: crate::registry::ArgKind::U64 },
+            crate::registry::ArgSpec { name: "dbms_bytes", kind: crate::registry::ArgKind::U64 },
+            crate::registry::ArgSpec { name: "service", kind: crate::registry::ArgKind::U64 },
+            crate::registry::ArgSpec { name: "call", kind: crate::registry::ArgKind::U64 },
+            crate::registry::ArgSpec { name: "number_of_active_sessions", kind: crate::registry::ArgKind::U64 },
+            crate::registry::ArgSpec { name: "number_of_sessions", kind: crate::registry::ArgKind::U64 },
+            crate::registry::ArgSpec { name: "error_message", kind: crate::registry::ArgKind::String },
+            crate::registry::ArgSpec { name: "descr", kind: crate::registry::ArgKind::String },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "name", ty: crate::registry::WireType::Str8 },
+            crate::registry::WireField { name: "counter", ty: crate::registry::WireType::Str8 },
+            crate::registry::WireField { name: "action", ty: crate::registry::WireType::U8 },
+            crate::registry::WireField { name: "duration", ty: crate::registry::WireType::U64Be },
+            crate::registry::WireField { name: "cpu_time", ty: crate::registry::WireType::U64Be },
+            crate::registry::WireField { name: "memory", ty: crate::registry::WireType::U64Be },
+            crate::registry::WireField { name: "read", ty: crate::registry::WireType::U64Be },
+            crate::registry::WireField { name: "write", ty: crate::registry::WireType::U64Be },
+            crate::registry::WireField { name: "duration_dbms", ty: crate::registry::WireType::U64Be },
+            crate::registry::WireField { name: "dbms_bytes", ty: crate::registry::WireType::U64Be },
+            crate::registry::WireField { name: "service", ty: crate::registry::WireType::U64Be },
+            crate::registry::WireField { name: "call", ty: crate::registry::WireType::U64Be },
+            crate::registry::WireField { name: "number_of_active_sessions", ty: crate::registry::WireType::U64Be },
+            crate::registry::WireField { name: "number_of_sessions", ty: crate::registry::WireType::U64Be },
+            crate::registry::WireField { name: "error_message", ty: crate::registry::WireType::Str8 },
+            crate::registry::WireField { name: "descr", ty: crate::registry::WireType::Str8 },
+        ],
+        meta: RPC_LIMIT_UPDATE_META,
+        validate: crate::registry::validate::<LimitUpdateRpc>,
+        call: crate::registry::call::<LimitUpdateRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "limit.remove",
+        request: "LimitRemoveRpc",
+        response: "AckResponse",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "name", kind: crate::registry::ArgKind::String },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "name", ty: crate::registry::WireType::Str8 },
+        ],
+        meta: RPC_LIMIT_REMOVE_META,
+        validate: crate::registry::validate::<LimitRemoveRpc>,
+        call: crate::registry::call::<LimitRemoveRpc>,
+    },
+];
+
 
@@ -13,6 +13,7 @@ mod generated {
     include!("lock_generated.rs");
 }
 
+pub(crate) use generated::COMMANDS;
 pub use generated::{LockListResp, LockListRpc, LockRecordRaw};
 
 pub fn lock_list(client: &mut RacClient, cluster: Uuid16) -> Result<LockListResp> {
@@ -45,8 +46,8 @@ mod tests {
         out.push(descr.len() as u8);
         out.extend_from_slice(descr.as_bytes());
         push_u64_be(out, locked_raw);
-        push_uuid(out, session);
         push_uuid(out, object);
+        push_uuid(out, session);
     }
 
     fn append_record_with_flag(
@@ -63,8 +64,8 @@ mod tests {
         out.push(flag);
         out.extend_from_slice(descr.as_bytes());
         push_u64_be(out, locked_raw);
-        push_uuid(out, session);
         push_uuid(out, object);
+        push_uuid(out, session);
     }
 
     #[test]
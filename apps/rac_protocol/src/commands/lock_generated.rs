@@ -5,6 +5,7 @@ use crate::protocol::ProtocolVersion;
 use crate::codec::RecordCursor;
 use crate::error::Result;
 use serde::Serialize;
+use serde::Deserialize;
 
 pub const METHOD_LOCK_LIST_REQ: u8 = 0x48;
 pub const METHOD_LOCK_LIST_RESP: u8 = 0x49;
@@ -14,8 +15,8 @@ pub struct LockRecordRaw {
     pub connection: Uuid16,
     pub descr: LockDescr,
     pub locked_at: String,
-    pub session: Uuid16,
     pub object: Uuid16,
+    pub session: Uuid16,
 }
 
 impl LockRecordRaw {
@@ -51,19 +52,21 @@ impl LockRecordRaw {
             }
         };
         let locked_at = v8_datetime_to_iso(cursor.take_u64_be()?).unwrap_or_default();
-        let session = cursor.take_uuid()?;
         let object = cursor.take_uuid()?;
+        let session = cursor.take_uuid()?;
         Ok(Self {
             connection,
             descr,
             locked_at,
-            session,
             object,
+            session,
         })
     }
 }
 
+#[derive(Deserialize)]
 pub struct LockListRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
 }
 
@@ -80,7 +83,7 @@ impl crate::rpc::Request for LockListRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc LockList unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 });
@@ -110,10 +113,29 @@ impl crate::rpc::Response for LockListResp {
 
 
 pub const RPC_LOCK_LIST_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "lock.list",
     method_req: METHOD_LOCK_LIST_REQ,
     method_resp: Some(METHOD_LOCK_LIST_RESP),
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
+pub const COMMANDS: &[crate::registry::CommandSpec] = &[
+    crate::registry::CommandSpec {
+        name: "lock.list",
+        request: "LockListRpc",
+        response: "LockListResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+        ],
+        meta: RPC_LOCK_LIST_META,
+        validate: crate::registry::validate::<LockListRpc>,
+        call: crate::registry::call::<LockListRpc>,
+    },
+];
+
 
@@ -6,6 +6,7 @@ mod generated {
     include!("manager_generated.rs");
 }
 
+pub(crate) use generated::COMMANDS;
 pub use generated::{
     ManagerInfoResp,
     ManagerInfoRpc,
@@ -4,6 +4,7 @@ use crate::protocol::ProtocolVersion;
 use crate::codec::RecordCursor;
 use crate::error::Result;
 use serde::Serialize;
+use serde::Deserialize;
 
 pub const METHOD_MANAGER_LIST_REQ: u8 = 0x12;
 pub const METHOD_MANAGER_LIST_RESP: u8 = 0x13;
@@ -39,7 +40,9 @@ impl ManagerRecord {
     }
 }
 
+#[derive(Deserialize)]
 pub struct ManagerListRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
 }
 
@@ -56,7 +59,7 @@ impl crate::rpc::Request for ManagerListRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc ManagerList unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 });
@@ -67,8 +70,11 @@ impl crate::rpc::Request for ManagerListRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct ManagerInfoRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub manager: Uuid16,
 }
 
@@ -85,7 +91,7 @@ impl crate::rpc::Request for ManagerInfoRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc ManagerInfo unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 });
@@ -126,7 +132,7 @@ impl crate::rpc::Response for ManagerInfoResp {
         let protocol_version = _codec.protocol_version();
         let record = parse_manager_info_body(body, protocol_version)?;
         Ok(Self {
-            record: record,
+            record,
         })
     }
 }
@@ -142,19 +148,56 @@ pub fn parse_manager_info_body(body: &[u8], protocol_version: ProtocolVersion) -
 
 
 pub const RPC_MANAGER_LIST_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "manager.list",
     method_req: METHOD_MANAGER_LIST_REQ,
     method_resp: Some(METHOD_MANAGER_LIST_RESP),
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
 pub const RPC_MANAGER_INFO_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "manager.info",
     method_req: METHOD_MANAGER_INFO_REQ,
     method_resp: Some(METHOD_MANAGER_INFO_RESP),
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
+pub const COMMANDS: &[crate::registry::CommandSpec] = &[
+    crate::registry::CommandSpec {
+        name: "manager.list",
+        request: "ManagerListRpc",
+        response: "ManagerListResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+        ],
+        meta: RPC_MANAGER_LIST_META,
+        validate: crate::registry::validate::<ManagerListRpc>,
+        call: crate::registry::call::<ManagerListRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "manager.info",
+        request: "ManagerInfoRpc",
+        response: "ManagerInfoResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "manager", kind: crate::registry::ArgKind::Uuid },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "manager", ty: crate::registry::WireType::Uuid },
+        ],
+        meta: RPC_MANAGER_INFO_META,
+        validate: crate::registry::validate::<ManagerInfoRpc>,
+        call: crate::registry::call::<ManagerInfoRpc>,
+    },
+];
+
 #[cfg(all(test, feature = "artifacts"))]
 mod tests {
     use super::*;
@@ -21,7 +21,9 @@ pub use self::agent::{
     agent_admin_list, agent_admin_register, agent_admin_remove, agent_version,
     AgentAdminListResp, AgentAdminRecord,
 };
-pub use self::auth::{agent_auth_optional, cluster_auth_optional, AuthPair};
+pub use self::auth::{
+    agent_auth_optional, cluster_auth_optional, infobase_auth_optional, AuthPair,
+};
 pub use self::cluster::{
     cluster_admin_list, cluster_admin_register, cluster_auth, cluster_info, cluster_list,
     ClusterAdminRecord, ClusterRecord,
@@ -6,6 +6,7 @@ mod generated {
     include!("process_generated.rs");
 }
 
+pub(crate) use generated::COMMANDS;
 pub use generated::{
     ProcessInfoResp,
     ProcessInfoRpc,
@@ -5,6 +5,7 @@ use crate::protocol::ProtocolVersion;
 use crate::codec::RecordCursor;
 use crate::error::Result;
 use serde::Serialize;
+use serde::Deserialize;
 
 pub const METHOD_PROCESS_LIST_REQ: u8 = 0x1d;
 pub const METHOD_PROCESS_LIST_RESP: u8 = 0x1e;
@@ -152,7 +153,9 @@ impl ProcessRecord {
     }
 }
 
+#[derive(Deserialize)]
 pub struct ProcessListRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
 }
 
@@ -169,7 +172,7 @@ impl crate::rpc::Request for ProcessListRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc ProcessList unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 });
@@ -180,8 +183,11 @@ impl crate::rpc::Request for ProcessListRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct ProcessInfoRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub process: Uuid16,
 }
 
@@ -198,7 +204,7 @@ impl crate::rpc::Request for ProcessInfoRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc ProcessInfo unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 });
@@ -239,7 +245,7 @@ impl crate::rpc::Response for ProcessInfoResp {
         let protocol_version = _codec.protocol_version();
         let record = parse_process_info_body(body, protocol_version)?;
         Ok(Self {
-            record: record,
+            record,
         })
     }
 }
@@ -255,17 +261,54 @@ pub fn parse_process_info_body(body: &[u8], protocol_version: ProtocolVersion) -
 
 
 pub const RPC_PROCESS_LIST_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "process.list",
     method_req: METHOD_PROCESS_LIST_REQ,
     method_resp: Some(METHOD_PROCESS_LIST_RESP),
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
 pub const RPC_PROCESS_INFO_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "process.info",
     method_req: METHOD_PROCESS_INFO_REQ,
     method_resp: Some(METHOD_PROCESS_INFO_RESP),
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
+pub const COMMANDS: &[crate::registry::CommandSpec] = &[
+    crate::registry::CommandSpec {
+        name: "process.list",
+        request: "ProcessListRpc",
+        response: "ProcessListResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+        ],
+        meta: RPC_PROCESS_LIST_META,
+        validate: crate::registry::validate::<ProcessListRpc>,
+        call: crate::registry::call::<ProcessListRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "process.info",
+        request: "ProcessInfoRpc",
+        response: "ProcessInfoResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "process", kind: crate::registry::ArgKind::Uuid },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "process", ty: crate::registry::WireType::Uuid },
+        ],
+        meta: RPC_PROCESS_INFO_META,
+        validate: crate::registry::validate::<ProcessInfoRpc>,
+        call: crate::registry::call::<ProcessInfoRpc>,
+    },
+];
+
 
@@ -6,6 +6,7 @@ mod generated {
     include!("profile_generated.rs");
 }
 
+pub(crate) use generated::COMMANDS;
 pub use generated::{ProfileListResp, ProfileListRpc, ProfileRecord, ProfileUpdateRpc};
 
 use crate::rpc::AckResponse;
@@ -3,6 +3,7 @@ use crate::protocol::ProtocolVersion;
 use crate::codec::RecordCursor;
 use crate::error::Result;
 use serde::Serialize;
+use serde::Deserialize;
 use crate::Uuid16;
 use crate::rac_wire::encode_with_len_u8;
 
@@ -10,7 +11,7 @@ pub const METHOD_PROFILE_LIST_REQ: u8 = 0x59;
 pub const METHOD_PROFILE_LIST_RESP: u8 = 0x5a;
 pub const METHOD_PROFILE_UPDATE_REQ: u8 = 0x5b;
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ProfileRecord {
     pub name: String,
     pub descr: String,
@@ -72,7 +73,9 @@ impl ProfileRecord {
     }
 }
 
+#[derive(Deserialize)]
 pub struct ProfileListRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
 }
 
@@ -89,7 +92,7 @@ impl crate::rpc::Request for ProfileListRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc ProfileList unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 });
@@ -100,7 +103,9 @@ impl crate::rpc::Request for ProfileListRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct ProfileUpdateRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
     pub name: String,
     pub descr: String,
@@ -134,7 +139,7 @@ impl crate::rpc::Request for ProfileUpdateRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc ProfileUpdate unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.name.len() } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.descr.len() } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.right_extension_definition_roles.len() } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.modules_available_for_extension.len() } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.modules_not_available_for_extension.len() } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.privileged_mode_roles.len() } else { 0 });
@@ -215,19 +220,88 @@ impl crate::rpc::Response for ProfileListResp {
 
 
 pub const RPC_PROFILE_LIST_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "profile.list",
     method_req: METHOD_PROFILE_LIST_REQ,
     method_resp: Some(METHOD_PROFILE_LIST_RESP),
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
 pub const RPC_PROFILE_UPDATE_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "profile.update",
     method_req: METHOD_PROFILE_UPDATE_REQ,
     method_resp: None,
+    mutation: crate::rpc::Mutation::Modify,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
+pub const COMMANDS: &[crate::registry::CommandSpec] = &[
+    crate::registry::CommandSpec {
+        name: "profile.list",
+        request: "ProfileListRpc",
+        response: "ProfileListResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+        ],
+        meta: RPC_PROFILE_LIST_META,
+        validate: crate::registry::validate::<ProfileListRpc>,
+        call: crate::registry::call::<ProfileListRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "profile.update",
+        request: "ProfileUpdateRpc",
+        response: "AckResponse",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "name", kind: crate::registry::ArgKind::String },
+            crate::registry::ArgSpec { name: "descr", kind: crate::registry::ArgKind::String },
+            crate::registry::ArgSpec { name: "directory_access", kind: crate::registry::ArgKind::U8 },
+            crate::registry::ArgSpec { name: "com_access", kind: crate::registry::ArgKind::U8 },
+            crate::registry::ArgSpec { name: "addin_access", kind: crate::registry::ArgKind::U8 },
+            crate::registry::ArgSpec { name: "module_access", kind: crate::registry::ArgKind::U8 },
+            crate::registry::ArgSpec { name: "app_access", kind: crate::registry::ArgKind::U8 },
+            crate::registry::ArgSpec { name: "config", kind: crate::registry::ArgKind::Bool },
+            crate::registry::ArgSpec { name: "privileged_mode", kind: crate::registry::ArgKind::Bool },
+            crate::registry::ArgSpec { name: "inet_access", kind: crate::registry::ArgKind::U8 },
+            crate::registry::ArgSpec { name: "crypto", kind: crate::registry::ArgKind::Bool },
+            crate::registry::ArgSpec { name: "right_extension", kind: crate::registry::ArgKind::Bool },
+            crate::registry::ArgSpec { name: "right_extension_definition_roles", kind: crate::registry::ArgKind::String },
+            crate::registry::ArgSpec { name: "all_modules_extension", kind: crate::registry::ArgKind::Bool },
+            crate::registry::ArgSpec { name: "modules_available_for_extension", kind: crate::registry::ArgKind::String },
+            crate::registry::ArgSpec { name: "modules_not_available_for_extension", kind: crate::registry::ArgKind::String },
+            crate::registry::ArgSpec { name: "privileged_mode_roles", kind: crate::registry::ArgKind::String },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "name", ty: crate::registry::WireType::Str8 },
+            crate::registry::WireField { name: "descr", ty: crate::registry::WireType::Str8 },
+            crate::registry::WireField { name: "directory_access", ty: crate::registry::WireType::U8 },
+            crate::registry::WireField { name: "com_access", ty: crate::registry::WireType::U8 },
+            crate::registry::WireField { name: "addin_access", ty: crate::registry::WireType::U8 },
+            crate::registry::WireField { name: "module_access", ty: crate::registry::WireType::U8 },
+            crate::registry::WireField { name: "app_access", ty: crate::registry::WireType::U8 },
+            crate::registry::WireField { name: "config", ty: crate::registry::WireType::Bool },
+            crate::registry::WireField { name: "privileged_mode", ty: crate::registry::WireType::Bool },
+            crate::registry::WireField { name: "inet_access", ty: crate::registry::WireType::U8 },
+            crate::registry::WireField { name: "crypto", ty: crate::registry::WireType::Bool },
+            crate::registry::WireField { name: "right_extension", ty: crate::registry::WireType::Bool },
+            crate::registry::WireField { name: "right_extension_definition_roles", ty: crate::registry::WireType::Str8 },
+            crate::registry::WireField { name: "all_modules_extension", ty: crate::registry::WireType::Bool },
+            crate::registry::WireField { name: "modules_available_for_extension", ty: crate::registry::WireType::Str8 },
+            crate::registry::WireField { name: "modules_not_available_for_extension", ty: crate::registry::WireType::Str8 },
+            crate::registry::WireField { name: "privileged_mode_roles", ty: crate::registry::WireType::Str8 },
+        ],
+        meta: RPC_PROFILE_UPDATE_META,
+        validate: crate::registry::validate::<ProfileUpdateRpc>,
+        call: crate::registry::call::<ProfileUpdateRpc>,
+    },
+];
+
 #[cfg(all(test, feature = "artifacts"))]
 mod tests {
     use super::*;
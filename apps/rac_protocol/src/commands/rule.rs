@@ -7,6 +7,7 @@ mod generated {
     include!("rule_generated.rs");
 }
 
+pub(crate) use generated::COMMANDS;
 pub use generated::{
     RuleApplyRpc,
     RuleIdRecord,
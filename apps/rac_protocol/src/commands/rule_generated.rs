@@ -4,6 +4,7 @@ use crate::protocol::ProtocolVersion;
 use crate::codec::RecordCursor;
 use crate::error::Result;
 use serde::Serialize;
+use serde::Deserialize;
 use crate::rac_wire::encode_with_len_u8;
 
 pub const METHOD_RULE_LIST_REQ: u8 = 0x55;
@@ -60,8 +61,11 @@ impl RuleIdRecord {
     }
 }
 
+#[derive(Deserialize)]
 pub struct RuleListRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub server: Uuid16,
 }
 
@@ -78,7 +82,7 @@ impl crate::rpc::Request for RuleListRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc RuleList unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 });
@@ -92,9 +96,13 @@ impl crate::rpc::Request for RuleListRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct RuleInfoRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub server: Uuid16,
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub rule: Uuid16,
 }
 
@@ -111,7 +119,7 @@ impl crate::rpc::Request for RuleInfoRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc RuleInfo unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 });
@@ -128,7 +136,9 @@ impl crate::rpc::Request for RuleInfoRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct RuleApplyRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
     pub mode: u32,
 }
@@ -146,7 +156,7 @@ impl crate::rpc::Request for RuleApplyRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc RuleApply unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 4 } else { 0 });
@@ -160,9 +170,13 @@ impl crate::rpc::Request for RuleApplyRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct RuleRemoveRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub server: Uuid16,
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub rule: Uuid16,
 }
 
@@ -179,7 +193,7 @@ impl crate::rpc::Request for RuleRemoveRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc RuleRemove unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 });
@@ -196,9 +210,13 @@ impl crate::rpc::Request for RuleRemoveRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct RuleInsertRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub server: Uuid16,
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub rule: Uuid16,
     pub position: u32,
     pub object_type: u32,
@@ -221,7 +239,7 @@ impl crate::rpc::Request for RuleInsertRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc RuleInsert unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 4 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 4 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.infobase_name.len() } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.application_ext.len() } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 4 } else { 0 });
@@ -256,9 +274,13 @@ impl crate::rpc::Request for RuleInsertRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct RuleUpdateRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub server: Uuid16,
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub rule: Uuid16,
     pub position: u32,
     pub object_type: u32,
@@ -281,7 +303,7 @@ impl crate::rpc::Request for RuleUpdateRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc RuleUpdate unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 4 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 4 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.infobase_name.len() } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.application_ext.len() } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 4 } else { 0 });
@@ -343,7 +365,7 @@ impl crate::rpc::Response for RuleInfoResp {
         let protocol_version = _codec.protocol_version();
         let record = parse_rule_info_body(body, protocol_version)?;
         Ok(Self {
-            record: record,
+            record,
         })
     }
 }
@@ -407,45 +429,188 @@ pub fn parse_rule_update_body(body: &[u8], protocol_version: ProtocolVersion) ->
 
 
 pub const RPC_RULE_LIST_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "rule.list",
     method_req: METHOD_RULE_LIST_REQ,
     method_resp: Some(METHOD_RULE_LIST_RESP),
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
 pub const RPC_RULE_INFO_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "rule.info",
     method_req: METHOD_RULE_INFO_REQ,
     method_resp: Some(METHOD_RULE_INFO_RESP),
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
 pub const RPC_RULE_APPLY_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "rule.apply",
     method_req: METHOD_RULE_APPLY_REQ,
     method_resp: None,
+    mutation: crate::rpc::Mutation::Modify,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
 pub const RPC_RULE_REMOVE_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "rule.remove",
     method_req: METHOD_RULE_REMOVE_REQ,
     method_resp: None,
+    mutation: crate::rpc::Mutation::Destructive,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
 pub const RPC_RULE_INSERT_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "rule.insert",
     method_req: METHOD_RULE_INSERT_REQ,
     method_resp: Some(METHOD_RULE_INSERT_RESP),
+    mutation: crate::rpc::Mutation::Modify,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
 pub const RPC_RULE_UPDATE_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "rule.update",
     method_req: METHOD_RULE_UPDATE_REQ,
     method_resp: Some(METHOD_RULE_UPDATE_RESP),
+    mutation: crate::rpc::Mutation::Modify,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
+pub const COMMANDS: &[crate::registry::CommandSpec] = &[
+    crate::registry::CommandSpec {
+        name: "rule.list",
+        request: "RuleListRpc",
+        response: "RuleListResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "server", kind: crate::registry::ArgKind::Uuid },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "server", ty: crate::registry::WireType::Uuid },
+        ],
+        meta: RPC_RULE_LIST_META,
+        validate: crate::registry::validate::<RuleListRpc>,
+        call: crate::registry::call::<RuleListRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "rule.info",
+        request: "RuleInfoRpc",
+        response: "RuleInfoResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "server", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "rule", kind: crate::registry::ArgKind::Uuid },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "server", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "rule", ty: crate::registry::WireType::Uuid },
+        ],
+        meta: RPC_RULE_INFO_META,
+        validate: crate::registry::validate::<RuleInfoRpc>,
+        call: crate::registry::call::<RuleInfoRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "rule.apply",
+        request: "RuleApplyRpc",
+        response: "AckResponse",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "mode", kind: crate::registry::ArgKind::U32 },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "mode", ty: crate::registry::WireType::U32Be },
+        ],
+        meta: RPC_RULE_APPLY_META,
+        validate: crate::registry::validate::<RuleApplyRpc>,
+        call: crate::registry::call::<RuleApplyRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "rule.remove",
+        request: "RuleRemoveRpc",
+        response: "AckResponse",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "server", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "rule", kind: crate::registry::ArgKind::Uuid },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "server", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "rule", ty: crate::registry::WireType::Uuid },
+        ],
+        meta: RPC_RULE_REMOVE_META,
+        validate: crate::registry::validate::<RuleRemoveRpc>,
+        call: crate::registry::call::<RuleRemoveRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "rule.insert",
+        request: "RuleInsertRpc",
+        response: "RuleInsertResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "server", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "rule", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "position", kind: crate::registry::ArgKind::U32 },
+            crate::registry::ArgSpec { name: "object_type", kind: crate::registry::ArgKind::U32 },
+            crate::registry::ArgSpec { name: "infobase_name", kind: crate::registry::ArgKind::String },
+            crate::registry::ArgSpec { name: "rule_type", kind: crate::registry::ArgKind::U8 },
+            crate::registry::ArgSpec { name: "application_ext", kind: crate::registry::ArgKind::String },
+            crate::registry::ArgSpec { name: "priority", kind: crate::registry::ArgKind::U32 },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "server", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "rule", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "position", ty: crate::registry::WireType::U32Be },
+            crate::registry::WireField { name: "object_type", ty: crate::registry::WireType::U32Be },
+            crate::registry::WireField { name: "infobase_name", ty: crate::registry::WireType::Str8 },
+            crate::registry::WireField { name: "rule_type", ty: crate::registry::WireType::U8 },
+            crate::registry::WireField { name: "application_ext", ty: crate::registry::WireType::Str8 },
+            crate::registry::WireField { name: "priority", ty: crate::registry::WireType::U32Be },
+        ],
+        meta: RPC_RULE_INSERT_META,
+        validate: crate::registry::validate::<RuleInsertRpc>,
+        call: crate::registry::call::<RuleInsertRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "rule.update",
+        request: "RuleUpdateRpc",
+        response: "RuleUpdateResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "server", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "rule", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "position", kind: crate::registry::ArgKind::U32 },
+            crate::registry::ArgSpec { name: "object_type", kind: crate::registry::ArgKind::U32 },
+            crate::registry::ArgSpec { name: "infobase_name", kind: crate::registry::ArgKind::String },
+            crate::registry::ArgSpec { name: "rule_type", kind: crate::registry::ArgKind::U8 },
+            crate::registry::ArgSpec { name: "application_ext", kind: crate::registry::ArgKind::String },
+            crate::registry::ArgSpec { name: "priority", kind: crate::registry::ArgKind::U32 },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "server", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "rule", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "position", ty: crate::registry::WireType::U32Be },
+            crate::registry::WireField { name: "object_type", ty: crate::registry::WireType::U32Be },
+            crate::registry::WireField { name: "infobase_name", ty: crate::registry::WireType::Str8 },
+            crate::registry::WireField { name: "rule_type", ty: crate::registry::WireType::U8 },
+            crate::registry::WireField { name: "application_ext", ty: crate::registry::WireType::Str8 },
+            crate::registry::WireField { name: "priority", ty: crate::registry::WireType::U32Be },
+        ],
+        meta: RPC_RULE_UPDATE_META,
+        validate: crate::registry::validate::<RuleUpdateRpc>,
+        call: crate::registry::call::<RuleUpdateRpc>,
+    },
+];
+
 
@@ -6,6 +6,7 @@ mod generated {
     include!("server_generated.rs");
 }
 
+pub(crate) use generated::COMMANDS;
 pub use generated::{
     ServerInfoResp,
     ServerInfoRpc,
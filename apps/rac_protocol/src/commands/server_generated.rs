@@ -4,6 +4,7 @@ use crate::protocol::ProtocolVersion;
 use crate::codec::RecordCursor;
 use crate::error::Result;
 use serde::Serialize;
+use serde::Deserialize;
 
 pub const METHOD_SERVER_LIST_REQ: u8 = 0x16;
 pub const METHOD_SERVER_LIST_RESP: u8 = 0x17;
@@ -96,7 +97,9 @@ impl ServerRecord {
     }
 }
 
+#[derive(Deserialize)]
 pub struct ServerListRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
 }
 
@@ -113,7 +116,7 @@ impl crate::rpc::Request for ServerListRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc ServerList unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 });
@@ -124,8 +127,11 @@ impl crate::rpc::Request for ServerListRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct ServerInfoRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub server: Uuid16,
 }
 
@@ -142,7 +148,7 @@ impl crate::rpc::Request for ServerInfoRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc ServerInfo unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 });
@@ -183,7 +189,7 @@ impl crate::rpc::Response for ServerInfoResp {
         let protocol_version = _codec.protocol_version();
         let record = parse_server_info_body(body, protocol_version)?;
         Ok(Self {
-            record: record,
+            record,
         })
     }
 }
@@ -199,17 +205,54 @@ pub fn parse_server_info_body(body: &[u8], protocol_version: ProtocolVersion) ->
 
 
 pub const RPC_SERVER_LIST_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "server.list",
     method_req: METHOD_SERVER_LIST_REQ,
     method_resp: Some(METHOD_SERVER_LIST_RESP),
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
 pub const RPC_SERVER_INFO_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "server.info",
     method_req: METHOD_SERVER_INFO_REQ,
     method_resp: Some(METHOD_SERVER_INFO_RESP),
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
+pub const COMMANDS: &[crate::registry::CommandSpec] = &[
+    crate::registry::CommandSpec {
+        name: "server.list",
+        request: "ServerListRpc",
+        response: "ServerListResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+        ],
+        meta: RPC_SERVER_LIST_META,
+        validate: crate::registry::validate::<ServerListRpc>,
+        call: crate::registry::call::<ServerListRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "server.info",
+        request: "ServerInfoRpc",
+        response: "ServerInfoResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "server", kind: crate::registry::ArgKind::Uuid },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "server", ty: crate::registry::WireType::Uuid },
+        ],
+        meta: RPC_SERVER_INFO_META,
+        validate: crate::registry::validate::<ServerInfoRpc>,
+        call: crate::registry::call::<ServerInfoRpc>,
+    },
+];
+
 
@@ -7,6 +7,7 @@ mod generated {
     include!("service_setting_generated.rs");
 }
 
+pub(crate) use generated::COMMANDS;
 pub use generated::{
     ServiceSettingApplyRpc,
     ServiceSettingGetDataDirsResp,
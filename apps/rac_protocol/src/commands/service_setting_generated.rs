@@ -4,6 +4,7 @@ use crate::protocol::ProtocolVersion;
 use crate::codec::RecordCursor;
 use crate::error::Result;
 use serde::Serialize;
+use serde::Deserialize;
 use crate::rac_wire::encode_with_len_u8;
 
 pub const METHOD_SERVICE_SETTING_INFO_REQ: u8 = 0x89;
@@ -98,9 +99,13 @@ impl ServiceSettingIdRecord {
     }
 }
 
+#[derive(Deserialize)]
 pub struct ServiceSettingInfoRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub server: Uuid16,
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub setting: Uuid16,
 }
 
@@ -117,7 +122,7 @@ impl crate::rpc::Request for ServiceSettingInfoRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc ServiceSettingInfo unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 });
@@ -134,8 +139,11 @@ impl crate::rpc::Request for ServiceSettingInfoRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct ServiceSettingListRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub server: Uuid16,
 }
 
@@ -152,7 +160,7 @@ impl crate::rpc::Request for ServiceSettingListRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc ServiceSettingList unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 });
@@ -166,8 +174,11 @@ impl crate::rpc::Request for ServiceSettingListRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct ServiceSettingInsertRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub server: Uuid16,
     pub service_name: String,
     pub infobase_name: String,
@@ -188,7 +199,7 @@ impl crate::rpc::Request for ServiceSettingInsertRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc ServiceSettingInsert unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.service_name.len() } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.infobase_name.len() } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.service_data_dir.len() } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 2 } else { 0 });
@@ -217,9 +228,13 @@ impl crate::rpc::Request for ServiceSettingInsertRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct ServiceSettingUpdateRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub server: Uuid16,
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub setting: Uuid16,
     pub service_name: String,
     pub infobase_name: String,
@@ -240,7 +255,7 @@ impl crate::rpc::Request for ServiceSettingUpdateRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc ServiceSettingUpdate unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.service_name.len() } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.infobase_name.len() } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.service_data_dir.len() } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 2 } else { 0 });
@@ -269,9 +284,13 @@ impl crate::rpc::Request for ServiceSettingUpdateRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct ServiceSettingRemoveRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub server: Uuid16,
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub setting: Uuid16,
 }
 
@@ -288,7 +307,7 @@ impl crate::rpc::Request for ServiceSettingRemoveRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc ServiceSettingRemove unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 });
@@ -305,8 +324,11 @@ impl crate::rpc::Request for ServiceSettingRemoveRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct ServiceSettingApplyRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub server: Uuid16,
 }
 
@@ -323,7 +345,7 @@ impl crate::rpc::Request for ServiceSettingApplyRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc ServiceSettingApply unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 });
@@ -337,8 +359,11 @@ impl crate::rpc::Request for ServiceSettingApplyRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct ServiceSettingGetDataDirsRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub server: Uuid16,
     pub service_name: String,
 }
@@ -356,7 +381,7 @@ impl crate::rpc::Request for ServiceSettingGetDataDirsRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc ServiceSettingGetDataDirs unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.service_name.len() } else { 0 });
@@ -400,7 +425,7 @@ impl crate::rpc::Response for ServiceSettingInfoResp {
         let protocol_version = _codec.protocol_version();
         let record = parse_service_setting_info_body(body, protocol_version)?;
         Ok(Self {
-            record: record,
+            record,
         })
     }
 }
@@ -479,52 +504,206 @@ pub fn parse_service_setting_update_body(body: &[u8], protocol_version: Protocol
 
 
 pub const RPC_SERVICE_SETTING_INFO_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "service_setting.info",
     method_req: METHOD_SERVICE_SETTING_INFO_REQ,
     method_resp: Some(METHOD_SERVICE_SETTING_INFO_RESP),
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
 pub const RPC_SERVICE_SETTING_LIST_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "service_setting.list",
     method_req: METHOD_SERVICE_SETTING_LIST_REQ,
     method_resp: Some(METHOD_SERVICE_SETTING_LIST_RESP),
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
 pub const RPC_SERVICE_SETTING_INSERT_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "service_setting.insert",
     method_req: METHOD_SERVICE_SETTING_INSERT_REQ,
     method_resp: Some(METHOD_SERVICE_SETTING_INSERT_RESP),
+    mutation: crate::rpc::Mutation::Modify,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
 pub const RPC_SERVICE_SETTING_UPDATE_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "service_setting.update",
     method_req: METHOD_SERVICE_SETTING_UPDATE_REQ,
     method_resp: Some(METHOD_SERVICE_SETTING_UPDATE_RESP),
+    mutation: crate::rpc::Mutation::Modify,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
 pub const RPC_SERVICE_SETTING_REMOVE_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "service_setting.remove",
     method_req: METHOD_SERVICE_SETTING_REMOVE_REQ,
     method_resp: None,
+    mutation: crate::rpc::Mutation::Destructive,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
 pub const RPC_SERVICE_SETTING_APPLY_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "service_setting.apply",
     method_req: METHOD_SERVICE_SETTING_APPLY_REQ,
     method_resp: None,
+    mutation: crate::rpc::Mutation::Modify,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
 pub const RPC_SERVICE_SETTING_GET_DATA_DIRS_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "service_setting.get_data_dirs",
     method_req: METHOD_SERVICE_SETTING_GET_DATA_DIRS_REQ,
     method_resp: Some(METHOD_SERVICE_SETTING_GET_DATA_DIRS_RESP),
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
+pub const COMMANDS: &[crate::registry::CommandSpec] = &[
+    crate::registry::CommandSpec {
+        name: "service_setting.info",
+        request: "ServiceSettingInfoRpc",
+        response: "ServiceSettingInfoResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "server", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "setting", kind: crate::registry::ArgKind::Uuid },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "server", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "setting", ty: crate::registry::WireType::Uuid },
+        ],
+        meta: RPC_SERVICE_SETTING_INFO_META,
+        validate: crate::registry::validate::<ServiceSettingInfoRpc>,
+        call: crate::registry::call::<ServiceSettingInfoRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "service_setting.list",
+        request: "ServiceSettingListRpc",
+        response: "ServiceSettingListResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "server", kind: crate::registry::ArgKind::Uuid },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "server", ty: crate::registry::WireType::Uuid },
+        ],
+        meta: RPC_SERVICE_SETTING_LIST_META,
+        validate: crate::registry::validate::<ServiceSettingListRpc>,
+        call: crate::registry::call::<ServiceSettingListRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "service_setting.insert",
+        request: "ServiceSettingInsertRpc",
+        response: "ServiceSettingInsertResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "server", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "service_name", kind: crate::registry::ArgKind::String },
+            crate::registry::ArgSpec { name: "infobase_name", kind: crate::registry::ArgKind::String },
+            crate::registry::ArgSpec { name: "service_data_dir", kind: crate::registry::ArgKind::String },
+            crate::registry::ArgSpec { name: "active", kind: crate::registry::ArgKind::U16 },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "server", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "padding", ty: crate::registry::WireType::Bytes(16) },
+            crate::registry::WireField { name: "service_name", ty: crate::registry::WireType::Str8 },
+            crate::registry::WireField { name: "infobase_name", ty: crate::registry::WireType::Str8 },
+            crate::registry::WireField { name: "service_data_dir", ty: crate::registry::WireType::Str8 },
+            crate::registry::WireField { name: "active", ty: crate::registry::WireType::U16Be },
+        ],
+        meta: RPC_SERVICE_SETTING_INSERT_META,
+        validate: crate::registry::validate::<ServiceSettingInsertRpc>,
+        call: crate::registry::call::<ServiceSettingInsertRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "service_setting.update",
+        request: "ServiceSettingUpdateRpc",
+        response: "ServiceSettingUpdateResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "server", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "setting", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "service_name", kind: crate::registry::ArgKind::String },
+            crate::registry::ArgSpec { name: "infobase_name", kind: crate::registry::ArgKind::String },
+            crate::registry::ArgSpec { name: "service_data_dir", kind: crate::registry::ArgKind::String },
+            crate::registry::ArgSpec { name: "active", kind: crate::registry::ArgKind::U16 },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "server", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "setting", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "service_name", ty: crate::registry::WireType::Str8 },
+            crate::registry::WireField { name: "infobase_name", ty: crate::registry::WireType::Str8 },
+            crate::registry::WireField { name: "service_data_dir", ty: crate::registry::WireType::Str8 },
+            crate::registry::WireField { name: "active", ty: crate::registry::WireType::U16Be },
+        ],
+        meta: RPC_SERVICE_SETTING_UPDATE_META,
+        validate: crate::registry::validate::<ServiceSettingUpdateRpc>,
+        call: crate::registry::call::<ServiceSettingUpdateRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "service_setting.remove",
+        request: "ServiceSettingRemoveRpc",
+        response: "AckResponse",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "server", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "setting", kind: crate::registry::ArgKind::Uuid },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "server", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "setting", ty: crate::registry::WireType::Uuid },
+        ],
+        meta: RPC_SERVICE_SETTING_REMOVE_META,
+        validate: crate::registry::validate::<ServiceSettingRemoveRpc>,
+        call: crate::registry::call::<ServiceSettingRemoveRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "service_setting.apply",
+        request: "ServiceSettingApplyRpc",
+        response: "AckResponse",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "server", kind: crate::registry::ArgKind::Uuid },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "server", ty: crate::registry::WireType::Uuid },
+        ],
+        meta: RPC_SERVICE_SETTING_APPLY_META,
+        validate: crate::registry::validate::<ServiceSettingApplyRpc>,
+        call: crate::registry::call::<ServiceSettingApplyRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "service_setting.get_data_dirs",
+        request: "ServiceSettingGetDataDirsRpc",
+        response: "ServiceSettingGetDataDirsResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "server", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "service_name", kind: crate::registry::ArgKind::String },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "server", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "service_name", ty: crate::registry::WireType::Str8 },
+        ],
+        meta: RPC_SERVICE_SETTING_GET_DATA_DIRS_META,
+        validate: crate::registry::validate::<ServiceSettingGetDataDirsRpc>,
+        call: crate::registry::call::<ServiceSettingGetDataDirsRpc>,
+    },
+];
+
 
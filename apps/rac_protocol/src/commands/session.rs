@@ -8,6 +8,7 @@ mod generated {
     include!("session_generated.rs");
 }
 
+pub(crate) use generated::COMMANDS;
 pub use generated::{
     SessionInfoResp,
     SessionInfoRpc,
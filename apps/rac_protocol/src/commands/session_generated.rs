@@ -5,6 +5,7 @@ use crate::protocol::ProtocolVersion;
 use crate::codec::RecordCursor;
 use crate::error::Result;
 use serde::Serialize;
+use serde::Deserialize;
 use crate::rac_wire::encode_with_len_u8;
 
 pub const METHOD_SESSION_LIST_REQ: u8 = 0x41;
@@ -250,7 +251,9 @@ impl SessionRecord {
     }
 }
 
+#[derive(Deserialize)]
 pub struct SessionListRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
 }
 
@@ -267,7 +270,7 @@ impl crate::rpc::Request for SessionListRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc SessionList unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 });
@@ -278,8 +281,11 @@ impl crate::rpc::Request for SessionListRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct SessionInfoRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub session: Uuid16,
 }
 
@@ -296,7 +302,7 @@ impl crate::rpc::Request for SessionInfoRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc SessionInfo unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 });
@@ -310,8 +316,11 @@ impl crate::rpc::Request for SessionInfoRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct SessionTerminateRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub session: Uuid16,
     pub error_message: String,
 }
@@ -329,7 +338,7 @@ impl crate::rpc::Request for SessionTerminateRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc SessionTerminate unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.error_message.len() } else { 0 });
@@ -346,8 +355,11 @@ impl crate::rpc::Request for SessionTerminateRpc {
     }
 }
 
+#[derive(Deserialize)]
 pub struct SessionInterruptCurrentServerCallRpc {
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub cluster: Uuid16,
+    #[serde(deserialize_with = "crate::registry::uuid_arg")]
     pub session: Uuid16,
     pub error_message: String,
 }
@@ -365,7 +377,7 @@ impl crate::rpc::Request for SessionInterruptCurrentServerCallRpc {
 
     fn encode_body(&self, _codec: &dyn crate::protocol::ProtocolCodec) -> Result<Vec<u8>> {
         let protocol_version = _codec.protocol_version();
-        if !(protocol_version >= ProtocolVersion::V11_0) {
+        if protocol_version < ProtocolVersion::V11_0 {
             return Err(RacError::Unsupported("rpc SessionInterruptCurrentServerCall unsupported for protocol"));
         }
         let mut out = Vec::with_capacity(if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 16 } else { 0 } + if protocol_version >= ProtocolVersion::V11_0 { 1 + self.error_message.len() } else { 0 });
@@ -409,7 +421,7 @@ impl crate::rpc::Response for SessionInfoResp {
         let protocol_version = _codec.protocol_version();
         let record = parse_session_info_body(body, protocol_version)?;
         Ok(Self {
-            record: record,
+            record,
         })
     }
 }
@@ -425,31 +437,108 @@ pub fn parse_session_info_body(body: &[u8], protocol_version: ProtocolVersion) -
 
 
 pub const RPC_SESSION_LIST_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "session.list",
     method_req: METHOD_SESSION_LIST_REQ,
     method_resp: Some(METHOD_SESSION_LIST_RESP),
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
 pub const RPC_SESSION_INFO_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "session.info",
     method_req: METHOD_SESSION_INFO_REQ,
     method_resp: Some(METHOD_SESSION_INFO_RESP),
+    mutation: crate::rpc::Mutation::Read,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
 pub const RPC_SESSION_TERMINATE_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "session.terminate",
     method_req: METHOD_SESSION_TERMINATE_REQ,
     method_resp: None,
+    mutation: crate::rpc::Mutation::Destructive,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
 pub const RPC_SESSION_INTERRUPT_CURRENT_SERVER_CALL_META: crate::rpc::Meta = crate::rpc::Meta {
+    name: "session.interrupt_current_server_call",
     method_req: METHOD_SESSION_INTERRUPT_CURRENT_SERVER_CALL_REQ,
     method_resp: None,
+    mutation: crate::rpc::Mutation::Destructive,
     requires_cluster_context: true,
     requires_infobase_context: false,
 };
 
+pub const COMMANDS: &[crate::registry::CommandSpec] = &[
+    crate::registry::CommandSpec {
+        name: "session.list",
+        request: "SessionListRpc",
+        response: "SessionListResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+        ],
+        meta: RPC_SESSION_LIST_META,
+        validate: crate::registry::validate::<SessionListRpc>,
+        call: crate::registry::call::<SessionListRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "session.info",
+        request: "SessionInfoRpc",
+        response: "SessionInfoResp",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "session", kind: crate::registry::ArgKind::Uuid },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "session", ty: crate::registry::WireType::Uuid },
+        ],
+        meta: RPC_SESSION_INFO_META,
+        validate: crate::registry::validate::<SessionInfoRpc>,
+        call: crate::registry::call::<SessionInfoRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "session.terminate",
+        request: "SessionTerminateRpc",
+        response: "AckResponse",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "session", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "error_message", kind: crate::registry::ArgKind::String },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "session", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "error_message", ty: crate::registry::WireType::Str8 },
+        ],
+        meta: RPC_SESSION_TERMINATE_META,
+        validate: crate::registry::validate::<SessionTerminateRpc>,
+        call: crate::registry::call::<SessionTerminateRpc>,
+    },
+    crate::registry::CommandSpec {
+        name: "session.interrupt_current_server_call",
+        request: "SessionInterruptCurrentServerCallRpc",
+        response: "AckResponse",
+        args: &[
+            crate::registry::ArgSpec { name: "cluster", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "session", kind: crate::registry::ArgKind::Uuid },
+            crate::registry::ArgSpec { name: "error_message", kind: crate::registry::ArgKind::String },
+        ],
+        wire: &[
+            crate::registry::WireField { name: "cluster", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "session", ty: crate::registry::WireType::Uuid },
+            crate::registry::WireField { name: "error_message", ty: crate::registry::WireType::Str8 },
+        ],
+        meta: RPC_SESSION_INTERRUPT_CURRENT_SERVER_CALL_META,
+        validate: crate::registry::validate::<SessionInterruptCurrentServerCallRpc>,
+        call: crate::registry::call::<SessionInterruptCurrentServerCallRpc>,
+    },
+];
+
 
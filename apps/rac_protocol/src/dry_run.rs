@@ -0,0 +1,360 @@
+//! RPCs recorded by [`RacClient::dry_run`](crate::client::RacClient::dry_run)
+//! and their annotated hex dump, plus a field guesser for replies no schema
+//! describes.
+
+use std::fmt::Write as _;
+
+use serde::Serialize;
+
+use crate::client::ACK;
+use crate::codec::RecordCursor;
+use crate::rac_wire::{decode_rpc_method, format_uuid};
+use crate::registry::{WireField, WireType};
+use crate::rpc::Mutation;
+use crate::Uuid16;
+
+/// Body of the cluster and infobase context RPCs the client sends on its own:
+/// the cluster with empty credentials.
+pub(crate) const CONTEXT_WIRE: &[WireField] = &[
+    WireField {
+        name: "cluster",
+        ty: WireType::Uuid,
+    },
+    WireField {
+        name: "user",
+        ty: WireType::Str8,
+    },
+    WireField {
+        name: "pwd",
+        ty: WireType::Str8,
+    },
+];
+
+/// Length of the `01 00 00 01 <method>` RPC header.
+const HEADER_LEN: usize = 5;
+const BYTES_PER_LINE: usize = 16;
+/// Shorter length-prefixed runs are too likely to be numbers.
+const MIN_GUESSED_STR: usize = 2;
+
+#[derive(Debug, Clone)]
+pub struct PlannedRpc {
+    /// Registry name such as `session.terminate`, or `cluster.context` and
+    /// `infobase.context` for context switches.
+    pub name: &'static str,
+    pub mutation: Mutation,
+    pub payload: Vec<u8>,
+    pub wire: &'static [WireField],
+}
+
+/// A labelled run of payload bytes.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct Span {
+    pub start: usize,
+    pub len: usize,
+    pub label: String,
+}
+
+/// Splits an RPC payload into its header and the fields of `wire`. Bytes the
+/// layout does not account for end up in a final `unparsed` span.
+pub fn annotate(payload: &[u8], wire: &[WireField]) -> Vec<Span> {
+    let mut spans = Vec::new();
+    if payload.len() < HEADER_LEN {
+        spans.push(Span {
+            start: 0,
+            len: payload.len(),
+            label: "truncated header".to_string(),
+        });
+        return spans;
+    }
+    spans.push(Span {
+        start: 0,
+        len: HEADER_LEN,
+        label: format!("header, method 0x{:02x}", payload[HEADER_LEN - 1]),
+    });
+    let body = &payload[HEADER_LEN..];
+    let mut cursor = RecordCursor::new(body);
+    for field in wire {
+        let start = body.len() - cursor.remaining_len();
+        let Some(value) = take_field(&mut cursor, field.ty) else {
+            break;
+        };
+        let len = body.len() - cursor.remaining_len() - start;
+        let label = match value {
+            Some(value) => format!("{} = {value}", field.name),
+            None => field.name.to_string(),
+        };
+        spans.push(Span {
+            start: HEADER_LEN + start,
+            len,
+            label,
+        });
+    }
+    if cursor.remaining_len() > 0 {
+        spans.push(Span {
+            start: payload.len() - cursor.remaining_len(),
+            len: cursor.remaining_len(),
+            label: "unparsed".to_string(),
+        });
+    }
+    spans
+}
+
+/// `None` when the payload ends early; `Some(None)` for opaque bytes.
+fn take_field(cursor: &mut RecordCursor<'_>, ty: WireType) -> Option<Option<String>> {
+    let value = match ty {
+        WireType::Uuid => format_uuid(&cursor.take_uuid().ok()?),
+        WireType::Str8 => format!("{:?}", cursor.take_str8().ok()?),
+        WireType::StrU14 => {
+            let first = usize::from(cursor.take_u8().ok()?);
+            let len = if first & 0x40 != 0 {
+                (first & 0x3f) | usize::from(cursor.take_u8().ok()?) << 6
+            } else {
+                first
+            };
+            format!(
+                "{:?}",
+                String::from_utf8_lossy(&cursor.take_bytes(len).ok()?)
+            )
+        }
+        WireType::U8 => cursor.take_u8().ok()?.to_string(),
+        WireType::Bool => (cursor.take_u8().ok()? != 0).to_string(),
+        WireType::U16Be => cursor.take_u16_be().ok()?.to_string(),
+        WireType::U32Be => cursor.take_u32_be().ok()?.to_string(),
+        WireType::U64Be => cursor.take_u64_be().ok()?.to_string(),
+        WireType::Bytes(len) => {
+            cursor.take_bytes(len).ok()?;
+            return Some(None);
+        }
+    };
+    Some(Some(value))
+}
+
+/// Numbered hex dump of `planned`.
+pub fn render(planned: &[PlannedRpc]) -> String {
+    let mut out = String::new();
+    for (index, rpc) in planned.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "{}. {} ({}, {} bytes)",
+            index + 1,
+            rpc.name,
+            rpc.mutation.as_str(),
+            rpc.payload.len()
+        );
+        out.push_str(&dump(&rpc.payload, &annotate(&rpc.payload, rpc.wire)));
+    }
+    out
+}
+
+/// Hex dump of `payload`, one line per span and 16 bytes at most.
+pub fn dump(payload: &[u8], spans: &[Span]) -> String {
+    let mut out = String::new();
+    for span in spans {
+        let bytes = &payload[span.start..span.start + span.len];
+        if bytes.is_empty() {
+            let _ = writeln!(out, "  {:04x}  {:<47}  {}", span.start, "", span.label);
+        }
+        for (line, chunk) in bytes.chunks(BYTES_PER_LINE).enumerate() {
+            let hex: Vec<String> = chunk.iter().map(|byte| format!("{byte:02x}")).collect();
+            let label = if line == 0 { span.label.as_str() } else { "" };
+            let offset = span.start + line * BYTES_PER_LINE;
+            let _ = writeln!(out, "  {offset:04x}  {:<47}  {label}", hex.join(" "));
+        }
+    }
+    out
+}
+
+/// Guesses the fields of a payload without a known layout: UUIDs by their
+/// version and variant bits, printable strings behind a length byte, and the
+/// bytes between them as big-endian numbers where the width fits. Labels of
+/// guessed fields end with `?`.
+pub fn scan(payload: &[u8]) -> Vec<Span> {
+    if payload == ACK {
+        return vec![Span {
+            start: 0,
+            len: payload.len(),
+            label: "ack".to_string(),
+        }];
+    }
+    let mut spans = Vec::new();
+    let mut pos = 0;
+    if let Some(method) = decode_rpc_method(payload) {
+        spans.push(Span {
+            start: 0,
+            len: HEADER_LEN,
+            label: format!("header, method 0x{method:02x}"),
+        });
+        pos = HEADER_LEN;
+    }
+    let mut gap = pos;
+    while pos < payload.len() {
+        let rest = &payload[pos..];
+        let Some((len, label)) = guess_uuid(rest).or_else(|| guess_str8(rest)) else {
+            pos += 1;
+            continue;
+        };
+        push_gap(&mut spans, payload, gap, pos);
+        spans.push(Span {
+            start: pos,
+            len,
+            label,
+        });
+        pos += len;
+        gap = pos;
+    }
+    push_gap(&mut spans, payload, gap, pos);
+    spans
+}
+
+fn guess_uuid(bytes: &[u8]) -> Option<(usize, String)> {
+    let uuid: Uuid16 = bytes.get(..16)?.try_into().ok()?;
+    let version = uuid[6] >> 4;
+    if !(1..=5).contains(&version) || uuid[8] & 0xc0 != 0x80 {
+        return None;
+    }
+    Some((uuid.len(), format!("uuid? {}", format_uuid(&uuid))))
+}
+
+fn guess_str8(bytes: &[u8]) -> Option<(usize, String)> {
+    let len = usize::from(*bytes.first()?);
+    if len < MIN_GUESSED_STR {
+        return None;
+    }
+    let text = std::str::from_utf8(bytes.get(1..=len)?).ok()?;
+    if text.chars().any(char::is_control) {
+        return None;
+    }
+    Some((len + 1, format!("str? {text:?}")))
+}
+
+fn push_gap(spans: &mut Vec<Span>, payload: &[u8], start: usize, end: usize) {
+    let bytes = &payload[start..end];
+    let label = match bytes {
+        [] => return,
+        [value] => format!("u8? {value}"),
+        [a, b] => format!("u16? {}", u16::from_be_bytes([*a, *b])),
+        _ => match <[u8; 4]>::try_from(bytes) {
+            Ok(word) => format!("u32? {}", u32::from_be_bytes(word)),
+            Err(_) => match <[u8; 8]>::try_from(bytes) {
+                Ok(word) => format!("u64? {}", u64::from_be_bytes(word)),
+                Err(_) => "unknown".to_string(),
+            },
+        },
+    };
+    spans.push(Span {
+        start,
+        len: bytes.len(),
+        label,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::RacClient;
+    use crate::commands::{session_list, session_terminate};
+    use crate::error::RacError;
+    use crate::protocol::ProtocolVersion;
+    use crate::rac_wire::{encode_rpc, parse_uuid};
+
+    #[test]
+    fn labels_header_fields_and_leftovers() {
+        let mut body = vec![0u8; 15];
+        body.push(7);
+        body.extend_from_slice(&[5, b'a', b'd', b'm', b'i', b'n', 0, 0xff]);
+        let payload = encode_rpc(0x09, &body);
+        let spans = annotate(&payload, CONTEXT_WIRE);
+        let labels: Vec<(usize, usize, &str)> = spans
+            .iter()
+            .map(|span| (span.start, span.len, span.label.as_str()))
+            .collect();
+        assert_eq!(
+            labels,
+            [
+                (0, 5, "header, method 0x09"),
+                (5, 16, "cluster = 00000000-0000-0000-0000-000000000007"),
+                (21, 6, "user = \"admin\""),
+                (27, 1, "pwd = \"\""),
+                (28, 1, "unparsed"),
+            ]
+        );
+
+        let dump = render(&[PlannedRpc {
+            name: "cluster.auth",
+            mutation: Mutation::Read,
+            payload,
+            wire: CONTEXT_WIRE,
+        }]);
+        let lines: Vec<&str> = dump.lines().collect();
+        assert_eq!(lines[0], "1. cluster.auth (read, 29 bytes)");
+        assert!(
+            lines[1].starts_with("  0000  01 00 00 01 09 "),
+            "{}",
+            lines[1]
+        );
+        assert!(
+            lines[3].starts_with("  0015  05 61 64 6d 69 6e "),
+            "{}",
+            lines[3]
+        );
+        assert!(lines[3].ends_with("  user = \"admin\""), "{}", lines[3]);
+    }
+
+    #[test]
+    fn dry_run_client_records_up_to_the_first_change() {
+        let cluster = [1u8; 16];
+        let mut client = RacClient::dry_run(ProtocolVersion::V11_0);
+        let err = session_terminate(&mut client, "admin", "", cluster, [2; 16], String::new())
+            .expect_err("stops before the reply");
+        assert!(matches!(err, RacError::DryRun));
+        let names: Vec<&str> = client.planned().iter().map(|rpc| rpc.name).collect();
+        assert_eq!(
+            names,
+            ["cluster.auth", "cluster.context", "session.terminate"]
+        );
+        let terminate = &client.planned()[2];
+        assert_eq!(terminate.mutation, Mutation::Destructive);
+        assert_eq!(terminate.wire.len(), 3);
+
+        let err = session_list(&mut client, cluster).expect_err("needs a reply");
+        assert!(matches!(err, RacError::Unsupported(_)));
+        assert_eq!(client.planned().len(), 3);
+    }
+
+    #[test]
+    fn stops_at_short_payloads() {
+        let payload = encode_rpc(0x47, &[1, 2, 3]);
+        let wire = [WireField {
+            name: "cluster",
+            ty: WireType::Uuid,
+        }];
+        let spans = annotate(&payload, &wire);
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[1].label, "unparsed");
+        assert_eq!(annotate(&[1, 0], &wire)[0].label, "truncated header");
+    }
+
+    #[test]
+    fn scan_guesses_uuids_strings_and_numbers() {
+        let uuid = parse_uuid("6e2b1f4a-2c3d-4e5f-8a6b-7c8d9e0f1a2b").expect("uuid");
+        let mut body = uuid.to_vec();
+        body.extend_from_slice(&[4, b'M', b'a', b'i', b'n']);
+        body.extend_from_slice(&1541u32.to_be_bytes());
+        body.push(1);
+        let payload = encode_rpc(0x0b, &body);
+        let labels: Vec<String> = scan(&payload).into_iter().map(|span| span.label).collect();
+        assert_eq!(
+            labels,
+            [
+                "header, method 0x0b",
+                "uuid? 6e2b1f4a-2c3d-4e5f-8a6b-7c8d9e0f1a2b",
+                "str? \"Main\"",
+                "unknown",
+            ]
+        );
+        assert_eq!(scan(&ACK)[0].label, "ack");
+        let tail = scan(&[0, 0, 0x06, 0x05]);
+        assert_eq!(tail.len(), 1);
+        assert_eq!(tail[0].label, "u32? 1541");
+    }
+}
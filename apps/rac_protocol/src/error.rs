@@ -1,7 +1,9 @@
 use std::fmt;
 use std::io;
+use std::time::Duration;
 
 use crate::rac_wire::WireError;
+use crate::rpc::Mutation;
 
 #[derive(Debug)]
 pub enum RacError {
@@ -10,10 +12,38 @@ pub enum RacError {
     Protocol(&'static str),
     ProtocolMessage(String),
     Unsupported(&'static str),
-    UnsupportedService { payload: Vec<u8> },
+    UnsupportedService {
+        payload: Vec<u8>,
+    },
     Decode(&'static str),
     DecodeMessage(String),
-    UnexpectedMethod { got: u8, expected: u8 },
+    UnexpectedMethod {
+        got: u8,
+        expected: u8,
+    },
+    PoolTimeout(Duration),
+    PoolClosed,
+    NameNotFound {
+        kind: &'static str,
+        name: String,
+    },
+    AmbiguousName {
+        kind: &'static str,
+        name: String,
+        candidates: Vec<String>,
+    },
+    InvalidArgs(String),
+    /// A non-read RPC on a client configured as read-only.
+    ReadOnly {
+        method: u8,
+        mutation: Mutation,
+    },
+    /// A dry-run client reached an RPC that changes cluster state.
+    DryRun,
+    /// An action a deny rule of the policy covers.
+    Forbidden(String),
+    /// The user declined a confirmation prompt.
+    Cancelled,
 }
 
 impl fmt::Display for RacError {
@@ -33,6 +63,31 @@ impl fmt::Display for RacError {
                     "unexpected rpc method 0x{got:02x}, expected 0x{expected:02x}"
                 )
             }
+            RacError::PoolTimeout(timeout) => write!(
+                f,
+                "no connection available within {} ms",
+                timeout.as_millis()
+            ),
+            RacError::PoolClosed => write!(f, "connection pool is closed"),
+            RacError::NameNotFound { kind, name } => write!(f, "{kind} not found: {name}"),
+            RacError::AmbiguousName {
+                kind,
+                name,
+                candidates,
+            } => write!(
+                f,
+                "{kind} name {name} is ambiguous: {}",
+                candidates.join(", ")
+            ),
+            RacError::InvalidArgs(msg) => write!(f, "invalid args: {msg}"),
+            RacError::ReadOnly { method, mutation } => write!(
+                f,
+                "read-only client refuses {} rpc 0x{method:02x}",
+                mutation.as_str()
+            ),
+            RacError::DryRun => write!(f, "dry run, nothing sent"),
+            RacError::Forbidden(msg) => write!(f, "forbidden by policy: {msg}"),
+            RacError::Cancelled => write!(f, "cancelled"),
         }
     }
 }
@@ -1,9 +1,15 @@
 pub mod client;
 pub mod codec;
 pub mod commands;
+pub mod dry_run;
 pub mod error;
+pub mod policy;
+pub mod pool;
 pub mod protocol;
 pub mod rac_wire;
+pub mod registry;
+pub mod resolve;
 pub mod rpc;
+pub mod tabular;
 
 pub type Uuid16 = [u8; 16];
@@ -0,0 +1,225 @@
+//! Protection for actions that end sessions or remove objects: the object an
+//! action is about to hit, and a policy file that forbids some of them, e.g.
+//!
+//! ```toml
+//! [[deny]]
+//! action = "session.*"
+//! match = { app_id = "Designer" }
+//! reason = "configurator sessions are never ended remotely"
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::client::RacClient;
+use crate::commands::{infobase_summary_info, session_info};
+use crate::error::{RacError, Result};
+use crate::rac_wire::format_uuid;
+use crate::Uuid16;
+
+/// An object an action is about to hit, with the attributes a prompt shows
+/// and a policy matches on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Target {
+    /// Registry command name such as `session.terminate`.
+    pub action: String,
+    pub fields: Vec<(String, String)>,
+}
+
+impl Target {
+    pub fn new(action: impl Into<String>) -> Self {
+        Self {
+            action: action.into(),
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn with(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.fields.push((name.to_string(), value.into()));
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.action)?;
+        for (index, (name, value)) in self.fields.iter().enumerate() {
+            let sep = if index == 0 { ':' } else { ',' };
+            write!(f, "{sep} {name} {value:?}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Policy {
+    #[serde(default)]
+    pub deny: Vec<DenyRule>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DenyRule {
+    /// Command name; `session.*` covers every session action and `*` all.
+    pub action: String,
+    /// Target fields that must all be equal, ignoring case.
+    #[serde(default, rename = "match")]
+    pub fields: BTreeMap<String, String>,
+    pub reason: Option<String>,
+}
+
+impl DenyRule {
+    fn matches(&self, target: &Target) -> bool {
+        let action = match self.action.strip_suffix('*') {
+            Some(prefix) => target.action.starts_with(prefix),
+            None => target.action == self.action,
+        };
+        action
+            && self.fields.iter().all(|(name, expected)| {
+                target
+                    .get(name)
+                    .is_some_and(|value| value.to_lowercase() == expected.to_lowercase())
+            })
+    }
+}
+
+impl Policy {
+    pub fn parse(raw: &str) -> std::result::Result<Self, String> {
+        toml::from_str(raw).map_err(|err| err.to_string())
+    }
+
+    /// An empty policy when the file does not exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(raw) => Self::parse(&raw)
+                .map_err(|err| RacError::InvalidArgs(format!("{}: {err}", path.display()))),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// `Err(Forbidden)` when a deny rule covers `target`.
+    pub fn check(&self, target: &Target) -> Result<()> {
+        match self.deny.iter().find(|rule| rule.matches(target)) {
+            Some(rule) => Err(RacError::Forbidden(match &rule.reason {
+                Some(reason) => format!("{target} ({reason})"),
+                None => target.to_string(),
+            })),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Resolves what an action is about to hit and refuses it when `policy`
+/// denies any target. Returns the targets for a confirmation prompt; none
+/// when `client` would not send the action anyway.
+pub fn enforce(
+    client: &mut RacClient,
+    policy: &Policy,
+    targets: impl FnOnce(&mut RacClient) -> Result<Vec<Target>>,
+) -> Result<Vec<Target>> {
+    if !client.sends_changes() {
+        return Ok(Vec::new());
+    }
+    let targets = targets(client)?;
+    for target in &targets {
+        policy.check(target)?;
+    }
+    Ok(targets)
+}
+
+/// A session with its number, user, host, application and infobase name.
+pub fn session_target(
+    client: &mut RacClient,
+    action: &str,
+    cluster: Uuid16,
+    session: Uuid16,
+) -> Result<Target> {
+    let record = session_info(client, cluster, session)?.record;
+    let infobase = infobase_summary_info(client, cluster, record.infobase)
+        .map(|resp| resp.summary.name)
+        .unwrap_or_else(|_| format_uuid(&record.infobase));
+    Ok(Target::new(action)
+        .with("session", record.session_id.to_string())
+        .with("user", record.user_name)
+        .with("host", record.host)
+        .with("app_id", record.app_id)
+        .with("infobase", infobase))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn denies_matching_actions_and_fields() {
+        let policy = Policy::parse(
+            r#"
+[[deny]]
+action = "session.*"
+match = { app_id = "Designer" }
+reason = "configurator"
+
+[[deny]]
+action = "session.terminate"
+match = { user = "Robot" }
+
+[[deny]]
+action = "agent.admin_remove"
+"#,
+        )
+        .expect("policy");
+        let session = |app: &str, user: &str| {
+            Target::new("session.terminate")
+                .with("session", "12")
+                .with("user", user)
+                .with("app_id", app)
+        };
+
+        let err = policy
+            .check(&session("designer", "ivanov"))
+            .expect_err("designer");
+        assert!(matches!(err, RacError::Forbidden(_)));
+        assert_eq!(
+            err.to_string(),
+            "forbidden by policy: session.terminate: session \"12\", user \"ivanov\", \
+             app_id \"designer\" (configurator)"
+        );
+        assert!(policy.check(&session("1CV8C", "robot")).is_err());
+        assert!(policy.check(&session("1CV8C", "ivanov")).is_ok());
+        let interrupt = Target::new("session.interrupt_current_server_call")
+            .with("user", "robot")
+            .with("app_id", "1CV8C");
+        assert!(policy.check(&interrupt).is_ok());
+        assert!(policy
+            .check(&Target::new("agent.admin_remove").with("name", "x"))
+            .is_err());
+        assert!(policy.check(&Target::new("limit.remove")).is_ok());
+
+        assert!(Policy::parse("[[deny]]\nactoin = \"x\"\n").is_err());
+        let missing = Policy::load(Path::new("/nonexistent/policy.toml")).expect("missing");
+        assert!(missing.deny.is_empty());
+    }
+
+    #[test]
+    fn enforce_skips_clients_that_send_no_changes() {
+        let policy = Policy::parse("[[deny]]\naction = \"*\"\n").expect("policy");
+        let mut client = RacClient::dry_run(crate::protocol::ProtocolVersion::V11_0);
+        let targets = enforce(&mut client, &policy, |_| panic!("dry run resolved targets"));
+        assert_eq!(targets.expect("dry run"), Vec::new());
+        assert!(client.planned().is_empty());
+    }
+}
@@ -0,0 +1,784 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Condvar, Mutex, MutexGuard, RwLock, RwLockReadGuard};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::client::{ClientConfig, RacClient};
+use crate::commands::{agent_version, cluster_auth_optional, infobase_auth_optional};
+use crate::error::{RacError, Result};
+use crate::Uuid16;
+
+pub trait Clock: Send + Sync + 'static {
+    fn now(&self) -> Instant;
+}
+
+#[derive(Debug, Clone)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Connection managed by [`Pool`]; implemented for [`RacClient`].
+pub trait Connection: Send + Sized + 'static {
+    fn connect(addr: &str, cfg: &ClientConfig) -> Result<Self>;
+    /// Cheap liveness probe run before reusing a long idle connection.
+    fn validate(&mut self) -> bool;
+    fn close(self);
+}
+
+impl Connection for RacClient {
+    fn connect(addr: &str, cfg: &ClientConfig) -> Result<Self> {
+        RacClient::connect(addr, cfg.clone())
+    }
+
+    fn validate(&mut self) -> bool {
+        agent_version(self).is_ok()
+    }
+
+    fn close(self) {
+        let _ = RacClient::close(self);
+    }
+}
+
+/// Connections are shared only between callers with the same address and
+/// identity (the user the connections authenticate as).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PoolKey {
+    pub addr: String,
+    pub identity: Option<String>,
+}
+
+impl PoolKey {
+    pub fn new(addr: &str, identity: Option<&str>) -> Self {
+        Self {
+            addr: addr.to_string(),
+            identity: identity.map(str::to_string),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PoolConfig {
+    pub client_cfg: ClientConfig,
+    /// Connections per key.
+    pub max: usize,
+    pub idle_ttl: Duration,
+    pub checkout_timeout: Duration,
+    /// Idle connections older than this are probed before reuse.
+    pub validate_after: Duration,
+    /// Idle connections per registered key kept open by [`Pool::maintain`].
+    pub min_idle: usize,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            client_cfg: ClientConfig::default(),
+            max: 4,
+            idle_ttl: Duration::from_secs(60),
+            checkout_timeout: Duration::from_secs(5),
+            validate_after: Duration::from_secs(10),
+            min_idle: 0,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, Serialize)]
+pub struct PoolCounters {
+    pub connects: u64,
+    pub connect_failures: u64,
+    pub checkout_timeouts: u64,
+    pub validation_failures: u64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct PoolStats {
+    pub max: usize,
+    pub in_use: usize,
+    pub idle: usize,
+    pub waiters: usize,
+    #[serde(flatten)]
+    pub counters: PoolCounters,
+}
+
+/// What a connection authenticated to. Infobase credentials are sent per
+/// cluster: the RPC names no infobase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AuthScope {
+    Cluster(Uuid16),
+    Infobase(Uuid16),
+}
+
+/// An authentication and a fingerprint of its credentials, so that a
+/// connection authenticated as someone else does not count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Auth {
+    pub scope: AuthScope,
+    pub fingerprint: u64,
+}
+
+impl Auth {
+    pub fn new(scope: AuthScope, user: Option<&str>, pwd: Option<&str>) -> Self {
+        let mut hasher = DefaultHasher::new();
+        (user, pwd).hash(&mut hasher);
+        Self {
+            scope,
+            fingerprint: hasher.finish(),
+        }
+    }
+}
+
+/// Checked out connection together with the clusters and infobases it has
+/// already authenticated to.
+#[derive(Debug)]
+pub struct Pooled<T> {
+    conn: T,
+    key: PoolKey,
+    /// Credential fingerprint per scope.
+    auth: HashMap<AuthScope, u64>,
+    /// [`KeyState::generation`] the authentications belong to.
+    generation: u64,
+}
+
+impl<T> Pooled<T> {
+    pub fn key(&self) -> &PoolKey {
+        &self.key
+    }
+
+    pub fn is_authenticated(&self, auth: Auth) -> bool {
+        self.auth.get(&auth.scope) == Some(&auth.fingerprint)
+    }
+
+    pub fn mark_authenticated(&mut self, auth: Auth) {
+        self.auth.insert(auth.scope, auth.fingerprint);
+    }
+
+    pub fn into_inner(self) -> T {
+        self.conn
+    }
+}
+
+impl Pooled<RacClient> {
+    /// Authenticates to `cluster` unless this connection already did with
+    /// the same credentials.
+    pub fn authenticate_cluster(
+        &mut self,
+        cluster: Uuid16,
+        user: Option<&str>,
+        pwd: Option<&str>,
+    ) -> Result<()> {
+        let auth = Auth::new(AuthScope::Cluster(cluster), user, pwd);
+        if self.is_authenticated(auth) {
+            return Ok(());
+        }
+        self.auth.remove(&auth.scope);
+        cluster_auth_optional(&mut self.conn, cluster, user, pwd)?;
+        self.mark_authenticated(auth);
+        Ok(())
+    }
+
+    /// As [`Self::authenticate_cluster`], for the infobases of `cluster`.
+    pub fn authenticate_infobase(
+        &mut self,
+        cluster: Uuid16,
+        user: Option<&str>,
+        pwd: Option<&str>,
+    ) -> Result<()> {
+        let auth = Auth::new(AuthScope::Infobase(cluster), user, pwd);
+        if self.is_authenticated(auth) {
+            return Ok(());
+        }
+        self.auth.remove(&auth.scope);
+        infobase_auth_optional(&mut self.conn, cluster, user, pwd)?;
+        self.mark_authenticated(auth);
+        Ok(())
+    }
+}
+
+impl<T> Deref for Pooled<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.conn
+    }
+}
+
+impl<T> DerefMut for Pooled<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.conn
+    }
+}
+
+#[derive(Debug)]
+struct IdleConn<T> {
+    conn: Pooled<T>,
+    last_used: Instant,
+}
+
+#[derive(Debug)]
+struct KeyState<T> {
+    idle: Vec<IdleConn<T>>,
+    in_use: usize,
+    waiters: usize,
+    counters: PoolCounters,
+    /// Bumped by [`Pool::reset_authentication`].
+    generation: u64,
+}
+
+impl<T> Default for KeyState<T> {
+    fn default() -> Self {
+        Self {
+            idle: Vec::new(),
+            in_use: 0,
+            waiters: 0,
+            counters: PoolCounters::default(),
+            generation: 0,
+        }
+    }
+}
+
+/// Blocking connection pool keyed by [`PoolKey`].
+pub struct Pool<T: Connection = RacClient, C: Clock = SystemClock> {
+    cfg: RwLock<PoolConfig>,
+    clock: C,
+    inner: Mutex<HashMap<PoolKey, KeyState<T>>>,
+    condvar: Condvar,
+    closed: AtomicBool,
+}
+
+impl<T: Connection, C: Clock> Pool<T, C> {
+    pub fn new(cfg: PoolConfig, clock: C) -> Self {
+        Self {
+            cfg: RwLock::new(cfg),
+            clock,
+            inner: Mutex::new(HashMap::new()),
+            condvar: Condvar::new(),
+            closed: AtomicBool::new(false),
+        }
+    }
+
+    pub fn config(&self) -> PoolConfig {
+        self.cfg().clone()
+    }
+
+    /// Applies new settings without dropping open connections: idle ones
+    /// are pruned by the new `idle_ttl`, new ones use the new client config.
+    pub fn reconfigure(&self, cfg: PoolConfig) {
+        *self.cfg.write().unwrap_or_else(|err| err.into_inner()) = cfg;
+        self.condvar.notify_all();
+    }
+
+    /// Forgets the authentications of every connection for `key`, so the
+    /// next request to a cluster authenticates again (after the cluster
+    /// credentials changed). Connections stay open.
+    pub fn reset_authentication(&self, key: &PoolKey) {
+        let mut inner = self.lock();
+        let state = inner.entry(key.clone()).or_default();
+        state.generation += 1;
+        let generation = state.generation;
+        for idle in &mut state.idle {
+            idle.conn.auth.clear();
+            idle.conn.generation = generation;
+        }
+    }
+
+    /// Closes every idle connection; connections in use are closed when
+    /// released and further checkouts fail with [`RacError::PoolClosed`].
+    pub fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+        let idle: Vec<_> = self
+            .lock()
+            .values_mut()
+            .flat_map(|state| state.idle.drain(..).map(|idle| idle.conn))
+            .collect();
+        self.condvar.notify_all();
+        close_all(idle);
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+
+    /// Makes `key` known to [`Pool::maintain`] and [`Pool::stats`] before
+    /// its first checkout.
+    pub fn register(&self, key: &PoolKey) {
+        self.lock().entry(key.clone()).or_default();
+    }
+
+    /// Hands out an idle connection for `key`, preferring one that already
+    /// holds most of `wanted`, or opens a new one. Waits at most
+    /// `checkout_timeout` for a free slot.
+    pub fn checkout(&self, key: &PoolKey, wanted: &[Auth]) -> Result<Pooled<T>> {
+        let cfg = self.config();
+        let deadline = Instant::now() + cfg.checkout_timeout;
+        loop {
+            if self.is_closed() {
+                return Err(RacError::PoolClosed);
+            }
+            let now = self.clock.now();
+            let mut inner = self.lock();
+            let state = inner.entry(key.clone()).or_default();
+            let expired = prune_idle(&mut state.idle, cfg.idle_ttl, now);
+            if !expired.is_empty() {
+                drop(inner);
+                close_all(expired);
+                inner = self.lock();
+            }
+            let state = inner.entry(key.clone()).or_default();
+
+            if let Some(idx) = pick_idle(&state.idle, wanted) {
+                let idle = state.idle.remove(idx);
+                state.in_use += 1;
+                drop(inner);
+                let mut conn = idle.conn;
+                if now.duration_since(idle.last_used) < cfg.validate_after || conn.conn.validate() {
+                    return Ok(conn);
+                }
+                conn.conn.close();
+                let mut inner = self.lock();
+                let state = inner.entry(key.clone()).or_default();
+                state.in_use = state.in_use.saturating_sub(1);
+                state.counters.validation_failures += 1;
+                self.condvar.notify_all();
+                continue;
+            }
+
+            if state.in_use + state.idle.len() < cfg.max {
+                state.in_use += 1;
+                drop(inner);
+                return match self.connect(key) {
+                    Ok(conn) => Ok(conn),
+                    Err(err) => {
+                        let mut inner = self.lock();
+                        let state = inner.entry(key.clone()).or_default();
+                        state.in_use = state.in_use.saturating_sub(1);
+                        self.condvar.notify_all();
+                        Err(err)
+                    }
+                };
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                state.counters.checkout_timeouts += 1;
+                return Err(RacError::PoolTimeout(cfg.checkout_timeout));
+            }
+            state.waiters += 1;
+            let (mut inner, _) = self
+                .condvar
+                .wait_timeout(inner, remaining)
+                .unwrap_or_else(|err| err.into_inner());
+            let state = inner.entry(key.clone()).or_default();
+            state.waiters = state.waiters.saturating_sub(1);
+        }
+    }
+
+    /// Returns a connection; `ok = false` closes it (use after I/O or
+    /// protocol errors).
+    pub fn release(&self, mut conn: Pooled<T>, ok: bool) {
+        let now = self.clock.now();
+        let key = conn.key.clone();
+        if ok && !self.is_closed() {
+            let mut inner = self.lock();
+            let state = inner.entry(key).or_default();
+            state.in_use = state.in_use.saturating_sub(1);
+            if conn.generation != state.generation {
+                conn.auth.clear();
+                conn.generation = state.generation;
+            }
+            state.idle.push(IdleConn {
+                conn,
+                last_used: now,
+            });
+        } else {
+            conn.conn.close();
+            let mut inner = self.lock();
+            let state = inner.entry(key).or_default();
+            state.in_use = state.in_use.saturating_sub(1);
+        }
+        self.condvar.notify_all();
+    }
+
+    /// Closes expired idle connections and reconnects every registered key
+    /// up to `min_idle`. Meant to be called periodically.
+    pub fn maintain(&self) -> Result<()> {
+        if self.is_closed() {
+            return Ok(());
+        }
+        let cfg = self.config();
+        let now = self.clock.now();
+        let mut expired = Vec::new();
+        let mut missing = Vec::new();
+        {
+            let mut inner = self.lock();
+            for (key, state) in inner.iter_mut() {
+                expired.extend(prune_idle(&mut state.idle, cfg.idle_ttl, now));
+                let total = state.in_use + state.idle.len();
+                let count = cfg
+                    .min_idle
+                    .saturating_sub(state.idle.len())
+                    .min(cfg.max.saturating_sub(total));
+                // Reserve the slots so concurrent checkouts do not overshoot `max`.
+                state.in_use += count;
+                missing.extend(std::iter::repeat_n(key.clone(), count));
+            }
+        }
+        close_all(expired);
+
+        let mut result = Ok(());
+        for key in missing {
+            let connected = self.connect(&key);
+            let mut inner = self.lock();
+            let state = inner.entry(key).or_default();
+            state.in_use = state.in_use.saturating_sub(1);
+            match connected {
+                // Closed while connecting.
+                Ok(conn) if self.is_closed() => {
+                    drop(inner);
+                    conn.conn.close();
+                    continue;
+                }
+                Ok(conn) => state.idle.push(IdleConn {
+                    conn,
+                    last_used: self.clock.now(),
+                }),
+                Err(err) => result = Err(err),
+            }
+            self.condvar.notify_all();
+        }
+        result
+    }
+
+    pub fn stats(&self, key: &PoolKey) -> Option<PoolStats> {
+        let max = self.cfg().max;
+        self.lock().get(key).map(|state| PoolStats {
+            max,
+            in_use: state.in_use,
+            idle: state.idle.len(),
+            waiters: state.waiters,
+            counters: state.counters,
+        })
+    }
+
+    fn connect(&self, key: &PoolKey) -> Result<Pooled<T>> {
+        let client_cfg = self.cfg().client_cfg.clone();
+        let result = T::connect(&key.addr, &client_cfg);
+        let mut inner = self.lock();
+        let state = inner.entry(key.clone()).or_default();
+        match result {
+            Ok(conn) => {
+                state.counters.connects += 1;
+                Ok(Pooled {
+                    conn,
+                    key: key.clone(),
+                    auth: HashMap::new(),
+                    generation: state.generation,
+                })
+            }
+            Err(err) => {
+                state.counters.connect_failures += 1;
+                Err(err)
+            }
+        }
+    }
+
+    fn cfg(&self) -> RwLockReadGuard<'_, PoolConfig> {
+        self.cfg.read().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<PoolKey, KeyState<T>>> {
+        self.inner.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+/// The idle connection holding most of `wanted`; the most recently used
+/// one among equals.
+fn pick_idle<T>(idle: &[IdleConn<T>], wanted: &[Auth]) -> Option<usize> {
+    let held = |item: &IdleConn<T>| {
+        wanted
+            .iter()
+            .filter(|auth| item.conn.is_authenticated(**auth))
+            .count()
+    };
+    idle.iter()
+        .enumerate()
+        .max_by_key(|(idx, item)| (held(item), *idx))
+        .map(|(idx, _)| idx)
+}
+
+fn prune_idle<T>(idle: &mut Vec<IdleConn<T>>, ttl: Duration, now: Instant) -> Vec<Pooled<T>> {
+    let mut expired = Vec::new();
+    let mut kept = Vec::with_capacity(idle.len());
+    for item in idle.drain(..) {
+        if now.duration_since(item.last_used) > ttl {
+            expired.push(item.conn);
+        } else {
+            kept.push(item);
+        }
+    }
+    *idle = kept;
+    expired
+}
+
+fn close_all<T: Connection>(conns: Vec<Pooled<T>>) {
+    for conn in conns {
+        conn.conn.close();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+
+    #[derive(Debug)]
+    struct FakeConn {
+        id: usize,
+        alive: bool,
+    }
+
+    impl Connection for FakeConn {
+        fn connect(addr: &str, _cfg: &ClientConfig) -> Result<Self> {
+            if addr == "down" {
+                return Err(RacError::Protocol("connection refused"));
+            }
+            Ok(FakeConn {
+                id: NEXT_ID.fetch_add(1, Ordering::SeqCst),
+                alive: true,
+            })
+        }
+
+        fn validate(&mut self) -> bool {
+            self.alive
+        }
+
+        fn close(self) {}
+    }
+
+    #[derive(Debug)]
+    struct TestClock {
+        now: Mutex<Instant>,
+    }
+
+    impl TestClock {
+        fn new(now: Instant) -> Self {
+            Self {
+                now: Mutex::new(now),
+            }
+        }
+
+        fn advance(&self, by: Duration) {
+            *self.now.lock().expect("lock") += by;
+        }
+    }
+
+    impl Clock for Arc<TestClock> {
+        fn now(&self) -> Instant {
+            *self.now.lock().expect("lock")
+        }
+    }
+
+    fn pool(max: usize) -> (Pool<FakeConn, Arc<TestClock>>, Arc<TestClock>) {
+        let clock = Arc::new(TestClock::new(Instant::now()));
+        let cfg = PoolConfig {
+            max,
+            checkout_timeout: Duration::from_millis(50),
+            ..PoolConfig::default()
+        };
+        (Pool::new(cfg, clock.clone()), clock)
+    }
+
+    #[test]
+    fn checkout_prefers_connection_authenticated_to_cluster() {
+        let (pool, _) = pool(4);
+        let key = PoolKey::new("ras:1545", Some("admin"));
+        let cluster = Auth::new(AuthScope::Cluster([7u8; 16]), Some("admin"), None);
+        let first = pool.checkout(&key, &[]).expect("first");
+        let mut second = pool.checkout(&key, &[]).expect("second");
+        second.mark_authenticated(cluster);
+        let second_id = second.id;
+        pool.release(second, true);
+        pool.release(first, true);
+
+        let conn = pool.checkout(&key, &[cluster]).expect("affinity");
+        assert_eq!(conn.id, second_id);
+        assert!(conn.is_authenticated(cluster));
+        assert_eq!(pool.stats(&key).expect("stats").counters.connects, 2);
+    }
+
+    #[test]
+    fn checkout_routes_on_scope_and_credentials() {
+        let (pool, _) = pool(4);
+        let key = PoolKey::new("ras:1545", Some("admin"));
+        let cluster = [7u8; 16];
+        let as_admin = Auth::new(AuthScope::Cluster(cluster), Some("admin"), Some("a"));
+        let as_other = Auth::new(AuthScope::Cluster(cluster), Some("admin"), Some("b"));
+        let infobases = Auth::new(AuthScope::Infobase(cluster), Some("ib"), Some("c"));
+        assert_ne!(as_admin, as_other);
+
+        let mut conns: Vec<_> = (0..3)
+            .map(|_| pool.checkout(&key, &[]).expect("checkout"))
+            .collect();
+        conns[0].mark_authenticated(as_admin);
+        conns[0].mark_authenticated(infobases);
+        conns[1].mark_authenticated(as_admin);
+        conns[2].mark_authenticated(as_other);
+        let ids: Vec<usize> = conns.iter().map(|conn| conn.id).collect();
+        for conn in conns {
+            pool.release(conn, true);
+        }
+
+        let conn = pool.checkout(&key, &[as_admin, infobases]).expect("both");
+        assert_eq!(conn.id, ids[0]);
+        let other = pool.checkout(&key, &[as_other]).expect("other");
+        assert_eq!(other.id, ids[2]);
+        assert!(!other.is_authenticated(as_admin));
+        let last = pool.checkout(&key, &[infobases]).expect("last");
+        assert_eq!(last.id, ids[1]);
+        assert!(!last.is_authenticated(infobases));
+        assert_eq!(pool.stats(&key).expect("stats").counters.connects, 3);
+    }
+
+    #[test]
+    fn identities_do_not_share_connections() {
+        let (pool, _) = pool(4);
+        let admin = PoolKey::new("ras:1545", Some("admin"));
+        let viewer = PoolKey::new("ras:1545", Some("viewer"));
+        let conn = pool.checkout(&admin, &[]).expect("admin");
+        let admin_id = conn.id;
+        pool.release(conn, true);
+        let other = pool.checkout(&viewer, &[]).expect("viewer");
+        assert_ne!(other.id, admin_id);
+        assert_eq!(pool.stats(&admin).expect("stats").idle, 1);
+    }
+
+    #[test]
+    fn checkout_times_out_when_key_is_exhausted() {
+        let (pool, _) = pool(1);
+        let key = PoolKey::new("ras:1545", None);
+        let held = pool.checkout(&key, &[]).expect("held");
+        match pool.checkout(&key, &[]) {
+            Err(RacError::PoolTimeout(waited)) => assert_eq!(waited, Duration::from_millis(50)),
+            other => panic!("unexpected {other:?}"),
+        }
+        let stats = pool.stats(&key).expect("stats");
+        assert_eq!(stats.counters.checkout_timeouts, 1);
+        assert_eq!(stats.in_use, 1);
+        assert_eq!(stats.waiters, 0);
+        pool.release(held, false);
+        assert_eq!(pool.stats(&key).expect("stats").in_use, 0);
+    }
+
+    #[test]
+    fn stale_idle_connection_is_validated_and_replaced() {
+        let (pool, clock) = pool(2);
+        let key = PoolKey::new("ras:1545", None);
+        let mut conn = pool.checkout(&key, &[]).expect("conn");
+        let dead_id = conn.id;
+        conn.alive = false;
+        pool.release(conn, true);
+
+        clock.advance(Duration::from_secs(30));
+        let fresh = pool.checkout(&key, &[]).expect("fresh");
+        assert_ne!(fresh.id, dead_id);
+        assert_eq!(
+            pool.stats(&key)
+                .expect("stats")
+                .counters
+                .validation_failures,
+            1
+        );
+    }
+
+    #[test]
+    fn expired_idle_connections_are_dropped() {
+        let (pool, clock) = pool(2);
+        let key = PoolKey::new("ras:1545", None);
+        let conn = pool.checkout(&key, &[]).expect("conn");
+        pool.release(conn, true);
+        clock.advance(Duration::from_secs(120));
+        pool.maintain().expect("maintain");
+        assert_eq!(pool.stats(&key).expect("stats").idle, 0);
+    }
+
+    #[test]
+    fn maintain_keeps_min_idle_for_registered_keys() {
+        let clock = Arc::new(TestClock::new(Instant::now()));
+        let cfg = PoolConfig {
+            max: 2,
+            min_idle: 1,
+            ..PoolConfig::default()
+        };
+        let pool: Pool<FakeConn, _> = Pool::new(cfg, clock);
+        let up = PoolKey::new("ras:1545", None);
+        let down = PoolKey::new("down", None);
+        pool.register(&up);
+        pool.register(&down);
+        assert!(pool.maintain().is_err());
+        assert_eq!(pool.stats(&up).expect("stats").idle, 1);
+        let down_stats = pool.stats(&down).expect("stats");
+        assert_eq!(down_stats.idle, 0);
+        assert_eq!(down_stats.in_use, 0);
+        assert_eq!(down_stats.counters.connect_failures, 1);
+    }
+
+    #[test]
+    fn reconfigure_keeps_connections_and_resets_authentication() {
+        let (pool, _clock) = pool(1);
+        let key = PoolKey::new("ras:1545", Some("admin"));
+        let cluster = Auth::new(AuthScope::Cluster([7u8; 16]), Some("admin"), None);
+        let mut conn = pool.checkout(&key, &[]).expect("checkout");
+        let id = conn.id;
+        conn.mark_authenticated(cluster);
+        pool.release(conn, true);
+
+        pool.reconfigure(PoolConfig {
+            max: 3,
+            ..pool.config()
+        });
+        assert_eq!(pool.stats(&key).expect("stats").max, 3);
+        pool.reset_authentication(&key);
+        let conn = pool.checkout(&key, &[cluster]).expect("checkout");
+        assert_eq!(conn.id, id);
+        assert!(!conn.is_authenticated(cluster));
+
+        // Authenticated before the reset, released after it.
+        let mut conn = conn;
+        conn.mark_authenticated(cluster);
+        pool.reset_authentication(&key);
+        pool.release(conn, true);
+        let conn = pool.checkout(&key, &[cluster]).expect("checkout");
+        assert!(!conn.is_authenticated(cluster));
+        pool.release(conn, true);
+    }
+
+    #[test]
+    fn closed_pool_closes_idle_and_released_connections() {
+        let (pool, _clock) = pool(2);
+        let key = PoolKey::new("ras:1545", None);
+        let idle = pool.checkout(&key, &[]).expect("checkout");
+        let busy = pool.checkout(&key, &[]).expect("checkout");
+        pool.release(idle, true);
+
+        pool.close();
+        assert!(matches!(
+            pool.checkout(&key, &[]),
+            Err(RacError::PoolClosed)
+        ));
+        pool.release(busy, true);
+        let stats = pool.stats(&key).expect("stats");
+        assert_eq!((stats.idle, stats.in_use), (0, 0));
+        pool.maintain().expect("maintain");
+    }
+}
@@ -0,0 +1,295 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+use crate::client::RacClient;
+use crate::commands::{
+    agent, cluster, connection, counter, infobase, limit, lock, manager, process, profile, rule,
+    server, service_setting, session,
+};
+use crate::error::{RacError, Result};
+use crate::rac_wire::parse_uuid;
+use crate::rpc::{Meta, Mutation, Request};
+use crate::Uuid16;
+
+/// Generated command tables, one per schema in `schemas/rac`.
+static TABLES: &[&[CommandSpec]] = &[
+    agent::COMMANDS,
+    cluster::COMMANDS,
+    connection::COMMANDS,
+    counter::COMMANDS,
+    infobase::COMMANDS,
+    limit::COMMANDS,
+    lock::COMMANDS,
+    manager::COMMANDS,
+    process::COMMANDS,
+    profile::COMMANDS,
+    rule::COMMANDS,
+    server::COMMANDS,
+    service_setting::COMMANDS,
+    session::COMMANDS,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgKind {
+    /// Passed as a UUID string.
+    Uuid,
+    String,
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArgSpec {
+    pub name: &'static str,
+    pub kind: ArgKind,
+}
+
+/// Encoding of a request field on the wire, from its schema type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WireType {
+    Uuid,
+    /// One length byte, then UTF-8.
+    Str8,
+    /// One length byte, or two when bit 0x40 of the first is set.
+    StrU14,
+    U8,
+    Bool,
+    U16Be,
+    U32Be,
+    U64Be,
+    Bytes(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WireField {
+    pub name: &'static str,
+    pub ty: WireType,
+}
+
+/// One generated `Request` type: `rule.list` is `RuleListRpc`, answered with
+/// `RuleListResp`. Arguments are the fields of the request struct, passed as
+/// a JSON object.
+#[derive(Debug, Clone, Copy)]
+pub struct CommandSpec {
+    pub name: &'static str,
+    pub request: &'static str,
+    pub response: &'static str,
+    pub args: &'static [ArgSpec],
+    /// Body fields in encoding order, including fixed padding.
+    pub wire: &'static [WireField],
+    pub meta: Meta,
+    pub(crate) validate: fn(&Value) -> Result<()>,
+    pub(crate) call: fn(&mut RacClient, Value) -> Result<Value>,
+}
+
+impl CommandSpec {
+    /// `session` for `session.info`.
+    pub fn object(&self) -> &'static str {
+        self.name
+            .split_once('.')
+            .map_or(self.name, |(object, _)| object)
+    }
+
+    /// `info` for `session.info`.
+    pub fn action(&self) -> &'static str {
+        self.name.split_once('.').map_or("", |(_, action)| action)
+    }
+
+    pub fn arg(&self, name: &str) -> Option<&'static ArgSpec> {
+        self.args.iter().find(|arg| arg.name == name)
+    }
+
+    /// Whether the command only reads cluster state, per the `mutation`
+    /// class of its schema.
+    pub fn is_read(&self) -> bool {
+        self.meta.mutation == Mutation::Read
+    }
+
+    /// Checks that `args` decode into the request without sending it.
+    pub fn validate(&self, args: &Value) -> Result<()> {
+        (self.validate)(args)
+    }
+
+    /// Decodes `args` into the request, sends it and returns the serialized
+    /// response. UUIDs in the response are serialized as byte arrays.
+    pub fn call(&self, client: &mut RacClient, args: Value) -> Result<Value> {
+        (self.call)(client, args)
+    }
+}
+
+pub fn commands() -> impl Iterator<Item = &'static CommandSpec> {
+    TABLES.iter().flat_map(|table| table.iter())
+}
+
+pub fn find(name: &str) -> Option<&'static CommandSpec> {
+    commands().find(|spec| spec.name == name)
+}
+
+pub(crate) fn validate<R: DeserializeOwned>(args: &Value) -> Result<()> {
+    decode_args::<R>(args.clone()).map(|_| ())
+}
+
+pub(crate) fn call<R>(client: &mut RacClient, args: Value) -> Result<Value>
+where
+    R: Request + DeserializeOwned,
+    R::Response: Serialize,
+{
+    let request = decode_args::<R>(args)?;
+    let response = client.call_typed(request)?;
+    serde_json::to_value(response).map_err(|err| RacError::DecodeMessage(err.to_string()))
+}
+
+/// Commands without arguments accept `null` or an empty object.
+fn decode_args<R: DeserializeOwned>(args: Value) -> Result<R> {
+    let args = match args {
+        Value::Object(map) if map.is_empty() => Value::Null,
+        other => other,
+    };
+    serde_json::from_value(args).map_err(|err| RacError::InvalidArgs(err.to_string()))
+}
+
+pub(crate) fn uuid_arg<'de, D>(deserializer: D) -> std::result::Result<Uuid16, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw = String::deserialize(deserializer)?;
+    parse_uuid(&raw).map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::ProtocolVersion;
+    use serde_json::json;
+    use std::collections::HashSet;
+
+    const CLUSTER: &str = "550e8400-e29b-41d4-a716-446655440000";
+
+    #[test]
+    fn command_names_are_unique_and_match_requests() {
+        let mut names = HashSet::new();
+        for spec in commands() {
+            assert!(names.insert(spec.name), "duplicate {}", spec.name);
+            assert!(spec.request.ends_with("Rpc"), "{}", spec.request);
+            assert!(!spec.object().is_empty() && !spec.action().is_empty());
+        }
+        assert!(names.len() >= 50);
+        for spec in commands() {
+            // Every argument is encoded, in the same order.
+            let wire: Vec<&str> = spec.wire.iter().map(|field| field.name).collect();
+            let args: Vec<&str> = spec.args.iter().map(|arg| arg.name).collect();
+            let encoded: Vec<&str> = wire
+                .iter()
+                .copied()
+                .filter(|name| args.contains(name))
+                .collect();
+            assert_eq!(encoded, args, "{}", spec.name);
+        }
+        let rule_list = find("rule.list").expect("rule.list");
+        assert_eq!(rule_list.request, "RuleListRpc");
+        assert_eq!(rule_list.response, "RuleListResp");
+        let args: Vec<&str> = rule_list.args.iter().map(|arg| arg.name).collect();
+        assert_eq!(args, ["cluster", "server"]);
+        assert_eq!(
+            rule_list.arg("server").map(|arg| arg.kind),
+            Some(ArgKind::Uuid)
+        );
+    }
+
+    #[test]
+    fn generated_requests_resolve_to_their_own_spec() {
+        let mut recorded = 0;
+        for spec in commands() {
+            assert_eq!(spec.meta.name, spec.name);
+            assert!(find(spec.meta.name).is_some_and(|found| std::ptr::eq(found, spec)));
+
+            let args: serde_json::Map<String, Value> = spec
+                .args
+                .iter()
+                .map(|arg| {
+                    let value = match arg.kind {
+                        ArgKind::Uuid => json!(CLUSTER),
+                        ArgKind::String => json!("x"),
+                        ArgKind::Bool => json!(false),
+                        _ => json!(0),
+                    };
+                    (arg.name.to_string(), value)
+                })
+                .collect();
+            let mut client = RacClient::dry_run(ProtocolVersion::V16_0);
+            // Reads that wait for a reply are refused before they are recorded.
+            if let Err(RacError::Unsupported(_)) = spec.call(&mut client, Value::Object(args)) {
+                continue;
+            }
+            let rpc = client.planned().last().expect(spec.name);
+            assert_eq!(rpc.name, spec.name);
+            assert_eq!(rpc.wire, spec.wire, "{}", spec.name);
+            recorded += 1;
+        }
+        assert!(recorded >= 20, "{recorded}");
+    }
+
+    #[test]
+    fn classifies_commands_by_schema_mutation() {
+        for name in [
+            "agent.version",
+            "cluster.admin_list",
+            "cluster.auth",
+            "connection.list_by_infobase",
+            "counter.accumulated_values",
+            "service_setting.get_data_dirs",
+        ] {
+            assert!(find(name).expect(name).is_read(), "{name}");
+        }
+        for (name, mutation) in [
+            ("rule.apply", Mutation::Modify),
+            ("infobase.summary_update", Mutation::Modify),
+            ("session.terminate", Mutation::Destructive),
+            ("connection.disconnect", Mutation::Destructive),
+            ("counter.clear", Mutation::Destructive),
+        ] {
+            let spec = find(name).expect(name);
+            assert_eq!(spec.meta.mutation, mutation, "{name}");
+            assert!(!spec.is_read(), "{name}");
+        }
+        for spec in commands() {
+            if matches!(spec.action(), "list" | "info") {
+                assert!(spec.is_read(), "{}", spec.name);
+            }
+            if spec.action().starts_with("remove") {
+                assert_eq!(spec.meta.mutation, Mutation::Destructive, "{}", spec.name);
+            }
+        }
+    }
+
+    #[test]
+    fn validates_args_against_request_struct() {
+        let info = find("session.info").expect("session.info");
+        info.validate(&json!({ "cluster": CLUSTER, "session": CLUSTER }))
+            .expect("valid");
+        let err = info
+            .validate(&json!({ "cluster": CLUSTER }))
+            .expect_err("missing session");
+        assert!(err.to_string().contains("session"), "{err}");
+        let err = info
+            .validate(&json!({ "cluster": "nope", "session": CLUSTER }))
+            .expect_err("bad uuid");
+        assert!(matches!(err, RacError::InvalidArgs(_)));
+
+        let version = find("agent.version").expect("agent.version");
+        version.validate(&Value::Null).expect("null");
+        version.validate(&json!({})).expect("empty object");
+        version
+            .validate(&json!({ "cluster": CLUSTER }))
+            .expect_err("no args accepted");
+
+        let values = find("counter.values").expect("counter.values");
+        values
+            .validate(&json!({ "cluster": CLUSTER, "counter": "cpu", "object": "" }))
+            .expect("string args");
+    }
+}
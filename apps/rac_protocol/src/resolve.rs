@@ -0,0 +1,258 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::client::RacClient;
+use crate::commands::{cluster_list, infobase_summary_list};
+use crate::error::{RacError, Result};
+use crate::pool::{Clock, SystemClock};
+use crate::rac_wire::{format_uuid, parse_uuid};
+use crate::Uuid16;
+
+pub const DEFAULT_TTL: Duration = Duration::from_secs(30);
+
+/// Object with the names it can be referred to by.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Named {
+    pub uuid: Uuid16,
+    pub names: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum ListKey {
+    Clusters,
+    Infobases(Uuid16),
+}
+
+#[derive(Debug)]
+struct Cached {
+    fetched: Instant,
+    entries: Arc<Vec<Named>>,
+}
+
+/// Maps cluster names (`display_name` or `host:port`) and infobase names to
+/// UUIDs. Inputs that already are UUIDs are returned without a round trip;
+/// name lists are cached for `ttl`.
+#[derive(Debug)]
+pub struct Resolver<C: Clock = SystemClock> {
+    ttl: Duration,
+    clock: C,
+    lists: Mutex<HashMap<ListKey, Cached>>,
+}
+
+impl Resolver {
+    pub fn new(ttl: Duration) -> Self {
+        Self::with_clock(ttl, SystemClock)
+    }
+}
+
+impl Default for Resolver {
+    fn default() -> Self {
+        Self::new(DEFAULT_TTL)
+    }
+}
+
+impl<C: Clock> Resolver<C> {
+    pub fn with_clock(ttl: Duration, clock: C) -> Self {
+        Self {
+            ttl,
+            clock,
+            lists: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn cluster(&self, client: &mut RacClient, input: &str) -> Result<Uuid16> {
+        if let Ok(uuid) = parse_uuid(input) {
+            return Ok(uuid);
+        }
+        self.lookup(ListKey::Clusters, "cluster", input, || {
+            Ok(cluster_list(client)?
+                .into_iter()
+                .map(|cluster| Named {
+                    uuid: cluster.uuid,
+                    names: vec![
+                        cluster.display_name,
+                        format!("{}:{}", cluster.host, cluster.port),
+                    ],
+                })
+                .collect())
+        })
+    }
+
+    /// Listing infobases may require cluster authentication; callers
+    /// authenticate before resolving names.
+    pub fn infobase(&self, client: &mut RacClient, cluster: Uuid16, input: &str) -> Result<Uuid16> {
+        if let Ok(uuid) = parse_uuid(input) {
+            return Ok(uuid);
+        }
+        self.lookup(ListKey::Infobases(cluster), "infobase", input, || {
+            Ok(infobase_summary_list(client, cluster)?
+                .summaries
+                .into_iter()
+                .map(|summary| Named {
+                    uuid: summary.infobase,
+                    names: vec![summary.name],
+                })
+                .collect())
+        })
+    }
+
+    pub fn invalidate(&self) {
+        self.lists
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .clear();
+    }
+
+    /// Resolves against the cached list and refetches once when the list is
+    /// stale or does not know the name (it may have been created since).
+    fn lookup<F>(&self, key: ListKey, kind: &'static str, input: &str, fetch: F) -> Result<Uuid16>
+    where
+        F: FnOnce() -> Result<Vec<Named>>,
+    {
+        let cached = {
+            let lists = self.lists.lock().unwrap_or_else(|err| err.into_inner());
+            lists
+                .get(&key)
+                .filter(|cached| self.clock.now().duration_since(cached.fetched) < self.ttl)
+                .map(|cached| cached.entries.clone())
+        };
+        if let Some(entries) = cached {
+            match match_name(kind, input, &entries) {
+                Err(RacError::NameNotFound { .. }) => {}
+                result => return result,
+            }
+        }
+        let entries = Arc::new(fetch()?);
+        self.lists
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .insert(
+                key,
+                Cached {
+                    fetched: self.clock.now(),
+                    entries: entries.clone(),
+                },
+            );
+        match_name(kind, input, &entries)
+    }
+}
+
+/// Exact matches win over case-insensitive ones; several objects sharing
+/// the name are reported as ambiguous.
+pub fn match_name(kind: &'static str, input: &str, entries: &[Named]) -> Result<Uuid16> {
+    let exact = matching(entries, |name| name == input);
+    let found = if exact.is_empty() {
+        matching(entries, |name| name.to_lowercase() == input.to_lowercase())
+    } else {
+        exact
+    };
+    match found.as_slice() {
+        [uuid] => Ok(*uuid),
+        [] => Err(RacError::NameNotFound {
+            kind,
+            name: input.to_string(),
+        }),
+        many => Err(RacError::AmbiguousName {
+            kind,
+            name: input.to_string(),
+            candidates: many.iter().map(format_uuid).collect(),
+        }),
+    }
+}
+
+fn matching(entries: &[Named], pred: impl Fn(&str) -> bool) -> Vec<Uuid16> {
+    let mut found: Vec<Uuid16> = Vec::new();
+    for entry in entries {
+        if entry.names.iter().any(|name| pred(name)) && !found.contains(&entry.uuid) {
+            found.push(entry.uuid);
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    struct TestClock {
+        now: Mutex<Instant>,
+    }
+
+    impl Clock for Arc<TestClock> {
+        fn now(&self) -> Instant {
+            *self.now.lock().expect("lock")
+        }
+    }
+
+    fn named(byte: u8, names: &[&str]) -> Named {
+        Named {
+            uuid: [byte; 16],
+            names: names.iter().map(|name| name.to_string()).collect(),
+        }
+    }
+
+    fn clusters() -> Vec<Named> {
+        vec![
+            named(1, &["Main", "srv1:1541"]),
+            named(2, &["Reserve", "srv2:1541"]),
+            named(3, &["reserve", "srv3:1541"]),
+        ]
+    }
+
+    #[test]
+    fn matches_names_exactly_then_ignoring_case() {
+        let entries = clusters();
+        assert_eq!(match_name("cluster", "Main", &entries).unwrap(), [1; 16]);
+        assert_eq!(match_name("cluster", "main", &entries).unwrap(), [1; 16]);
+        assert_eq!(
+            match_name("cluster", "srv2:1541", &entries).unwrap(),
+            [2; 16]
+        );
+        assert_eq!(match_name("cluster", "reserve", &entries).unwrap(), [3; 16]);
+    }
+
+    #[test]
+    fn reports_ambiguous_and_unknown_names() {
+        let entries = clusters();
+        match match_name("cluster", "RESERVE", &entries) {
+            Err(RacError::AmbiguousName {
+                kind, candidates, ..
+            }) => {
+                assert_eq!(kind, "cluster");
+                assert_eq!(candidates.len(), 2);
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+        assert!(matches!(
+            match_name("cluster", "missing", &entries),
+            Err(RacError::NameNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn caches_lists_for_ttl_and_refetches_unknown_names() {
+        let clock = Arc::new(TestClock {
+            now: Mutex::new(Instant::now()),
+        });
+        let resolver = Resolver::with_clock(Duration::from_secs(30), clock.clone());
+        let fetches = Cell::new(0);
+        let fetch = || {
+            fetches.set(fetches.get() + 1);
+            Ok(clusters())
+        };
+
+        let lookup = |name: &str| resolver.lookup(ListKey::Clusters, "cluster", name, fetch);
+        assert_eq!(lookup("Main").unwrap(), [1; 16]);
+        assert_eq!(lookup("srv2:1541").unwrap(), [2; 16]);
+        assert_eq!(fetches.get(), 1);
+
+        assert!(lookup("missing").is_err());
+        assert_eq!(fetches.get(), 2);
+
+        *clock.now.lock().expect("lock") += Duration::from_secs(31);
+        assert_eq!(lookup("Main").unwrap(), [1; 16]);
+        assert_eq!(fetches.get(), 3);
+    }
+}
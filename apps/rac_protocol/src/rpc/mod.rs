@@ -5,25 +5,40 @@ use crate::protocol::{ProtocolCodec, SerializedRpc};
 use crate::Uuid16;
 use serde::Serialize;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub struct RequiredContext {
     pub cluster: Option<Uuid16>,
     pub infobase_cluster: Option<Uuid16>,
 }
 
-impl Default for RequiredContext {
-    fn default() -> Self {
-        Self {
-            cluster: None,
-            infobase_cluster: None,
+/// What an RPC does to cluster state, from the `mutation` key of the schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Mutation {
+    /// Lists, info and authentication.
+    Read,
+    Modify,
+    /// Removes objects or ends sessions and connections.
+    Destructive,
+}
+
+impl Mutation {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Mutation::Read => "read",
+            Mutation::Modify => "modify",
+            Mutation::Destructive => "destructive",
         }
     }
 }
 
 #[derive(Debug, Clone, Copy)]
 pub struct Meta {
+    /// Registry name, e.g. `rule.list`; see [`crate::registry::find`].
+    pub name: &'static str,
     pub method_req: u8,
     pub method_resp: Option<u8>,
+    pub mutation: Mutation,
     pub requires_cluster_context: bool,
     pub requires_infobase_context: bool,
 }
@@ -84,3 +99,38 @@ impl Response for Vec<u8> {
         Ok(payload.to_vec())
     }
 }
+
+/// An RPC with an arbitrary method and body, for probing methods the registry
+/// does not describe. Its effect is unknown, so it counts as a change.
+#[derive(Debug, Clone)]
+pub struct RawRpc {
+    pub method: u8,
+    /// Reply method to wait for; without it the first reply is returned.
+    pub expect: Option<u8>,
+    /// Cluster whose context is set before the call.
+    pub cluster: Option<Uuid16>,
+    pub body: Vec<u8>,
+}
+
+impl Request for RawRpc {
+    type Response = Vec<u8>;
+
+    fn meta(&self) -> Meta {
+        Meta {
+            name: "raw",
+            method_req: self.method,
+            method_resp: self.expect,
+            mutation: Mutation::Modify,
+            requires_cluster_context: self.cluster.is_some(),
+            requires_infobase_context: false,
+        }
+    }
+
+    fn cluster(&self) -> Option<Uuid16> {
+        self.cluster
+    }
+
+    fn encode_body(&self, _codec: &dyn ProtocolCodec) -> Result<Vec<u8>> {
+        Ok(self.body.clone())
+    }
+}
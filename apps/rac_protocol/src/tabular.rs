@@ -0,0 +1,339 @@
+//! Tabular views of serialized responses: CSV, NDJSON and aligned text.
+//!
+//! A response is reduced to rows first: a wrapper with a single field
+//! (`{"records": [...]}`, `{"record": {...}}`) is unwrapped, an array gives
+//! one row per item and an object gives a single row. Nested objects are
+//! flattened into dotted columns (`license.process_id`), arrays of objects
+//! into indexed ones (`licenses.0.process_id`).
+
+use serde_json::{Map, Value};
+
+use crate::rac_wire::format_uuid;
+use crate::Uuid16;
+
+/// Column used for rows that are plain values rather than records.
+const VALUE_COLUMN: &str = "value";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Csv,
+    Ndjson,
+    Table,
+}
+
+impl Format {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Format::Json => "application/json",
+            Format::Csv => "text/csv; charset=utf-8",
+            Format::Ndjson => "application/x-ndjson",
+            Format::Table => "text/plain; charset=utf-8",
+        }
+    }
+}
+
+/// Renders `value` in `format`; JSON is pretty-printed.
+pub fn render(format: Format, value: &Value) -> String {
+    let value = normalize_uuids(value.clone());
+    match format {
+        Format::Json => serde_json::to_string_pretty(&value).unwrap_or_default(),
+        Format::Csv => to_csv(&value),
+        Format::Ndjson => to_ndjson(&value),
+        Format::Table => to_table(&value),
+    }
+}
+
+/// Records of a list response: the response itself when it is an array, or
+/// the array in a wrapper with a single field (`{"records": [...]}`).
+pub fn list_records(value: &Value) -> Option<&Vec<Value>> {
+    match value {
+        Value::Array(items) => Some(items),
+        Value::Object(map) if map.len() == 1 => map.values().next()?.as_array(),
+        _ => None,
+    }
+}
+
+pub fn list_records_mut(value: &mut Value) -> Option<&mut Vec<Value>> {
+    match value {
+        Value::Array(items) => Some(items),
+        Value::Object(map) if map.len() == 1 => map.values_mut().next()?.as_array_mut(),
+        _ => None,
+    }
+}
+
+/// Flattened rows of `value`; see the module documentation.
+pub fn rows(value: &Value) -> Vec<Map<String, Value>> {
+    if let Some(items) = list_records(value) {
+        return items.iter().map(row).collect();
+    }
+    let value = match value {
+        Value::Object(map) if map.len() == 1 => match map.values().next() {
+            Some(inner @ Value::Object(_)) => inner,
+            _ => value,
+        },
+        _ => value,
+    };
+    match value {
+        Value::Null => Vec::new(),
+        other => vec![row(other)],
+    }
+}
+
+/// Column names of `rows` in order of first appearance.
+pub fn columns(rows: &[Map<String, Value>]) -> Vec<String> {
+    let mut columns: Vec<String> = Vec::new();
+    for row in rows {
+        for key in row.keys() {
+            if !columns.contains(key) {
+                columns.push(key.clone());
+            }
+        }
+    }
+    columns
+}
+
+pub fn to_csv(value: &Value) -> String {
+    let rows = rows(value);
+    let columns = columns(&rows);
+    let mut out = String::new();
+    push_csv_line(&mut out, columns.iter().map(String::as_str));
+    for row in &rows {
+        let cells: Vec<String> = columns.iter().map(|col| cell(row.get(col))).collect();
+        push_csv_line(&mut out, cells.iter().map(String::as_str));
+    }
+    out
+}
+
+pub fn to_ndjson(value: &Value) -> String {
+    let mut out = String::new();
+    for row in rows(value) {
+        out.push_str(&Value::Object(row).to_string());
+        out.push('\n');
+    }
+    out
+}
+
+/// Columns padded to the widest cell, one line per row.
+pub fn to_table(value: &Value) -> String {
+    let rows = rows(value);
+    let columns = columns(&rows);
+    let cells: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| columns.iter().map(|col| cell(row.get(col))).collect())
+        .collect();
+    let widths: Vec<usize> = columns
+        .iter()
+        .enumerate()
+        .map(|(idx, col)| {
+            cells
+                .iter()
+                .map(|row| row[idx].chars().count())
+                .fold(col.chars().count(), usize::max)
+        })
+        .collect();
+    let mut out = String::new();
+    for line in std::iter::once(&columns).chain(cells.iter()) {
+        let mut text = String::new();
+        for (idx, value) in line.iter().enumerate() {
+            if idx > 0 {
+                text.push_str("  ");
+            }
+            text.push_str(value);
+            let pad = widths[idx] - value.chars().count();
+            text.extend(std::iter::repeat_n(' ', pad));
+        }
+        out.push_str(text.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+/// Replaces serialized `Uuid16` values (arrays of 16 bytes) with UUID strings.
+pub fn normalize_uuids(value: Value) -> Value {
+    match value {
+        Value::Array(items) => {
+            if let Some(uuid) = uuid_from_json_array(&items) {
+                Value::String(format_uuid(&uuid))
+            } else {
+                Value::Array(items.into_iter().map(normalize_uuids).collect())
+            }
+        }
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| (key, normalize_uuids(value)))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+fn uuid_from_json_array(items: &[Value]) -> Option<Uuid16> {
+    if items.len() != 16 {
+        return None;
+    }
+    let mut bytes = [0u8; 16];
+    for (idx, item) in items.iter().enumerate() {
+        let value = item.as_u64()?;
+        if value > u8::MAX as u64 {
+            return None;
+        }
+        bytes[idx] = value as u8;
+    }
+    Some(bytes)
+}
+
+fn row(value: &Value) -> Map<String, Value> {
+    let mut out = Map::new();
+    match value {
+        Value::Object(_) => flatten("", value, &mut out),
+        other => {
+            out.insert(VALUE_COLUMN.to_string(), other.clone());
+        }
+    }
+    out
+}
+
+fn flatten(prefix: &str, value: &Value, out: &mut Map<String, Value>) {
+    let key = |name: &str| {
+        if prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{prefix}.{name}")
+        }
+    };
+    match value {
+        Value::Object(map) => {
+            for (name, value) in map {
+                flatten(&key(name), value, out);
+            }
+        }
+        Value::Array(items) if items.iter().any(Value::is_object) => {
+            for (idx, item) in items.iter().enumerate() {
+                flatten(&key(&idx.to_string()), item, out);
+            }
+        }
+        other => {
+            out.insert(prefix.to_string(), other.clone());
+        }
+    }
+}
+
+/// Text of one cell: strings as is, `null` empty, scalar arrays joined by `;`.
+fn cell(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(|item| cell(Some(item)))
+            .collect::<Vec<_>>()
+            .join(";"),
+        Some(other) => other.to_string(),
+    }
+}
+
+fn push_csv_line<'a>(out: &mut String, cells: impl Iterator<Item = &'a str>) {
+    for (idx, cell) in cells.enumerate() {
+        if idx > 0 {
+            out.push(',');
+        }
+        if cell.contains([',', '"', '\n', '\r']) {
+            out.push('"');
+            out.push_str(&cell.replace('"', "\"\""));
+            out.push('"');
+        } else {
+            out.push_str(cell);
+        }
+    }
+    out.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sessions() -> Value {
+        json!({
+            "records": [
+                {
+                    "session": [0x55, 0x0e, 0x84, 0x00, 0xe2, 0x9b, 0x41, 0xd4,
+                                0xa7, 0x16, 0x44, 0x66, 0x55, 0x44, 0x00, 0x00],
+                    "user_name": "Ivanov, I.",
+                    "license": { "process_id": "1234", "license_type": 1 },
+                },
+                {
+                    "session": "550e8400-e29b-41d4-a716-446655440001",
+                    "user_name": "say \"hi\"",
+                    "license": { "process_id": "", "license_type": 0 },
+                    "tags": ["a", "b"],
+                },
+            ]
+        })
+    }
+
+    #[test]
+    fn flattens_nested_records_into_dotted_columns() {
+        let value = normalize_uuids(sessions());
+        let rows = rows(&value);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(
+            columns(&rows),
+            [
+                "license.license_type",
+                "license.process_id",
+                "session",
+                "user_name",
+                "tags"
+            ]
+        );
+        assert_eq!(rows[0]["session"], "550e8400-e29b-41d4-a716-446655440000");
+
+        assert_eq!(list_records(&json!([1])).map(Vec::len), Some(1));
+        assert_eq!(list_records(&sessions()).map(Vec::len), Some(2));
+        assert!(list_records(&json!({ "record": {} })).is_none());
+        assert!(list_records(&json!({ "a": [], "b": [] })).is_none());
+
+        let nested = json!({ "record": { "licenses": [{ "id": 1 }, { "id": 2 }] } });
+        let rows = super::rows(&nested);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0]["licenses.1.id"], 2);
+
+        assert_eq!(
+            super::rows(&json!({ "version": "8.3" }))[0]["version"],
+            "8.3"
+        );
+        assert_eq!(super::rows(&json!(["a"]))[0][VALUE_COLUMN], "a");
+    }
+
+    #[test]
+    fn renders_csv_ndjson_and_table() {
+        let csv = render(Format::Csv, &sessions());
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+        assert_eq!(
+            lines[0],
+            "license.license_type,license.process_id,session,user_name,tags"
+        );
+        assert_eq!(
+            lines[1],
+            "1,1234,550e8400-e29b-41d4-a716-446655440000,\"Ivanov, I.\","
+        );
+        assert_eq!(
+            lines[2],
+            "0,,550e8400-e29b-41d4-a716-446655440001,\"say \"\"hi\"\"\",a;b"
+        );
+
+        let ndjson = render(Format::Ndjson, &sessions());
+        let first: Value =
+            serde_json::from_str(ndjson.lines().next().expect("line")).expect("json line");
+        assert_eq!(first["license.process_id"], "1234");
+        assert_eq!(ndjson.lines().count(), 2);
+
+        let table = render(
+            Format::Table,
+            &json!([{ "a": "x", "b": 10 }, { "a": "long" }]),
+        );
+        assert_eq!(table, "a     b\nx     10\nlong\n");
+    }
+}
@@ -0,0 +1,334 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use tokio::sync::broadcast;
+
+use rac_protocol::tabular::list_records;
+use rac_protocol::Uuid16;
+
+use crate::RpcError;
+
+const CHANNEL_CAPACITY: usize = 256;
+
+/// Cluster list polled for the event stream; records are matched between
+/// snapshots by the `key` fields.
+#[derive(Debug)]
+pub struct EntitySpec {
+    pub name: &'static str,
+    pub key: &'static [&'static str],
+    /// Registry command listing the entity for one cluster.
+    pub command: &'static str,
+}
+
+pub const ENTITIES: &[EntitySpec] = &[
+    EntitySpec {
+        name: "session",
+        key: &["session"],
+        command: "session.list",
+    },
+    EntitySpec {
+        name: "connection",
+        key: &["connection"],
+        command: "connection.list",
+    },
+    EntitySpec {
+        name: "lock",
+        key: &["connection", "session", "object"],
+        command: "lock.list",
+    },
+    EntitySpec {
+        name: "process",
+        key: &["process"],
+        command: "process.list",
+    },
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldChange {
+    pub old: Value,
+    pub new: Value,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum ChangeEvent {
+    Added {
+        entity: &'static str,
+        id: String,
+        record: Value,
+    },
+    Removed {
+        entity: &'static str,
+        id: String,
+        record: Value,
+    },
+    Changed {
+        entity: &'static str,
+        id: String,
+        changes: BTreeMap<String, FieldChange>,
+        record: Value,
+    },
+    Error {
+        entity: &'static str,
+        error: RpcError,
+    },
+}
+
+impl ChangeEvent {
+    /// SSE event name, e.g. `session.added`.
+    pub fn name(&self) -> String {
+        let (entity, kind) = match self {
+            ChangeEvent::Added { entity, .. } => (entity, "added"),
+            ChangeEvent::Removed { entity, .. } => (entity, "removed"),
+            ChangeEvent::Changed { entity, .. } => (entity, "changed"),
+            ChangeEvent::Error { entity, .. } => (entity, "error"),
+        };
+        format!("{entity}.{kind}")
+    }
+}
+
+/// Records of one list response keyed by their identity.
+pub type Snapshot = BTreeMap<String, Value>;
+
+/// Builds a snapshot from a list response, either a bare array or a
+/// `{"records": [...]}` body. Records sharing a key, such as several locks
+/// of one session on the nil object, get `#2`, `#3`, ... in list order.
+pub fn snapshot(entity: &EntitySpec, list: &Value) -> Snapshot {
+    let Some(items) = list_records(list) else {
+        return Snapshot::new();
+    };
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    items
+        .iter()
+        .map(|item| {
+            let id = record_id(entity, item);
+            let count = seen.entry(id.clone()).or_default();
+            *count += 1;
+            let id = match *count {
+                1 => id,
+                n => format!("{id}#{n}"),
+            };
+            (id, item.clone())
+        })
+        .collect()
+}
+
+fn record_id(entity: &EntitySpec, record: &Value) -> String {
+    entity
+        .key
+        .iter()
+        .map(|field| match &record[*field] {
+            Value::String(value) => value.clone(),
+            other => other.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+pub fn diff_snapshots(entity: &EntitySpec, prev: &Snapshot, next: &Snapshot) -> Vec<ChangeEvent> {
+    let mut events = Vec::new();
+    for (id, record) in prev {
+        if !next.contains_key(id) {
+            events.push(ChangeEvent::Removed {
+                entity: entity.name,
+                id: id.clone(),
+                record: record.clone(),
+            });
+        }
+    }
+    for (id, record) in next {
+        match prev.get(id) {
+            None => events.push(ChangeEvent::Added {
+                entity: entity.name,
+                id: id.clone(),
+                record: record.clone(),
+            }),
+            Some(old) if old != record => events.push(ChangeEvent::Changed {
+                entity: entity.name,
+                id: id.clone(),
+                changes: changed_fields(old, record),
+                record: record.clone(),
+            }),
+            Some(_) => {}
+        }
+    }
+    events
+}
+
+fn changed_fields(old: &Value, new: &Value) -> BTreeMap<String, FieldChange> {
+    let empty = Map::new();
+    let old = old.as_object().unwrap_or(&empty);
+    let new = new.as_object().unwrap_or(&empty);
+    let mut changes = BTreeMap::new();
+    for key in old.keys().chain(new.keys()) {
+        let before = old.get(key).cloned().unwrap_or(Value::Null);
+        let after = new.get(key).cloned().unwrap_or(Value::Null);
+        if before != after {
+            changes.insert(
+                key.clone(),
+                FieldChange {
+                    old: before,
+                    new: after,
+                },
+            );
+        }
+    }
+    changes
+}
+
+pub type StreamKey = (String, Uuid16);
+
+/// Fan-out of per-cluster event streams: one poller per (agent, cluster)
+/// shared by all subscribers.
+#[derive(Default)]
+pub struct EventHub {
+    streams: Mutex<HashMap<StreamKey, broadcast::Sender<Arc<ChangeEvent>>>>,
+}
+
+impl EventHub {
+    /// Returns a receiver and, when no poller is running for `key` yet, the
+    /// sender the caller must start one with.
+    pub fn subscribe(
+        &self,
+        key: StreamKey,
+    ) -> (
+        broadcast::Receiver<Arc<ChangeEvent>>,
+        Option<broadcast::Sender<Arc<ChangeEvent>>>,
+    ) {
+        let mut streams = self.streams.lock().unwrap_or_else(|err| err.into_inner());
+        if let Some(tx) = streams.get(&key) {
+            return (tx.subscribe(), None);
+        }
+        let (tx, rx) = broadcast::channel(CHANNEL_CAPACITY);
+        streams.insert(key, tx.clone());
+        (rx, Some(tx))
+    }
+
+    /// Drops the stream once nobody listens. Returns `true` when the poller
+    /// should stop.
+    pub fn release_if_idle(
+        &self,
+        key: &StreamKey,
+        tx: &broadcast::Sender<Arc<ChangeEvent>>,
+    ) -> bool {
+        let mut streams = self.streams.lock().unwrap_or_else(|err| err.into_inner());
+        if tx.receiver_count() > 0 {
+            return false;
+        }
+        streams.remove(key);
+        true
+    }
+}
+
+pub fn event_json(event: &ChangeEvent) -> Value {
+    serde_json::to_value(event)
+        .unwrap_or_else(|err| json!({ "kind": "error", "error": err.to_string() }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entity(name: &str) -> &'static EntitySpec {
+        ENTITIES.iter().find(|e| e.name == name).expect("entity")
+    }
+
+    #[test]
+    fn diff_reports_added_removed_and_changed_fields() {
+        let sessions = entity("session");
+        let prev = snapshot(
+            sessions,
+            &json!([
+                { "session": "s1", "app_id": "1CV8C", "cpu_time_total": 10 },
+                { "session": "s2", "app_id": "Designer", "cpu_time_total": 0 },
+            ]),
+        );
+        let next = snapshot(
+            sessions,
+            &json!([
+                { "session": "s1", "app_id": "1CV8C", "cpu_time_total": 25 },
+                { "session": "s3", "app_id": "WebClient", "cpu_time_total": 0 },
+            ]),
+        );
+        let events = diff_snapshots(sessions, &prev, &next);
+        let names: Vec<String> = events.iter().map(ChangeEvent::name).collect();
+        assert_eq!(
+            names,
+            ["session.removed", "session.changed", "session.added"]
+        );
+        match &events[1] {
+            ChangeEvent::Changed { id, changes, .. } => {
+                assert_eq!(id, "s1");
+                assert_eq!(changes.len(), 1);
+                assert_eq!(
+                    changes["cpu_time_total"],
+                    FieldChange {
+                        old: json!(10),
+                        new: json!(25)
+                    }
+                );
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+        let value = event_json(&events[2]);
+        assert_eq!(value["kind"], "added");
+        assert_eq!(value["id"], "s3");
+        assert_eq!(value["record"]["app_id"], "WebClient");
+    }
+
+    #[test]
+    fn lock_identity_uses_composite_key() {
+        let locks = entity("lock");
+        let snap = snapshot(
+            locks,
+            &json!({ "records": [
+                { "connection": "c", "session": "s", "object": "o1" },
+                { "connection": "c", "session": "s", "object": "o2" },
+            ] }),
+        );
+        assert_eq!(snap.len(), 2);
+        assert!(snap.contains_key("c/s/o1"));
+    }
+
+    #[test]
+    fn locks_sharing_a_key_are_tracked_separately() {
+        let locks = entity("lock");
+        let nil = "00000000-0000-0000-0000-000000000000";
+        let lock = |descr: &str| json!({ "connection": "c", "session": "s", "object": nil, "descr": descr });
+        let prev = snapshot(locks, &json!([lock("DB")]));
+        let next = snapshot(locks, &json!([lock("DB"), lock("Config")]));
+        assert_eq!(next.len(), 2);
+
+        let events = diff_snapshots(locks, &prev, &next);
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            ChangeEvent::Added { id, record, .. } => {
+                assert_eq!(id, &format!("c/s/{nil}#2"));
+                assert_eq!(record["descr"], "Config");
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+        let names: Vec<String> = diff_snapshots(locks, &next, &prev)
+            .iter()
+            .map(ChangeEvent::name)
+            .collect();
+        assert_eq!(names, ["lock.removed"]);
+    }
+
+    #[test]
+    fn hub_shares_stream_and_releases_when_idle() {
+        let hub = EventHub::default();
+        let key = ("default".to_string(), [0u8; 16]);
+        let (rx1, tx) = hub.subscribe(key.clone());
+        let tx = tx.expect("first subscriber starts poller");
+        let (rx2, again) = hub.subscribe(key.clone());
+        assert!(again.is_none());
+        assert!(!hub.release_if_idle(&key, &tx));
+        drop(rx1);
+        drop(rx2);
+        assert!(hub.release_if_idle(&key, &tx));
+        assert!(hub.subscribe(key).1.is_some());
+    }
+}
@@ -0,0 +1,420 @@
+//! Observability of the gateway itself: request IDs, JSON access log lines
+//! and per-command latency histograms served on `/metrics/gateway`.
+//!
+//! Each HTTP request runs with a [`RequestTrace`] in a task-local; blocking
+//! workers started through [`spawn_blocking`] see the same trace, so the
+//! upstream calls they make are attributed to the request.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde_json::{json, Value};
+use tokio::task::{self, JoinHandle};
+
+use crate::metrics::escape_label;
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Longest client-supplied request ID that is kept as is.
+const MAX_REQUEST_ID_LEN: usize = 64;
+
+/// Histogram bucket bounds, seconds.
+const BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Metric families in output order: name, type and help text.
+const FAMILIES: &[(&str, &str, &str)] = &[
+    (
+        "rac_gateway_requests",
+        "counter",
+        "HTTP requests by route and status.",
+    ),
+    (
+        "rac_gateway_request_duration_seconds",
+        "histogram",
+        "HTTP request duration by route.",
+    ),
+    (
+        "rac_gateway_command_duration_seconds",
+        "histogram",
+        "Upstream RAC call duration by agent and command.",
+    ),
+    (
+        "rac_gateway_command_errors",
+        "counter",
+        "Failed upstream RAC calls by agent, command and error code.",
+    ),
+    (
+        "rac_gateway_pool_wait_seconds",
+        "histogram",
+        "Time spent waiting for a pooled connection by agent.",
+    ),
+];
+
+tokio::task_local! {
+    static TRACE: Arc<RequestTrace>;
+}
+
+/// The client's request ID when it is usable, a fresh one otherwise.
+pub fn request_id(supplied: Option<&str>) -> String {
+    match supplied {
+        Some(id)
+            if !id.is_empty()
+                && id.len() <= MAX_REQUEST_ID_LEN
+                && id.bytes().all(|b| b.is_ascii_graphic()) =>
+        {
+            id.to_string()
+        }
+        _ => new_request_id(),
+    }
+}
+
+fn new_request_id() -> String {
+    static SEQ: AtomicU64 = AtomicU64::new(0);
+    let seq = SEQ.fetch_add(1, Ordering::Relaxed);
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u64(seq);
+    format!("{:016x}", hasher.finish())
+}
+
+/// What one HTTP request did upstream.
+#[derive(Debug)]
+pub struct RequestTrace {
+    id: String,
+    data: Mutex<TraceData>,
+}
+
+#[derive(Debug, Default)]
+struct TraceData {
+    agent: Option<String>,
+    commands: Vec<String>,
+    upstream: Duration,
+    pool_wait: Duration,
+}
+
+impl RequestTrace {
+    pub fn new(id: String) -> Self {
+        Self {
+            id,
+            data: Mutex::default(),
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Notes a command run for the request, served from cache or not.
+    pub fn command(&self, agent: &str, command: &str) {
+        let mut data = self.lock();
+        data.agent.get_or_insert_with(|| agent.to_string());
+        data.commands.push(command.to_string());
+    }
+
+    pub fn upstream(&self, upstream: Duration, pool_wait: Duration) {
+        let mut data = self.lock();
+        data.upstream += upstream;
+        data.pool_wait += pool_wait;
+    }
+
+    /// One access log line.
+    pub fn log_line(&self, method: &str, route: &str, status: u16, duration: Duration) -> Value {
+        let data = self.lock();
+        let ts = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        json!({
+            "ts": ts.as_millis() as u64,
+            "request_id": self.id,
+            "method": method,
+            "route": route,
+            "status": status,
+            "duration_ms": millis(duration),
+            "agent": data.agent,
+            "commands": data.commands,
+            "upstream_ms": millis(data.upstream),
+            "pool_wait_ms": millis(data.pool_wait),
+        })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, TraceData> {
+        self.data.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    (duration.as_secs_f64() * 1_000_000.0).round() / 1_000.0
+}
+
+/// The trace of the request being served, if any.
+pub fn current() -> Option<Arc<RequestTrace>> {
+    TRACE.try_with(Arc::clone).ok()
+}
+
+/// Runs `fut` with `trace` as the current trace.
+pub async fn scope<F: std::future::Future>(trace: Arc<RequestTrace>, fut: F) -> F::Output {
+    TRACE.scope(trace, fut).await
+}
+
+/// [`tokio::task::spawn_blocking`] that carries the current trace over to
+/// the worker.
+pub fn spawn_blocking<F, R>(f: F) -> JoinHandle<R>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    match current() {
+        Some(trace) => task::spawn_blocking(move || TRACE.sync_scope(trace, f)),
+        None => task::spawn_blocking(f),
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Histogram {
+    /// Per-bucket counts, not cumulative; the last one is `+Inf`.
+    counts: Vec<u64>,
+    sum: f64,
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self {
+            counts: vec![0; BUCKETS.len() + 1],
+            sum: 0.0,
+        }
+    }
+}
+
+impl Histogram {
+    fn observe(&mut self, value: Duration) {
+        let secs = value.as_secs_f64();
+        let idx = BUCKETS
+            .iter()
+            .position(|bound| secs <= *bound)
+            .unwrap_or(BUCKETS.len());
+        self.counts[idx] += 1;
+        self.sum += secs;
+    }
+
+    fn render(&self, out: &mut String, name: &str, labels: &str) {
+        let sep = if labels.is_empty() { "" } else { "," };
+        let mut cumulative = 0;
+        for (idx, count) in self.counts.iter().enumerate() {
+            cumulative += count;
+            let le = BUCKETS
+                .get(idx)
+                .map(|bound| bound.to_string())
+                .unwrap_or_else(|| "+Inf".to_string());
+            let _ = writeln!(
+                out,
+                "{name}_bucket{{{labels}{sep}le=\"{le}\"}} {cumulative}"
+            );
+        }
+        let _ = writeln!(out, "{name}_count{{{labels}}} {cumulative}");
+        let _ = writeln!(out, "{name}_sum{{{labels}}} {}", self.sum);
+    }
+}
+
+/// Counters and histograms of the gateway, keyed by rendered label sets.
+#[derive(Default)]
+pub struct GatewayMetrics {
+    data: Mutex<MetricsData>,
+}
+
+#[derive(Default)]
+struct MetricsData {
+    counters: BTreeMap<&'static str, BTreeMap<String, u64>>,
+    histograms: BTreeMap<&'static str, BTreeMap<String, Histogram>>,
+}
+
+impl MetricsData {
+    fn count(&mut self, name: &'static str, labels: &[(&str, &str)]) {
+        *self
+            .counters
+            .entry(name)
+            .or_default()
+            .entry(render_labels(labels))
+            .or_default() += 1;
+    }
+
+    fn observe(&mut self, name: &'static str, labels: &[(&str, &str)], value: Duration) {
+        self.histograms
+            .entry(name)
+            .or_default()
+            .entry(render_labels(labels))
+            .or_default()
+            .observe(value);
+    }
+}
+
+impl GatewayMetrics {
+    pub fn observe_request(&self, route: &str, status: u16, duration: Duration) {
+        let mut data = self.lock();
+        let status = status.to_string();
+        data.count(
+            "rac_gateway_requests",
+            &[("route", route), ("status", &status)],
+        );
+        data.observe(
+            "rac_gateway_request_duration_seconds",
+            &[("route", route)],
+            duration,
+        );
+    }
+
+    /// Records one upstream call; `error` is the error code of a failed one.
+    pub fn observe_command(
+        &self,
+        agent: &str,
+        command: &str,
+        upstream: Duration,
+        pool_wait: Duration,
+        error: Option<&str>,
+    ) {
+        let mut data = self.lock();
+        data.observe(
+            "rac_gateway_pool_wait_seconds",
+            &[("agent", agent)],
+            pool_wait,
+        );
+        let labels = [("agent", agent), ("command", command)];
+        data.observe("rac_gateway_command_duration_seconds", &labels, upstream);
+        if let Some(code) = error {
+            data.count(
+                "rac_gateway_command_errors",
+                &[("agent", agent), ("command", command), ("code", code)],
+            );
+        }
+    }
+
+    /// OpenMetrics text exposition.
+    pub fn render(&self) -> String {
+        let data = self.lock();
+        let mut out = String::new();
+        for (name, kind, help) in FAMILIES {
+            let _ = writeln!(out, "# TYPE {name} {kind}");
+            let _ = writeln!(out, "# HELP {name} {help}");
+            if let Some(samples) = data.counters.get(name) {
+                for (labels, value) in samples {
+                    let _ = writeln!(out, "{name}_total{{{labels}}} {value}");
+                }
+            }
+            if let Some(samples) = data.histograms.get(name) {
+                for (labels, histogram) in samples {
+                    histogram.render(&mut out, name, labels);
+                }
+            }
+        }
+        out.push_str("# EOF\n");
+        out
+    }
+
+    fn lock(&self) -> MutexGuard<'_, MetricsData> {
+        self.data.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+fn render_labels(labels: &[(&str, &str)]) -> String {
+    let mut rendered = String::new();
+    for (idx, (key, val)) in labels.iter().enumerate() {
+        if idx > 0 {
+            rendered.push(',');
+        }
+        let _ = write!(rendered, "{key}=\"{}\"", escape_label(val));
+    }
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_id_keeps_sane_client_ids() {
+        assert_eq!(request_id(Some("abc-123")), "abc-123");
+        for bad in [Some(""), Some("has space"), Some(&"x".repeat(65)[..]), None] {
+            let id = request_id(bad);
+            assert_eq!(id.len(), 16);
+            assert!(id.bytes().all(|b| b.is_ascii_hexdigit()));
+        }
+        assert_ne!(request_id(None), request_id(None));
+    }
+
+    #[test]
+    fn trace_sums_upstream_time_into_log_line() {
+        let trace = RequestTrace::new("r1".to_string());
+        trace.command("prod", "session.list");
+        trace.upstream(Duration::from_millis(12), Duration::from_millis(3));
+        trace.command("prod", "cluster.list");
+        trace.upstream(Duration::from_millis(8), Duration::from_micros(500));
+
+        let line = trace.log_line("GET", "/clusters", 200, Duration::from_millis(25));
+        assert_eq!(line["request_id"], "r1");
+        assert_eq!(line["route"], "/clusters");
+        assert_eq!(line["agent"], "prod");
+        assert_eq!(line["commands"], json!(["session.list", "cluster.list"]));
+        assert_eq!(line["upstream_ms"], 20.0);
+        assert_eq!(line["pool_wait_ms"], 3.5);
+    }
+
+    #[tokio::test]
+    async fn blocking_workers_see_the_request_trace() {
+        let trace = Arc::new(RequestTrace::new("r2".to_string()));
+        let seen = scope(trace, async {
+            spawn_blocking(|| current().map(|trace| trace.id().to_string()))
+                .await
+                .expect("worker")
+        })
+        .await;
+        assert_eq!(seen.as_deref(), Some("r2"));
+        let outside = spawn_blocking(|| current().is_none())
+            .await
+            .expect("worker");
+        assert!(outside);
+    }
+
+    #[test]
+    fn renders_histograms_and_counters() {
+        let metrics = GatewayMetrics::default();
+        metrics.observe_request("/clusters", 200, Duration::from_millis(30));
+        metrics.observe_command(
+            "prod",
+            "cluster.list",
+            Duration::from_millis(20),
+            Duration::from_millis(1),
+            None,
+        );
+        metrics.observe_command(
+            "prod",
+            "cluster.list",
+            Duration::from_secs(20),
+            Duration::ZERO,
+            Some("rac_error"),
+        );
+
+        let text = metrics.render();
+        assert!(text.contains("# TYPE rac_gateway_command_duration_seconds histogram\n"));
+        assert!(text.contains(
+            "rac_gateway_command_duration_seconds_bucket{agent=\"prod\",command=\"cluster.list\",le=\"0.025\"} 1\n"
+        ));
+        assert!(text.contains(
+            "rac_gateway_command_duration_seconds_bucket{agent=\"prod\",command=\"cluster.list\",le=\"+Inf\"} 2\n"
+        ));
+        assert!(text.contains(
+            "rac_gateway_command_duration_seconds_count{agent=\"prod\",command=\"cluster.list\"} 2\n"
+        ));
+        assert!(text.contains(
+            "rac_gateway_command_errors_total{agent=\"prod\",command=\"cluster.list\",code=\"rac_error\"} 1\n"
+        ));
+        assert!(text.contains("rac_gateway_requests_total{route=\"/clusters\",status=\"200\"} 1\n"));
+        assert!(
+            text.contains("rac_gateway_pool_wait_seconds_bucket{agent=\"prod\",le=\"0.005\"} 2\n")
+        );
+        assert!(text.ends_with("# EOF\n"));
+    }
+}
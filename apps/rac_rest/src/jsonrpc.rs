@@ -0,0 +1,239 @@
+use serde_json::{json, Map, Value};
+
+use crate::{command_spec, RpcError, RpcMeta, RpcRequest, RpcResponse};
+
+pub const VERSION: &str = "2.0";
+
+/// Lists every command with its parameters (OpenRPC document).
+pub const DISCOVER_METHOD: &str = "rpc.discover";
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+pub const INTERNAL_ERROR: i64 = -32603;
+
+/// One call of a `/rpc` body: either the original `{command, args}` object
+/// or a JSON-RPC 2.0 request. Both formats may be mixed in a batch.
+#[derive(Debug)]
+pub enum Call {
+    Legacy(RpcRequest),
+    JsonRpc {
+        /// `None` for notifications, which get no response.
+        id: Option<Value>,
+        req: RpcRequest,
+    },
+}
+
+impl Call {
+    pub fn request(&self) -> &RpcRequest {
+        match self {
+            Call::Legacy(req) | Call::JsonRpc { req, .. } => req,
+        }
+    }
+
+    /// Response in the format of the call; `None` for notifications.
+    pub fn respond(&self, result: Result<Value, RpcError>, agent: &str) -> Option<Value> {
+        match self {
+            Call::Legacy(req) => {
+                let resp = match result {
+                    Ok(value) => RpcResponse {
+                        result: Some(value),
+                        error: None,
+                        meta: Some(RpcMeta {
+                            command: req.command.clone(),
+                            agent: agent.to_string(),
+                        }),
+                    },
+                    Err(err) => RpcResponse::from(err),
+                };
+                Some(serde_json::to_value(resp).unwrap_or(Value::Null))
+            }
+            Call::JsonRpc { id: None, .. } => None,
+            Call::JsonRpc { id: Some(id), .. } => Some(match result {
+                Ok(value) => json!({ "jsonrpc": VERSION, "id": id, "result": value }),
+                Err(err) => error_response(id.clone(), &err),
+            }),
+        }
+    }
+}
+
+/// Parses one element of a `/rpc` body. Invalid elements yield the error
+/// response to send in their place.
+pub fn parse_call(value: Value) -> Result<Call, Value> {
+    let Value::Object(mut map) = value else {
+        return Err(invalid_request(Value::Null, "request must be an object"));
+    };
+    if !map.contains_key("jsonrpc") {
+        return serde_json::from_value(Value::Object(map))
+            .map(Call::Legacy)
+            .map_err(|err| {
+                let err = RpcError::new("bad_request", format!("invalid request: {err}"));
+                serde_json::to_value(RpcResponse::from(err)).unwrap_or(Value::Null)
+            });
+    }
+
+    let id = map.remove("id");
+    let response_id = id.clone().unwrap_or(Value::Null);
+    if map.get("jsonrpc").and_then(Value::as_str) != Some(VERSION) {
+        return Err(invalid_request(response_id, "jsonrpc must be \"2.0\""));
+    }
+    if !matches!(
+        id,
+        None | Some(Value::Null | Value::String(_) | Value::Number(_))
+    ) {
+        return Err(invalid_request(
+            Value::Null,
+            "id must be a string or number",
+        ));
+    }
+    let Some(Value::String(method)) = map.remove("method") else {
+        return Err(invalid_request(response_id, "method must be a string"));
+    };
+    let args = match map.remove("params") {
+        None | Some(Value::Null) => None,
+        Some(Value::Object(params)) => Some(Value::Object(params)),
+        Some(Value::Array(params)) if params.is_empty() => None,
+        Some(_) => {
+            return Err(error_object(
+                response_id,
+                INVALID_PARAMS,
+                "params must be an object with named arguments",
+                None,
+            ))
+        }
+    };
+    let agent = match map.remove("agent") {
+        None | Some(Value::Null) => None,
+        Some(Value::String(agent)) => Some(agent),
+        Some(_) => return Err(invalid_request(response_id, "agent must be a string")),
+    };
+    if method != DISCOVER_METHOD && command_spec(&method).is_none() {
+        return Err(error_object(
+            response_id,
+            METHOD_NOT_FOUND,
+            &format!("unknown method: {method}"),
+            None,
+        ));
+    }
+    Ok(Call::JsonRpc {
+        id,
+        req: RpcRequest {
+            command: method,
+            args,
+            agent,
+        },
+    })
+}
+
+/// JSON-RPC error code for an `RpcError` code; gateway errors use the
+/// implementation-defined server error range.
+pub fn error_code(code: &str) -> i64 {
+    match code {
+        "bad_request" => INVALID_PARAMS,
+        "rac_error" => -32000,
+        "service_unavailable" => -32001,
+        "not_found" => -32002,
+        "ambiguous_name" => -32003,
+        "read_only" => -32004,
+        "forbidden" => -32005,
+        _ => INTERNAL_ERROR,
+    }
+}
+
+pub fn error_response(id: Value, err: &RpcError) -> Value {
+    let mut data = Map::new();
+    data.insert("code".to_string(), Value::String(err.code.clone()));
+    if let Some(details) = &err.details {
+        data.insert("details".to_string(), details.clone());
+    }
+    error_object(
+        id,
+        error_code(&err.code),
+        &err.message,
+        Some(Value::Object(data)),
+    )
+}
+
+pub fn parse_error(message: &str) -> Value {
+    error_object(Value::Null, PARSE_ERROR, message, None)
+}
+
+pub fn invalid_request(id: Value, message: &str) -> Value {
+    error_object(id, INVALID_REQUEST, message, None)
+}
+
+fn error_object(id: Value, code: i64, message: &str, data: Option<Value>) -> Value {
+    let mut error = json!({ "code": code, "message": message });
+    if let Some(data) = data {
+        error["data"] = data;
+    }
+    json!({ "jsonrpc": VERSION, "id": id, "error": error })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_both_call_formats() {
+        let call = parse_call(json!({ "command": "cluster.list" })).expect("legacy");
+        assert!(matches!(call, Call::Legacy(_)));
+        let resp = call
+            .respond(Ok(json!([])), "default")
+            .expect("legacy always responds");
+        assert_eq!(resp["meta"]["agent"], "default");
+
+        let call = parse_call(json!({
+            "jsonrpc": "2.0",
+            "id": 7,
+            "method": "session.list",
+            "params": { "cluster": "Main" },
+            "agent": "prod",
+        }))
+        .expect("jsonrpc");
+        assert_eq!(call.request().command, "session.list");
+        assert_eq!(call.request().agent.as_deref(), Some("prod"));
+        let resp = call
+            .respond(Ok(json!({ "records": [] })), "prod")
+            .expect("response");
+        assert_eq!(
+            resp,
+            json!({ "jsonrpc": "2.0", "id": 7, "result": { "records": [] } })
+        );
+    }
+
+    #[test]
+    fn notifications_get_no_response() {
+        let call = parse_call(json!({ "jsonrpc": "2.0", "method": "agent.version" }))
+            .expect("notification");
+        assert!(call.respond(Ok(Value::Null), "default").is_none());
+    }
+
+    #[test]
+    fn invalid_calls_map_to_spec_errors() {
+        let resp = parse_call(json!({ "jsonrpc": "2.0", "id": "a", "method": "nope" }))
+            .expect_err("unknown method");
+        assert_eq!(resp["error"]["code"], METHOD_NOT_FOUND);
+        assert_eq!(resp["id"], "a");
+
+        let resp = parse_call(
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "cluster.info", "params": [1] }),
+        )
+        .expect_err("positional params");
+        assert_eq!(resp["error"]["code"], INVALID_PARAMS);
+
+        let resp = parse_call(json!(1)).expect_err("not an object");
+        assert_eq!(resp["error"]["code"], INVALID_REQUEST);
+
+        let err = RpcError::with_details(
+            "ambiguous_name",
+            "ambiguous".to_string(),
+            json!({ "candidates": ["a", "b"] }),
+        );
+        let resp = error_response(json!(3), &err);
+        assert_eq!(resp["error"]["code"], -32003);
+        assert_eq!(resp["error"]["data"]["code"], "ambiguous_name");
+        assert_eq!(resp["error"]["data"]["details"]["candidates"][1], "b");
+    }
+}
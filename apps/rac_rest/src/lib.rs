@@ -1,5 +1,8 @@
+use std::collections::HashMap;
 use std::fs;
-use std::sync::{Condvar, Mutex};
+use std::hash::{Hash, Hasher};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, OnceLock};
 use std::time::{Duration, Instant};
 
 use serde::{Deserialize, Serialize};
@@ -7,16 +10,25 @@ use serde_json::{json, Value};
 use std::fmt;
 
 use rac_protocol::client::{ClientConfig, RacClient};
-use rac_protocol::commands::{
-    agent_version, cluster_info, cluster_list, connection_info, connection_list, counter_info,
-    counter_list, infobase_info, infobase_summary_info, infobase_summary_list, limit_info,
-    limit_list, lock_list, manager_info, manager_list, process_info, process_list, profile_list,
-    server_info, server_list, session_info, session_list,
-};
 use rac_protocol::error::RacError;
+use rac_protocol::pool::{Auth, AuthScope, PoolConfig, PoolKey, Pooled};
 use rac_protocol::rac_wire::{format_uuid, parse_uuid};
+use rac_protocol::registry::{self, ArgKind, ArgSpec, CommandSpec};
+use rac_protocol::resolve::Resolver;
+use rac_protocol::tabular::{self, Format};
 use rac_protocol::Uuid16;
 
+pub use rac_protocol::pool::{Clock, SystemClock};
+
+use crate::openapi::FieldKind;
+
+pub mod events;
+pub mod gateway;
+pub mod jsonrpc;
+pub mod metrics;
+pub mod openapi;
+pub mod query;
+
 const DEFAULT_LISTEN_ADDR: &str = "127.0.0.1:8080";
 const DEFAULT_RAC_ADDR: &str = "127.0.0.1:1545";
 const DEFAULT_CONNECT_TIMEOUT_MS: u64 = 5_000;
@@ -24,6 +36,13 @@ const DEFAULT_READ_TIMEOUT_MS: u64 = 5_000;
 const DEFAULT_WRITE_TIMEOUT_MS: u64 = 5_000;
 const DEFAULT_POOL_MAX: usize = 4;
 const DEFAULT_IDLE_TTL_SECS: u64 = 60;
+const DEFAULT_CHECKOUT_TIMEOUT_MS: u64 = 5_000;
+const DEFAULT_VALIDATE_AFTER_MS: u64 = 10_000;
+const DEFAULT_MIN_IDLE: usize = 0;
+const DEFAULT_AGENT_NAME: &str = "default";
+const DEFAULT_EVENTS_POLL_MS: u64 = 2_000;
+const DEFAULT_METRICS_SCRAPE_INTERVAL_SECS: u64 = 30;
+const DEFAULT_SHUTDOWN_TIMEOUT_MS: u64 = 10_000;
 
 #[derive(Debug, Clone)]
 pub struct Config {
@@ -34,6 +53,19 @@ pub struct Config {
     pub write_timeout_ms: u64,
     pub pool_max: usize,
     pub idle_ttl_secs: u64,
+    pub checkout_timeout_ms: u64,
+    pub validate_after_ms: u64,
+    pub min_idle: usize,
+    pub agents: Vec<AgentConfig>,
+    pub cache: CacheConfig,
+    pub events_poll_ms: u64,
+    pub metrics: Option<MetricsConfig>,
+    /// How long shutdown waits for in-flight requests.
+    pub shutdown_timeout_ms: u64,
+    /// Interval of config file change checks; `0` reloads on SIGHUP only.
+    pub config_watch_ms: u64,
+    /// Logs RAC frames to stderr, tagged with the HTTP request ID.
+    pub debug_raw: bool,
 }
 
 impl Default for Config {
@@ -46,10 +78,115 @@ impl Default for Config {
             write_timeout_ms: DEFAULT_WRITE_TIMEOUT_MS,
             pool_max: DEFAULT_POOL_MAX,
             idle_ttl_secs: DEFAULT_IDLE_TTL_SECS,
+            checkout_timeout_ms: DEFAULT_CHECKOUT_TIMEOUT_MS,
+            validate_after_ms: DEFAULT_VALIDATE_AFTER_MS,
+            min_idle: DEFAULT_MIN_IDLE,
+            agents: Vec::new(),
+            cache: CacheConfig::default(),
+            events_poll_ms: DEFAULT_EVENTS_POLL_MS,
+            metrics: None,
+            shutdown_timeout_ms: DEFAULT_SHUTDOWN_TIMEOUT_MS,
+            config_watch_ms: 0,
+            debug_raw: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AgentConfig {
+    pub name: String,
+    pub rac_addr: String,
+    pub cluster_user: Option<String>,
+    pub cluster_pwd: Option<String>,
+    pub connect_timeout_ms: u64,
+    pub read_timeout_ms: u64,
+    pub write_timeout_ms: u64,
+    pub pool_max: usize,
+    pub idle_ttl_secs: u64,
+    pub checkout_timeout_ms: u64,
+    pub validate_after_ms: u64,
+    pub min_idle: usize,
+    pub debug_raw: bool,
+}
+
+impl AgentConfig {
+    pub fn pool_config(&self) -> PoolConfig {
+        let client_cfg = ClientConfig {
+            connect_timeout: Duration::from_millis(self.connect_timeout_ms),
+            read_timeout: Duration::from_millis(self.read_timeout_ms),
+            write_timeout: Duration::from_millis(self.write_timeout_ms),
+            debug_raw: self.debug_raw,
+            read_only: true,
+            ..ClientConfig::default()
+        };
+        PoolConfig {
+            client_cfg,
+            max: self.pool_max,
+            idle_ttl: Duration::from_secs(self.idle_ttl_secs),
+            checkout_timeout: Duration::from_millis(self.checkout_timeout_ms),
+            validate_after: Duration::from_millis(self.validate_after_ms),
+            min_idle: self.min_idle.min(self.pool_max),
+        }
+    }
+
+    /// Key of this agent's connections inside its own pool; each agent has
+    /// a separate pool. A reload keeps the pool only while the key stays the
+    /// same (see [`pool_reload`]).
+    pub fn pool_key(&self) -> PoolKey {
+        PoolKey::new(&self.rac_addr, self.cluster_user.as_deref())
+    }
+}
+
+/// What a config reload does with the connection pool of an agent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PoolReload {
+    /// New agent, or its address or user changed: open a new pool.
+    Open,
+    /// Keep the pool and its connections, apply the new settings.
+    Keep,
+    /// As `Keep`, but connections authenticate to clusters again because
+    /// the password changed.
+    KeepReauthenticate,
+}
+
+pub fn pool_reload(old: Option<&AgentConfig>, new: &AgentConfig) -> PoolReload {
+    match old {
+        Some(old) if old.pool_key() == new.pool_key() => {
+            if old.cluster_pwd == new.cluster_pwd {
+                PoolReload::Keep
+            } else {
+                PoolReload::KeepReauthenticate
+            }
         }
+        _ => PoolReload::Open,
+    }
+}
+
+/// Response cache TTLs; `0` disables caching (concurrent identical
+/// requests are still coalesced).
+#[derive(Debug, Clone, Default)]
+pub struct CacheConfig {
+    pub default_ttl_ms: u64,
+    pub ttl_ms: HashMap<String, u64>,
+}
+
+impl CacheConfig {
+    pub fn ttl(&self, cmd: &Command) -> Duration {
+        let ms = self
+            .ttl_ms
+            .get(cmd.name())
+            .copied()
+            .unwrap_or(self.default_ttl_ms);
+        Duration::from_millis(ms)
     }
 }
 
+/// Background scrape for `/metrics`; enabled by the `[metrics]` section.
+#[derive(Debug, Clone)]
+pub struct MetricsConfig {
+    pub scrape_interval_secs: u64,
+}
+
 #[derive(Debug, Deserialize)]
 struct ConfigFile {
     listen_addr: Option<String>,
@@ -59,12 +196,54 @@ struct ConfigFile {
     write_timeout_ms: Option<u64>,
     pool_max: Option<usize>,
     idle_ttl_secs: Option<u64>,
+    checkout_timeout_ms: Option<u64>,
+    validate_after_ms: Option<u64>,
+    min_idle: Option<usize>,
+    agents: Option<Vec<AgentConfigFile>>,
+    cache: Option<CacheConfigFile>,
+    events_poll_ms: Option<u64>,
+    metrics: Option<MetricsConfigFile>,
+    shutdown_timeout_ms: Option<u64>,
+    config_watch_ms: Option<u64>,
+    debug_raw: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetricsConfigFile {
+    scrape_interval_secs: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CacheConfigFile {
+    default_ttl_ms: Option<u64>,
+    ttl_ms: Option<HashMap<String, u64>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AgentConfigFile {
+    name: String,
+    rac_addr: String,
+    cluster_user: Option<String>,
+    cluster_pwd: Option<String>,
+    connect_timeout_ms: Option<u64>,
+    read_timeout_ms: Option<u64>,
+    write_timeout_ms: Option<u64>,
+    pool_max: Option<usize>,
+    idle_ttl_secs: Option<u64>,
+    checkout_timeout_ms: Option<u64>,
+    validate_after_ms: Option<u64>,
+    min_idle: Option<usize>,
+    debug_raw: Option<bool>,
 }
 
 pub fn load_config(path: &str) -> Result<Config, RpcError> {
     let raw = fs::read_to_string(path)
         .map_err(|err| RpcError::new("config_error", format!("config read failed: {err}")))?;
-    let file: ConfigFile = toml::from_str(&raw)
+    parse_config(&raw)
+}
+
+pub fn parse_config(raw: &str) -> Result<Config, RpcError> {
+    let file: ConfigFile = toml::from_str(raw)
         .map_err(|err| RpcError::new("config_error", format!("config parse failed: {err}")))?;
     let mut cfg = Config::default();
     if let Some(value) = file.listen_addr {
@@ -88,6 +267,99 @@ pub fn load_config(path: &str) -> Result<Config, RpcError> {
     if let Some(value) = file.idle_ttl_secs {
         cfg.idle_ttl_secs = value;
     }
+    if let Some(value) = file.checkout_timeout_ms {
+        cfg.checkout_timeout_ms = value;
+    }
+    if let Some(value) = file.validate_after_ms {
+        cfg.validate_after_ms = value;
+    }
+    if let Some(value) = file.min_idle {
+        cfg.min_idle = value;
+    }
+    if let Some(value) = file.events_poll_ms {
+        cfg.events_poll_ms = value.max(1);
+    }
+    if let Some(value) = file.shutdown_timeout_ms {
+        cfg.shutdown_timeout_ms = value;
+    }
+    if let Some(value) = file.config_watch_ms {
+        cfg.config_watch_ms = value;
+    }
+    if let Some(value) = file.debug_raw {
+        cfg.debug_raw = value;
+    }
+    if let Some(metrics) = file.metrics {
+        cfg.metrics = Some(MetricsConfig {
+            scrape_interval_secs: metrics
+                .scrape_interval_secs
+                .unwrap_or(DEFAULT_METRICS_SCRAPE_INTERVAL_SECS)
+                .max(1),
+        });
+    }
+    if let Some(cache) = file.cache {
+        if let Some(value) = cache.default_ttl_ms {
+            cfg.cache.default_ttl_ms = value;
+        }
+        for (command, ttl) in cache.ttl_ms.unwrap_or_default() {
+            if command_spec(&command).is_none() {
+                return Err(RpcError::new(
+                    "config_error",
+                    format!("unknown command in cache.ttl_ms: {command}"),
+                ));
+            }
+            cfg.cache.ttl_ms.insert(command, ttl);
+        }
+    }
+    match file.agents {
+        Some(agents) if !agents.is_empty() => {
+            for agent in agents {
+                if agent.name.is_empty() || agent.name.contains('/') {
+                    return Err(RpcError::new(
+                        "config_error",
+                        format!("invalid agent name: {:?}", agent.name),
+                    ));
+                }
+                if cfg.agents.iter().any(|known| known.name == agent.name) {
+                    return Err(RpcError::new(
+                        "config_error",
+                        format!("duplicate agent name: {}", agent.name),
+                    ));
+                }
+                cfg.agents.push(AgentConfig {
+                    name: agent.name,
+                    rac_addr: agent.rac_addr,
+                    cluster_user: agent.cluster_user,
+                    cluster_pwd: agent.cluster_pwd,
+                    connect_timeout_ms: agent.connect_timeout_ms.unwrap_or(cfg.connect_timeout_ms),
+                    read_timeout_ms: agent.read_timeout_ms.unwrap_or(cfg.read_timeout_ms),
+                    write_timeout_ms: agent.write_timeout_ms.unwrap_or(cfg.write_timeout_ms),
+                    pool_max: agent.pool_max.unwrap_or(cfg.pool_max),
+                    idle_ttl_secs: agent.idle_ttl_secs.unwrap_or(cfg.idle_ttl_secs),
+                    checkout_timeout_ms: agent
+                        .checkout_timeout_ms
+                        .unwrap_or(cfg.checkout_timeout_ms),
+                    validate_after_ms: agent.validate_after_ms.unwrap_or(cfg.validate_after_ms),
+                    min_idle: agent.min_idle.unwrap_or(cfg.min_idle),
+                    debug_raw: agent.debug_raw.unwrap_or(cfg.debug_raw),
+                });
+            }
+        }
+        _ => cfg.agents.push(AgentConfig {
+            name: DEFAULT_AGENT_NAME.to_string(),
+            rac_addr: cfg.rac_addr.clone(),
+            cluster_user: None,
+            cluster_pwd: None,
+            connect_timeout_ms: cfg.connect_timeout_ms,
+            read_timeout_ms: cfg.read_timeout_ms,
+            write_timeout_ms: cfg.write_timeout_ms,
+            pool_max: cfg.pool_max,
+            idle_ttl_secs: cfg.idle_ttl_secs,
+            checkout_timeout_ms: cfg.checkout_timeout_ms,
+            validate_after_ms: cfg.validate_after_ms,
+            min_idle: cfg.min_idle,
+            debug_raw: cfg.debug_raw,
+        }),
+    }
     Ok(cfg)
 }
 
@@ -95,11 +367,14 @@ pub fn load_config(path: &str) -> Result<Config, RpcError> {
 pub struct RpcRequest {
     pub command: String,
     pub args: Option<Value>,
+    #[serde(default)]
+    pub agent: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct RpcMeta {
     pub command: String,
+    pub agent: String,
 }
 
 #[derive(Debug, Serialize)]
@@ -146,545 +421,587 @@ impl fmt::Display for RpcError {
 
 impl std::error::Error for RpcError {}
 
-#[derive(Debug)]
-pub enum Command {
-    AgentVersion,
-    ClusterList,
-    ClusterInfo { cluster: Uuid16 },
-    ManagerList { cluster: Uuid16 },
-    ManagerInfo { cluster: Uuid16, manager: Uuid16 },
-    ServerList { cluster: Uuid16 },
-    ServerInfo { cluster: Uuid16, server: Uuid16 },
-    ProcessList { cluster: Uuid16 },
-    ProcessInfo { cluster: Uuid16, process: Uuid16 },
-    InfobaseSummaryList { cluster: Uuid16 },
-    InfobaseSummaryInfo { cluster: Uuid16, infobase: Uuid16 },
-    InfobaseInfo { cluster: Uuid16, infobase: Uuid16 },
-    ConnectionList { cluster: Uuid16 },
-    ConnectionInfo { cluster: Uuid16, connection: Uuid16 },
-    SessionList { cluster: Uuid16 },
-    SessionInfo { cluster: Uuid16, session: Uuid16 },
-    LockList { cluster: Uuid16 },
-    ProfileList { cluster: Uuid16 },
-    CounterList { cluster: Uuid16 },
-    CounterInfo { cluster: Uuid16, counter: String },
-    LimitList { cluster: Uuid16 },
-    LimitInfo { cluster: Uuid16, limit: String },
+/// Commands that keep the response shape published before they came from the
+/// registry: the record (list) itself instead of the generated wrapper.
+const UNWRAPPED: &[(&str, &str)] = &[("cluster.list", "clusters"), ("cluster.info", "cluster")];
+
+/// `(command, old, new)`: arguments still accepted under their former name.
+const ARG_ALIASES: &[(&str, &str, &str)] = &[("limit.info", "limit", "name")];
+
+/// Parent objects that routes nest under, e.g.
+/// `/clusters/{cluster}/servers/{server}/rules`.
+const ROUTE_PARENTS: &[&str] = &["cluster", "server", "infobase"];
+
+/// A registry command with validated arguments.
+#[derive(Debug, Clone)]
+pub struct Command {
+    spec: &'static CommandSpec,
+    args: Value,
+    /// Serialized `args`; identifies the command in the response cache.
+    key: String,
 }
 
-pub fn parse_command(req: RpcRequest) -> Result<Command, RpcError> {
-    match req.command.as_str() {
-        "agent.version" => ensure_no_args(req.args).map(|_| Command::AgentVersion),
-        "cluster.list" => ensure_no_args(req.args).map(|_| Command::ClusterList),
-        "cluster.info" => {
-            let args = parse_args::<ClusterArg>(req.args)?;
-            Ok(Command::ClusterInfo {
-                cluster: parse_uuid_arg(&args.cluster)?,
-            })
-        }
-        "manager.list" => {
-            let args = parse_args::<ClusterArg>(req.args)?;
-            Ok(Command::ManagerList {
-                cluster: parse_uuid_arg(&args.cluster)?,
-            })
-        }
-        "manager.info" => {
-            let args = parse_args::<ClusterManagerArg>(req.args)?;
-            Ok(Command::ManagerInfo {
-                cluster: parse_uuid_arg(&args.cluster)?,
-                manager: parse_uuid_arg(&args.manager)?,
-            })
-        }
-        "server.list" => {
-            let args = parse_args::<ClusterArg>(req.args)?;
-            Ok(Command::ServerList {
-                cluster: parse_uuid_arg(&args.cluster)?,
-            })
-        }
-        "server.info" => {
-            let args = parse_args::<ClusterServerArg>(req.args)?;
-            Ok(Command::ServerInfo {
-                cluster: parse_uuid_arg(&args.cluster)?,
-                server: parse_uuid_arg(&args.server)?,
-            })
-        }
-        "process.list" => {
-            let args = parse_args::<ClusterArg>(req.args)?;
-            Ok(Command::ProcessList {
-                cluster: parse_uuid_arg(&args.cluster)?,
-            })
-        }
-        "process.info" => {
-            let args = parse_args::<ClusterProcessArg>(req.args)?;
-            Ok(Command::ProcessInfo {
-                cluster: parse_uuid_arg(&args.cluster)?,
-                process: parse_uuid_arg(&args.process)?,
-            })
-        }
-        "infobase.summary_list" => {
-            let args = parse_args::<ClusterArg>(req.args)?;
-            Ok(Command::InfobaseSummaryList {
-                cluster: parse_uuid_arg(&args.cluster)?,
-            })
-        }
-        "infobase.summary_info" => {
-            let args = parse_args::<ClusterInfobaseArg>(req.args)?;
-            Ok(Command::InfobaseSummaryInfo {
-                cluster: parse_uuid_arg(&args.cluster)?,
-                infobase: parse_uuid_arg(&args.infobase)?,
-            })
-        }
-        "infobase.info" => {
-            let args = parse_args::<ClusterInfobaseArg>(req.args)?;
-            Ok(Command::InfobaseInfo {
-                cluster: parse_uuid_arg(&args.cluster)?,
-                infobase: parse_uuid_arg(&args.infobase)?,
-            })
-        }
-        "connection.list" => {
-            let args = parse_args::<ClusterArg>(req.args)?;
-            Ok(Command::ConnectionList {
-                cluster: parse_uuid_arg(&args.cluster)?,
-            })
-        }
-        "connection.info" => {
-            let args = parse_args::<ClusterConnectionArg>(req.args)?;
-            Ok(Command::ConnectionInfo {
-                cluster: parse_uuid_arg(&args.cluster)?,
-                connection: parse_uuid_arg(&args.connection)?,
-            })
-        }
-        "session.list" => {
-            let args = parse_args::<ClusterArg>(req.args)?;
-            Ok(Command::SessionList {
-                cluster: parse_uuid_arg(&args.cluster)?,
-            })
-        }
-        "session.info" => {
-            let args = parse_args::<ClusterSessionArg>(req.args)?;
-            Ok(Command::SessionInfo {
-                cluster: parse_uuid_arg(&args.cluster)?,
-                session: parse_uuid_arg(&args.session)?,
-            })
-        }
-        "lock.list" => {
-            let args = parse_args::<ClusterArg>(req.args)?;
-            Ok(Command::LockList {
-                cluster: parse_uuid_arg(&args.cluster)?,
-            })
-        }
-        "profile.list" => {
-            let args = parse_args::<ClusterArg>(req.args)?;
-            Ok(Command::ProfileList {
-                cluster: parse_uuid_arg(&args.cluster)?,
-            })
-        }
-        "counter.list" => {
-            let args = parse_args::<ClusterArg>(req.args)?;
-            Ok(Command::CounterList {
-                cluster: parse_uuid_arg(&args.cluster)?,
-            })
-        }
-        "counter.info" => {
-            let args = parse_args::<ClusterCounterArg>(req.args)?;
-            Ok(Command::CounterInfo {
-                cluster: parse_uuid_arg(&args.cluster)?,
-                counter: args.counter,
-            })
-        }
-        "limit.list" => {
-            let args = parse_args::<ClusterArg>(req.args)?;
-            Ok(Command::LimitList {
-                cluster: parse_uuid_arg(&args.cluster)?,
-            })
-        }
-        "limit.info" => {
-            let args = parse_args::<ClusterLimitArg>(req.args)?;
-            Ok(Command::LimitInfo {
-                cluster: parse_uuid_arg(&args.cluster)?,
-                limit: args.limit,
-            })
-        }
-        other => Err(RpcError::new(
-            "bad_request",
-            format!("unknown command: {other}"),
-        )),
+impl PartialEq for Command {
+    fn eq(&self, other: &Self) -> bool {
+        self.spec.name == other.spec.name && self.key == other.key
     }
 }
 
-pub fn dispatch_command(
-    client: &mut RacClient,
-    cmd: Command,
-) -> Result<Value, RpcError> {
-    let value = match cmd {
-        Command::AgentVersion => {
-            let resp = agent_version(client).map_err(map_rac_error)?;
-            response_value(resp)?
-        }
-        Command::ClusterList => {
-            let resp = cluster_list(client).map_err(map_rac_error)?;
-            response_value(resp)?
-        }
-        Command::ClusterInfo { cluster } => {
-            let resp = cluster_info(client, cluster).map_err(map_rac_error)?;
-            response_value(resp)?
-        }
-        Command::ManagerList { cluster } => {
-            let resp = manager_list(client, cluster).map_err(map_rac_error)?;
-            response_value(resp)?
-        }
-        Command::ManagerInfo { cluster, manager } => {
-            let resp = manager_info(client, cluster, manager).map_err(map_rac_error)?;
-            response_value(resp)?
-        }
-        Command::ServerList { cluster } => {
-            let resp = server_list(client, cluster).map_err(map_rac_error)?;
-            response_value(resp)?
-        }
-        Command::ServerInfo { cluster, server } => {
-            let resp = server_info(client, cluster, server).map_err(map_rac_error)?;
-            response_value(resp)?
-        }
-        Command::ProcessList { cluster } => {
-            let resp = process_list(client, cluster).map_err(map_rac_error)?;
-            response_value(resp)?
-        }
-        Command::ProcessInfo { cluster, process } => {
-            let resp = process_info(client, cluster, process).map_err(map_rac_error)?;
-            response_value(resp)?
-        }
-        Command::InfobaseSummaryList { cluster } => {
-            let resp = infobase_summary_list(client, cluster).map_err(map_rac_error)?;
-            response_value(resp)?
-        }
-        Command::InfobaseSummaryInfo { cluster, infobase } => {
-            let resp = infobase_summary_info(client, cluster, infobase).map_err(map_rac_error)?;
-            response_value(resp)?
-        }
-        Command::InfobaseInfo { cluster, infobase } => {
-            let resp = infobase_info(client, cluster, infobase).map_err(map_rac_error)?;
-            response_value(resp)?
-        }
-        Command::ConnectionList { cluster } => {
-            let resp = connection_list(client, cluster).map_err(map_rac_error)?;
-            response_value(resp)?
-        }
-        Command::ConnectionInfo { cluster, connection } => {
-            let resp = connection_info(client, cluster, connection).map_err(map_rac_error)?;
-            response_value(resp)?
-        }
-        Command::SessionList { cluster } => {
-            let resp = session_list(client, cluster).map_err(map_rac_error)?;
-            response_value(resp)?
-        }
-        Command::SessionInfo { cluster, session } => {
-            let resp = session_info(client, cluster, session).map_err(map_rac_error)?;
-            response_value(resp)?
-        }
-        Command::LockList { cluster } => {
-            let resp = lock_list(client, cluster).map_err(map_rac_error)?;
-            response_value(resp)?
-        }
-        Command::ProfileList { cluster } => {
-            let resp = profile_list(client, cluster).map_err(map_rac_error)?;
-            response_value(resp)?
-        }
-        Command::CounterList { cluster } => {
-            let resp = counter_list(client, cluster).map_err(map_rac_error)?;
-            response_value(resp)?
-        }
-        Command::CounterInfo { cluster, counter } => {
-            let resp = counter_info(client, cluster, &counter).map_err(map_rac_error)?;
-            response_value(resp)?
+impl Eq for Command {}
+
+impl Hash for Command {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.spec.name.hash(state);
+        self.key.hash(state);
+    }
+}
+
+impl Command {
+    pub fn new(name: &str, args: Option<Value>) -> Result<Self, RpcError> {
+        let spec = command_spec(name)
+            .ok_or_else(|| RpcError::new("bad_request", format!("unknown command: {name}")))?;
+        let mut args = args.unwrap_or(Value::Null);
+        if let Value::Object(map) = &mut args {
+            for (_, old, new) in ARG_ALIASES.iter().filter(|(cmd, ..)| *cmd == spec.name) {
+                if let Some(value) = map.remove(*old) {
+                    map.entry(*new).or_insert(value);
+                }
+            }
         }
-        Command::LimitList { cluster } => {
-            let resp = limit_list(client, cluster).map_err(map_rac_error)?;
-            response_value(resp)?
+        let empty = match &args {
+            Value::Null => true,
+            Value::Object(map) => map.is_empty(),
+            _ => false,
+        };
+        if spec.args.is_empty() && !empty {
+            return Err(RpcError::new(
+                "bad_request",
+                "command does not accept args".to_string(),
+            ));
         }
-        Command::LimitInfo { cluster, limit } => {
-            let resp = limit_info(client, cluster, &limit).map_err(map_rac_error)?;
-            response_value(resp)?
+        if !spec.args.is_empty() && empty {
+            return Err(RpcError::new(
+                "bad_request",
+                "command requires args".to_string(),
+            ));
         }
-    };
-    Ok(value)
+        spec.validate(&args).map_err(map_rac_error)?;
+        let key = args.to_string();
+        Ok(Self { spec, args, key })
+    }
+
+    pub fn for_cluster(name: &str, cluster: Uuid16) -> Result<Self, RpcError> {
+        Self::new(name, Some(json!({ "cluster": format_uuid(&cluster) })))
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.spec.name
+    }
+
+    pub fn spec(&self) -> &'static CommandSpec {
+        self.spec
+    }
+
+    pub fn args(&self) -> &Value {
+        &self.args
+    }
+
+    pub fn cluster(&self) -> Option<Uuid16> {
+        self.args
+            .get("cluster")
+            .and_then(Value::as_str)
+            .and_then(|cluster| parse_uuid(cluster).ok())
+    }
 }
 
-fn response_value<T: Serialize>(resp: T) -> Result<Value, RpcError> {
-    let mut value =
-        serde_json::to_value(resp).map_err(|err| RpcError::new("internal", err.to_string()))?;
-    Ok(normalize_uuid_value(value))
+/// Registry command served over HTTP. The gateway is read-only, so commands
+/// that change cluster state are not exposed.
+pub fn command_spec(name: &str) -> Option<&'static CommandSpec> {
+    registry::find(name).filter(|spec| is_exposed(spec))
 }
 
-fn map_rac_error(err: RacError) -> RpcError {
-    RpcError::with_details(
-        "rac_error",
-        err.to_string(),
-        json!({ "kind": format!("{err:?}") }),
-    )
+/// Read commands other than authentication, which the gateway does itself
+/// with the configured credentials.
+fn is_exposed(spec: &CommandSpec) -> bool {
+    spec.is_read() && spec.action() != "auth"
 }
 
-fn ensure_no_args(args: Option<Value>) -> Result<(), RpcError> {
-    match args {
-        None => Ok(()),
-        Some(Value::Object(map)) if map.is_empty() => Ok(()),
-        Some(_) => Err(RpcError::new(
-            "bad_request",
-            "command does not accept args".to_string(),
-        )),
-    }
+pub fn parse_command(req: RpcRequest) -> Result<Command, RpcError> {
+    Command::new(&req.command, req.args)
 }
 
-fn parse_args<T: for<'de> Deserialize<'de>>(args: Option<Value>) -> Result<T, RpcError> {
-    let value = args.ok_or_else(|| {
-        RpcError::new("bad_request", "command requires args".to_string())
-    })?;
-    serde_json::from_value(value)
-        .map_err(|err| RpcError::new("bad_request", format!("invalid args: {err}")))
+#[derive(Debug)]
+pub struct RouteSpec {
+    pub path: String,
+    pub command: &'static str,
+    pub spec: &'static CommandSpec,
+    pub response: FieldKind,
+    pub summary: String,
+    /// Arguments that are not path segments; passed as query parameters.
+    pub query_args: Vec<&'static ArgSpec>,
+    list: bool,
 }
 
-fn parse_uuid_arg(input: &str) -> Result<Uuid16, RpcError> {
-    parse_uuid(input).map_err(|err| RpcError::new("bad_request", err.to_string()))
+impl RouteSpec {
+    fn new(spec: &'static CommandSpec) -> Self {
+        let (path, query_args) = route_path(spec);
+        let response = response_kind(spec);
+        let list = match response {
+            FieldKind::List(_) => true,
+            FieldKind::Record(name) => openapi::RESPONSE_SCHEMAS
+                .iter()
+                .any(|resp| resp.name == name && matches!(resp.kind, FieldKind::List(_))),
+            _ => false,
+        };
+        let summary = format!("{} {}", spec.object(), spec.action()).replace('_', " ");
+        Self {
+            path,
+            command: spec.name,
+            spec,
+            response,
+            summary: summary[..1].to_uppercase() + &summary[1..],
+            query_args,
+            list,
+        }
+    }
+
+    /// List routes accept the generic query parameters of [`query::ListQuery`].
+    pub fn is_list(&self) -> bool {
+        self.list
+    }
 }
 
-fn normalize_uuid_value(value: Value) -> Value {
-    match value {
-        Value::Array(items) => {
-            if let Some(uuid) = uuid_from_json_array(&items) {
-                Value::String(format_uuid(&uuid))
-            } else {
-                Value::Array(
-                    items
-                        .into_iter()
-                        .map(normalize_uuid_value)
-                        .collect(),
-                )
-            }
+/// GET routes of every command exposed over HTTP, in registry order.
+pub fn routes() -> &'static [RouteSpec] {
+    static ROUTES: OnceLock<Vec<RouteSpec>> = OnceLock::new();
+    ROUTES.get_or_init(|| {
+        registry::commands()
+            .filter(|spec| is_exposed(spec))
+            .map(RouteSpec::new)
+            .collect()
+    })
+}
+
+/// Derives the route from the command name and its arguments: parent UUIDs
+/// become path segments, `list` maps to the collection, `info` to the
+/// collection item (the last argument) and other actions to a sub-path.
+fn route_path(spec: &'static CommandSpec) -> (String, Vec<&'static ArgSpec>) {
+    let object = spec.object();
+    let action = spec.action();
+    let (sub, verb) = match action.rsplit_once('_') {
+        Some((sub, verb @ ("list" | "info"))) => (Some(sub), verb),
+        _ if action.starts_with("list") => (None, "list"),
+        _ => (None, action),
+    };
+    let key = match verb {
+        "list" => None,
+        "info" => spec.args.last(),
+        _ => spec.arg(object),
+    };
+
+    let mut path = String::new();
+    let mut query_args = Vec::new();
+    let mut nested_in_object = false;
+    for arg in spec.args {
+        if key.is_some_and(|key| key.name == arg.name) {
+            continue;
         }
-        Value::Object(map) => {
-            let mut out = serde_json::Map::with_capacity(map.len());
-            for (key, value) in map {
-                out.insert(key, normalize_uuid_value(value));
-            }
-            Value::Object(out)
+        if arg.kind == ArgKind::Uuid && ROUTE_PARENTS.contains(&arg.name) {
+            path.push_str(&format!("/{}/{{{}}}", collection(arg.name), arg.name));
+            nested_in_object |= arg.name == object;
+        } else {
+            query_args.push(arg);
         }
-        other => other,
     }
+    if !nested_in_object {
+        path.push('/');
+        path.push_str(&collection(object));
+    }
+    if let Some(sub) = sub {
+        path.push('/');
+        path.push_str(&collection(sub));
+    }
+    if let Some(key) = key {
+        path.push_str(&format!("/{{{}}}", key.name));
+    }
+    if verb != "list" && verb != "info" {
+        path.push('/');
+        path.push_str(verb);
+    }
+    (path, query_args)
 }
 
-fn uuid_from_json_array(items: &[Value]) -> Option<Uuid16> {
-    if items.len() != 16 {
-        return None;
+fn collection(name: &str) -> String {
+    match name {
+        "agent" | "summary" => name.to_string(),
+        "process" => "processes".to_string(),
+        _ => format!("{name}s"),
     }
-    let mut bytes = [0u8; 16];
-    for (idx, item) in items.iter().enumerate() {
-        let value = item.as_u64()?;
-        if value > u8::MAX as u64 {
-            return None;
-        }
-        bytes[idx] = value as u8;
+}
+
+fn response_kind(spec: &CommandSpec) -> FieldKind {
+    let unwrapped = UNWRAPPED.iter().any(|(command, _)| *command == spec.name);
+    openapi::RESPONSE_SCHEMAS
+        .iter()
+        .find(|resp| unwrapped && resp.name == spec.response)
+        .map(|resp| resp.kind)
+        .unwrap_or(FieldKind::Record(spec.response))
+}
+
+pub fn dispatch_command(client: &mut RacClient, cmd: Command) -> Result<Value, RpcError> {
+    let mut value = cmd.spec.call(client, cmd.args).map_err(map_rac_error)?;
+    if let Some((_, field)) = UNWRAPPED
+        .iter()
+        .find(|(command, _)| *command == cmd.spec.name)
+    {
+        value = value[*field].take();
     }
-    Some(bytes)
+    response_value(value)
 }
 
-#[derive(Debug, Deserialize)]
-struct ClusterArg {
-    cluster: String,
+/// Authentication `cmd` needs, for routing it to a connection holding it.
+pub fn wanted_auth(agent: &AgentConfig, cmd: &Command) -> Vec<Auth> {
+    cmd.cluster()
+        .map(|cluster| {
+            Auth::new(
+                AuthScope::Cluster(cluster),
+                agent.cluster_user.as_deref(),
+                agent.cluster_pwd.as_deref(),
+            )
+        })
+        .into_iter()
+        .collect()
 }
 
-#[derive(Debug, Deserialize)]
-struct ClusterManagerArg {
-    cluster: String,
-    manager: String,
+/// Authenticates to the command's cluster once per pooled connection.
+pub fn authenticate(
+    client: &mut Pooled<RacClient>,
+    agent: &AgentConfig,
+    cmd: &Command,
+) -> Result<(), RpcError> {
+    if agent.cluster_user.is_none() && agent.cluster_pwd.is_none() {
+        return Ok(());
+    }
+    if let Some(cluster) = cmd.cluster() {
+        client
+            .authenticate_cluster(
+                cluster,
+                agent.cluster_user.as_deref(),
+                agent.cluster_pwd.as_deref(),
+            )
+            .map_err(map_rac_error)?;
+    }
+    Ok(())
 }
 
-#[derive(Debug, Deserialize)]
-struct ClusterServerArg {
-    cluster: String,
-    server: String,
+/// `args` fields that accept a name in place of a UUID.
+const NAMED_ARGS: &[&str] = &["cluster", "infobase"];
+
+/// Whether `args` refer to a cluster or infobase by name.
+pub fn has_named_args(args: &Value) -> bool {
+    NAMED_ARGS.iter().any(|key| {
+        args.get(*key)
+            .and_then(Value::as_str)
+            .is_some_and(|value| parse_uuid(value).is_err())
+    })
 }
 
-#[derive(Debug, Deserialize)]
-struct ClusterProcessArg {
-    cluster: String,
-    process: String,
+/// Replaces cluster and infobase names in `args` with their UUIDs.
+pub fn resolve_named_args(
+    client: &mut Pooled<RacClient>,
+    resolver: &Resolver,
+    agent: &AgentConfig,
+    args: &mut Value,
+) -> Result<(), RpcError> {
+    let Some(cluster) = args.get("cluster").and_then(Value::as_str) else {
+        return Ok(());
+    };
+    let cluster = resolver.cluster(client, cluster).map_err(map_rac_error)?;
+    args["cluster"] = Value::String(format_uuid(&cluster));
+    if let Some(infobase) = args.get("infobase").and_then(Value::as_str) {
+        if parse_uuid(infobase).is_err() {
+            if agent.cluster_user.is_some() || agent.cluster_pwd.is_some() {
+                client
+                    .authenticate_cluster(
+                        cluster,
+                        agent.cluster_user.as_deref(),
+                        agent.cluster_pwd.as_deref(),
+                    )
+                    .map_err(map_rac_error)?;
+            }
+            let infobase = resolver
+                .infobase(client, cluster, infobase)
+                .map_err(map_rac_error)?;
+            args["infobase"] = Value::String(format_uuid(&infobase));
+        }
+    }
+    Ok(())
 }
 
-#[derive(Debug, Deserialize)]
-struct ClusterInfobaseArg {
-    cluster: String,
-    infobase: String,
+#[derive(Debug, Serialize)]
+pub struct AgentStatus {
+    pub name: String,
+    pub rac_addr: String,
+    pub status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
 }
 
-#[derive(Debug, Deserialize)]
-struct ClusterConnectionArg {
-    cluster: String,
-    connection: String,
+impl AgentStatus {
+    pub fn new(agent: &AgentConfig, error: Option<RpcError>) -> Self {
+        Self {
+            name: agent.name.clone(),
+            rac_addr: agent.rac_addr.clone(),
+            status: if error.is_some() { "error" } else { "ok" },
+            version: None,
+            error,
+        }
+    }
 }
 
-#[derive(Debug, Deserialize)]
-struct ClusterSessionArg {
-    cluster: String,
-    session: String,
+/// Merges per-agent `cluster.list` results into one list, tagging every
+/// record with the agent it came from. Failed agents are reported in
+/// `agents` instead of failing the whole response.
+pub fn aggregate_clusters(results: Vec<(&AgentConfig, Result<Value, RpcError>)>) -> Value {
+    let mut clusters = Vec::new();
+    let mut agents = Vec::with_capacity(results.len());
+    for (agent, result) in results {
+        match result {
+            Ok(Value::Array(items)) => {
+                clusters.extend(items.into_iter().map(|item| tag_agent(item, &agent.name)));
+                agents.push(AgentStatus::new(agent, None));
+            }
+            Ok(other) => {
+                clusters.push(tag_agent(other, &agent.name));
+                agents.push(AgentStatus::new(agent, None));
+            }
+            Err(err) => agents.push(AgentStatus::new(agent, Some(err))),
+        }
+    }
+    json!({ "clusters": clusters, "agents": agents })
 }
 
-#[derive(Debug, Deserialize)]
-struct ClusterCounterArg {
-    cluster: String,
-    counter: String,
+fn tag_agent(mut value: Value, agent: &str) -> Value {
+    if let Value::Object(map) = &mut value {
+        map.insert("agent".to_string(), Value::String(agent.to_string()));
+    }
+    value
 }
 
-#[derive(Debug, Deserialize)]
-struct ClusterLimitArg {
-    cluster: String,
-    limit: String,
+fn response_value<T: Serialize>(resp: T) -> Result<Value, RpcError> {
+    let value =
+        serde_json::to_value(resp).map_err(|err| RpcError::new("internal", err.to_string()))?;
+    Ok(tabular::normalize_uuids(value))
 }
 
-#[derive(Debug, Clone)]
-pub struct PoolConfig {
-    pub addr: String,
-    pub client_cfg: ClientConfig,
-    pub max: usize,
-    pub idle_ttl: Duration,
+pub fn map_rac_error(err: RacError) -> RpcError {
+    match err {
+        RacError::PoolTimeout(timeout) => RpcError::new(
+            "service_unavailable",
+            format!(
+                "no RAC connection available within {} ms",
+                timeout.as_millis()
+            ),
+        ),
+        RacError::PoolClosed => RpcError::new("service_unavailable", err.to_string()),
+        RacError::NameNotFound { .. } => RpcError::new("not_found", err.to_string()),
+        RacError::InvalidArgs(_) => RpcError::new("bad_request", err.to_string()),
+        RacError::ReadOnly { .. } => RpcError::new("read_only", err.to_string()),
+        RacError::Forbidden(_) => RpcError::new("forbidden", err.to_string()),
+        RacError::AmbiguousName { ref candidates, .. } => RpcError::with_details(
+            "ambiguous_name",
+            err.to_string(),
+            json!({ "candidates": candidates }),
+        ),
+        err => rac_error(err),
+    }
 }
 
-pub trait Clock: Send + Sync + 'static {
-    fn now(&self) -> Instant;
+fn rac_error(err: RacError) -> RpcError {
+    RpcError::with_details(
+        "rac_error",
+        err.to_string(),
+        json!({ "kind": format!("{err:?}") }),
+    )
 }
 
-#[derive(Debug, Clone)]
-pub struct SystemClock;
+pub fn parse_uuid_arg(input: &str) -> Result<Uuid16, RpcError> {
+    parse_uuid(input).map_err(|err| RpcError::new("bad_request", err.to_string()))
+}
 
-impl Clock for SystemClock {
-    fn now(&self) -> Instant {
-        Instant::now()
+#[derive(Debug)]
+pub struct CachedResponse {
+    pub body: Value,
+    pub etag: String,
+}
+
+impl CachedResponse {
+    pub fn new(body: Value) -> Self {
+        let etag = etag_for(&body);
+        Self { body, etag }
     }
 }
 
-#[derive(Debug)]
-struct IdleClient<T> {
-    client: T,
-    last_used: Instant,
+type FetchResult = Result<Arc<CachedResponse>, RpcError>;
+
+#[derive(Debug, Default)]
+struct Flight {
+    result: Mutex<Option<FetchResult>>,
+    condvar: Condvar,
 }
 
 #[derive(Debug)]
-struct PoolState<T> {
-    idle: Vec<IdleClient<T>>,
-    in_use: usize,
+enum CacheEntry {
+    Fresh {
+        resp: Arc<CachedResponse>,
+        expires: Instant,
+    },
+    InFlight(Arc<Flight>),
 }
 
-pub struct Pool<C: Clock> {
-    cfg: PoolConfig,
+/// In-memory cache of command results. Concurrent lookups of the same
+/// command while a fetch is running wait for that fetch instead of issuing
+/// their own.
+pub struct ResponseCache<C: Clock> {
     clock: C,
-    inner: Mutex<PoolState<RacClient>>,
-    condvar: Condvar,
+    entries: Mutex<HashMap<Command, CacheEntry>>,
 }
 
-impl<C: Clock> Pool<C> {
-    pub fn new(cfg: PoolConfig, clock: C) -> Self {
+impl<C: Clock> ResponseCache<C> {
+    pub fn new(clock: C) -> Self {
         Self {
-            cfg,
             clock,
-            inner: Mutex::new(PoolState {
-                idle: Vec::new(),
-                in_use: 0,
-            }),
-            condvar: Condvar::new(),
+            entries: Mutex::new(HashMap::new()),
         }
     }
 
-    pub fn checkout(&self) -> Result<RacClient, RpcError> {
-        loop {
+    pub fn get_or_fetch<F>(&self, cmd: Command, ttl: Duration, fetch: F) -> FetchResult
+    where
+        F: FnOnce() -> Result<Value, RpcError>,
+    {
+        let flight = {
+            let mut entries = self.lock_entries()?;
             let now = self.clock.now();
-            let mut state = self
-                .inner
-                .lock()
-                .map_err(|_| RpcError::new("service_unavailable", "pool lock poisoned".into()))?;
-            let expired = prune_idle(&mut state.idle, self.cfg.idle_ttl, now);
-            drop(state);
-            close_idle(expired);
-
-            let mut state = self
-                .inner
-                .lock()
-                .map_err(|_| RpcError::new("service_unavailable", "pool lock poisoned".into()))?;
-            if let Some(client) = state.idle.pop() {
-                state.in_use += 1;
-                return Ok(client.client);
+            match entries.get(&cmd) {
+                Some(CacheEntry::Fresh { resp, expires }) if *expires > now => {
+                    return Ok(resp.clone());
+                }
+                Some(CacheEntry::InFlight(flight)) => {
+                    let flight = flight.clone();
+                    drop(entries);
+                    return wait_flight(&flight);
+                }
+                _ => {}
             }
+            entries.retain(|_, entry| match entry {
+                CacheEntry::Fresh { expires, .. } => *expires > now,
+                CacheEntry::InFlight(_) => true,
+            });
+            let flight = Arc::new(Flight::default());
+            entries.insert(cmd.clone(), CacheEntry::InFlight(flight.clone()));
+            flight
+        };
+
+        // A panicking fetch must still release the waiters.
+        let result = panic::catch_unwind(AssertUnwindSafe(fetch))
+            .unwrap_or_else(|_| Err(RpcError::new("internal", "fetch panicked".into())))
+            .map(|body| Arc::new(CachedResponse::new(body)));
 
-            let total = state.in_use + state.idle.len();
-            if total < self.cfg.max {
-                state.in_use += 1;
-                drop(state);
-                match RacClient::connect(&self.cfg.addr, self.cfg.client_cfg.clone()) {
-                    Ok(client) => return Ok(client),
-                    Err(err) => {
-                        let mut state = self.inner.lock().map_err(|_| {
-                            RpcError::new("service_unavailable", "pool lock poisoned".into())
-                        })?;
-                        state.in_use = state.in_use.saturating_sub(1);
-                        self.condvar.notify_one();
-                        return Err(map_rac_error(err));
-                    }
+        {
+            let mut entries = self.lock_entries()?;
+            match &result {
+                Ok(resp) if !ttl.is_zero() => {
+                    entries.insert(
+                        cmd,
+                        CacheEntry::Fresh {
+                            resp: resp.clone(),
+                            expires: self.clock.now() + ttl,
+                        },
+                    );
+                }
+                _ => {
+                    entries.remove(&cmd);
                 }
             }
-
-            let _guard = self
-                .condvar
-                .wait(state)
-                .map_err(|_| RpcError::new("service_unavailable", "pool lock poisoned".into()))?;
         }
+        if let Ok(mut slot) = flight.result.lock() {
+            *slot = Some(result.clone());
+        }
+        flight.condvar.notify_all();
+        result
     }
 
-    pub fn release(&self, client: RacClient, ok: bool) -> Result<(), RpcError> {
-        let now = self.clock.now();
-        let mut state = self
-            .inner
+    fn lock_entries(&self) -> Result<MutexGuard<'_, HashMap<Command, CacheEntry>>, RpcError> {
+        self.entries
             .lock()
-            .map_err(|_| RpcError::new("service_unavailable", "pool lock poisoned".into()))?;
-        state.in_use = state.in_use.saturating_sub(1);
-        if ok {
-            state.idle.push(IdleClient {
-                client,
-                last_used: now,
-            });
-        } else {
-            drop(state);
-            let _ = client.close();
-            let mut state = self
-                .inner
-                .lock()
-                .map_err(|_| RpcError::new("service_unavailable", "pool lock poisoned".into()))?;
-            let expired = prune_idle(&mut state.idle, self.cfg.idle_ttl, now);
-            drop(state);
-            close_idle(expired);
-            self.condvar.notify_one();
-            return Ok(());
-        }
-        self.condvar.notify_one();
-        Ok(())
+            .map_err(|_| RpcError::new("internal", "cache lock poisoned".into()))
     }
 }
 
-fn prune_idle<T>(idle: &mut Vec<IdleClient<T>>, ttl: Duration, now: Instant) -> Vec<T> {
-    let mut expired = Vec::new();
-    let mut kept = Vec::with_capacity(idle.len());
-    while let Some(item) = idle.pop() {
-        if now.duration_since(item.last_used) > ttl {
-            expired.push(item.client);
-        } else {
-            kept.push(item);
+fn wait_flight(flight: &Flight) -> FetchResult {
+    let poisoned = || RpcError::new("internal", "cache lock poisoned".into());
+    let mut slot = flight.result.lock().map_err(|_| poisoned())?;
+    loop {
+        if let Some(result) = slot.as_ref() {
+            return result.clone();
         }
+        slot = flight.condvar.wait(slot).map_err(|_| poisoned())?;
     }
-    kept.reverse();
-    *idle = kept;
-    expired
 }
 
-fn close_idle(clients: Vec<RacClient>) {
-    for client in clients {
-        let _ = client.close();
+/// Strong ETag of the serialized body (64-bit FNV-1a).
+pub fn etag_for(body: &Value) -> String {
+    let bytes = serde_json::to_vec(body).unwrap_or_default();
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in bytes {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
     }
+    format!("\"{hash:016x}\"")
+}
+
+/// Evaluates an `If-None-Match` header value against the current ETag.
+pub fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    if_none_match.split(',').map(str::trim).any(|candidate| {
+        candidate == "*" || candidate.strip_prefix("W/").unwrap_or(candidate) == etag
+    })
+}
+
+/// Response format for an `Accept` header: the one of `text/csv`,
+/// `application/x-ndjson` and `application/json` with the highest `q`, the
+/// first listed on a tie; `q=0` excludes a type. JSON otherwise.
+pub fn negotiate_format(accept: Option<&str>) -> Format {
+    let mut best: Option<(f32, Format)> = None;
+    for item in accept.into_iter().flat_map(|accept| accept.split(',')) {
+        let mut parts = item.split(';');
+        let format = match parts.next().unwrap_or("").trim() {
+            "text/csv" => Format::Csv,
+            "application/x-ndjson" => Format::Ndjson,
+            "application/json" | "*/*" => Format::Json,
+            _ => continue,
+        };
+        let q = parts
+            .filter_map(|param| param.trim().strip_prefix("q="))
+            .find_map(|q| q.trim().parse::<f32>().ok())
+            .unwrap_or(1.0);
+        if q > 0.0 && best.is_none_or(|(best, _)| q > best) {
+            best = Some((q, format));
+        }
+    }
+    best.map_or(Format::Json, |(_, format)| format)
+}
+
+/// ETag of `body` rendered in `format`; representations differ per format.
+pub fn format_etag(etag: &str, format: Format) -> String {
+    let suffix = match format {
+        Format::Json => return etag.to_string(),
+        Format::Csv => "csv",
+        Format::Ndjson => "ndjson",
+        Format::Table => "table",
+    };
+    format!("{}-{suffix}\"", etag.trim_end_matches('"'))
 }
 
 impl From<RpcError> for RpcResponse {
@@ -706,12 +1023,12 @@ mod tests {
         let req = RpcRequest {
             command: "cluster.info".to_string(),
             args: Some(json!({ "cluster": "550e8400-e29b-41d4-a716-446655440000" })),
+            agent: None,
         };
         let cmd = parse_command(req).expect("command parse");
         let expected = parse_uuid("550e8400-e29b-41d4-a716-446655440000").expect("uuid");
-        let formatted = format!("{cmd:?}");
-        let expected_formatted = format!("ClusterInfo {{ cluster: {expected:?} }}");
-        assert_eq!(formatted, expected_formatted);
+        assert_eq!(cmd.name(), "cluster.info");
+        assert_eq!(cmd.cluster(), Some(expected));
     }
 
     #[test]
@@ -719,6 +1036,7 @@ mod tests {
         let req = RpcRequest {
             command: "cluster.delete".to_string(),
             args: None,
+            agent: None,
         };
         let err = parse_command(req).expect_err("unknown command");
         assert_eq!(err.code, "bad_request");
@@ -729,11 +1047,98 @@ mod tests {
         let req = RpcRequest {
             command: "cluster.info".to_string(),
             args: None,
+            agent: None,
         };
         let err = parse_command(req).expect_err("missing args");
         assert_eq!(err.code, "bad_request");
     }
 
+    #[test]
+    fn routes_map_to_commands() {
+        for route in routes() {
+            let args: serde_json::Map<String, Value> = route
+                .path
+                .split('/')
+                .filter_map(|s| s.strip_prefix('{').and_then(|s| s.strip_suffix('}')))
+                .chain(route.query_args.iter().map(|arg| arg.name))
+                .map(|name| {
+                    let value = Value::String("550e8400-e29b-41d4-a716-446655440000".to_string());
+                    (name.to_string(), value)
+                })
+                .collect();
+            let req = RpcRequest {
+                command: route.command.to_string(),
+                args: if args.is_empty() {
+                    None
+                } else {
+                    Some(Value::Object(args))
+                },
+                agent: None,
+            };
+            let cmd = parse_command(req).expect("route command parse");
+            assert_eq!(cmd.name(), route.command);
+        }
+    }
+
+    #[test]
+    fn parse_config_legacy_single_agent() {
+        let cfg = parse_config("rac_addr = \"10.0.0.1:1545\"\npool_max = 2\n").expect("config");
+        assert_eq!(cfg.agents.len(), 1);
+        assert_eq!(cfg.agents[0].name, "default");
+        assert_eq!(cfg.agents[0].rac_addr, "10.0.0.1:1545");
+        assert_eq!(cfg.agents[0].pool_max, 2);
+    }
+
+    #[test]
+    fn parse_config_agents_inherit_defaults() {
+        let raw = r#"
+pool_max = 8
+
+[[agents]]
+name = "farm1"
+rac_addr = "10.0.0.1:1545"
+cluster_user = "admin"
+cluster_pwd = "secret"
+
+[[agents]]
+name = "farm2"
+rac_addr = "10.0.0.2:1545"
+pool_max = 2
+"#;
+        let cfg = parse_config(raw).expect("config");
+        assert_eq!(cfg.agents.len(), 2);
+        assert_eq!(cfg.agents[0].pool_max, 8);
+        assert_eq!(cfg.agents[0].cluster_user.as_deref(), Some("admin"));
+        assert_eq!(cfg.agents[1].pool_max, 2);
+
+        let dup = "[[agents]]\nname = \"a\"\nrac_addr = \"x:1\"\n[[agents]]\nname = \"a\"\nrac_addr = \"y:1\"\n";
+        let err = parse_config(dup).expect_err("duplicate agent");
+        assert_eq!(err.code, "config_error");
+    }
+
+    #[test]
+    fn aggregate_clusters_tags_records_and_keeps_failures() {
+        let cfg = parse_config(
+            "[[agents]]\nname = \"a\"\nrac_addr = \"x:1\"\n[[agents]]\nname = \"b\"\nrac_addr = \"y:1\"\n",
+        )
+        .expect("config");
+        let value = aggregate_clusters(vec![
+            (
+                &cfg.agents[0],
+                Ok(json!([{ "uuid": "u1" }, { "uuid": "u2" }])),
+            ),
+            (
+                &cfg.agents[1],
+                Err(RpcError::new("rac_error", "connection refused".to_string())),
+            ),
+        ]);
+        assert_eq!(value["clusters"].as_array().expect("clusters").len(), 2);
+        assert_eq!(value["clusters"][1]["agent"], "a");
+        assert_eq!(value["agents"][0]["status"], "ok");
+        assert_eq!(value["agents"][1]["status"], "error");
+        assert_eq!(value["agents"][1]["error"]["code"], "rac_error");
+    }
+
     #[test]
     fn response_value_formats_uuid_as_string() {
         let uuid = parse_uuid("550e8400-e29b-41d4-a716-446655440000").expect("uuid");
@@ -774,22 +1179,320 @@ mod tests {
     }
 
     #[test]
-    fn prune_idle_respects_ttl() {
+    fn parse_config_cache_ttls() {
+        let raw = "[cache]\ndefault_ttl_ms = 500\n[cache.ttl_ms]\n\"session.list\" = 3000\n";
+        let cfg = parse_config(raw).expect("config");
+        let cluster = [0u8; 16];
+        assert_eq!(
+            cfg.cache
+                .ttl(&Command::for_cluster("session.list", cluster).expect("command")),
+            Duration::from_millis(3000)
+        );
+        assert_eq!(
+            cfg.cache
+                .ttl(&Command::for_cluster("process.list", cluster).expect("command")),
+            Duration::from_millis(500)
+        );
+        let err = parse_config("[cache.ttl_ms]\n\"session.drop\" = 1\n").expect_err("unknown");
+        assert_eq!(err.code, "config_error");
+    }
+
+    fn cluster_list() -> Command {
+        Command::new("cluster.list", None).expect("command")
+    }
+
+    #[test]
+    fn routes_keep_published_paths() {
+        let path = |command: &str| {
+            routes()
+                .iter()
+                .find(|route| route.command == command)
+                .map(|route| route.path.as_str())
+                .unwrap_or_else(|| panic!("no route for {command}"))
+        };
+        for (command, expected) in [
+            ("agent.version", "/agent/version"),
+            ("cluster.list", "/clusters"),
+            ("cluster.info", "/clusters/{cluster}"),
+            ("manager.info", "/clusters/{cluster}/managers/{manager}"),
+            ("process.list", "/clusters/{cluster}/processes"),
+            (
+                "infobase.summary_list",
+                "/clusters/{cluster}/infobases/summary",
+            ),
+            (
+                "infobase.summary_info",
+                "/clusters/{cluster}/infobases/summary/{infobase}",
+            ),
+            ("infobase.info", "/clusters/{cluster}/infobases/{infobase}"),
+            ("counter.info", "/clusters/{cluster}/counters/{counter}"),
+            ("limit.info", "/clusters/{cluster}/limits/{name}"),
+            ("cluster.admin_list", "/clusters/{cluster}/admins"),
+            ("agent.admin_list", "/agent/admins"),
+            (
+                "rule.info",
+                "/clusters/{cluster}/servers/{server}/rules/{rule}",
+            ),
+            (
+                "connection.list_by_infobase",
+                "/clusters/{cluster}/infobases/{infobase}/connections",
+            ),
+            (
+                "counter.values",
+                "/clusters/{cluster}/counters/{counter}/values",
+            ),
+        ] {
+            assert_eq!(path(command), expected, "{command}");
+        }
+        let values = routes()
+            .iter()
+            .find(|route| route.command == "counter.values")
+            .expect("counter.values");
+        assert_eq!(values.query_args.len(), 1);
+        assert_eq!(values.query_args[0].name, "object");
+        assert!(values.is_list());
+        let mut paths: Vec<&str> = routes().iter().map(|route| route.path.as_str()).collect();
+        paths.sort_unstable();
+        paths.dedup();
+        assert_eq!(paths.len(), routes().len());
+        assert!(routes().iter().all(|route| route.spec.is_read()));
+    }
+
+    #[test]
+    fn parse_command_rejects_mutations_and_accepts_aliases() {
+        let cluster = "550e8400-e29b-41d4-a716-446655440000";
+        let err = Command::new(
+            "session.terminate",
+            Some(json!({ "cluster": cluster, "session": cluster, "error_message": "" })),
+        )
+        .expect_err("mutation");
+        assert_eq!(err.message, "unknown command: session.terminate");
+
+        let cmd = Command::new(
+            "limit.info",
+            Some(json!({ "cluster": cluster, "limit": "cpu" })),
+        )
+        .expect("legacy arg name");
+        assert_eq!(cmd.args()["name"], "cpu");
+        assert_eq!(
+            cmd,
+            Command::new(
+                "limit.info",
+                Some(json!({ "name": "cpu", "cluster": cluster }))
+            )
+            .expect("command")
+        );
+
+        let err = Command::new("session.info", Some(json!({ "cluster": cluster })))
+            .expect_err("missing session");
+        assert_eq!(err.code, "bad_request");
+    }
+
+    #[test]
+    fn response_cache_serves_fresh_and_refetches_expired() {
         let base = Instant::now();
-        let clock = TestClock::new(base);
-        let mut idle = Vec::new();
-        idle.push(IdleClient {
-            client: 1u8,
-            last_used: base - Duration::from_secs(120),
-        });
-        idle.push(IdleClient {
-            client: 2u8,
-            last_used: base - Duration::from_secs(10),
+        let cache = ResponseCache::new(TestClock::new(base));
+        let ttl = Duration::from_secs(2);
+        let first = cache
+            .get_or_fetch(cluster_list(), ttl, || Ok(json!([1])))
+            .expect("fetch");
+        let cached = cache
+            .get_or_fetch(cluster_list(), ttl, || panic!("must be cached"))
+            .expect("cached");
+        assert!(Arc::ptr_eq(&first, &cached));
+
+        cache.clock.set(base + Duration::from_secs(3));
+        let refreshed = cache
+            .get_or_fetch(cluster_list(), ttl, || Ok(json!([2])))
+            .expect("refetch");
+        assert_eq!(refreshed.body, json!([2]));
+        assert_ne!(refreshed.etag, first.etag);
+    }
+
+    #[test]
+    fn response_cache_does_not_keep_errors() {
+        let cache = ResponseCache::new(TestClock::new(Instant::now()));
+        let ttl = Duration::from_secs(60);
+        let err = cache
+            .get_or_fetch(
+                Command::new("agent.version", None).expect("command"),
+                ttl,
+                || Err(RpcError::new("rac_error", "down".to_string())),
+            )
+            .expect_err("error");
+        assert_eq!(err.code, "rac_error");
+        let ok = cache
+            .get_or_fetch(
+                Command::new("agent.version", None).expect("command"),
+                ttl,
+                || Ok(json!("8.3")),
+            )
+            .expect("retry");
+        assert_eq!(ok.body, json!("8.3"));
+    }
+
+    #[test]
+    fn response_cache_coalesces_concurrent_fetches() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let cache = Arc::new(ResponseCache::new(SystemClock));
+        let calls = Arc::new(AtomicUsize::new(0));
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let cache = cache.clone();
+                let calls = calls.clone();
+                std::thread::spawn(move || {
+                    cache.get_or_fetch(cluster_list(), Duration::ZERO, || {
+                        calls.fetch_add(1, Ordering::SeqCst);
+                        std::thread::sleep(Duration::from_millis(200));
+                        Ok(json!([]))
+                    })
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().expect("join").expect("result");
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn etag_matching() {
+        let etag = etag_for(&json!({ "a": 1 }));
+        assert_eq!(etag, etag_for(&json!({ "a": 1 })));
+        assert!(etag.starts_with('"') && etag.ends_with('"'));
+        assert!(etag_matches(&etag, &etag));
+        assert!(etag_matches(&format!("\"x\", W/{etag}"), &etag));
+        assert!(etag_matches("*", &etag));
+        assert!(!etag_matches("\"x\"", &etag));
+    }
+
+    #[test]
+    fn negotiates_tabular_formats() {
+        assert_eq!(negotiate_format(None), Format::Json);
+        assert_eq!(negotiate_format(Some("text/csv")), Format::Csv);
+        assert_eq!(
+            negotiate_format(Some("text/html, application/x-ndjson;q=0.9, */*;q=0.1")),
+            Format::Ndjson
+        );
+        assert_eq!(negotiate_format(Some("*/*, text/csv")), Format::Json);
+        assert_eq!(negotiate_format(Some("text/html")), Format::Json);
+        assert_eq!(
+            negotiate_format(Some("application/json;q=0.1, text/csv")),
+            Format::Csv
+        );
+        assert_eq!(
+            negotiate_format(Some("text/csv;q=0.5, application/x-ndjson; q=0.5")),
+            Format::Csv
+        );
+        assert_eq!(
+            negotiate_format(Some("text/csv;q=0, application/x-ndjson;q=0.2")),
+            Format::Ndjson
+        );
+        assert_eq!(negotiate_format(Some("text/csv;q=0")), Format::Json);
+
+        let etag = etag_for(&json!([]));
+        assert_eq!(format_etag(&etag, Format::Json), etag);
+        let csv = format_etag(&etag, Format::Csv);
+        assert!(csv.ends_with("-csv\"") && csv != etag);
+    }
+
+    #[test]
+    fn reload_keeps_pools_of_unchanged_endpoints() {
+        let old = parse_config(
+            "[[agents]]\nname = \"a\"\nrac_addr = \"ras:1545\"\ncluster_user = \"admin\"\ncluster_pwd = \"1\"\n",
+        )
+        .expect("config");
+        let old = &old.agents[0];
+        let mut new = old.clone();
+        new.pool_max = 8;
+        assert_eq!(pool_reload(Some(old), &new), PoolReload::Keep);
+        new.cluster_pwd = Some("2".to_string());
+        assert_eq!(pool_reload(Some(old), &new), PoolReload::KeepReauthenticate);
+        new.cluster_user = Some("other".to_string());
+        assert_eq!(pool_reload(Some(old), &new), PoolReload::Open);
+        assert_eq!(pool_reload(None, old), PoolReload::Open);
+
+        let cfg =
+            parse_config("shutdown_timeout_ms = 500\nconfig_watch_ms = 1000\n").expect("config");
+        assert_eq!((cfg.shutdown_timeout_ms, cfg.config_watch_ms), (500, 1000));
+        let cfg = parse_config("").expect("config");
+        assert_eq!((cfg.shutdown_timeout_ms, cfg.config_watch_ms), (10_000, 0));
+    }
+
+    #[test]
+    fn parse_config_metrics_section() {
+        assert!(parse_config("").expect("config").metrics.is_none());
+        let cfg = parse_config("[metrics]\n").expect("config");
+        assert_eq!(cfg.metrics.expect("metrics").scrape_interval_secs, 30);
+        let cfg = parse_config("[metrics]\nscrape_interval_secs = 5\n").expect("config");
+        assert_eq!(cfg.metrics.expect("metrics").scrape_interval_secs, 5);
+    }
+
+    #[test]
+    fn agent_pool_key_uses_cluster_user_as_identity() {
+        let raw = "[[agents]]\nname = \"a\"\nrac_addr = \"ras:1545\"\ncluster_user = \"admin\"\npool_max = 2\nmin_idle = 5\n";
+        let cfg = parse_config(raw).expect("config");
+        let agent = &cfg.agents[0];
+        assert_eq!(agent.pool_key(), PoolKey::new("ras:1545", Some("admin")));
+        let pool_cfg = agent.pool_config();
+        assert_eq!(pool_cfg.max, 2);
+        assert_eq!(pool_cfg.min_idle, 2);
+
+        let command = |command: &str, args: Value| {
+            parse_command(RpcRequest {
+                command: command.to_string(),
+                args: Some(args),
+                agent: None,
+            })
+            .expect("command")
+        };
+        let info = command(
+            "cluster.info",
+            json!({ "cluster": "550e8400-e29b-41d4-a716-446655440000" }),
+        );
+        let cluster = info.cluster().expect("cluster");
+        assert_eq!(
+            wanted_auth(agent, &info),
+            [Auth::new(AuthScope::Cluster(cluster), Some("admin"), None)]
+        );
+        assert!(wanted_auth(agent, &command("cluster.list", json!({}))).is_empty());
+    }
+
+    #[test]
+    fn named_args_are_detected() {
+        assert!(has_named_args(&json!({ "cluster": "Main" })));
+        assert!(has_named_args(&json!({
+            "cluster": "550e8400-e29b-41d4-a716-446655440000",
+            "infobase": "buh_prod",
+        })));
+        assert!(!has_named_args(&json!({
+            "cluster": "550e8400-e29b-41d4-a716-446655440000",
+            "session": "s1",
+        })));
+        let err = map_rac_error(RacError::AmbiguousName {
+            kind: "infobase",
+            name: "buh".to_string(),
+            candidates: vec!["a".to_string(), "b".to_string()],
         });
-        clock.set(base);
-        let expired = prune_idle(&mut idle, Duration::from_secs(60), clock.now());
-        assert_eq!(expired, vec![1u8]);
-        assert_eq!(idle.len(), 1);
-        assert_eq!(idle[0].client, 2u8);
+        assert_eq!(err.code, "ambiguous_name");
+        assert_eq!(
+            err.details.expect("details")["candidates"],
+            json!(["a", "b"])
+        );
+    }
+
+    #[test]
+    fn pool_timeout_maps_to_service_unavailable() {
+        let err = map_rac_error(RacError::PoolTimeout(Duration::from_millis(100)));
+        assert_eq!(err.code, "service_unavailable");
+        assert_eq!(err.message, "no RAC connection available within 100 ms");
+    }
+
+    #[test]
+    fn policy_denial_maps_to_forbidden() {
+        let err = map_rac_error(RacError::Forbidden("session.terminate".to_string()));
+        assert_eq!(err.code, "forbidden");
+        assert_eq!(err.message, "forbidden by policy: session.terminate");
     }
 }
@@ -1,33 +1,177 @@
+use std::collections::{HashMap, HashSet};
+use std::convert::Infallible;
+use std::fs;
+use std::future::IntoFuture;
 use std::net::SocketAddr;
-use std::sync::Arc;
-use std::time::Duration;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant, SystemTime};
 
-use axum::extract::{Path, State};
-use axum::http::StatusCode;
+use axum::body::Bytes;
+use axum::extract::ws::{Message, WebSocketUpgrade};
+use axum::extract::{MatchedPath, Path, Query, Request, State};
+use axum::http::header::{HeaderName, ACCEPT, CONTENT_TYPE, ETAG, IF_NONE_MATCH};
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{Html, IntoResponse, Response};
 use axum::routing::{get, post};
 use axum::{Json, Router};
 use clap::Parser;
+use futures_util::stream::{self, Stream};
 use serde_json::{json, Value};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{broadcast, watch};
 use tokio::task;
+use tokio::time::{self, MissedTickBehavior};
 
-use rac_protocol::client::ClientConfig;
+use rac_protocol::client::RacClient;
+use rac_protocol::pool::{Pool, PoolKey, Pooled};
+use rac_protocol::registry::ArgKind;
+use rac_protocol::resolve::Resolver;
+use rac_protocol::tabular::{self, Format};
+use rac_protocol::Uuid16;
+use rac_rest::events::{diff_snapshots, event_json, snapshot, ChangeEvent, EventHub, ENTITIES};
+use rac_rest::gateway::{self, GatewayMetrics, RequestTrace};
+use rac_rest::jsonrpc::{self, Call};
+use rac_rest::metrics::{self, AgentScrape};
+use rac_rest::query::ListQuery;
 use rac_rest::{
-    dispatch_command, load_config, parse_command, Command, Pool, PoolConfig, RpcError, RpcMeta,
+    aggregate_clusters, authenticate, dispatch_command, etag_for, etag_matches, format_etag,
+    has_named_args, load_config, map_rac_error, negotiate_format, openapi, parse_command,
+    parse_uuid_arg, pool_reload, resolve_named_args, routes, wanted_auth, AgentConfig, AgentStatus,
+    CacheConfig, CachedResponse, Command, PoolReload, ResponseCache, RouteSpec, RpcError,
     RpcRequest, RpcResponse, SystemClock,
 };
-use rac_protocol::rac_wire::parse_uuid;
-use rac_protocol::Uuid16;
+
+const POOL_MAINTAIN_INTERVAL: Duration = Duration::from_secs(5);
+const X_TOTAL_COUNT: HeaderName = HeaderName::from_static("x-total-count");
 
 #[derive(Parser, Debug)]
-#[command(name = "rac_rest", version, about = "RAC REST gateway with cached connections")]
+#[command(
+    name = "rac_rest",
+    version,
+    about = "RAC REST gateway with cached connections"
+)]
 struct Cli {
     #[arg(long, default_value = "rac_rest.toml")]
     config: String,
 }
 
+/// The pool, resolver and cache outlive the agent when a config reload
+/// keeps its endpoint.
+struct Agent {
+    cfg: AgentConfig,
+    pool: Arc<Pool>,
+    pool_key: PoolKey,
+    resolver: Arc<Resolver>,
+    cache: Arc<ResponseCache<SystemClock>>,
+    gateway: Arc<GatewayMetrics>,
+}
+
+impl Agent {
+    fn new(cfg: &AgentConfig, gateway: Arc<GatewayMetrics>) -> Self {
+        let pool = Pool::new(cfg.pool_config(), SystemClock);
+        let pool_key = cfg.pool_key();
+        pool.register(&pool_key);
+        Self {
+            cfg: cfg.clone(),
+            pool: Arc::new(pool),
+            pool_key,
+            resolver: Arc::default(),
+            cache: Arc::new(ResponseCache::new(SystemClock)),
+            gateway,
+        }
+    }
+
+    /// The agent for `cfg` on top of this one's connections.
+    fn reconfigured(&self, cfg: &AgentConfig, reload: PoolReload) -> Self {
+        self.pool.reconfigure(cfg.pool_config());
+        if reload == PoolReload::KeepReauthenticate {
+            self.pool.reset_authentication(&self.pool_key);
+        }
+        Self {
+            cfg: cfg.clone(),
+            pool: self.pool.clone(),
+            pool_key: self.pool_key.clone(),
+            resolver: self.resolver.clone(),
+            cache: self.cache.clone(),
+            gateway: self.gateway.clone(),
+        }
+    }
+}
+
+/// Agents for `configs`, reusing the pools of `previous` agents whose
+/// address and user did not change. Also returns the pools left unused.
+fn build_agents(
+    configs: &[AgentConfig],
+    previous: &[Arc<Agent>],
+    gateway: &Arc<GatewayMetrics>,
+) -> (Vec<Arc<Agent>>, Vec<Arc<Pool>>) {
+    let agents: Vec<Arc<Agent>> = configs
+        .iter()
+        .map(|cfg| {
+            let old = previous.iter().find(|agent| agent.cfg.name == cfg.name);
+            match (old, pool_reload(old.map(|agent| &agent.cfg), cfg)) {
+                (Some(old), reload @ (PoolReload::Keep | PoolReload::KeepReauthenticate)) => {
+                    Arc::new(old.reconfigured(cfg, reload))
+                }
+                _ => Arc::new(Agent::new(cfg, gateway.clone())),
+            }
+        })
+        .collect();
+    let unused = previous
+        .iter()
+        .filter(|old| {
+            !agents
+                .iter()
+                .any(|agent| Arc::ptr_eq(&agent.pool, &old.pool))
+        })
+        .map(|old| old.pool.clone())
+        .collect();
+    (agents, unused)
+}
+
 #[derive(Clone)]
 struct AppState {
-    pool: Arc<Pool<SystemClock>>,
+    /// Swapped as a whole by config reloads.
+    agents: Arc<RwLock<Arc<Vec<Arc<Agent>>>>>,
+    cache: Arc<RwLock<Arc<CacheConfig>>>,
+    events: Arc<EventHub>,
+    events_poll: Duration,
+    metrics: Arc<RwLock<Option<String>>>,
+    gateway: Arc<GatewayMetrics>,
+    /// Set once on shutdown; ends event streams.
+    shutdown: watch::Receiver<bool>,
+}
+
+impl AppState {
+    fn agents(&self) -> Arc<Vec<Arc<Agent>>> {
+        self.agents
+            .read()
+            .unwrap_or_else(|err| err.into_inner())
+            .clone()
+    }
+
+    fn cache(&self) -> Arc<CacheConfig> {
+        self.cache
+            .read()
+            .unwrap_or_else(|err| err.into_inner())
+            .clone()
+    }
+
+    fn agent(&self, name: Option<&str>) -> Result<Arc<Agent>, RpcError> {
+        let agents = self.agents();
+        match name {
+            None => agents.first().cloned().ok_or_else(|| {
+                RpcError::new("service_unavailable", "no agents configured".to_string())
+            }),
+            Some(name) => agents
+                .iter()
+                .find(|agent| agent.cfg.name == name)
+                .cloned()
+                .ok_or_else(|| RpcError::new("not_found", format!("unknown agent: {name}"))),
+        }
+    }
 }
 
 #[tokio::main]
@@ -35,280 +179,883 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
     let cfg = load_config(&cli.config)?;
 
-    let mut client_cfg = ClientConfig::default();
-    client_cfg.connect_timeout = Duration::from_millis(cfg.connect_timeout_ms);
-    client_cfg.read_timeout = Duration::from_millis(cfg.read_timeout_ms);
-    client_cfg.write_timeout = Duration::from_millis(cfg.write_timeout_ms);
-
-    let pool_cfg = PoolConfig {
-        addr: cfg.rac_addr.clone(),
-        client_cfg,
-        max: cfg.pool_max,
-        idle_ttl: Duration::from_secs(cfg.idle_ttl_secs),
-    };
-    let pool = Arc::new(Pool::new(pool_cfg, SystemClock));
-
+    let gateway = Arc::new(GatewayMetrics::default());
+    let (agents, _) = build_agents(&cfg.agents, &[], &gateway);
+    let (shutdown_tx, shutdown) = watch::channel(false);
     let state = AppState {
-        pool,
+        agents: Arc::new(RwLock::new(Arc::new(agents))),
+        cache: Arc::new(RwLock::new(Arc::new(cfg.cache.clone()))),
+        events: Arc::new(EventHub::default()),
+        events_poll: Duration::from_millis(cfg.events_poll_ms),
+        metrics: Arc::new(RwLock::new(None)),
+        gateway,
+        shutdown,
     };
+    tokio::spawn(maintain_pools(state.clone()));
+    #[cfg(unix)]
+    tokio::spawn(reload_on_sighup(state.clone(), cli.config.clone()));
+    if cfg.config_watch_ms > 0 {
+        let interval = Duration::from_millis(cfg.config_watch_ms);
+        tokio::spawn(watch_config(state.clone(), cli.config.clone(), interval));
+    }
+    if let Some(metrics_cfg) = &cfg.metrics {
+        let interval = Duration::from_secs(metrics_cfg.scrape_interval_secs);
+        tokio::spawn(scrape_metrics(state.clone(), interval));
+    }
 
-    let app = Router::new()
+    let mut app = Router::new()
         .route("/rpc", post(rpc_handler))
-        .route("/agent/version", get(agent_version))
-        .route("/clusters", get(clusters_list))
-        .route("/clusters/{cluster}", get(clusters_info))
-        .route("/clusters/{cluster}/managers", get(managers_list))
-        .route("/clusters/{cluster}/managers/{manager}", get(managers_info))
-        .route("/clusters/{cluster}/servers", get(servers_list))
-        .route("/clusters/{cluster}/servers/{server}", get(servers_info))
-        .route("/clusters/{cluster}/processes", get(processes_list))
-        .route("/clusters/{cluster}/processes/{process}", get(processes_info))
-        .route(
-            "/clusters/{cluster}/infobases/summary",
-            get(infobase_summary_list),
-        )
-        .route(
-            "/clusters/{cluster}/infobases/summary/{infobase}",
-            get(infobase_summary_info),
-        )
-        .route("/clusters/{cluster}/infobases/{infobase}", get(infobase_info))
-        .route("/clusters/{cluster}/connections", get(connections_list))
+        .route("/openapi.json", get(openapi_json))
+        .route("/docs", get(docs))
+        .route("/agents", get(agents_health))
+        .route("/metrics", get(metrics_handler))
+        .route("/metrics/gateway", get(gateway_metrics))
+        .route("/debug/pool", get(pool_stats))
+        .route("/clusters", get(clusters_aggregate))
+        .route("/clusters/{cluster}/events", get(events_sse))
+        .route("/clusters/{cluster}/events/ws", get(events_ws))
+        .route("/agents/{agent}/clusters/{cluster}/events", get(events_sse))
         .route(
-            "/clusters/{cluster}/connections/{connection}",
-            get(connections_info),
-        )
-        .route("/clusters/{cluster}/sessions", get(sessions_list))
-        .route("/clusters/{cluster}/sessions/{session}", get(sessions_info))
-        .route("/clusters/{cluster}/locks", get(locks_list))
-        .route("/clusters/{cluster}/profiles", get(profiles_list))
-        .route("/clusters/{cluster}/counters", get(counters_list))
-        .route("/clusters/{cluster}/counters/{counter}", get(counters_info))
-        .route("/clusters/{cluster}/limits", get(limits_list))
-        .route("/clusters/{cluster}/limits/{limit}", get(limits_info))
-        .with_state(state);
+            "/agents/{agent}/clusters/{cluster}/events/ws",
+            get(events_ws),
+        );
+    for route in routes() {
+        let prefixed = format!("/agents/{{agent}}{}", route.path);
+        app = app.route(
+            &prefixed,
+            get(
+                move |State(state): State<AppState>,
+                      headers: HeaderMap,
+                      Path(mut params): Path<HashMap<String, String>>,
+                      Query(query): Query<Vec<(String, String)>>| async move {
+                    let agent = params.remove("agent");
+                    route_handler(state, headers, agent, route, params, query).await
+                },
+            ),
+        );
+        if route.path == "/clusters" {
+            continue;
+        }
+        let handler = if route.path.contains('{') {
+            get(
+                move |State(state): State<AppState>,
+                      headers: HeaderMap,
+                      Path(params): Path<HashMap<String, String>>,
+                      Query(query): Query<Vec<(String, String)>>| {
+                    route_handler(state, headers, None, route, params, query)
+                },
+            )
+        } else {
+            get(
+                move |State(state): State<AppState>,
+                      headers: HeaderMap,
+                      Query(query): Query<Vec<(String, String)>>| {
+                    route_handler(state, headers, None, route, HashMap::new(), query)
+                },
+            )
+        };
+        app = app.route(&route.path, handler);
+    }
+    let app = app
+        .layer(middleware::from_fn_with_state(state.clone(), trace_request))
+        .with_state(state.clone());
 
     let addr: SocketAddr = cfg.listen_addr.parse()?;
-    axum::serve(tokio::net::TcpListener::bind(addr).await?, app).await?;
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    let mut stopping = state.shutdown.clone();
+    let mut server = tokio::spawn(
+        axum::serve(listener, app)
+            .with_graceful_shutdown(async move {
+                let _ = stopping.wait_for(|stop| *stop).await;
+            })
+            .into_future(),
+    );
+    tokio::select! {
+        result = &mut server => result??,
+        _ = shutdown_signal() => {
+            // Stops accepting and ends event streams; requests in flight
+            // get `shutdown_timeout_ms` to finish.
+            shutdown_tx.send_replace(true);
+            let deadline = Duration::from_millis(cfg.shutdown_timeout_ms);
+            match time::timeout(deadline, &mut server).await {
+                Ok(result) => result??,
+                Err(_) => {
+                    eprintln!(
+                        "shutdown: requests still running after {} ms, aborting",
+                        deadline.as_millis()
+                    );
+                    server.abort();
+                }
+            }
+        }
+    }
+    let pools: Vec<_> = state
+        .agents()
+        .iter()
+        .map(|agent| agent.pool.clone())
+        .collect();
+    task::spawn_blocking(move || pools.iter().for_each(|pool| pool.close())).await?;
     Ok(())
 }
 
-async fn rpc_handler(
-    State(state): State<AppState>,
-    Json(req): Json<RpcRequest>,
-) -> Result<Json<RpcResponse>, (StatusCode, Json<RpcResponse>)> {
-    let command = match parse_command(req) {
-        Ok(cmd) => cmd,
-        Err(err) => return Err(error_response(err)),
+/// SIGTERM or Ctrl-C.
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut stream) => {
+                stream.recv().await;
+            }
+            Err(_) => std::future::pending().await,
+        }
     };
-    let command_label = command_name(&command).to_string();
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+    tokio::select! {
+        _ = tokio::signal::ctrl_c() => {}
+        _ = terminate => {}
+    }
+}
 
-    let pool = state.pool.clone();
-    let result = task::spawn_blocking(move || exec_command(pool, command)).await;
+#[cfg(unix)]
+async fn reload_on_sighup(state: AppState, path: String) {
+    use tokio::signal::unix::{signal, SignalKind};
+    let Ok(mut hangup) = signal(SignalKind::hangup()) else {
+        return;
+    };
+    while hangup.recv().await.is_some() {
+        reload_config(&state, &path).await;
+    }
+}
 
-    match result {
-        Ok(Ok(payload)) => Ok(Json(RpcResponse {
-            result: Some(payload),
-            error: None,
-            meta: Some(RpcMeta {
-                command: command_label,
-            }),
-        })),
-        Ok(Err(err)) => Err(error_response(err)),
-        Err(err) => Err(error_response(RpcError::new(
-            "internal",
-            format!("worker failed: {err}"),
-        ))),
+/// Reloads the config when the file modification time changes.
+async fn watch_config(state: AppState, path: String, interval: Duration) {
+    let modified = |path: &str| fs::metadata(path).and_then(|meta| meta.modified()).ok();
+    let mut last: Option<SystemTime> = modified(&path);
+    let mut ticker = time::interval(interval);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        ticker.tick().await;
+        let current = modified(&path);
+        if current.is_some() && current != last {
+            last = current;
+            reload_config(&state, &path).await;
+        }
     }
 }
 
-async fn agent_version(
-    State(state): State<AppState>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    exec_command_json(state, Command::AgentVersion).await
+/// Swaps agents and cache TTLs for the ones in `path`. Pools of agents whose
+/// address and user are unchanged keep their connections; the others are
+/// closed. An invalid file keeps the running config.
+async fn reload_config(state: &AppState, path: &str) {
+    let cfg = match load_config(path) {
+        Ok(cfg) => cfg,
+        Err(err) => {
+            eprintln!("config reload failed: {}", err.message);
+            return;
+        }
+    };
+    let unused = {
+        let mut agents = state.agents.write().unwrap_or_else(|err| err.into_inner());
+        let (next, unused) = build_agents(&cfg.agents, &agents, &state.gateway);
+        *agents = Arc::new(next);
+        unused
+    };
+    *state.cache.write().unwrap_or_else(|err| err.into_inner()) = Arc::new(cfg.cache);
+    let _ = task::spawn_blocking(move || unused.iter().for_each(|pool| pool.close())).await;
+    eprintln!("config reloaded: {} agent(s)", cfg.agents.len());
 }
 
-async fn clusters_list(
-    State(state): State<AppState>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    exec_command_json(state, Command::ClusterList).await
+async fn rpc_handler(State(state): State<AppState>, body: Bytes) -> Response {
+    let value: Value = match serde_json::from_slice(&body) {
+        Ok(value) => value,
+        Err(err) => {
+            let resp = jsonrpc::parse_error(&format!("invalid JSON: {err}"));
+            return (StatusCode::BAD_REQUEST, Json(resp)).into_response();
+        }
+    };
+    match value {
+        Value::Array(items) => rpc_batch(state, items).await,
+        value => rpc_single(state, value).await,
+    }
 }
 
-async fn clusters_info(
-    State(state): State<AppState>,
-    Path(cluster): Path<String>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let cluster = parse_uuid_param(&cluster)?;
-    exec_command_json(state, Command::ClusterInfo { cluster }).await
+async fn rpc_single(state: AppState, value: Value) -> Response {
+    let call = match jsonrpc::parse_call(value) {
+        Ok(call) => call,
+        Err(resp) if resp.get("jsonrpc").is_some() => return Json(resp).into_response(),
+        Err(resp) => return (StatusCode::BAD_REQUEST, Json(resp)).into_response(),
+    };
+    let agent = state.agent(call.request().agent.as_deref());
+    let agent_label = agent
+        .as_ref()
+        .map(|agent| agent.cfg.name.clone())
+        .unwrap_or_default();
+    let result = match agent {
+        Ok(agent) => exec_request(&state, agent, call.request()).await,
+        Err(err) => Err(err),
+    };
+    match (&call, result) {
+        (Call::Legacy(_), Err(err)) => error_response(err).into_response(),
+        (call, result) => match call.respond(result, &agent_label) {
+            Some(resp) => Json(resp).into_response(),
+            None => StatusCode::NO_CONTENT.into_response(),
+        },
+    }
 }
 
-async fn managers_list(
-    State(state): State<AppState>,
-    Path(cluster): Path<String>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let cluster = parse_uuid_param(&cluster)?;
-    exec_command_json(state, Command::ManagerList { cluster }).await
+/// Executes a single call through the response cache.
+async fn exec_request(
+    state: &AppState,
+    agent: Arc<Agent>,
+    req: &RpcRequest,
+) -> Result<Value, RpcError> {
+    if req.command == jsonrpc::DISCOVER_METHOD {
+        return Ok(openapi::rpc_discover());
+    }
+    let args = resolve_names(&agent, req.args.clone()).await?;
+    let command = parse_command(RpcRequest {
+        command: req.command.clone(),
+        args,
+        agent: None,
+    })?;
+    let ttl = state.cache().ttl(&command);
+    gateway::spawn_blocking(move || exec_cached(&agent, ttl, command))
+        .await
+        .unwrap_or_else(|err| Err(RpcError::new("internal", format!("worker failed: {err}"))))
+        .map(|resp| resp.body.clone())
 }
 
-async fn managers_info(
-    State(state): State<AppState>,
-    Path((cluster, manager)): Path<(String, String)>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let cluster = parse_uuid_param(&cluster)?;
-    let manager = parse_uuid_param(&manager)?;
-    exec_command_json(state, Command::ManagerInfo { cluster, manager }).await
+/// Runs a batch in order on one pooled connection so cluster context and
+/// authentication carry over between calls.
+async fn rpc_batch(state: AppState, items: Vec<Value>) -> Response {
+    if items.is_empty() {
+        return Json(jsonrpc::invalid_request(Value::Null, "empty batch")).into_response();
+    }
+    let calls: Vec<Result<Call, Value>> = items.into_iter().map(jsonrpc::parse_call).collect();
+    let agent_name = calls
+        .iter()
+        .filter_map(|call| call.as_ref().ok())
+        .find_map(|call| call.request().agent.clone());
+    let agent = match state.agent(agent_name.as_deref()) {
+        Ok(agent) => agent,
+        Err(err) => {
+            let responses: Vec<Value> = calls
+                .iter()
+                .filter_map(|call| match call {
+                    Ok(call) => call.respond(Err(err.clone()), ""),
+                    Err(resp) => Some(resp.clone()),
+                })
+                .collect();
+            return Json(responses).into_response();
+        }
+    };
+    let agent_label = agent.cfg.name.clone();
+    let requests: Vec<Result<RpcRequest, RpcError>> = calls
+        .iter()
+        .filter_map(|call| call.as_ref().ok())
+        .map(|call| {
+            let req = call.request();
+            match &req.agent {
+                Some(name) if *name != agent_label => Err(RpcError::new(
+                    "bad_request",
+                    format!("all calls of a batch must use one agent, got {name}"),
+                )),
+                _ => Ok(RpcRequest {
+                    command: req.command.clone(),
+                    args: req.args.clone(),
+                    agent: None,
+                }),
+            }
+        })
+        .collect();
+    let results = gateway::spawn_blocking(move || exec_batch(&agent, requests))
+        .await
+        .unwrap_or_else(|err| {
+            vec![Err(RpcError::new(
+                "internal",
+                format!("worker failed: {err}"),
+            ))]
+        });
+
+    let mut results = results.into_iter();
+    let responses: Vec<Value> = calls
+        .iter()
+        .filter_map(|call| match call {
+            Ok(call) => {
+                let result = results
+                    .next()
+                    .unwrap_or_else(|| Err(RpcError::new("internal", "batch aborted".to_string())));
+                call.respond(result, &agent_label)
+            }
+            Err(resp) => Some(resp.clone()),
+        })
+        .collect();
+    if responses.is_empty() {
+        return StatusCode::NO_CONTENT.into_response();
+    }
+    Json(responses).into_response()
 }
 
-async fn servers_list(
-    State(state): State<AppState>,
-    Path(cluster): Path<String>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let cluster = parse_uuid_param(&cluster)?;
-    exec_command_json(state, Command::ServerList { cluster }).await
+fn exec_batch(
+    agent: &Agent,
+    requests: Vec<Result<RpcRequest, RpcError>>,
+) -> Vec<Result<Value, RpcError>> {
+    let mut client = None;
+    let mut healthy = true;
+    let mut results = Vec::with_capacity(requests.len());
+    for req in requests {
+        let result = req.and_then(|req| exec_on(agent, &mut client, req));
+        healthy &= keeps_connection(&result);
+        results.push(result);
+    }
+    if let Some(mut client) = client {
+        client.set_request_id(None);
+        agent.pool.release(client, healthy);
+    }
+    results
 }
 
-async fn servers_info(
-    State(state): State<AppState>,
-    Path((cluster, server)): Path<(String, String)>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let cluster = parse_uuid_param(&cluster)?;
-    let server = parse_uuid_param(&server)?;
-    exec_command_json(state, Command::ServerInfo { cluster, server }).await
+/// Runs one batch call, checking out the shared connection on first use.
+fn exec_on(
+    agent: &Agent,
+    client: &mut Option<Pooled<RacClient>>,
+    mut req: RpcRequest,
+) -> Result<Value, RpcError> {
+    if req.command == jsonrpc::DISCOVER_METHOD {
+        return Ok(openapi::rpc_discover());
+    }
+    let command = match req.args.as_mut().filter(|args| has_named_args(args)) {
+        Some(args) => {
+            let client = batch_client(agent, client)?;
+            resolve_named_args(client, &agent.resolver, &agent.cfg, args)?;
+            parse_command(req)?
+        }
+        None => parse_command(req)?,
+    };
+    let client = batch_client(agent, client)?;
+    let name = command.name().to_string();
+    if let Some(trace) = gateway::current() {
+        trace.command(&agent.cfg.name, &name);
+    }
+    let started = Instant::now();
+    let result =
+        authenticate(client, &agent.cfg, &command).and_then(|_| dispatch_command(client, command));
+    record_command(agent, &name, started.elapsed(), Duration::ZERO, &result);
+    result
 }
 
-async fn processes_list(
-    State(state): State<AppState>,
-    Path(cluster): Path<String>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let cluster = parse_uuid_param(&cluster)?;
-    exec_command_json(state, Command::ProcessList { cluster }).await
+/// Only RAC failures may leave the connection unusable; bad arguments or a
+/// missing name do not.
+fn keeps_connection<T>(result: &Result<T, RpcError>) -> bool {
+    !matches!(result, Err(err) if err.code == "rac_error")
 }
 
-async fn processes_info(
-    State(state): State<AppState>,
-    Path((cluster, process)): Path<(String, String)>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let cluster = parse_uuid_param(&cluster)?;
-    let process = parse_uuid_param(&process)?;
-    exec_command_json(state, Command::ProcessInfo { cluster, process }).await
+fn batch_client<'a>(
+    agent: &Agent,
+    client: &'a mut Option<Pooled<RacClient>>,
+) -> Result<&'a mut Pooled<RacClient>, RpcError> {
+    match client {
+        Some(client) => Ok(client),
+        None => {
+            let started = Instant::now();
+            let mut pooled = agent
+                .pool
+                .checkout(&agent.pool_key, &[])
+                .map_err(map_rac_error)?;
+            if let Some(trace) = gateway::current() {
+                trace.upstream(Duration::ZERO, started.elapsed());
+            }
+            pooled.set_request_id(gateway::current().map(|trace| trace.id().to_string()));
+            Ok(client.insert(pooled))
+        }
+    }
 }
 
-async fn infobase_summary_list(
-    State(state): State<AppState>,
-    Path(cluster): Path<String>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let cluster = parse_uuid_param(&cluster)?;
-    exec_command_json(state, Command::InfobaseSummaryList { cluster }).await
+async fn route_handler(
+    state: AppState,
+    headers: HeaderMap,
+    agent: Option<String>,
+    route: &'static RouteSpec,
+    params: HashMap<String, String>,
+    query: Vec<(String, String)>,
+) -> Result<Response, (StatusCode, Json<Value>)> {
+    let agent = state.agent(agent.as_deref()).map_err(error_value)?;
+    let mut args: serde_json::Map<String, Value> = params
+        .into_iter()
+        .map(|(key, value)| (key, Value::String(value)))
+        .collect();
+    let (command_args, query): (Vec<_>, Vec<_>) = query
+        .into_iter()
+        .partition(|(key, _)| route.query_args.iter().any(|arg| arg.name == key));
+    for (key, value) in command_args {
+        let kind = route.spec.arg(&key).map(|arg| arg.kind);
+        args.insert(key, query_arg_value(kind, value));
+    }
+    let query = list_query(route, &query).map_err(error_value)?;
+    let args = if args.is_empty() {
+        None
+    } else {
+        Some(Value::Object(args))
+    };
+    let args = resolve_names(&agent, args).await.map_err(error_value)?;
+    let command = parse_command(RpcRequest {
+        command: route.command.to_string(),
+        args,
+        agent: None,
+    })
+    .map_err(error_value)?;
+    let ttl = state.cache().ttl(&command);
+    let resp = exec_command_json(agent, ttl, command).await?;
+    match query {
+        Some(query) => {
+            let result = query.apply(&resp.body).map_err(error_value)?;
+            let etag = etag_for(&result.body);
+            let total = [(X_TOTAL_COUNT, result.total.to_string())];
+            Ok((total, json_with_etag(&headers, etag, &result.body)).into_response())
+        }
+        None => Ok(json_with_etag(&headers, resp.etag.clone(), &resp.body)),
+    }
 }
 
-async fn infobase_summary_info(
-    State(state): State<AppState>,
-    Path((cluster, infobase)): Path<(String, String)>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let cluster = parse_uuid_param(&cluster)?;
-    let infobase = parse_uuid_param(&infobase)?;
-    exec_command_json(state, Command::InfobaseSummaryInfo { cluster, infobase }).await
+/// Replaces cluster and infobase names in `args` with UUIDs; takes a
+/// connection only when some name is not a UUID already.
+async fn resolve_names(agent: &Arc<Agent>, args: Option<Value>) -> Result<Option<Value>, RpcError> {
+    let mut args = match args {
+        Some(args) if has_named_args(&args) => args,
+        args => return Ok(args),
+    };
+    let agent = agent.clone();
+    gateway::spawn_blocking(move || {
+        let mut client = agent
+            .pool
+            .checkout(&agent.pool_key, &[])
+            .map_err(map_rac_error)?;
+        client.set_request_id(gateway::current().map(|trace| trace.id().to_string()));
+        let result = resolve_named_args(&mut client, &agent.resolver, &agent.cfg, &mut args);
+        client.set_request_id(None);
+        agent.pool.release(client, keeps_connection(&result));
+        result.map(|_| Some(args))
+    })
+    .await
+    .unwrap_or_else(|err| Err(RpcError::new("internal", format!("worker failed: {err}"))))
 }
 
-async fn infobase_info(
-    State(state): State<AppState>,
-    Path((cluster, infobase)): Path<(String, String)>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let cluster = parse_uuid_param(&cluster)?;
-    let infobase = parse_uuid_param(&infobase)?;
-    exec_command_json(state, Command::InfobaseInfo { cluster, infobase }).await
+/// Query parameters are strings; numeric and boolean arguments are passed
+/// on as JSON numbers and booleans when they parse.
+fn query_arg_value(kind: Option<ArgKind>, raw: String) -> Value {
+    match kind {
+        Some(ArgKind::U8 | ArgKind::U16 | ArgKind::U32 | ArgKind::U64) => raw
+            .parse::<u64>()
+            .map(Value::from)
+            .unwrap_or(Value::String(raw)),
+        Some(ArgKind::Bool) => raw
+            .parse::<bool>()
+            .map(Value::Bool)
+            .unwrap_or(Value::String(raw)),
+        _ => Value::String(raw),
+    }
 }
 
-async fn connections_list(
-    State(state): State<AppState>,
-    Path(cluster): Path<String>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let cluster = parse_uuid_param(&cluster)?;
-    exec_command_json(state, Command::ConnectionList { cluster }).await
+/// Parses the list query parameters; only list routes accept them.
+fn list_query(
+    route: &RouteSpec,
+    params: &[(String, String)],
+) -> Result<Option<ListQuery>, RpcError> {
+    if params.is_empty() {
+        return Ok(None);
+    }
+    if !route.is_list() {
+        return Err(RpcError::new(
+            "bad_request",
+            format!("{} does not accept query parameters", route.path),
+        ));
+    }
+    ListQuery::parse(params).map(Some)
 }
 
-async fn connections_info(
-    State(state): State<AppState>,
-    Path((cluster, connection)): Path<(String, String)>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let cluster = parse_uuid_param(&cluster)?;
-    let connection = parse_uuid_param(&connection)?;
-    exec_command_json(state, Command::ConnectionInfo { cluster, connection }).await
+fn json_with_etag(headers: &HeaderMap, etag: String, body: &Value) -> Response {
+    let format = accept_format(headers);
+    let etag = format_etag(&etag, format);
+    let not_modified = headers
+        .get(IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| etag_matches(value, &etag));
+    let etag = [(ETAG, etag)];
+    if not_modified {
+        return (StatusCode::NOT_MODIFIED, etag).into_response();
+    }
+    (etag, formatted(format, body)).into_response()
 }
 
-async fn sessions_list(
-    State(state): State<AppState>,
-    Path(cluster): Path<String>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let cluster = parse_uuid_param(&cluster)?;
-    exec_command_json(state, Command::SessionList { cluster }).await
+fn accept_format(headers: &HeaderMap) -> Format {
+    negotiate_format(headers.get(ACCEPT).and_then(|value| value.to_str().ok()))
 }
 
-async fn sessions_info(
-    State(state): State<AppState>,
-    Path((cluster, session)): Path<(String, String)>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let cluster = parse_uuid_param(&cluster)?;
-    let session = parse_uuid_param(&session)?;
-    exec_command_json(state, Command::SessionInfo { cluster, session }).await
+/// `body` as JSON, or as CSV/NDJSON rows for tabular formats.
+fn formatted(format: Format, body: &Value) -> Response {
+    match format {
+        Format::Json => Json(body).into_response(),
+        format => (
+            [(CONTENT_TYPE, format.content_type())],
+            tabular::render(format, body),
+        )
+            .into_response(),
+    }
 }
 
-async fn locks_list(
+async fn clusters_aggregate(
     State(state): State<AppState>,
-    Path(cluster): Path<String>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let cluster = parse_uuid_param(&cluster)?;
-    exec_command_json(state, Command::LockList { cluster }).await
+    headers: HeaderMap,
+    Query(query): Query<Vec<(String, String)>>,
+) -> Result<Response, (StatusCode, Json<Value>)> {
+    let format = accept_format(&headers);
+    let query = ListQuery::parse(&query).map_err(error_value)?;
+    let command = Command::new("cluster.list", None).map_err(error_value)?;
+    let agents = state.agents();
+    let results = run_on_all_agents(&state, &agents, command)
+        .await
+        .into_iter()
+        .map(|result| result.map(|resp| resp.body.clone()))
+        .collect::<Vec<_>>();
+    let results = agents.iter().map(|agent| &agent.cfg).zip(results).collect();
+    let mut body = aggregate_clusters(results);
+    // Tabular formats carry the cluster rows only; agent errors stay in JSON.
+    let rows = |body: &Value| match format {
+        Format::Json => formatted(format, body),
+        format => formatted(format, &body["clusters"]),
+    };
+    if query.is_empty() {
+        return Ok(rows(&body));
+    }
+    let clusters = query.apply(&body["clusters"]).map_err(error_value)?;
+    body["clusters"] = clusters.body;
+    let total = [(X_TOTAL_COUNT, clusters.total.to_string())];
+    Ok((total, rows(&body)).into_response())
 }
 
-async fn profiles_list(
+async fn agents_health(
     State(state): State<AppState>,
-    Path(cluster): Path<String>,
 ) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let cluster = parse_uuid_param(&cluster)?;
-    exec_command_json(state, Command::ProfileList { cluster }).await
+    let command = Command::new("agent.version", None).map_err(error_value)?;
+    let agents = state.agents();
+    let results = run_on_all_agents(&state, &agents, command).await;
+    let statuses: Vec<AgentStatus> = agents
+        .iter()
+        .zip(results)
+        .map(|(agent, result)| match result {
+            Ok(resp) => AgentStatus {
+                version: Some(resp.body["version"].clone()),
+                ..AgentStatus::new(&agent.cfg, None)
+            },
+            Err(err) => AgentStatus::new(&agent.cfg, Some(err)),
+        })
+        .collect();
+    Ok(Json(json!({ "agents": statuses })))
 }
 
-async fn counters_list(
-    State(state): State<AppState>,
-    Path(cluster): Path<String>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let cluster = parse_uuid_param(&cluster)?;
-    exec_command_json(state, Command::CounterList { cluster }).await
+async fn run_on_all_agents(
+    state: &AppState,
+    agents: &[Arc<Agent>],
+    command: Command,
+) -> Vec<Result<Arc<CachedResponse>, RpcError>> {
+    let ttl = state.cache().ttl(&command);
+    let handles: Vec<_> = agents
+        .iter()
+        .map(|agent| {
+            let agent = agent.clone();
+            let command = command.clone();
+            gateway::spawn_blocking(move || exec_cached(&agent, ttl, command))
+        })
+        .collect();
+    let mut results = Vec::with_capacity(handles.len());
+    for handle in handles {
+        results.push(
+            handle.await.unwrap_or_else(|err| {
+                Err(RpcError::new("internal", format!("worker failed: {err}")))
+            }),
+        );
+    }
+    results
 }
 
-async fn counters_info(
+async fn events_sse(
     State(state): State<AppState>,
-    Path((cluster, counter)): Path<(String, String)>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let cluster = parse_uuid_param(&cluster)?;
-    exec_command_json(state, Command::CounterInfo { cluster, counter }).await
+    Path(params): Path<HashMap<String, String>>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, (StatusCode, Json<Value>)> {
+    let rx = subscribe_events(&state, &params)
+        .await
+        .map_err(error_value)?;
+    let stopping = state.shutdown.clone();
+    let events = stream::unfold((rx, stopping), |(mut rx, mut stopping)| async move {
+        let received = tokio::select! {
+            received = rx.recv() => received,
+            _ = stopping.wait_for(|stop| *stop) => return None,
+        };
+        let event = match received {
+            Ok(event) => Event::default()
+                .event(event.name())
+                .data(event_json(&event).to_string()),
+            Err(RecvError::Lagged(skipped)) => Event::default()
+                .event("lagged")
+                .data(json!({ "skipped": skipped }).to_string()),
+            Err(RecvError::Closed) => return None,
+        };
+        Some((Ok(event), (rx, stopping)))
+    });
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
 }
 
-async fn limits_list(
+async fn events_ws(
     State(state): State<AppState>,
-    Path(cluster): Path<String>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let cluster = parse_uuid_param(&cluster)?;
-    exec_command_json(state, Command::LimitList { cluster }).await
+    Path(params): Path<HashMap<String, String>>,
+    ws: WebSocketUpgrade,
+) -> Result<Response, (StatusCode, Json<Value>)> {
+    let mut rx = subscribe_events(&state, &params)
+        .await
+        .map_err(error_value)?;
+    let mut stopping = state.shutdown.clone();
+    Ok(ws.on_upgrade(|mut socket| async move {
+        loop {
+            let received = tokio::select! {
+                received = rx.recv() => received,
+                _ = stopping.wait_for(|stop| *stop) => break,
+            };
+            let payload = match received {
+                Ok(event) => event_json(&event),
+                Err(RecvError::Lagged(skipped)) => json!({ "kind": "lagged", "skipped": skipped }),
+                Err(RecvError::Closed) => break,
+            };
+            if socket
+                .send(Message::Text(payload.to_string().into()))
+                .await
+                .is_err()
+            {
+                break;
+            }
+        }
+    }))
+}
+
+async fn subscribe_events(
+    state: &AppState,
+    params: &HashMap<String, String>,
+) -> Result<broadcast::Receiver<Arc<ChangeEvent>>, RpcError> {
+    let agent = state.agent(params.get("agent").map(String::as_str))?;
+    let cluster = params
+        .get("cluster")
+        .ok_or_else(|| RpcError::new("bad_request", "missing cluster".to_string()))?;
+    let args = resolve_names(&agent, Some(json!({ "cluster": cluster })))
+        .await?
+        .unwrap_or_default();
+    let cluster = parse_uuid_arg(args["cluster"].as_str().unwrap_or_default())?;
+    let (rx, poller) = state.events.subscribe((agent.cfg.name.clone(), cluster));
+    if let Some(tx) = poller {
+        tokio::spawn(poll_events(state.clone(), agent, cluster, tx));
+    }
+    Ok(rx)
+}
+
+async fn poll_events(
+    state: AppState,
+    agent: Arc<Agent>,
+    cluster: Uuid16,
+    tx: broadcast::Sender<Arc<ChangeEvent>>,
+) {
+    let key = (agent.cfg.name.clone(), cluster);
+    let mut snapshots = HashMap::new();
+    let mut failing = HashSet::new();
+    let mut ticker = time::interval(state.events_poll);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        ticker.tick().await;
+        if state.events.release_if_idle(&key, &tx) {
+            return;
+        }
+        // Picks up agents replaced by a config reload.
+        let agent = state.agent(Some(&key.0));
+        for entity in ENTITIES {
+            let command = agent
+                .clone()
+                .and_then(|agent| Ok((agent, Command::for_cluster(entity.command, cluster)?)));
+            let result = match command {
+                Ok((agent, command)) => {
+                    let ttl = state.cache().ttl(&command);
+                    let worker = agent.clone();
+                    task::spawn_blocking(move || exec_cached(&worker, ttl, command))
+                        .await
+                        .unwrap_or_else(|err| {
+                            Err(RpcError::new("internal", format!("worker failed: {err}")))
+                        })
+                }
+                Err(err) => Err(err),
+            };
+            match result {
+                Ok(resp) => {
+                    failing.remove(entity.name);
+                    let next = snapshot(entity, &resp.body);
+                    if let Some(prev) = snapshots.get(entity.name) {
+                        for event in diff_snapshots(entity, prev, &next) {
+                            let _ = tx.send(Arc::new(event));
+                        }
+                    }
+                    snapshots.insert(entity.name, next);
+                }
+                // Report an outage once, not on every poll.
+                Err(error) => {
+                    if failing.insert(entity.name) {
+                        let _ = tx.send(Arc::new(ChangeEvent::Error {
+                            entity: entity.name,
+                            error,
+                        }));
+                    }
+                }
+            }
+        }
+    }
+}
+
+async fn pool_stats(State(state): State<AppState>) -> Json<Value> {
+    let agents: Vec<Value> = state
+        .agents()
+        .iter()
+        .map(|agent| {
+            json!({
+                "name": agent.cfg.name,
+                "rac_addr": agent.cfg.rac_addr,
+                "stats": agent.pool.stats(&agent.pool_key),
+            })
+        })
+        .collect();
+    Json(json!({ "agents": agents }))
 }
 
-async fn limits_info(
+async fn maintain_pools(state: AppState) {
+    let mut ticker = time::interval(POOL_MAINTAIN_INTERVAL);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        ticker.tick().await;
+        for agent in state.agents().iter() {
+            let agent = agent.clone();
+            // Failures are visible in /debug/pool counters.
+            let _ = task::spawn_blocking(move || agent.pool.maintain()).await;
+        }
+    }
+}
+
+async fn metrics_handler(
     State(state): State<AppState>,
-    Path((cluster, limit)): Path<(String, String)>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let cluster = parse_uuid_param(&cluster)?;
-    exec_command_json(state, Command::LimitInfo { cluster, limit }).await
+) -> Result<Response, (StatusCode, Json<Value>)> {
+    let text = state
+        .metrics
+        .read()
+        .map_err(|_| error_value(RpcError::new("internal", "metrics lock poisoned".into())))?
+        .clone();
+    match text {
+        Some(text) => Ok(([(CONTENT_TYPE, metrics::CONTENT_TYPE)], text).into_response()),
+        None => Err(error_value(RpcError::new(
+            "service_unavailable",
+            "metrics are disabled or not scraped yet".to_string(),
+        ))),
+    }
+}
+
+async fn scrape_metrics(state: AppState, interval: Duration) {
+    let mut ticker = time::interval(interval);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        ticker.tick().await;
+        let handles: Vec<_> = state
+            .agents()
+            .iter()
+            .map(|agent| {
+                let agent = agent.clone();
+                task::spawn_blocking(move || {
+                    let started = Instant::now();
+                    let result = agent
+                        .pool
+                        .checkout(&agent.pool_key, &[])
+                        .map_err(map_rac_error)
+                        .and_then(|mut client| {
+                            let result = metrics::scrape_agent(&mut client, &agent.cfg);
+                            agent.pool.release(client, keeps_connection(&result));
+                            result
+                        });
+                    AgentScrape {
+                        agent: agent.cfg.name.clone(),
+                        duration: started.elapsed(),
+                        result,
+                    }
+                })
+            })
+            .collect();
+        let mut scrapes = Vec::with_capacity(handles.len());
+        for handle in handles {
+            if let Ok(scrape) = handle.await {
+                scrapes.push(scrape);
+            }
+        }
+        let text = metrics::render(&scrapes);
+        if let Ok(mut slot) = state.metrics.write() {
+            *slot = Some(text);
+        }
+    }
+}
+
+/// Tags the request with an ID (the client's `X-Request-Id` or a new one),
+/// echoes it in the response and writes one JSON access log line to stderr.
+async fn trace_request(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let started = Instant::now();
+    let supplied = request
+        .headers()
+        .get(gateway::REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok());
+    let trace = Arc::new(RequestTrace::new(gateway::request_id(supplied)));
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let mut response = gateway::scope(trace.clone(), next.run(request)).await;
+    if let Ok(value) = HeaderValue::from_str(trace.id()) {
+        response
+            .headers_mut()
+            .insert(gateway::REQUEST_ID_HEADER, value);
+    }
+    let status = response.status().as_u16();
+    let duration = started.elapsed();
+    state.gateway.observe_request(&route, status, duration);
+    eprintln!("{}", trace.log_line(&method, &route, status, duration));
+    response
+}
+
+async fn gateway_metrics(State(state): State<AppState>) -> Response {
+    (
+        [(CONTENT_TYPE, metrics::CONTENT_TYPE)],
+        state.gateway.render(),
+    )
+        .into_response()
+}
+
+async fn openapi_json() -> Json<Value> {
+    Json(openapi::openapi_document())
+}
+
+async fn docs() -> Html<&'static str> {
+    Html(openapi::DOCS_HTML)
 }
 
 async fn exec_command_json(
-    state: AppState,
+    agent: Arc<Agent>,
+    ttl: Duration,
     command: Command,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let pool = state.pool.clone();
-    let result = task::spawn_blocking(move || exec_command(pool, command)).await;
+) -> Result<Arc<CachedResponse>, (StatusCode, Json<Value>)> {
+    let result = gateway::spawn_blocking(move || exec_cached(&agent, ttl, command)).await;
 
     match result {
-        Ok(Ok(payload)) => Ok(Json(payload)),
+        Ok(Ok(resp)) => Ok(resp),
         Ok(Err(err)) => Err(error_value(err)),
         Err(err) => Err(error_value(RpcError::new(
             "internal",
@@ -317,19 +1064,58 @@ async fn exec_command_json(
     }
 }
 
-fn exec_command(
-    pool: Arc<Pool<SystemClock>>,
+fn exec_cached(
+    agent: &Agent,
+    ttl: Duration,
     command: Command,
-) -> Result<serde_json::Value, RpcError> {
-    let mut client = pool.checkout()?;
-    let result = dispatch_command(&mut client, command);
-    let ok = result.is_ok();
-    pool.release(client, ok)?;
+) -> Result<Arc<CachedResponse>, RpcError> {
+    if let Some(trace) = gateway::current() {
+        trace.command(&agent.cfg.name, command.name());
+    }
+    agent
+        .cache
+        .get_or_fetch(command.clone(), ttl, || exec_command(agent, command))
+}
+
+fn exec_command(agent: &Agent, command: Command) -> Result<serde_json::Value, RpcError> {
+    let trace = gateway::current();
+    let name = command.name().to_string();
+    let started = Instant::now();
+    let wanted = wanted_auth(&agent.cfg, &command);
+    let mut client = match agent.pool.checkout(&agent.pool_key, &wanted) {
+        Ok(client) => client,
+        Err(err) => {
+            let result = Err(map_rac_error(err));
+            record_command(agent, &name, Duration::ZERO, started.elapsed(), &result);
+            return result;
+        }
+    };
+    let pool_wait = started.elapsed();
+    client.set_request_id(trace.map(|trace| trace.id().to_string()));
+    let started = Instant::now();
+    let result = authenticate(&mut client, &agent.cfg, &command)
+        .and_then(|_| dispatch_command(&mut client, command));
+    client.set_request_id(None);
+    record_command(agent, &name, started.elapsed(), pool_wait, &result);
+    agent.pool.release(client, keeps_connection(&result));
     result
 }
 
-fn parse_uuid_param(input: &str) -> Result<Uuid16, (StatusCode, Json<Value>)> {
-    parse_uuid(input).map_err(|err| error_value(RpcError::new("bad_request", err.to_string())))
+/// Adds one upstream call to the gateway metrics and the request trace.
+fn record_command(
+    agent: &Agent,
+    command: &str,
+    upstream: Duration,
+    pool_wait: Duration,
+    result: &Result<Value, RpcError>,
+) {
+    let error = result.as_ref().err().map(|err| err.code.as_str());
+    agent
+        .gateway
+        .observe_command(&agent.cfg.name, command, upstream, pool_wait, error);
+    if let Some(trace) = gateway::current() {
+        trace.upstream(upstream, pool_wait);
+    }
 }
 
 fn error_response(err: RpcError) -> (StatusCode, Json<RpcResponse>) {
@@ -345,35 +1131,11 @@ fn error_value(err: RpcError) -> (StatusCode, Json<Value>) {
 fn status_from_rpc_error(code: &str) -> StatusCode {
     match code {
         "bad_request" => StatusCode::BAD_REQUEST,
+        "not_found" => StatusCode::NOT_FOUND,
+        "ambiguous_name" => StatusCode::CONFLICT,
         "service_unavailable" => StatusCode::SERVICE_UNAVAILABLE,
         "rac_error" => StatusCode::BAD_GATEWAY,
+        "read_only" | "forbidden" => StatusCode::FORBIDDEN,
         _ => StatusCode::INTERNAL_SERVER_ERROR,
     }
 }
-
-fn command_name(cmd: &Command) -> &'static str {
-    match cmd {
-        Command::AgentVersion => "agent.version",
-        Command::ClusterList => "cluster.list",
-        Command::ClusterInfo { .. } => "cluster.info",
-        Command::ManagerList { .. } => "manager.list",
-        Command::ManagerInfo { .. } => "manager.info",
-        Command::ServerList { .. } => "server.list",
-        Command::ServerInfo { .. } => "server.info",
-        Command::ProcessList { .. } => "process.list",
-        Command::ProcessInfo { .. } => "process.info",
-        Command::InfobaseSummaryList { .. } => "infobase.summary_list",
-        Command::InfobaseSummaryInfo { .. } => "infobase.summary_info",
-        Command::InfobaseInfo { .. } => "infobase.info",
-        Command::ConnectionList { .. } => "connection.list",
-        Command::ConnectionInfo { .. } => "connection.info",
-        Command::SessionList { .. } => "session.list",
-        Command::SessionInfo { .. } => "session.info",
-        Command::LockList { .. } => "lock.list",
-        Command::ProfileList { .. } => "profile.list",
-        Command::CounterList { .. } => "counter.list",
-        Command::CounterInfo { .. } => "counter.info",
-        Command::LimitList { .. } => "limit.list",
-        Command::LimitInfo { .. } => "limit.info",
-    }
-}
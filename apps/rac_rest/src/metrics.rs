@@ -0,0 +1,508 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::time::Duration;
+
+use serde_json::Value;
+
+use rac_protocol::client::RacClient;
+use rac_protocol::commands::{counter_values, CounterValuesRpc};
+use rac_protocol::pool::Pooled;
+use rac_protocol::tabular::list_records;
+
+use crate::{
+    authenticate, dispatch_command, map_rac_error, parse_uuid_arg, AgentConfig, Command, RpcError,
+};
+
+pub const CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+const COUNTER_FIELDS: &[&str] = &[
+    "duration",
+    "cpu_time",
+    "memory",
+    "read",
+    "write",
+    "duration_dbms",
+    "dbms_bytes",
+    "service",
+    "call",
+    "number_of_active_sessions",
+    "number_of_sessions",
+];
+
+/// Metric families in output order: name and help text.
+const FAMILIES: &[(&str, &str)] = &[
+    ("rac_up", "Whether the last scrape of the agent succeeded."),
+    (
+        "rac_scrape_duration_seconds",
+        "Duration of the last agent scrape.",
+    ),
+    (
+        "rac_cluster_up",
+        "Whether every list of the cluster was scraped.",
+    ),
+    ("rac_sessions", "Sessions per infobase and application."),
+    (
+        "rac_session_cpu_time_ms",
+        "Session CPU time since start, ms.",
+    ),
+    (
+        "rac_session_dbms_bytes",
+        "Bytes exchanged with the DBMS since session start.",
+    ),
+    ("rac_session_licenses", "Sessions holding a license."),
+    ("rac_process_memory_size", "Working process memory, KB."),
+    (
+        "rac_process_avg_call_time",
+        "Working process average call time.",
+    ),
+    (
+        "rac_process_connections",
+        "Connections served by the working process.",
+    ),
+    (
+        "rac_process_available_performance",
+        "Working process available performance.",
+    ),
+    (
+        "rac_connections",
+        "Connections per infobase and application.",
+    ),
+    ("rac_locks", "Locks per infobase."),
+    ("rac_counter_value", "Resource counter values per object."),
+];
+
+/// Lists gathered for one cluster. Lists that could not be read stay empty
+/// and the first failure is kept in `error`.
+#[derive(Debug, Default)]
+pub struct ClusterScrape {
+    pub cluster: Value,
+    pub processes: Vec<Value>,
+    pub sessions: Vec<Value>,
+    pub connections: Vec<Value>,
+    pub locks: Vec<Value>,
+    pub infobases: Vec<Value>,
+    pub counters: Vec<(String, Vec<Value>)>,
+    pub error: Option<RpcError>,
+}
+
+#[derive(Debug)]
+pub struct AgentScrape {
+    pub agent: String,
+    pub duration: Duration,
+    pub result: Result<Vec<ClusterScrape>, RpcError>,
+}
+
+pub fn scrape_agent(
+    client: &mut Pooled<RacClient>,
+    agent: &AgentConfig,
+) -> Result<Vec<ClusterScrape>, RpcError> {
+    let clusters = dispatch_command(client, Command::new("cluster.list", None)?)?;
+    let clusters = records(&clusters).to_vec();
+    let mut out = Vec::with_capacity(clusters.len());
+    for cluster in clusters {
+        let mut scrape = ClusterScrape {
+            cluster,
+            ..ClusterScrape::default()
+        };
+        if let Err(err) = scrape_cluster(client, agent, &mut scrape) {
+            scrape.error = Some(err);
+        }
+        out.push(scrape);
+    }
+    Ok(out)
+}
+
+fn scrape_cluster(
+    client: &mut Pooled<RacClient>,
+    agent: &AgentConfig,
+    scrape: &mut ClusterScrape,
+) -> Result<(), RpcError> {
+    let cluster = parse_uuid_arg(label(&scrape.cluster, "uuid").as_str())?;
+    authenticate(
+        client,
+        agent,
+        &Command::for_cluster("session.list", cluster)?,
+    )?;
+    let mut list = |name: &str| -> Result<Vec<Value>, RpcError> {
+        let value = dispatch_command(client, Command::for_cluster(name, cluster)?)?;
+        Ok(records(&value).to_vec())
+    };
+    scrape.infobases = list("infobase.summary_list")?;
+    scrape.processes = list("process.list")?;
+    scrape.sessions = list("session.list")?;
+    scrape.connections = list("connection.list")?;
+    scrape.locks = list("lock.list")?;
+    let counters = list("counter.list")?;
+
+    let user = agent.cluster_user.as_deref().unwrap_or("");
+    let pwd = agent.cluster_pwd.as_deref().unwrap_or("");
+    for counter in counters {
+        let name = label(&counter, "name");
+        let resp = counter_values(
+            client,
+            user,
+            pwd,
+            CounterValuesRpc {
+                cluster,
+                counter: name.clone(),
+                object: String::new(),
+            },
+        )
+        .map_err(map_rac_error)?;
+        let values = serde_json::to_value(resp.records)
+            .map_err(|err| RpcError::new("internal", err.to_string()))?;
+        scrape.counters.push((name, records(&values).to_vec()));
+    }
+    Ok(())
+}
+
+/// Renders the last scrape of every agent in the OpenMetrics text format.
+pub fn render(scrapes: &[AgentScrape]) -> String {
+    let mut families = Families::default();
+    for scrape in scrapes {
+        let agent = scrape.agent.as_str();
+        families.add(
+            "rac_up",
+            &[("agent", agent)],
+            bool_value(scrape.result.is_ok()),
+        );
+        families.add(
+            "rac_scrape_duration_seconds",
+            &[("agent", agent)],
+            scrape.duration.as_secs_f64(),
+        );
+        if let Ok(clusters) = &scrape.result {
+            for cluster in clusters {
+                render_cluster(&mut families, agent, cluster);
+            }
+        }
+    }
+    families.render()
+}
+
+fn render_cluster(families: &mut Families, agent: &str, scrape: &ClusterScrape) {
+    let cluster = label(&scrape.cluster, "uuid");
+    let cluster = cluster.as_str();
+    families.add(
+        "rac_cluster_up",
+        &[
+            ("agent", agent),
+            ("cluster", cluster),
+            ("cluster_name", &label(&scrape.cluster, "display_name")),
+        ],
+        bool_value(scrape.error.is_none()),
+    );
+
+    let infobase_names: HashMap<String, String> = scrape
+        .infobases
+        .iter()
+        .map(|ib| (label(ib, "infobase"), label(ib, "name")))
+        .collect();
+    let infobase_name = |id: &str| infobase_names.get(id).cloned().unwrap_or_default();
+
+    let mut sessions: BTreeMap<(String, String), f64> = BTreeMap::new();
+    let mut licenses: BTreeMap<(String, String), f64> = BTreeMap::new();
+    let mut session_infobase = HashMap::new();
+    for session in &scrape.sessions {
+        let infobase = label(session, "infobase");
+        let app_id = label(session, "app_id");
+        *sessions
+            .entry((infobase.clone(), app_id.clone()))
+            .or_default() += 1.0;
+        session_infobase.insert(label(session, "session"), infobase.clone());
+
+        let labels = [
+            ("agent", agent),
+            ("cluster", cluster),
+            ("infobase", infobase.as_str()),
+            ("session", &label(session, "session")),
+            ("session_id", &label(session, "session_id")),
+            ("app_id", app_id.as_str()),
+            ("user_name", &label(session, "user_name")),
+        ];
+        families.add(
+            "rac_session_cpu_time_ms",
+            &labels,
+            number(session, "cpu_time_total"),
+        );
+        families.add(
+            "rac_session_dbms_bytes",
+            &labels,
+            number(session, "dbms_bytes_all"),
+        );
+
+        let license = &session["license"];
+        if !label(license, "full_presentation").is_empty() {
+            let key = (
+                label(license, "license_type"),
+                label(license, "issued_by_server"),
+            );
+            *licenses.entry(key).or_default() += 1.0;
+        }
+    }
+    for ((infobase, app_id), count) in sessions {
+        families.add(
+            "rac_sessions",
+            &[
+                ("agent", agent),
+                ("cluster", cluster),
+                ("infobase", &infobase),
+                ("infobase_name", &infobase_name(&infobase)),
+                ("app_id", &app_id),
+            ],
+            count,
+        );
+    }
+    for ((license_type, issued_by_server), count) in licenses {
+        families.add(
+            "rac_session_licenses",
+            &[
+                ("agent", agent),
+                ("cluster", cluster),
+                ("license_type", &license_type),
+                ("issued_by_server", &issued_by_server),
+            ],
+            count,
+        );
+    }
+
+    for process in &scrape.processes {
+        let labels = [
+            ("agent", agent),
+            ("cluster", cluster),
+            ("process", &label(process, "process")),
+            ("host", &label(process, "host")),
+            ("port", &label(process, "port")),
+        ];
+        families.add(
+            "rac_process_memory_size",
+            &labels,
+            number(process, "memory_size"),
+        );
+        families.add(
+            "rac_process_avg_call_time",
+            &labels,
+            number(process, "avg_call_time"),
+        );
+        families.add(
+            "rac_process_connections",
+            &labels,
+            number(process, "connections"),
+        );
+        families.add(
+            "rac_process_available_performance",
+            &labels,
+            number(process, "available_performance"),
+        );
+    }
+
+    let mut connections: BTreeMap<(String, String), f64> = BTreeMap::new();
+    for connection in &scrape.connections {
+        let key = (
+            label(connection, "infobase"),
+            label(connection, "application"),
+        );
+        *connections.entry(key).or_default() += 1.0;
+    }
+    for ((infobase, application), count) in connections {
+        families.add(
+            "rac_connections",
+            &[
+                ("agent", agent),
+                ("cluster", cluster),
+                ("infobase", &infobase),
+                ("infobase_name", &infobase_name(&infobase)),
+                ("application", &application),
+            ],
+            count,
+        );
+    }
+
+    let mut locks: BTreeMap<String, f64> = BTreeMap::new();
+    for lock in &scrape.locks {
+        let infobase = session_infobase
+            .get(&label(lock, "session"))
+            .cloned()
+            .unwrap_or_default();
+        *locks.entry(infobase).or_default() += 1.0;
+    }
+    for (infobase, count) in locks {
+        families.add(
+            "rac_locks",
+            &[
+                ("agent", agent),
+                ("cluster", cluster),
+                ("infobase", &infobase),
+                ("infobase_name", &infobase_name(&infobase)),
+            ],
+            count,
+        );
+    }
+
+    for (counter, values) in &scrape.counters {
+        for value in values {
+            let object = label(value, "object");
+            for field in COUNTER_FIELDS {
+                families.add(
+                    "rac_counter_value",
+                    &[
+                        ("agent", agent),
+                        ("cluster", cluster),
+                        ("counter", counter),
+                        ("object", &object),
+                        ("field", field),
+                    ],
+                    number(value, field),
+                );
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct Families {
+    samples: HashMap<&'static str, BTreeMap<String, f64>>,
+}
+
+impl Families {
+    fn add(&mut self, name: &'static str, labels: &[(&str, &str)], value: f64) {
+        let mut rendered = String::new();
+        for (idx, (key, val)) in labels.iter().enumerate() {
+            if idx > 0 {
+                rendered.push(',');
+            }
+            let _ = write!(rendered, "{key}=\"{}\"", escape_label(val));
+        }
+        self.samples
+            .entry(name)
+            .or_default()
+            .insert(rendered, value);
+    }
+
+    fn render(&self) -> String {
+        let mut out = String::new();
+        for (name, help) in FAMILIES {
+            let _ = writeln!(out, "# TYPE {name} gauge");
+            let _ = writeln!(out, "# HELP {name} {help}");
+            if let Some(samples) = self.samples.get(name) {
+                for (labels, value) in samples {
+                    let _ = writeln!(out, "{name}{{{labels}}} {value}");
+                }
+            }
+        }
+        out.push_str("# EOF\n");
+        out
+    }
+}
+
+pub(crate) fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn records(value: &Value) -> &[Value] {
+    list_records(value).map_or(&[], Vec::as_slice)
+}
+
+fn label(record: &Value, field: &str) -> String {
+    match &record[field] {
+        Value::Null => String::new(),
+        Value::String(value) => value.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn number(record: &Value, field: &str) -> f64 {
+    match &record[field] {
+        Value::Bool(value) => bool_value(*value),
+        other => other.as_f64().unwrap_or(0.0),
+    }
+}
+
+fn bool_value(value: bool) -> f64 {
+    if value {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample_cluster() -> ClusterScrape {
+        ClusterScrape {
+            cluster: json!({ "uuid": "c1", "display_name": "Main \"prod\"" }),
+            infobases: vec![json!({ "infobase": "ib1", "name": "erp" })],
+            sessions: vec![
+                json!({
+                    "session": "s1", "session_id": 1, "infobase": "ib1", "app_id": "1CV8C",
+                    "user_name": "ivan", "cpu_time_total": 1500, "dbms_bytes_all": 4096,
+                    "license": { "full_presentation": "key", "license_type": 0, "issued_by_server": true },
+                }),
+                json!({
+                    "session": "s2", "session_id": 2, "infobase": "ib1", "app_id": "1CV8C",
+                    "user_name": "petr", "cpu_time_total": 0, "dbms_bytes_all": 0,
+                    "license": { "full_presentation": "" },
+                }),
+            ],
+            processes: vec![json!({
+                "process": "p1", "host": "srv", "port": 1560, "memory_size": 204800,
+                "avg_call_time": 1.25, "connections": 3, "available_performance": 180,
+            })],
+            connections: vec![json!({ "infobase": "ib1", "application": "1CV8C" })],
+            locks: vec![json!({ "session": "s1" }), json!({ "session": "gone" })],
+            counters: vec![(
+                "cpu".to_string(),
+                vec![json!({ "object": "ivan", "cpu_time": 42 })],
+            )],
+            error: None,
+        }
+    }
+
+    #[test]
+    fn render_emits_stable_openmetrics() {
+        let text = render(&[
+            AgentScrape {
+                agent: "prod".to_string(),
+                duration: Duration::from_millis(250),
+                result: Ok(vec![sample_cluster()]),
+            },
+            AgentScrape {
+                agent: "test".to_string(),
+                duration: Duration::from_millis(5),
+                result: Err(RpcError::new("rac_error", "refused".to_string())),
+            },
+        ]);
+        assert!(text.ends_with("# EOF\n"));
+        for line in [
+            "rac_up{agent=\"prod\"} 1",
+            "rac_up{agent=\"test\"} 0",
+            "rac_scrape_duration_seconds{agent=\"prod\"} 0.25",
+            "rac_cluster_up{agent=\"prod\",cluster=\"c1\",cluster_name=\"Main \\\"prod\\\"\"} 1",
+            "rac_sessions{agent=\"prod\",cluster=\"c1\",infobase=\"ib1\",infobase_name=\"erp\",app_id=\"1CV8C\"} 2",
+            "rac_session_cpu_time_ms{agent=\"prod\",cluster=\"c1\",infobase=\"ib1\",session=\"s1\",session_id=\"1\",app_id=\"1CV8C\",user_name=\"ivan\"} 1500",
+            "rac_session_licenses{agent=\"prod\",cluster=\"c1\",license_type=\"0\",issued_by_server=\"true\"} 1",
+            "rac_process_memory_size{agent=\"prod\",cluster=\"c1\",process=\"p1\",host=\"srv\",port=\"1560\"} 204800",
+            "rac_process_avg_call_time{agent=\"prod\",cluster=\"c1\",process=\"p1\",host=\"srv\",port=\"1560\"} 1.25",
+            "rac_connections{agent=\"prod\",cluster=\"c1\",infobase=\"ib1\",infobase_name=\"erp\",application=\"1CV8C\"} 1",
+            "rac_locks{agent=\"prod\",cluster=\"c1\",infobase=\"ib1\",infobase_name=\"erp\"} 1",
+            "rac_locks{agent=\"prod\",cluster=\"c1\",infobase=\"\",infobase_name=\"\"} 1",
+            "rac_counter_value{agent=\"prod\",cluster=\"c1\",counter=\"cpu\",object=\"ivan\",field=\"cpu_time\"} 42",
+        ] {
+            assert!(text.lines().any(|l| l == line), "missing {line}\n{text}");
+        }
+        assert_eq!(text.matches("# TYPE ").count(), FAMILIES.len());
+    }
+
+    #[test]
+    fn records_unwraps_list_bodies() {
+        assert_eq!(records(&json!({ "records": [1, 2] })).len(), 2);
+        assert_eq!(records(&json!([1])).len(), 1);
+        assert!(records(&json!("x")).is_empty());
+    }
+}
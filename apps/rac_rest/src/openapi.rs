@@ -0,0 +1,920 @@
+use serde_json::{json, Map, Value};
+
+use crate::jsonrpc::{DISCOVER_METHOD, VERSION as JSONRPC_VERSION};
+use rac_protocol::registry::{ArgKind, ArgSpec};
+
+use crate::{routes, RouteSpec};
+
+const OPENAPI_VERSION: &str = "3.0.3";
+const OPENRPC_VERSION: &str = "1.2.6";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    Uuid,
+    DateTime,
+    String,
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    F64,
+    Bytes,
+    StringList,
+    Record(&'static str),
+    List(&'static str),
+}
+
+#[derive(Debug)]
+pub struct FieldSchema {
+    pub name: &'static str,
+    pub kind: FieldKind,
+    pub optional: bool,
+}
+
+#[derive(Debug)]
+pub struct RecordSchema {
+    pub name: &'static str,
+    pub fields: &'static [FieldSchema],
+}
+
+#[derive(Debug)]
+pub struct ResponseSchema {
+    pub name: &'static str,
+    pub field: &'static str,
+    pub kind: FieldKind,
+}
+
+include!("openapi_generated.rs");
+
+pub const DOCS_HTML: &str = r#"<!DOCTYPE html>
+<html>
+  <head>
+    <title>rac_rest API</title>
+    <meta charset="utf-8"/>
+    <meta name="viewport" content="width=device-width, initial-scale=1">
+  </head>
+  <body>
+    <redoc spec-url="/openapi.json"></redoc>
+    <script src="https://cdn.redoc.ly/redoc/latest/bundles/redoc.standalone.js"></script>
+  </body>
+</html>
+"#;
+
+pub fn openapi_document() -> Value {
+    let mut paths = Map::new();
+    paths.insert("/rpc".to_string(), rpc_path_item());
+    paths.insert("/agents".to_string(), agents_path_item());
+    paths.insert("/metrics".to_string(), metrics_path_item());
+    paths.insert("/metrics/gateway".to_string(), gateway_metrics_path_item());
+    paths.insert("/debug/pool".to_string(), pool_path_item());
+    for prefix in ["", "/agents/{agent}"] {
+        paths.insert(
+            format!("{prefix}/clusters/{{cluster}}/events"),
+            events_path_item(!prefix.is_empty(), false),
+        );
+        paths.insert(
+            format!("{prefix}/clusters/{{cluster}}/events/ws"),
+            events_path_item(!prefix.is_empty(), true),
+        );
+    }
+    for route in routes() {
+        let item = if route.path == "/clusters" {
+            aggregated_clusters_path_item()
+        } else {
+            route_path_item(route, false)
+        };
+        paths.insert(route.path.clone(), item);
+        paths.insert(
+            format!("/agents/{{agent}}{}", route.path),
+            route_path_item(route, true),
+        );
+    }
+
+    json!({
+        "openapi": OPENAPI_VERSION,
+        "info": {
+            "title": "rac_rest",
+            "description": "REST gateway to the 1C RAC administration protocol.",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "paths": paths,
+        "components": { "schemas": component_schemas() },
+    })
+}
+
+fn route_path_item(route: &RouteSpec, prefixed: bool) -> Value {
+    let mut parameters: Vec<Value> = Vec::new();
+    if prefixed {
+        parameters.push(json!({
+            "name": "agent",
+            "in": "path",
+            "required": true,
+            "description": "Agent name from the `[[agents]]` config section.",
+            "schema": { "type": "string" },
+        }));
+    }
+    parameters.extend(path_params(&route.path).map(|name| {
+        json!({
+            "name": name,
+            "in": "path",
+            "required": true,
+            "schema": route.spec.arg(name).map_or_else(|| json!({ "type": "string" }), arg_schema),
+        })
+    }));
+    parameters.extend(route.query_args.iter().map(|arg| {
+        json!({
+            "name": arg.name,
+            "in": "query",
+            "required": true,
+            "schema": arg_schema(arg),
+        })
+    }));
+    if route.is_list() {
+        parameters.extend(list_query_params());
+    }
+    parameters.push(json!({
+        "name": "If-None-Match",
+        "in": "header",
+        "required": false,
+        "schema": { "type": "string" },
+    }));
+    let mut operation_id = operation_id(route.command);
+    if prefixed {
+        operation_id = format!(
+            "agent{}{}",
+            operation_id[..1].to_ascii_uppercase(),
+            &operation_id[1..]
+        );
+    }
+    json!({
+        "get": {
+            "operationId": operation_id,
+            "summary": route.summary,
+            "tags": [tag(route.command)],
+            "parameters": parameters,
+            "responses": with_error_responses(json!({
+                "200": {
+                    "description": "OK",
+                    "headers": response_headers(route),
+                    "content": tabular_content(json!({ "schema": kind_schema(route.response) })),
+                },
+                "304": { "description": "Not modified since the ETag sent in If-None-Match" },
+            })),
+        }
+    })
+}
+
+/// JSON body plus the CSV and NDJSON renderings chosen by `Accept`; nested
+/// fields become dotted columns.
+fn tabular_content(json_body: Value) -> Value {
+    json!({
+        "application/json": json_body,
+        "text/csv": { "schema": { "type": "string" } },
+        "application/x-ndjson": { "schema": { "type": "string" } },
+    })
+}
+
+/// Generic filtering, sorting, projection and pagination of list routes;
+/// see [`crate::query::ListQuery`].
+fn list_query_params() -> Vec<Value> {
+    vec![
+        json!({
+            "name": "sort",
+            "in": "query",
+            "required": false,
+            "description": "Comma-separated record fields; `-` prefix sorts descending.",
+            "schema": { "type": "string" },
+        }),
+        json!({
+            "name": "fields",
+            "in": "query",
+            "required": false,
+            "description": "Comma-separated record fields to return.",
+            "schema": { "type": "string" },
+        }),
+        json!({
+            "name": "limit",
+            "in": "query",
+            "required": false,
+            "schema": { "type": "integer", "minimum": 0 },
+        }),
+        json!({
+            "name": "offset",
+            "in": "query",
+            "required": false,
+            "schema": { "type": "integer", "minimum": 0 },
+        }),
+        json!({
+            "name": "filters",
+            "in": "query",
+            "required": false,
+            "description": "Any other parameter filters records: `field=value` keeps exact matches, `field~=value` case-insensitive substrings.",
+            "style": "form",
+            "explode": true,
+            "schema": { "type": "object", "additionalProperties": { "type": "string" } },
+        }),
+    ]
+}
+
+fn response_headers(route: &RouteSpec) -> Value {
+    let mut headers = json!({ "ETag": { "schema": { "type": "string" } } });
+    if route.is_list() {
+        headers["X-Total-Count"] = json!({
+            "description": "Records matching the filters before `limit`/`offset`.",
+            "schema": { "type": "integer" },
+        });
+    }
+    headers
+}
+
+fn agents_path_item() -> Value {
+    json!({
+        "get": {
+            "operationId": "agentsHealth",
+            "summary": "Configured agents with their reachability and version",
+            "tags": ["agents"],
+            "responses": {
+                "200": {
+                    "description": "OK",
+                    "content": { "application/json": { "schema": object_schema(
+                        json!({ "agents": { "type": "array", "items": schema_ref("AgentStatus") } }),
+                        &["agents"],
+                    ) } },
+                },
+            },
+        }
+    })
+}
+
+fn pool_path_item() -> Value {
+    json!({
+        "get": {
+            "operationId": "debugPool",
+            "summary": "Connection pool state per agent",
+            "tags": ["debug"],
+            "responses": {
+                "200": {
+                    "description": "OK",
+                    "content": { "application/json": { "schema": object_schema(
+                        json!({ "agents": { "type": "array", "items": object_schema(
+                            json!({
+                                "name": { "type": "string" },
+                                "rac_addr": { "type": "string" },
+                                "stats": schema_ref("PoolStats"),
+                            }),
+                            &["name", "rac_addr"],
+                        ) } }),
+                        &["agents"],
+                    ) } },
+                },
+            },
+        }
+    })
+}
+
+fn metrics_path_item() -> Value {
+    json!({
+        "get": {
+            "operationId": "metrics",
+            "summary": "Cluster state gauges from the last background scrape",
+            "tags": ["metrics"],
+            "responses": {
+                "200": {
+                    "description": "OpenMetrics text exposition",
+                    "content": { "application/openmetrics-text": { "schema": { "type": "string" } } },
+                },
+                "503": {
+                    "description": "Metrics disabled or first scrape not finished",
+                    "content": { "application/json": { "schema": schema_ref("ErrorBody") } },
+                },
+            },
+        }
+    })
+}
+
+fn gateway_metrics_path_item() -> Value {
+    json!({
+        "get": {
+            "operationId": "gatewayMetrics",
+            "summary": "Request and upstream call latency histograms of the gateway",
+            "tags": ["metrics"],
+            "responses": {
+                "200": {
+                    "description": "OpenMetrics text exposition",
+                    "content": { "application/openmetrics-text": { "schema": { "type": "string" } } },
+                },
+            },
+        }
+    })
+}
+
+fn events_path_item(prefixed: bool, ws: bool) -> Value {
+    let mut parameters = Vec::new();
+    if prefixed {
+        parameters.push(json!({
+            "name": "agent",
+            "in": "path",
+            "required": true,
+            "schema": { "type": "string" },
+        }));
+    }
+    parameters.push(json!({
+        "name": "cluster",
+        "in": "path",
+        "required": true,
+        "schema": arg_schema(&ArgSpec { name: "cluster", kind: ArgKind::Uuid }),
+    }));
+    let (operation_id, summary, response) = if ws {
+        (
+            "clusterEventsWs",
+            "Cluster change events over WebSocket (one JSON ChangeEvent per text message)",
+            json!({ "101": { "description": "Switching to WebSocket" } }),
+        )
+    } else {
+        (
+            "clusterEvents",
+            "Cluster change events as Server-Sent Events named `<entity>.<kind>`",
+            json!({
+                "200": {
+                    "description": "Event stream; every `data` line is a ChangeEvent",
+                    "content": { "text/event-stream": { "schema": { "type": "string" } } },
+                },
+            }),
+        )
+    };
+    let operation_id = if prefixed {
+        format!(
+            "agent{}{}",
+            operation_id[..1].to_ascii_uppercase(),
+            &operation_id[1..]
+        )
+    } else {
+        operation_id.to_string()
+    };
+    json!({
+        "get": {
+            "operationId": operation_id,
+            "summary": summary,
+            "tags": ["events"],
+            "parameters": parameters,
+            "responses": with_error_responses(response),
+        }
+    })
+}
+
+fn aggregated_clusters_path_item() -> Value {
+    json!({
+        "get": {
+            "operationId": "clusterList",
+            "summary": "Clusters of every configured agent",
+            "tags": ["cluster"],
+            "parameters": list_query_params(),
+            "responses": {
+                "200": {
+                    "description": "OK; unreachable agents are reported in `agents`",
+                    "headers": { "X-Total-Count": { "schema": { "type": "integer" } } },
+                    "content": tabular_content(json!({ "schema": schema_ref("AggregatedClusters") })),
+                },
+            },
+        }
+    })
+}
+
+fn rpc_path_item() -> Value {
+    json!({
+        "post": {
+            "operationId": "rpc",
+            "summary": "Execute a RAC command by name",
+            "description": "Accepts a single call or an array of calls executed in order on one connection. Calls are either RpcRequest objects or JSON-RPC 2.0 requests; `rpc.discover` lists all methods.",
+            "tags": ["rpc"],
+            "requestBody": {
+                "required": true,
+                "content": { "application/json": { "schema": one_or_batch(&["RpcRequest", "JsonRpcRequest"]) } },
+            },
+            "responses": {
+                "200": {
+                    "description": "Command result, or one result per call for batches; JSON-RPC errors are returned with status 200",
+                    "content": { "application/json": { "schema": one_or_batch(&["RpcResponse", "JsonRpcResponse"]) } },
+                },
+                "204": { "description": "Only JSON-RPC notifications were sent" },
+                "400": rpc_error_response("Invalid command or arguments"),
+                "404": rpc_error_response("Unknown agent"),
+                "500": rpc_error_response("Internal error"),
+                "502": rpc_error_response("RAC server returned an error"),
+                "503": rpc_error_response("No RAC connection available"),
+            },
+        }
+    })
+}
+
+fn one_or_batch(names: &[&str]) -> Value {
+    let single: Vec<Value> = names.iter().map(|name| schema_ref(name)).collect();
+    let mut variants = single.clone();
+    variants.push(json!({ "type": "array", "items": { "oneOf": single } }));
+    json!({ "oneOf": variants })
+}
+
+fn with_error_responses(value: Value) -> Value {
+    let mut responses = match value {
+        Value::Object(map) => map,
+        _ => Map::new(),
+    };
+    for (status, description) in [
+        ("400", "Invalid path parameter"),
+        ("404", "Unknown agent, cluster or infobase name"),
+        ("409", "Cluster or infobase name matches several objects"),
+        ("500", "Internal error"),
+        ("502", "RAC server returned an error"),
+        ("503", "No RAC connection available"),
+    ] {
+        responses.insert(
+            status.to_string(),
+            json!({
+                "description": description,
+                "content": { "application/json": { "schema": schema_ref("ErrorBody") } },
+            }),
+        );
+    }
+    Value::Object(responses)
+}
+
+fn rpc_error_response(description: &str) -> Value {
+    json!({
+        "description": description,
+        "content": { "application/json": { "schema": schema_ref("RpcResponse") } },
+    })
+}
+
+fn component_schemas() -> Map<String, Value> {
+    let mut schemas = Map::new();
+    for record in RECORD_SCHEMAS {
+        let properties: Map<String, Value> = record
+            .fields
+            .iter()
+            .map(|field| {
+                (
+                    field.name.to_string(),
+                    field_schema(field.kind, field.optional),
+                )
+            })
+            .collect();
+        let required: Vec<&str> = record.fields.iter().map(|field| field.name).collect();
+        schemas.insert(
+            record.name.to_string(),
+            object_schema(Value::Object(properties), &required),
+        );
+    }
+    for resp in RESPONSE_SCHEMAS {
+        let mut properties = Map::new();
+        properties.insert(resp.field.to_string(), kind_schema(resp.kind));
+        schemas.insert(
+            resp.name.to_string(),
+            object_schema(Value::Object(properties), &[resp.field]),
+        );
+    }
+    schemas.insert(
+        "LockDescr".to_string(),
+        object_schema(
+            json!({
+                "descr": { "type": "string" },
+                "descr_flag": { "type": "integer", "minimum": 0, "maximum": 255, "nullable": true },
+            }),
+            &["descr", "descr_flag"],
+        ),
+    );
+    schemas.insert(
+        "AckResponse".to_string(),
+        object_schema(
+            json!({ "acknowledged": { "type": "boolean" } }),
+            &["acknowledged"],
+        ),
+    );
+    schemas.insert(
+        "RpcError".to_string(),
+        object_schema(
+            json!({
+                "code": {
+                    "type": "string",
+                    "enum": [
+                        "bad_request",
+                        "not_found",
+                        "ambiguous_name",
+                        "service_unavailable",
+                        "rac_error",
+                        "read_only",
+                        "forbidden",
+                        "internal",
+                        "config_error",
+                    ],
+                },
+                "message": { "type": "string" },
+                "details": { "type": "object", "additionalProperties": true },
+            }),
+            &["code", "message"],
+        ),
+    );
+    schemas.insert(
+        "AgentStatus".to_string(),
+        object_schema(
+            json!({
+                "name": { "type": "string" },
+                "rac_addr": { "type": "string" },
+                "status": { "type": "string", "enum": ["ok", "error"] },
+                "version": { "type": "string" },
+                "error": schema_ref("RpcError"),
+            }),
+            &["name", "rac_addr", "status"],
+        ),
+    );
+    let counter = json!({ "type": "integer", "minimum": 0 });
+    schemas.insert(
+        "PoolStats".to_string(),
+        object_schema(
+            json!({
+                "max": counter,
+                "in_use": counter,
+                "idle": counter,
+                "waiters": counter,
+                "connects": counter,
+                "connect_failures": counter,
+                "checkout_timeouts": counter,
+                "validation_failures": counter,
+            }),
+            &[
+                "max",
+                "in_use",
+                "idle",
+                "waiters",
+                "connects",
+                "connect_failures",
+                "checkout_timeouts",
+                "validation_failures",
+            ],
+        ),
+    );
+    schemas.insert(
+        "ChangeEvent".to_string(),
+        object_schema(
+            json!({
+                "kind": { "type": "string", "enum": ["added", "removed", "changed", "error"] },
+                "entity": { "type": "string", "enum": ["session", "connection", "lock", "process"] },
+                "id": {
+                    "type": "string",
+                    "description": "Record UUID; `connection/session/object` for locks.",
+                },
+                "record": { "type": "object", "description": "Record as returned by the list route." },
+                "changes": {
+                    "type": "object",
+                    "description": "Changed fields of a `changed` event.",
+                    "additionalProperties": object_schema(json!({ "old": {}, "new": {} }), &["old", "new"]),
+                },
+                "error": schema_ref("RpcError"),
+            }),
+            &["kind", "entity"],
+        ),
+    );
+    schemas.insert(
+        "AggregatedClusters".to_string(),
+        object_schema(
+            json!({
+                "clusters": {
+                    "type": "array",
+                    "items": { "allOf": [
+                        schema_ref("ClusterRecord"),
+                        object_schema(json!({ "agent": { "type": "string" } }), &["agent"]),
+                    ] },
+                },
+                "agents": { "type": "array", "items": schema_ref("AgentStatus") },
+            }),
+            &["clusters", "agents"],
+        ),
+    );
+    schemas.insert(
+        "ErrorBody".to_string(),
+        object_schema(json!({ "error": schema_ref("RpcError") }), &["error"]),
+    );
+    let commands: Vec<&str> = routes().iter().map(|route| route.command).collect();
+    schemas.insert(
+        "RpcRequest".to_string(),
+        object_schema(
+            json!({
+                "command": { "type": "string", "enum": commands },
+                "args": {
+                    "type": "object",
+                    "description": "Command arguments: the path and query parameters of the matching GET route. `rpc.discover` lists them with their types.",
+                },
+                "agent": {
+                    "type": "string",
+                    "description": "Agent name; the first configured agent when omitted.",
+                },
+            }),
+            &["command"],
+        ),
+    );
+    schemas.insert(
+        "RpcResponse".to_string(),
+        object_schema(
+            json!({
+                "result": { "description": "Same payload as the matching GET route." },
+                "error": schema_ref("RpcError"),
+                "meta": object_schema(
+                    json!({ "command": { "type": "string" }, "agent": { "type": "string" } }),
+                    &["command", "agent"],
+                ),
+            }),
+            &[],
+        ),
+    );
+    let mut methods = commands.clone();
+    methods.push(DISCOVER_METHOD);
+    schemas.insert(
+        "JsonRpcRequest".to_string(),
+        object_schema(
+            json!({
+                "jsonrpc": { "type": "string", "enum": [JSONRPC_VERSION] },
+                "id": { "oneOf": [{ "type": "string" }, { "type": "integer" }], "nullable": true },
+                "method": { "type": "string", "enum": methods },
+                "params": {
+                    "type": "object",
+                    "description": "Named arguments, same as `args` of RpcRequest.",
+                },
+                "agent": {
+                    "type": "string",
+                    "description": "Agent name (extension member); the first configured agent when omitted.",
+                },
+            }),
+            &["jsonrpc", "method"],
+        ),
+    );
+    schemas.insert(
+        "JsonRpcResponse".to_string(),
+        object_schema(
+            json!({
+                "jsonrpc": { "type": "string", "enum": [JSONRPC_VERSION] },
+                "id": { "oneOf": [{ "type": "string" }, { "type": "integer" }], "nullable": true },
+                "result": { "description": "Same payload as the matching GET route." },
+                "error": object_schema(
+                    json!({
+                        "code": { "type": "integer" },
+                        "message": { "type": "string" },
+                        "data": object_schema(
+                            json!({
+                                "code": { "type": "string", "description": "RpcError code" },
+                                "details": { "type": "object" },
+                            }),
+                            &[],
+                        ),
+                    }),
+                    &["code", "message"],
+                ),
+            }),
+            &["jsonrpc", "id"],
+        ),
+    );
+    schemas
+}
+
+/// OpenRPC document returned by the `rpc.discover` method.
+pub fn rpc_discover() -> Value {
+    let methods: Vec<Value> = routes()
+        .iter()
+        .map(|route| {
+            let params: Vec<Value> = route
+                .spec
+                .args
+                .iter()
+                .map(|arg| json!({ "name": arg.name, "required": true, "schema": arg_schema(arg) }))
+                .collect();
+            json!({
+                "name": route.command,
+                "summary": route.summary,
+                "tags": [{ "name": tag(route.command) }],
+                "paramStructure": "by-name",
+                "params": params,
+                "result": { "name": "result", "schema": kind_schema(route.response) },
+            })
+        })
+        .collect();
+    json!({
+        "openrpc": OPENRPC_VERSION,
+        "info": {
+            "title": "rac_rest",
+            "description": "RAC commands available over POST /rpc.",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "methods": methods,
+        "components": { "schemas": component_schemas() },
+    })
+}
+
+fn object_schema(properties: Value, required: &[&str]) -> Value {
+    let mut schema = json!({ "type": "object", "properties": properties });
+    if !required.is_empty() {
+        schema["required"] = json!(required);
+    }
+    schema
+}
+
+fn field_schema(kind: FieldKind, optional: bool) -> Value {
+    let mut schema = kind_schema(kind);
+    if optional {
+        if schema.get("$ref").is_some() {
+            schema = json!({ "allOf": [schema], "nullable": true });
+        } else {
+            schema["nullable"] = Value::Bool(true);
+        }
+    }
+    schema
+}
+
+fn kind_schema(kind: FieldKind) -> Value {
+    match kind {
+        FieldKind::Uuid => json!({ "type": "string", "format": "uuid" }),
+        FieldKind::DateTime => json!({
+            "type": "string",
+            "format": "date-time",
+            "description": "Server local time without offset; empty string when unset.",
+        }),
+        FieldKind::String => json!({ "type": "string" }),
+        FieldKind::Bool => json!({ "type": "boolean" }),
+        FieldKind::U8 => json!({ "type": "integer", "minimum": 0, "maximum": 255 }),
+        FieldKind::U16 => json!({ "type": "integer", "minimum": 0, "maximum": 65535 }),
+        FieldKind::U32 => json!({ "type": "integer", "format": "int64", "minimum": 0 }),
+        FieldKind::U64 => json!({ "type": "integer", "format": "int64", "minimum": 0 }),
+        FieldKind::F64 => json!({ "type": "number", "format": "double" }),
+        FieldKind::Bytes => json!({
+            "type": "array",
+            "items": { "type": "integer", "minimum": 0, "maximum": 255 },
+        }),
+        FieldKind::StringList => json!({ "type": "array", "items": { "type": "string" } }),
+        FieldKind::Record(name) => schema_ref(name),
+        FieldKind::List(name) => json!({ "type": "array", "items": schema_ref(name) }),
+    }
+}
+
+fn schema_ref(name: &str) -> Value {
+    json!({ "$ref": format!("#/components/schemas/{name}") })
+}
+
+fn path_params(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/')
+        .filter_map(|segment| segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')))
+}
+
+fn arg_schema(arg: &ArgSpec) -> Value {
+    match arg.kind {
+        ArgKind::Uuid if matches!(arg.name, "cluster" | "infobase") => json!({
+            "type": "string",
+            "description": "UUID or name; clusters also match `host:port`.",
+        }),
+        ArgKind::Uuid => kind_schema(FieldKind::Uuid),
+        ArgKind::String => kind_schema(FieldKind::String),
+        ArgKind::Bool => kind_schema(FieldKind::Bool),
+        ArgKind::U8 => kind_schema(FieldKind::U8),
+        ArgKind::U16 => kind_schema(FieldKind::U16),
+        ArgKind::U32 => kind_schema(FieldKind::U32),
+        ArgKind::U64 => kind_schema(FieldKind::U64),
+    }
+}
+
+fn operation_id(command: &str) -> String {
+    let mut out = String::with_capacity(command.len());
+    let mut upper = false;
+    for ch in command.chars() {
+        if ch == '.' || ch == '_' {
+            upper = true;
+        } else if upper {
+            out.push(ch.to_ascii_uppercase());
+            upper = false;
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
+
+fn tag(command: &str) -> &str {
+    command.split('.').next().unwrap_or(command)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect_refs(value: &Value, out: &mut Vec<String>) {
+        match value {
+            Value::Object(map) => {
+                if let Some(Value::String(target)) = map.get("$ref") {
+                    out.push(target.clone());
+                }
+                for item in map.values() {
+                    collect_refs(item, out);
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    collect_refs(item, out);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn openapi_refs_resolve() {
+        let doc = openapi_document();
+        let schemas = doc["components"]["schemas"].as_object().expect("schemas");
+        let mut refs = Vec::new();
+        collect_refs(&doc, &mut refs);
+        assert!(!refs.is_empty());
+        for target in refs {
+            let name = target
+                .strip_prefix("#/components/schemas/")
+                .expect("local ref");
+            assert!(schemas.contains_key(name), "unresolved ref {target}");
+        }
+    }
+
+    #[test]
+    fn openapi_covers_every_route() {
+        let doc = openapi_document();
+        let paths = doc["paths"].as_object().expect("paths");
+        assert_eq!(paths.len(), routes().len() * 2 + 9);
+        let op = &paths["/clusters/{cluster}/sessions/{session}"]["get"];
+        assert_eq!(op["operationId"], "sessionInfo");
+        assert_eq!(op["parameters"].as_array().expect("params").len(), 3);
+        assert_eq!(op["parameters"][1]["schema"]["format"], "uuid");
+        let op = &paths["/agents/{agent}/clusters/{cluster}/sessions/{session}"]["get"];
+        assert_eq!(op["operationId"], "agentSessionInfo");
+        assert_eq!(op["parameters"].as_array().expect("params").len(), 4);
+        assert_eq!(op["parameters"][0]["name"], "agent");
+        assert_eq!(op["parameters"][3]["name"], "If-None-Match");
+        assert!(op["responses"]["304"].is_object());
+        let op = &paths["/clusters/{cluster}/sessions"]["get"];
+        let names: Vec<&str> = op["parameters"]
+            .as_array()
+            .expect("params")
+            .iter()
+            .filter_map(|param| param["name"].as_str())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "cluster",
+                "sort",
+                "fields",
+                "limit",
+                "offset",
+                "filters",
+                "If-None-Match"
+            ]
+        );
+        assert!(op["responses"]["200"]["headers"]["X-Total-Count"].is_object());
+        assert_eq!(
+            paths["/clusters"]["get"]["responses"]["200"]["content"]["application/json"]["schema"]
+                ["$ref"],
+            "#/components/schemas/AggregatedClusters"
+        );
+    }
+
+    #[test]
+    fn rpc_discover_lists_every_command() {
+        let doc = rpc_discover();
+        let methods = doc["methods"].as_array().expect("methods");
+        assert_eq!(methods.len(), routes().len());
+        let info = methods
+            .iter()
+            .find(|method| method["name"] == "session.info")
+            .expect("session.info");
+        let params: Vec<&str> = info["params"]
+            .as_array()
+            .expect("params")
+            .iter()
+            .filter_map(|param| param["name"].as_str())
+            .collect();
+        assert_eq!(params, ["cluster", "session"]);
+        let mut refs = Vec::new();
+        collect_refs(&doc, &mut refs);
+        let schemas = doc["components"]["schemas"].as_object().expect("schemas");
+        for target in refs {
+            let name = target.trim_start_matches("#/components/schemas/");
+            assert!(schemas.contains_key(name), "unresolved ref {target}");
+        }
+    }
+
+    #[test]
+    fn openapi_session_record_types() {
+        let doc = openapi_document();
+        let record = &doc["components"]["schemas"]["SessionRecord"]["properties"];
+        assert_eq!(record["session"]["format"], "uuid");
+        assert_eq!(record["started_at"]["format"], "date-time");
+        assert_eq!(
+            record["license"]["$ref"],
+            "#/components/schemas/SessionLicense"
+        );
+        assert_eq!(record["hibernate"]["type"], "boolean");
+        let cluster = &doc["components"]["schemas"]["ClusterRecord"]["properties"];
+        assert_eq!(cluster["ping_period"]["nullable"], true);
+    }
+}
@@ -0,0 +1,1692 @@
+// @generated by scripts/rac/openapi_codegen.py. DO NOT EDIT.
+// source: schemas/rac
+
+pub const RECORD_SCHEMAS: &[RecordSchema] = &[
+    RecordSchema {
+        name: "AgentAdminRecord",
+        fields: &[
+            FieldSchema {
+                name: "name",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "descr",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "record_marker",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "auth_pwd",
+                kind: FieldKind::U8,
+                optional: false,
+            },
+            FieldSchema {
+                name: "auth_os",
+                kind: FieldKind::U8,
+                optional: false,
+            },
+            FieldSchema {
+                name: "os_user",
+                kind: FieldKind::String,
+                optional: false,
+            },
+        ],
+    },
+    RecordSchema {
+        name: "AgentVersionRecord",
+        fields: &[FieldSchema {
+            name: "version",
+            kind: FieldKind::String,
+            optional: false,
+        }],
+    },
+    RecordSchema {
+        name: "ClusterAdminRecord",
+        fields: &[
+            FieldSchema {
+                name: "name",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "descr",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "record_marker",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "auth_pwd",
+                kind: FieldKind::U8,
+                optional: false,
+            },
+            FieldSchema {
+                name: "auth_os",
+                kind: FieldKind::U8,
+                optional: false,
+            },
+            FieldSchema {
+                name: "os_user",
+                kind: FieldKind::String,
+                optional: false,
+            },
+        ],
+    },
+    RecordSchema {
+        name: "ClusterRecord",
+        fields: &[
+            FieldSchema {
+                name: "uuid",
+                kind: FieldKind::Uuid,
+                optional: false,
+            },
+            FieldSchema {
+                name: "expiration_timeout",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "host",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "lifetime_limit",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "port",
+                kind: FieldKind::U16,
+                optional: false,
+            },
+            FieldSchema {
+                name: "max_memory_size",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "max_memory_time_limit",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "display_name",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "security_level",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "session_fault_tolerance_level",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "load_balancing_mode",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "errors_count_threshold",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "kill_problem_processes",
+                kind: FieldKind::Bool,
+                optional: false,
+            },
+            FieldSchema {
+                name: "kill_by_memory_with_dump",
+                kind: FieldKind::Bool,
+                optional: false,
+            },
+            FieldSchema {
+                name: "allow_access_right_audit_events_recording",
+                kind: FieldKind::Bool,
+                optional: true,
+            },
+            FieldSchema {
+                name: "ping_period",
+                kind: FieldKind::U32,
+                optional: true,
+            },
+            FieldSchema {
+                name: "ping_timeout",
+                kind: FieldKind::U32,
+                optional: true,
+            },
+            FieldSchema {
+                name: "restart_schedule_len",
+                kind: FieldKind::U8,
+                optional: true,
+            },
+            FieldSchema {
+                name: "restart_schedule_cron",
+                kind: FieldKind::String,
+                optional: true,
+            },
+            FieldSchema {
+                name: "restart_interval",
+                kind: FieldKind::U32,
+                optional: true,
+            },
+        ],
+    },
+    RecordSchema {
+        name: "ConnectionRecord",
+        fields: &[
+            FieldSchema {
+                name: "connection",
+                kind: FieldKind::Uuid,
+                optional: false,
+            },
+            FieldSchema {
+                name: "application",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "blocked_by_ls",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "connected_at",
+                kind: FieldKind::DateTime,
+                optional: false,
+            },
+            FieldSchema {
+                name: "conn_id",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "host",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "infobase",
+                kind: FieldKind::Uuid,
+                optional: false,
+            },
+            FieldSchema {
+                name: "process",
+                kind: FieldKind::Uuid,
+                optional: false,
+            },
+            FieldSchema {
+                name: "session_number",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+        ],
+    },
+    RecordSchema {
+        name: "CounterRecord",
+        fields: &[
+            FieldSchema {
+                name: "name",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "collection_time",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "group",
+                kind: FieldKind::U8,
+                optional: false,
+            },
+            FieldSchema {
+                name: "filter_type",
+                kind: FieldKind::U8,
+                optional: false,
+            },
+            FieldSchema {
+                name: "filter",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "duration",
+                kind: FieldKind::U8,
+                optional: false,
+            },
+            FieldSchema {
+                name: "cpu_time",
+                kind: FieldKind::U8,
+                optional: false,
+            },
+            FieldSchema {
+                name: "duration_dbms",
+                kind: FieldKind::U8,
+                optional: false,
+            },
+            FieldSchema {
+                name: "service",
+                kind: FieldKind::U8,
+                optional: false,
+            },
+            FieldSchema {
+                name: "memory",
+                kind: FieldKind::U8,
+                optional: false,
+            },
+            FieldSchema {
+                name: "read",
+                kind: FieldKind::U8,
+                optional: false,
+            },
+            FieldSchema {
+                name: "write",
+                kind: FieldKind::U8,
+                optional: false,
+            },
+            FieldSchema {
+                name: "dbms_bytes",
+                kind: FieldKind::U8,
+                optional: false,
+            },
+            FieldSchema {
+                name: "call",
+                kind: FieldKind::U8,
+                optional: false,
+            },
+            FieldSchema {
+                name: "number_of_active_sessions",
+                kind: FieldKind::U8,
+                optional: false,
+            },
+            FieldSchema {
+                name: "number_of_sessions",
+                kind: FieldKind::U8,
+                optional: false,
+            },
+            FieldSchema {
+                name: "descr",
+                kind: FieldKind::String,
+                optional: false,
+            },
+        ],
+    },
+    RecordSchema {
+        name: "CounterValuesRecord",
+        fields: &[
+            FieldSchema {
+                name: "object",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "collection_time",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "duration",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "cpu_time",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "memory",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "read",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "write",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "duration_dbms",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "dbms_bytes",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "service",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "call",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "number_of_active_sessions",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "number_of_sessions",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "time",
+                kind: FieldKind::DateTime,
+                optional: false,
+            },
+        ],
+    },
+    RecordSchema {
+        name: "InfobaseSummary",
+        fields: &[
+            FieldSchema {
+                name: "infobase",
+                kind: FieldKind::Uuid,
+                optional: false,
+            },
+            FieldSchema {
+                name: "descr",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "name",
+                kind: FieldKind::String,
+                optional: false,
+            },
+        ],
+    },
+    RecordSchema {
+        name: "InfobaseInfoRecord",
+        fields: &[
+            FieldSchema {
+                name: "infobase",
+                kind: FieldKind::Uuid,
+                optional: false,
+            },
+            FieldSchema {
+                name: "tag",
+                kind: FieldKind::U8,
+                optional: false,
+            },
+            FieldSchema {
+                name: "unknown_u32_0",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "dbms",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "name",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "unknown_str_0",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "db_server",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "db_user",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "unknown_str_1",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "unknown_str_2",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "unknown_bytes_0",
+                kind: FieldKind::Bytes,
+                optional: false,
+            },
+            FieldSchema {
+                name: "denied_message",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "denied_parameter",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "unknown_str_3",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "unknown_str_4",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "unknown_u32_1",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "descr",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "locale",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "db_name",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "permission_code",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "tail",
+                kind: FieldKind::Bytes,
+                optional: false,
+            },
+        ],
+    },
+    RecordSchema {
+        name: "LimitRecord",
+        fields: &[
+            FieldSchema {
+                name: "name",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "counter",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "action",
+                kind: FieldKind::U8,
+                optional: false,
+            },
+            FieldSchema {
+                name: "duration",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "cpu_time",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "memory",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "read",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "write",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "duration_dbms",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "dbms_bytes",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "service",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "call",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "number_of_active_sessions",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "number_of_sessions",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "error_message",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "descr",
+                kind: FieldKind::String,
+                optional: false,
+            },
+        ],
+    },
+    RecordSchema {
+        name: "LockRecordRaw",
+        fields: &[
+            FieldSchema {
+                name: "connection",
+                kind: FieldKind::Uuid,
+                optional: false,
+            },
+            FieldSchema {
+                name: "descr",
+                kind: FieldKind::Record("LockDescr"),
+                optional: false,
+            },
+            FieldSchema {
+                name: "locked_at",
+                kind: FieldKind::DateTime,
+                optional: false,
+            },
+            FieldSchema {
+                name: "object",
+                kind: FieldKind::Uuid,
+                optional: false,
+            },
+            FieldSchema {
+                name: "session",
+                kind: FieldKind::Uuid,
+                optional: false,
+            },
+        ],
+    },
+    RecordSchema {
+        name: "ManagerRecord",
+        fields: &[
+            FieldSchema {
+                name: "manager",
+                kind: FieldKind::Uuid,
+                optional: false,
+            },
+            FieldSchema {
+                name: "descr",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "host",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "using",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "port",
+                kind: FieldKind::U16,
+                optional: false,
+            },
+            FieldSchema {
+                name: "pid",
+                kind: FieldKind::String,
+                optional: false,
+            },
+        ],
+    },
+    RecordSchema {
+        name: "ProcessLicense",
+        fields: &[
+            FieldSchema {
+                name: "file_name",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "full_presentation",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "issued_by_server",
+                kind: FieldKind::Bool,
+                optional: false,
+            },
+            FieldSchema {
+                name: "license_type",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "max_users_all",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "max_users_current",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "network_key",
+                kind: FieldKind::Bool,
+                optional: false,
+            },
+            FieldSchema {
+                name: "server_address",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "process_id",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "server_port",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "key_series",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "brief_presentation",
+                kind: FieldKind::String,
+                optional: false,
+            },
+        ],
+    },
+    RecordSchema {
+        name: "ProcessRecord",
+        fields: &[
+            FieldSchema {
+                name: "process",
+                kind: FieldKind::Uuid,
+                optional: false,
+            },
+            FieldSchema {
+                name: "avg_call_time",
+                kind: FieldKind::F64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "avg_db_call_time",
+                kind: FieldKind::F64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "avg_lock_call_time",
+                kind: FieldKind::F64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "avg_server_call_time",
+                kind: FieldKind::F64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "avg_threads",
+                kind: FieldKind::F64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "capacity",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "connections",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "host",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "licenses",
+                kind: FieldKind::List("ProcessLicense"),
+                optional: false,
+            },
+            FieldSchema {
+                name: "port",
+                kind: FieldKind::U16,
+                optional: false,
+            },
+            FieldSchema {
+                name: "memory_excess_time",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "memory_size",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "pid",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "use_status",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "selection_size",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "started_at",
+                kind: FieldKind::DateTime,
+                optional: false,
+            },
+            FieldSchema {
+                name: "running",
+                kind: FieldKind::Bool,
+                optional: false,
+            },
+            FieldSchema {
+                name: "available_performance",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "reserve",
+                kind: FieldKind::Bool,
+                optional: false,
+            },
+            FieldSchema {
+                name: "turned_on",
+                kind: FieldKind::Bool,
+                optional: false,
+            },
+        ],
+    },
+    RecordSchema {
+        name: "ProfileRecord",
+        fields: &[
+            FieldSchema {
+                name: "name",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "descr",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "directory_access",
+                kind: FieldKind::U8,
+                optional: false,
+            },
+            FieldSchema {
+                name: "com_access",
+                kind: FieldKind::U8,
+                optional: false,
+            },
+            FieldSchema {
+                name: "addin_access",
+                kind: FieldKind::U8,
+                optional: false,
+            },
+            FieldSchema {
+                name: "module_access",
+                kind: FieldKind::U8,
+                optional: false,
+            },
+            FieldSchema {
+                name: "app_access",
+                kind: FieldKind::U8,
+                optional: false,
+            },
+            FieldSchema {
+                name: "config",
+                kind: FieldKind::Bool,
+                optional: false,
+            },
+            FieldSchema {
+                name: "privileged_mode",
+                kind: FieldKind::Bool,
+                optional: false,
+            },
+            FieldSchema {
+                name: "inet_access",
+                kind: FieldKind::U8,
+                optional: false,
+            },
+            FieldSchema {
+                name: "crypto",
+                kind: FieldKind::Bool,
+                optional: false,
+            },
+            FieldSchema {
+                name: "right_extension",
+                kind: FieldKind::Bool,
+                optional: false,
+            },
+            FieldSchema {
+                name: "right_extension_definition_roles",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "all_modules_extension",
+                kind: FieldKind::Bool,
+                optional: false,
+            },
+            FieldSchema {
+                name: "modules_available_for_extension",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "modules_not_available_for_extension",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "privileged_mode_roles",
+                kind: FieldKind::String,
+                optional: false,
+            },
+        ],
+    },
+    RecordSchema {
+        name: "RuleRecord",
+        fields: &[
+            FieldSchema {
+                name: "rule",
+                kind: FieldKind::Uuid,
+                optional: false,
+            },
+            FieldSchema {
+                name: "object_type",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "infobase_name",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "rule_type",
+                kind: FieldKind::U8,
+                optional: false,
+            },
+            FieldSchema {
+                name: "application_ext",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "priority",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+        ],
+    },
+    RecordSchema {
+        name: "RuleIdRecord",
+        fields: &[FieldSchema {
+            name: "rule",
+            kind: FieldKind::Uuid,
+            optional: false,
+        }],
+    },
+    RecordSchema {
+        name: "ServerRecord",
+        fields: &[
+            FieldSchema {
+                name: "server",
+                kind: FieldKind::Uuid,
+                optional: false,
+            },
+            FieldSchema {
+                name: "agent_host",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "agent_port",
+                kind: FieldKind::U16,
+                optional: false,
+            },
+            FieldSchema {
+                name: "name",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "using",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "dedicate_managers",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "gap_1",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "safe_call_memory_limit",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "gap_2",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "infobases_limit",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "gap_3",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "gap_4",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "gap_4_pad",
+                kind: FieldKind::U8,
+                optional: false,
+            },
+            FieldSchema {
+                name: "cluster_port",
+                kind: FieldKind::U16,
+                optional: false,
+            },
+            FieldSchema {
+                name: "connections_limit",
+                kind: FieldKind::U16,
+                optional: false,
+            },
+            FieldSchema {
+                name: "port_range_end",
+                kind: FieldKind::U16,
+                optional: false,
+            },
+            FieldSchema {
+                name: "port_range_start",
+                kind: FieldKind::U16,
+                optional: false,
+            },
+            FieldSchema {
+                name: "critical_total_memory",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "gap_5",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "temporary_allowed_total_memory",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "gap_6",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "temporary_allowed_total_memory_time_limit",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "service_principal_name",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "restart_schedule",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "gap_7",
+                kind: FieldKind::U8,
+                optional: false,
+            },
+        ],
+    },
+    RecordSchema {
+        name: "ServiceSettingRecord",
+        fields: &[
+            FieldSchema {
+                name: "setting",
+                kind: FieldKind::Uuid,
+                optional: false,
+            },
+            FieldSchema {
+                name: "service_name",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "infobase_name",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "service_data_dir",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "active",
+                kind: FieldKind::Bool,
+                optional: false,
+            },
+        ],
+    },
+    RecordSchema {
+        name: "ServiceSettingTransferDataDirRecord",
+        fields: &[
+            FieldSchema {
+                name: "service_name",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "user",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "source_dir_flag",
+                kind: FieldKind::U8,
+                optional: false,
+            },
+            FieldSchema {
+                name: "source_dir",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "target_dir_flag",
+                kind: FieldKind::U8,
+                optional: false,
+            },
+            FieldSchema {
+                name: "target_dir",
+                kind: FieldKind::String,
+                optional: false,
+            },
+        ],
+    },
+    RecordSchema {
+        name: "ServiceSettingIdRecord",
+        fields: &[FieldSchema {
+            name: "setting",
+            kind: FieldKind::Uuid,
+            optional: false,
+        }],
+    },
+    RecordSchema {
+        name: "SessionLicense",
+        fields: &[
+            FieldSchema {
+                name: "file_name",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "full_presentation",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "issued_by_server",
+                kind: FieldKind::Bool,
+                optional: false,
+            },
+            FieldSchema {
+                name: "license_type",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "max_users_all",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "max_users_current",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "network_key",
+                kind: FieldKind::Bool,
+                optional: false,
+            },
+            FieldSchema {
+                name: "server_address",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "process_id",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "server_port",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "key_series",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "brief_presentation",
+                kind: FieldKind::String,
+                optional: false,
+            },
+        ],
+    },
+    RecordSchema {
+        name: "SessionRecord",
+        fields: &[
+            FieldSchema {
+                name: "session",
+                kind: FieldKind::Uuid,
+                optional: false,
+            },
+            FieldSchema {
+                name: "app_id",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "blocked_by_dbms",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "blocked_by_ls",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "bytes_all",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "bytes_last_5min",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "calls_all",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "calls_last_5min",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "connection",
+                kind: FieldKind::Uuid,
+                optional: false,
+            },
+            FieldSchema {
+                name: "dbms_bytes_all",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "dbms_bytes_last_5min",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "db_proc_info",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "db_proc_took",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "db_proc_took_at",
+                kind: FieldKind::DateTime,
+                optional: false,
+            },
+            FieldSchema {
+                name: "duration_all",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "duration_all_dbms",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "duration_current",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "duration_current_dbms",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "duration_last_5min",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "duration_last_5min_dbms",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "host",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "infobase",
+                kind: FieldKind::Uuid,
+                optional: false,
+            },
+            FieldSchema {
+                name: "last_active_at",
+                kind: FieldKind::DateTime,
+                optional: false,
+            },
+            FieldSchema {
+                name: "hibernate",
+                kind: FieldKind::Bool,
+                optional: false,
+            },
+            FieldSchema {
+                name: "passive_session_hibernate_time",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "hibernate_session_terminate_time",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "license",
+                kind: FieldKind::Record("SessionLicense"),
+                optional: false,
+            },
+            FieldSchema {
+                name: "locale",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "process",
+                kind: FieldKind::Uuid,
+                optional: false,
+            },
+            FieldSchema {
+                name: "session_id",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "started_at",
+                kind: FieldKind::DateTime,
+                optional: false,
+            },
+            FieldSchema {
+                name: "user_name",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "memory_current",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "memory_last_5min",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "memory_total",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "read_current",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "read_last_5min",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "read_total",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "write_current",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "write_last_5min",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "write_total",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "duration_current_service",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "duration_last_5min_service",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "duration_all_service",
+                kind: FieldKind::U32,
+                optional: false,
+            },
+            FieldSchema {
+                name: "current_service_name",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "cpu_time_current",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "cpu_time_last_5min",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "cpu_time_total",
+                kind: FieldKind::U64,
+                optional: false,
+            },
+            FieldSchema {
+                name: "data_separation",
+                kind: FieldKind::String,
+                optional: false,
+            },
+            FieldSchema {
+                name: "client_ip",
+                kind: FieldKind::String,
+                optional: false,
+            },
+        ],
+    },
+];
+
+pub const RESPONSE_SCHEMAS: &[ResponseSchema] = &[
+    ResponseSchema {
+        name: "AgentAdminListResp",
+        field: "admins",
+        kind: FieldKind::List("AgentAdminRecord"),
+    },
+    ResponseSchema {
+        name: "AgentVersionResp",
+        field: "version",
+        kind: FieldKind::String,
+    },
+    ResponseSchema {
+        name: "ClusterAdminListResp",
+        field: "admins",
+        kind: FieldKind::List("ClusterAdminRecord"),
+    },
+    ResponseSchema {
+        name: "ClusterListResp",
+        field: "clusters",
+        kind: FieldKind::List("ClusterRecord"),
+    },
+    ResponseSchema {
+        name: "ClusterInfoResp",
+        field: "cluster",
+        kind: FieldKind::Record("ClusterRecord"),
+    },
+    ResponseSchema {
+        name: "ConnectionListResp",
+        field: "records",
+        kind: FieldKind::List("ConnectionRecord"),
+    },
+    ResponseSchema {
+        name: "ConnectionInfoResp",
+        field: "record",
+        kind: FieldKind::Record("ConnectionRecord"),
+    },
+    ResponseSchema {
+        name: "CounterListResp",
+        field: "records",
+        kind: FieldKind::List("CounterRecord"),
+    },
+    ResponseSchema {
+        name: "CounterInfoResp",
+        field: "record",
+        kind: FieldKind::Record("CounterRecord"),
+    },
+    ResponseSchema {
+        name: "CounterValuesResp",
+        field: "records",
+        kind: FieldKind::List("CounterValuesRecord"),
+    },
+    ResponseSchema {
+        name: "CounterAccumulatedValuesResp",
+        field: "records",
+        kind: FieldKind::List("CounterValuesRecord"),
+    },
+    ResponseSchema {
+        name: "InfobaseSummaryListResp",
+        field: "summaries",
+        kind: FieldKind::List("InfobaseSummary"),
+    },
+    ResponseSchema {
+        name: "InfobaseSummaryInfoResp",
+        field: "summary",
+        kind: FieldKind::Record("InfobaseSummary"),
+    },
+    ResponseSchema {
+        name: "InfobaseInfoResp",
+        field: "info",
+        kind: FieldKind::Record("InfobaseInfoRecord"),
+    },
+    ResponseSchema {
+        name: "LimitListResp",
+        field: "limits",
+        kind: FieldKind::List("LimitRecord"),
+    },
+    ResponseSchema {
+        name: "LimitInfoResp",
+        field: "record",
+        kind: FieldKind::Record("LimitRecord"),
+    },
+    ResponseSchema {
+        name: "LockListResp",
+        field: "records",
+        kind: FieldKind::List("LockRecordRaw"),
+    },
+    ResponseSchema {
+        name: "ManagerListResp",
+        field: "managers",
+        kind: FieldKind::List("ManagerRecord"),
+    },
+    ResponseSchema {
+        name: "ManagerInfoResp",
+        field: "record",
+        kind: FieldKind::Record("ManagerRecord"),
+    },
+    ResponseSchema {
+        name: "ProcessListResp",
+        field: "records",
+        kind: FieldKind::List("ProcessRecord"),
+    },
+    ResponseSchema {
+        name: "ProcessInfoResp",
+        field: "record",
+        kind: FieldKind::Record("ProcessRecord"),
+    },
+    ResponseSchema {
+        name: "ProfileListResp",
+        field: "profiles",
+        kind: FieldKind::List("ProfileRecord"),
+    },
+    ResponseSchema {
+        name: "RuleListResp",
+        field: "records",
+        kind: FieldKind::List("RuleRecord"),
+    },
+    ResponseSchema {
+        name: "RuleInfoResp",
+        field: "record",
+        kind: FieldKind::Record("RuleRecord"),
+    },
+    ResponseSchema {
+        name: "RuleInsertResp",
+        field: "rule",
+        kind: FieldKind::Uuid,
+    },
+    ResponseSchema {
+        name: "RuleUpdateResp",
+        field: "rule",
+        kind: FieldKind::Uuid,
+    },
+    ResponseSchema {
+        name: "ServerListResp",
+        field: "servers",
+        kind: FieldKind::List("ServerRecord"),
+    },
+    ResponseSchema {
+        name: "ServerInfoResp",
+        field: "record",
+        kind: FieldKind::Record("ServerRecord"),
+    },
+    ResponseSchema {
+        name: "ServiceSettingListResp",
+        field: "records",
+        kind: FieldKind::List("ServiceSettingRecord"),
+    },
+    ResponseSchema {
+        name: "ServiceSettingInfoResp",
+        field: "record",
+        kind: FieldKind::Record("ServiceSettingRecord"),
+    },
+    ResponseSchema {
+        name: "ServiceSettingInsertResp",
+        field: "setting",
+        kind: FieldKind::Uuid,
+    },
+    ResponseSchema {
+        name: "ServiceSettingUpdateResp",
+        field: "setting",
+        kind: FieldKind::Uuid,
+    },
+    ResponseSchema {
+        name: "ServiceSettingGetDataDirsResp",
+        field: "records",
+        kind: FieldKind::List("ServiceSettingTransferDataDirRecord"),
+    },
+    ResponseSchema {
+        name: "SessionListResp",
+        field: "records",
+        kind: FieldKind::List("SessionRecord"),
+    },
+    ResponseSchema {
+        name: "SessionInfoResp",
+        field: "record",
+        kind: FieldKind::Record("SessionRecord"),
+    },
+];
@@ -0,0 +1,310 @@
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+
+use rac_protocol::tabular::list_records_mut;
+use serde_json::{Map, Value};
+
+use crate::RpcError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterOp {
+    /// `field=value`
+    Eq,
+    /// `field~=value`, case-insensitive.
+    Contains,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Filter {
+    pub field: String,
+    pub op: FilterOp,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortKey {
+    pub field: String,
+    pub descending: bool,
+}
+
+/// Filtering, sorting, projection and pagination applied to the records of a
+/// serialized list response. `sort`, `fields`, `limit` and `offset` are
+/// reserved; every other parameter filters by the field of the same name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListQuery {
+    pub filters: Vec<Filter>,
+    pub sort: Vec<SortKey>,
+    pub fields: Option<Vec<String>>,
+    pub limit: Option<usize>,
+    pub offset: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryResult {
+    pub body: Value,
+    /// Matching records before `limit`/`offset`.
+    pub total: usize,
+}
+
+impl ListQuery {
+    pub fn parse(params: &[(String, String)]) -> Result<Self, RpcError> {
+        let mut query = ListQuery::default();
+        for (key, value) in params {
+            match key.as_str() {
+                "sort" => {
+                    query.sort = split_list(value)
+                        .map(|field| match field.strip_prefix('-') {
+                            Some(field) => SortKey {
+                                field: field.to_string(),
+                                descending: true,
+                            },
+                            None => SortKey {
+                                field: field.trim_start_matches('+').to_string(),
+                                descending: false,
+                            },
+                        })
+                        .collect();
+                }
+                "fields" => {
+                    query.fields = Some(split_list(value).map(str::to_string).collect());
+                }
+                "limit" => query.limit = Some(parse_count(key, value)?),
+                "offset" => query.offset = parse_count(key, value)?,
+                _ => {
+                    let (field, op) = match key.strip_suffix('~') {
+                        Some(field) => (field, FilterOp::Contains),
+                        None => (key.as_str(), FilterOp::Eq),
+                    };
+                    if field.is_empty() {
+                        return Err(RpcError::new(
+                            "bad_request",
+                            format!("invalid filter parameter: {key}"),
+                        ));
+                    }
+                    query.filters.push(Filter {
+                        field: field.to_string(),
+                        op,
+                        value: value.clone(),
+                    });
+                }
+            }
+        }
+        Ok(query)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == ListQuery::default()
+    }
+
+    /// Applies the query to a list body: either a bare array or an object
+    /// holding the records array (`{"records": [...]}`). The shape of the body
+    /// is preserved.
+    pub fn apply(&self, body: &Value) -> Result<QueryResult, RpcError> {
+        let mut body = body.clone();
+        let records = list_records_mut(&mut body).ok_or_else(|| {
+            RpcError::new(
+                "bad_request",
+                "query parameters are only supported on list responses".to_string(),
+            )
+        })?;
+        self.check_fields(records)?;
+
+        let mut selected: Vec<Value> = records
+            .drain(..)
+            .filter(|record| self.filters.iter().all(|filter| filter.matches(record)))
+            .collect();
+        if !self.sort.is_empty() {
+            selected.sort_by(|a, b| {
+                self.sort
+                    .iter()
+                    .map(|key| {
+                        let ord = compare_values(&a[&key.field], &b[&key.field]);
+                        if key.descending {
+                            ord.reverse()
+                        } else {
+                            ord
+                        }
+                    })
+                    .find(|ord| ord.is_ne())
+                    .unwrap_or(Ordering::Equal)
+            });
+        }
+        let total = selected.len();
+        *records = selected
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .map(|record| self.project(record))
+            .collect();
+        Ok(QueryResult { body, total })
+    }
+
+    /// Rejects unknown field names, unless there are no records to learn
+    /// the fields from.
+    fn check_fields(&self, records: &[Value]) -> Result<(), RpcError> {
+        let known: BTreeSet<&str> = records
+            .iter()
+            .filter_map(Value::as_object)
+            .flat_map(|record| record.keys().map(String::as_str))
+            .collect();
+        if known.is_empty() {
+            return Ok(());
+        }
+        let requested = self
+            .filters
+            .iter()
+            .map(|filter| &filter.field)
+            .chain(self.sort.iter().map(|key| &key.field))
+            .chain(self.fields.iter().flatten());
+        for field in requested {
+            if !known.contains(field.as_str()) {
+                return Err(RpcError::with_details(
+                    "bad_request",
+                    format!("unknown field: {field}"),
+                    serde_json::json!({ "fields": known }),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn project(&self, record: Value) -> Value {
+        match (&self.fields, record) {
+            (Some(fields), Value::Object(mut map)) => Value::Object(
+                fields
+                    .iter()
+                    .filter_map(|field| map.remove(field).map(|value| (field.clone(), value)))
+                    .collect::<Map<String, Value>>(),
+            ),
+            (_, record) => record,
+        }
+    }
+}
+
+impl Filter {
+    fn matches(&self, record: &Value) -> bool {
+        let actual = match &record[&self.field] {
+            Value::String(value) => value.clone(),
+            Value::Null => String::new(),
+            other => other.to_string(),
+        };
+        match self.op {
+            FilterOp::Eq => actual == self.value,
+            FilterOp::Contains => actual.to_lowercase().contains(&self.value.to_lowercase()),
+        }
+    }
+}
+
+fn split_list(value: &str) -> impl Iterator<Item = &str> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+}
+
+fn parse_count(key: &str, value: &str) -> Result<usize, RpcError> {
+    value.parse().map_err(|_| {
+        RpcError::new(
+            "bad_request",
+            format!("{key} must be a non-negative integer"),
+        )
+    })
+}
+
+/// Numbers compare numerically, strings lexicographically; nulls sort last.
+fn compare_values(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => {
+            let a = a.as_f64().unwrap_or(f64::NAN);
+            let b = b.as_f64().unwrap_or(f64::NAN);
+            a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+        }
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        (Value::Null, Value::Null) => Ordering::Equal,
+        (Value::Null, _) => Ordering::Greater,
+        (_, Value::Null) => Ordering::Less,
+        (a, b) => a.to_string().cmp(&b.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn params(raw: &[(&str, &str)]) -> Vec<(String, String)> {
+        raw.iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    fn sessions() -> Value {
+        json!({ "records": [
+            { "session": "s1", "user_name": "Ivanov", "app_id": "1CV8C", "memory_current": 100 },
+            { "session": "s2", "user_name": "Petrov", "app_id": "1CV8C", "memory_current": 300 },
+            { "session": "s3", "user_name": "ivanova", "app_id": "Designer", "memory_current": 200 },
+            { "session": "s4", "user_name": "Sidorov", "app_id": "1CV8C", "memory_current": 50 },
+        ] })
+    }
+
+    #[test]
+    fn filters_sorts_projects_and_pages() {
+        let query = ListQuery::parse(&params(&[
+            ("app_id", "1CV8C"),
+            ("sort", "-memory_current"),
+            ("fields", "session,memory_current"),
+            ("limit", "2"),
+            ("offset", "1"),
+        ]))
+        .expect("query");
+        let result = query.apply(&sessions()).expect("apply");
+        assert_eq!(result.total, 3);
+        assert_eq!(
+            result.body,
+            json!({ "records": [
+                { "session": "s1", "memory_current": 100 },
+                { "session": "s4", "memory_current": 50 },
+            ] })
+        );
+    }
+
+    #[test]
+    fn substring_filter_ignores_case() {
+        let query = ListQuery::parse(&params(&[("user_name~", "IVAN"), ("sort", "session")]))
+            .expect("query");
+        let result = query.apply(&sessions()).expect("apply");
+        let ids: Vec<&str> = result.body["records"]
+            .as_array()
+            .expect("records")
+            .iter()
+            .map(|record| record["session"].as_str().expect("session"))
+            .collect();
+        assert_eq!(ids, ["s1", "s3"]);
+    }
+
+    #[test]
+    fn bare_arrays_and_empty_queries_pass_through() {
+        let body = json!([{ "cluster": "c1" }, { "cluster": "c2" }]);
+        let result = ListQuery::default().apply(&body).expect("apply");
+        assert_eq!(result.body, body);
+        assert_eq!(result.total, 2);
+        assert!(ListQuery::default().is_empty());
+    }
+
+    #[test]
+    fn rejects_unknown_fields_and_bad_numbers() {
+        let query = ListQuery::parse(&params(&[("sort", "-memroy")])).expect("query");
+        let err = query.apply(&sessions()).expect_err("unknown field");
+        assert_eq!(err.code, "bad_request");
+        assert_eq!(err.message, "unknown field: memroy");
+
+        let err = ListQuery::parse(&params(&[("limit", "-1")])).expect_err("limit");
+        assert_eq!(err.code, "bad_request");
+
+        let err = ListQuery::default()
+            .apply(&json!({ "version": "8.3" }))
+            .expect_err("not a list");
+        assert_eq!(err.code, "bad_request");
+    }
+}
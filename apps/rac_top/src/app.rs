@@ -0,0 +1,491 @@
+//! Dashboard state and key handling, kept free of terminal and network I/O.
+
+use std::collections::HashMap;
+
+use ratatui::crossterm::event::KeyCode;
+
+use rac_protocol::commands::{InfobaseSummary, LockRecordRaw, ProcessRecord, SessionRecord};
+use rac_protocol::Uuid16;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pane {
+    Processes,
+    Sessions,
+    Locks,
+    Infobases,
+}
+
+impl Pane {
+    pub const ALL: [Pane; 4] = [
+        Pane::Processes,
+        Pane::Sessions,
+        Pane::Locks,
+        Pane::Infobases,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Pane::Processes => "Processes",
+            Pane::Sessions => "Sessions",
+            Pane::Locks => "Locks",
+            Pane::Infobases => "Infobases",
+        }
+    }
+
+    pub fn index(self) -> usize {
+        Pane::ALL.iter().position(|pane| *pane == self).unwrap_or(0)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SessionSort {
+    Cpu,
+    Memory,
+    Dbms,
+    Blocking,
+}
+
+impl SessionSort {
+    pub fn label(self) -> &'static str {
+        match self {
+            SessionSort::Cpu => "cpu",
+            SessionSort::Memory => "memory",
+            SessionSort::Dbms => "dbms time",
+            SessionSort::Blocking => "blocking",
+        }
+    }
+
+    fn key(self, session: &SessionRecord) -> u64 {
+        match self {
+            SessionSort::Cpu => session.cpu_time_current,
+            SessionSort::Memory => session.memory_current,
+            SessionSort::Dbms => u64::from(session.duration_current_dbms),
+            SessionSort::Blocking => {
+                u64::from(session.blocked_by_ls) + u64::from(session.blocked_by_dbms)
+            }
+        }
+    }
+}
+
+/// One refresh worth of cluster state.
+#[derive(Default)]
+pub struct Snapshot {
+    pub processes: Vec<ProcessRecord>,
+    pub sessions: Vec<SessionRecord>,
+    pub locks: Vec<LockRecordRaw>,
+    pub infobases: Vec<InfobaseSummary>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SessionAction {
+    Terminate,
+    Interrupt,
+}
+
+impl SessionAction {
+    pub fn verb(self) -> &'static str {
+        match self {
+            SessionAction::Terminate => "Terminate session",
+            SessionAction::Interrupt => "Interrupt the server call of session",
+        }
+    }
+
+    /// Registry command name, as deny rules of a policy spell it.
+    pub fn command(self) -> &'static str {
+        match self {
+            SessionAction::Terminate => "session.terminate",
+            SessionAction::Interrupt => "session.interrupt_current_server_call",
+        }
+    }
+
+    pub fn done(self) -> &'static str {
+        match self {
+            SessionAction::Terminate => "Session terminated",
+            SessionAction::Interrupt => "Server call interrupted",
+        }
+    }
+}
+
+pub enum Modal {
+    Details(Box<SessionRecord>),
+    Confirm {
+        action: SessionAction,
+        session: Uuid16,
+        label: String,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Action {
+    None,
+    Quit,
+    Refresh,
+    ShowSession(Uuid16),
+    Run(SessionAction, Uuid16),
+}
+
+pub struct App {
+    pub snapshot: Snapshot,
+    pub pane: Pane,
+    pub sort: SessionSort,
+    selected: [usize; 4],
+    pub modal: Option<Modal>,
+    pub status: String,
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self {
+            snapshot: Snapshot::default(),
+            pane: Pane::Sessions,
+            sort: SessionSort::Cpu,
+            selected: [0; 4],
+            modal: None,
+            status: String::new(),
+        }
+    }
+}
+
+impl App {
+    pub fn update(&mut self, snapshot: Snapshot) {
+        self.snapshot = snapshot;
+        for pane in Pane::ALL {
+            let rows = self.rows(pane);
+            let selected = &mut self.selected[pane.index()];
+            *selected = (*selected).min(rows.saturating_sub(1));
+        }
+    }
+
+    pub fn selected(&self, pane: Pane) -> usize {
+        self.selected[pane.index()]
+    }
+
+    /// Sessions in display order, heaviest first.
+    pub fn sessions(&self) -> Vec<&SessionRecord> {
+        let mut sessions: Vec<&SessionRecord> = self.snapshot.sessions.iter().collect();
+        sessions.sort_by(|a, b| {
+            self.sort
+                .key(b)
+                .cmp(&self.sort.key(a))
+                .then(a.session_id.cmp(&b.session_id))
+        });
+        sessions
+    }
+
+    pub fn selected_session(&self) -> Option<&SessionRecord> {
+        self.sessions().get(self.selected(Pane::Sessions)).copied()
+    }
+
+    pub fn infobase_name(&self, infobase: &Uuid16) -> &str {
+        self.snapshot
+            .infobases
+            .iter()
+            .find(|summary| summary.infobase == *infobase)
+            .map_or("", |summary| summary.name.as_str())
+    }
+
+    pub fn session(&self, session: &Uuid16) -> Option<&SessionRecord> {
+        self.snapshot
+            .sessions
+            .iter()
+            .find(|record| record.session == *session)
+    }
+
+    /// Infobase name with its session and lock counts, busiest first.
+    pub fn infobase_counts(&self) -> Vec<(&str, usize, usize)> {
+        let mut sessions: HashMap<Uuid16, usize> = HashMap::new();
+        for session in &self.snapshot.sessions {
+            *sessions.entry(session.infobase).or_default() += 1;
+        }
+        let mut locks: HashMap<Uuid16, usize> = HashMap::new();
+        for lock in &self.snapshot.locks {
+            if let Some(session) = self.session(&lock.session) {
+                *locks.entry(session.infobase).or_default() += 1;
+            }
+        }
+        let mut counts: Vec<(&str, usize, usize)> = self
+            .snapshot
+            .infobases
+            .iter()
+            .map(|summary| {
+                (
+                    summary.name.as_str(),
+                    sessions.get(&summary.infobase).copied().unwrap_or(0),
+                    locks.get(&summary.infobase).copied().unwrap_or(0),
+                )
+            })
+            .collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        counts
+    }
+
+    fn rows(&self, pane: Pane) -> usize {
+        match pane {
+            Pane::Processes => self.snapshot.processes.len(),
+            Pane::Sessions => self.snapshot.sessions.len(),
+            Pane::Locks => self.snapshot.locks.len(),
+            Pane::Infobases => self.snapshot.infobases.len(),
+        }
+    }
+
+    pub fn on_key(&mut self, key: KeyCode) -> Action {
+        if let Some(modal) = self.modal.take() {
+            return self.on_modal_key(modal, key);
+        }
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => return Action::Quit,
+            KeyCode::Char('r') => return Action::Refresh,
+            KeyCode::Tab => self.pane = Pane::ALL[(self.pane.index() + 1) % Pane::ALL.len()],
+            KeyCode::BackTab => {
+                self.pane = Pane::ALL[(self.pane.index() + Pane::ALL.len() - 1) % Pane::ALL.len()]
+            }
+            KeyCode::Char(digit @ '1'..='4') => {
+                self.pane = Pane::ALL[usize::from(digit as u8 - b'1')];
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            KeyCode::Char(key @ ('c' | 'm' | 'd' | 'b')) => {
+                self.sort = match key {
+                    'c' => SessionSort::Cpu,
+                    'm' => SessionSort::Memory,
+                    'd' => SessionSort::Dbms,
+                    _ => SessionSort::Blocking,
+                };
+                self.pane = Pane::Sessions;
+                self.selected[Pane::Sessions.index()] = 0;
+            }
+            KeyCode::Enter if self.pane == Pane::Sessions => {
+                if let Some(session) = self.selected_session() {
+                    return Action::ShowSession(session.session);
+                }
+            }
+            KeyCode::Char(key @ ('t' | 'i')) if self.pane == Pane::Sessions => {
+                let action = if key == 't' {
+                    SessionAction::Terminate
+                } else {
+                    SessionAction::Interrupt
+                };
+                if let Some(session) = self.selected_session() {
+                    self.modal = Some(Modal::Confirm {
+                        action,
+                        session: session.session,
+                        label: format!("{} ({})", session.session_id, session.user_name),
+                    });
+                }
+            }
+            _ => {}
+        }
+        Action::None
+    }
+
+    fn on_modal_key(&mut self, modal: Modal, key: KeyCode) -> Action {
+        match (modal, key) {
+            (
+                Modal::Confirm {
+                    action, session, ..
+                },
+                KeyCode::Char('y'),
+            ) => Action::Run(action, session),
+            (Modal::Confirm { .. }, KeyCode::Char('n') | KeyCode::Esc) => Action::None,
+            (Modal::Details(_), KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q')) => Action::None,
+            (modal, _) => {
+                self.modal = Some(modal);
+                Action::None
+            }
+        }
+    }
+
+    fn move_selection(&mut self, step: isize) {
+        let rows = self.rows(self.pane);
+        let selected = &mut self.selected[self.pane.index()];
+        *selected = selected
+            .saturating_add_signed(step)
+            .min(rows.saturating_sub(1));
+    }
+}
+
+/// `1536` -> `1.5 KiB`.
+pub fn human_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rac_protocol::commands::lock::LockDescr;
+
+    fn uuid(last: u8) -> Uuid16 {
+        let mut uuid = [0u8; 16];
+        uuid[15] = last;
+        uuid
+    }
+
+    fn session(id: u32, infobase: u8, cpu: u64, memory: u64) -> SessionRecord {
+        let mut record: SessionRecord = blank_session();
+        record.session = uuid(id as u8);
+        record.session_id = id;
+        record.infobase = uuid(infobase);
+        record.user_name = format!("user{id}");
+        record.cpu_time_current = cpu;
+        record.memory_current = memory;
+        record
+    }
+
+    fn blank_session() -> SessionRecord {
+        SessionRecord {
+            session: [0; 16],
+            app_id: String::new(),
+            blocked_by_dbms: 0,
+            blocked_by_ls: 0,
+            bytes_all: 0,
+            bytes_last_5min: 0,
+            calls_all: 0,
+            calls_last_5min: 0,
+            connection: [0; 16],
+            dbms_bytes_all: 0,
+            dbms_bytes_last_5min: 0,
+            db_proc_info: String::new(),
+            db_proc_took: 0,
+            db_proc_took_at: String::new(),
+            duration_all: 0,
+            duration_all_dbms: 0,
+            duration_current: 0,
+            duration_current_dbms: 0,
+            duration_last_5min: 0,
+            duration_last_5min_dbms: 0,
+            host: String::new(),
+            infobase: [0; 16],
+            last_active_at: String::new(),
+            hibernate: false,
+            passive_session_hibernate_time: 0,
+            hibernate_session_terminate_time: 0,
+            license: Default::default(),
+            locale: String::new(),
+            process: [0; 16],
+            session_id: 0,
+            started_at: String::new(),
+            user_name: String::new(),
+            memory_current: 0,
+            memory_last_5min: 0,
+            memory_total: 0,
+            read_current: 0,
+            read_last_5min: 0,
+            read_total: 0,
+            write_current: 0,
+            write_last_5min: 0,
+            write_total: 0,
+            duration_current_service: 0,
+            duration_last_5min_service: 0,
+            duration_all_service: 0,
+            current_service_name: String::new(),
+            cpu_time_current: 0,
+            cpu_time_last_5min: 0,
+            cpu_time_total: 0,
+            data_separation: String::new(),
+            client_ip: String::new(),
+        }
+    }
+
+    fn app() -> App {
+        let mut app = App::default();
+        app.update(Snapshot {
+            sessions: vec![
+                session(1, 10, 5, 300),
+                session(2, 10, 50, 100),
+                session(3, 11, 20, 200),
+            ],
+            locks: vec![LockRecordRaw {
+                connection: [0; 16],
+                descr: LockDescr {
+                    descr: "Document".to_string(),
+                    descr_flag: None,
+                },
+                locked_at: String::new(),
+                object: [0; 16],
+                session: uuid(3),
+            }],
+            infobases: vec![
+                InfobaseSummary {
+                    infobase: uuid(10),
+                    descr: String::new(),
+                    name: "trade".to_string(),
+                },
+                InfobaseSummary {
+                    infobase: uuid(11),
+                    descr: String::new(),
+                    name: "hr".to_string(),
+                },
+            ],
+            ..Snapshot::default()
+        });
+        app
+    }
+
+    #[test]
+    fn sorts_sessions_and_counts_per_infobase() {
+        let mut app = app();
+        let ids = |app: &App| {
+            app.sessions()
+                .iter()
+                .map(|s| s.session_id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(&app), [2, 3, 1]);
+        app.on_key(KeyCode::Char('m'));
+        assert_eq!(ids(&app), [1, 3, 2]);
+        assert_eq!(app.infobase_counts(), [("trade", 2, 0), ("hr", 1, 1)]);
+        assert_eq!(app.infobase_name(&uuid(11)), "hr");
+    }
+
+    #[test]
+    fn terminating_a_session_needs_confirmation() {
+        let mut app = app();
+        assert_eq!(app.on_key(KeyCode::Down), Action::None);
+        assert_eq!(app.on_key(KeyCode::Enter), Action::ShowSession(uuid(3)));
+        assert_eq!(app.on_key(KeyCode::Char('t')), Action::None);
+        assert!(matches!(
+            &app.modal,
+            Some(Modal::Confirm { label, .. }) if label == "3 (user3)"
+        ));
+        // Other keys keep the prompt open; `n` dismisses it.
+        assert_eq!(app.on_key(KeyCode::Char('q')), Action::None);
+        assert_eq!(app.on_key(KeyCode::Char('n')), Action::None);
+        assert!(app.modal.is_none());
+
+        app.on_key(KeyCode::Char('i'));
+        assert_eq!(
+            app.on_key(KeyCode::Char('y')),
+            Action::Run(SessionAction::Interrupt, uuid(3))
+        );
+        assert!(app.modal.is_none());
+        assert_eq!(app.on_key(KeyCode::Char('q')), Action::Quit);
+    }
+
+    #[test]
+    fn keeps_selection_within_the_list() {
+        let mut app = app();
+        for _ in 0..5 {
+            app.on_key(KeyCode::Down);
+        }
+        assert_eq!(app.selected(Pane::Sessions), 2);
+        app.update(Snapshot::default());
+        assert_eq!(app.selected(Pane::Sessions), 0);
+        app.on_key(KeyCode::Char('3'));
+        assert_eq!(app.pane, Pane::Locks);
+        app.on_key(KeyCode::BackTab);
+        assert_eq!(app.pane, Pane::Sessions);
+        assert_eq!(human_bytes(512), "512 B");
+        assert_eq!(human_bytes(1536), "1.5 KiB");
+    }
+}
@@ -0,0 +1,145 @@
+//! Command line of `rac_top`. Connection settings come from the flags or,
+//! for those omitted, from a profile of the `rac_lite` config file.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::Parser;
+use rac_cli::rac_lite::{default_config_path, ConfigFile, ProtocolArg};
+use rac_protocol::client::ClientConfig;
+
+#[derive(Parser, Debug)]
+#[command(name = "rac_top", version, about = "Live view of a 1C cluster")]
+pub struct Cli {
+    /// RAS address, `host:port`; the profile's when omitted.
+    pub addr: Option<String>,
+    /// Cluster name or UUID; the first cluster by default.
+    #[arg(long)]
+    pub cluster: Option<String>,
+    #[arg(long)]
+    pub cluster_user: Option<String>,
+    #[arg(long)]
+    pub cluster_pwd: Option<String>,
+    /// Seconds between refreshes.
+    #[arg(long, default_value_t = 2.0)]
+    pub interval: f64,
+    /// Deny rules for terminating and interrupting sessions; `policy.toml`
+    /// next to the rac_lite configuration by default.
+    #[arg(long)]
+    pub policy: Option<PathBuf>,
+    /// Refuse terminating and interrupting sessions.
+    #[arg(long)]
+    pub read_only: bool,
+    /// Named profile from the rac_lite config file; supplies the address,
+    /// cluster, credentials, protocol and timeouts not given here.
+    #[arg(long)]
+    pub profile: Option<String>,
+    /// Config file with profiles [default: ~/.config/rac_lite/config.toml].
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+    /// Protocol version; `auto` tries 16.0, then 11.0.
+    #[arg(long, value_enum)]
+    pub protocol: Option<ProtocolArg>,
+    #[arg(long)]
+    pub connect_timeout_ms: Option<u64>,
+    #[arg(long)]
+    pub read_timeout_ms: Option<u64>,
+    #[arg(long)]
+    pub write_timeout_ms: Option<u64>,
+}
+
+impl Cli {
+    /// Fills what the flags omit from the selected or default profile.
+    pub fn apply_profile(&mut self) -> Result<(), String> {
+        let config = match self.config.clone().or_else(default_config_path) {
+            Some(path) => ConfigFile::load(&path)?,
+            None => None,
+        };
+        let profile = match (&config, &self.profile) {
+            (Some(config), requested) => config.profile(requested.as_deref())?,
+            (None, Some(name)) => {
+                return Err(format!("profile {name} requested but no config file found"))
+            }
+            (None, None) => None,
+        };
+        let Some(profile) = profile else {
+            return Ok(());
+        };
+        self.addr = self.addr.take().or_else(|| profile.addr.clone());
+        self.cluster = self.cluster.take().or_else(|| profile.cluster.clone());
+        self.cluster_user = self
+            .cluster_user
+            .take()
+            .or_else(|| profile.cluster_user.clone());
+        if self.cluster_pwd.is_none() {
+            if let Some(secret) = &profile.cluster_pwd {
+                self.cluster_pwd = Some(secret.resolve()?);
+            }
+        }
+        self.protocol = self.protocol.or(profile.protocol);
+        self.connect_timeout_ms = self.connect_timeout_ms.or(profile.connect_timeout_ms);
+        self.read_timeout_ms = self.read_timeout_ms.or(profile.read_timeout_ms);
+        self.write_timeout_ms = self.write_timeout_ms.or(profile.write_timeout_ms);
+        Ok(())
+    }
+
+    pub fn client_cfg(&self) -> ClientConfig {
+        let mut cfg = ClientConfig {
+            read_only: self.read_only,
+            ..ClientConfig::default()
+        };
+        if let Some(protocol) = self.protocol {
+            cfg.protocol = protocol.into();
+        }
+        if let Some(ms) = self.connect_timeout_ms {
+            cfg.connect_timeout = Duration::from_millis(ms);
+        }
+        if let Some(ms) = self.read_timeout_ms {
+            cfg.read_timeout = Duration::from_millis(ms);
+        }
+        if let Some(ms) = self.write_timeout_ms {
+            cfg.write_timeout = Duration::from_millis(ms);
+        }
+        cfg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+
+    use rac_protocol::client::ProtocolPreference;
+
+    use super::*;
+
+    #[test]
+    fn profile_fills_omitted_settings() {
+        let path = env::temp_dir().join(format!("rac_top_config_{}.toml", std::process::id()));
+        fs::write(
+            &path,
+            "default_profile = \"prod\"\n\n[profiles.prod]\naddr = \"ras:1545\"\n\
+             cluster = \"Main\"\nprotocol = \"v16\"\nread_timeout_ms = 700\n\
+             cluster_user = \"admin\"\ncluster_pwd = \"secret\"\n",
+        )
+        .expect("write config");
+        let config = path.to_str().expect("path");
+
+        let mut cli = Cli::parse_from(["rac_top", "--config", config, "--cluster", "Other"]);
+        cli.apply_profile().expect("profile");
+        assert_eq!(cli.addr.as_deref(), Some("ras:1545"));
+        assert_eq!(cli.cluster.as_deref(), Some("Other"));
+        assert_eq!(cli.cluster_pwd.as_deref(), Some("secret"));
+        let cfg = cli.client_cfg();
+        assert!(matches!(cfg.protocol, ProtocolPreference::V16_0));
+        assert_eq!(cfg.read_timeout, Duration::from_millis(700));
+        assert!(!cfg.read_only);
+
+        let mut cli = Cli::parse_from(["rac_top", "--config", config, "--profile", "qa"]);
+        assert_eq!(cli.apply_profile(), Err("unknown profile: qa".to_string()));
+        let _ = fs::remove_file(path);
+
+        let cli = Cli::parse_from(["rac_top", "ras:1545", "--read-only"]);
+        assert!(cli.client_cfg().read_only);
+    }
+}
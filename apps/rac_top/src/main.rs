@@ -0,0 +1,245 @@
+mod app;
+mod cli;
+mod ui;
+
+use std::io;
+use std::process;
+use std::time::{Duration, Instant};
+
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser};
+use rac_cli::rac_lite::default_policy_path;
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
+use ratatui::DefaultTerminal;
+
+use rac_protocol::client::RacClient;
+use rac_protocol::commands::session::{SessionInterruptCurrentServerCallRpc, SessionTerminateRpc};
+use rac_protocol::commands::{
+    cluster_auth_optional, cluster_list, infobase_summary_list, lock_list, process_list,
+    session_info, session_list, SessionRecord,
+};
+use rac_protocol::error::{RacError, Result};
+use rac_protocol::policy::{self, session_target, Policy};
+use rac_protocol::resolve::Resolver;
+use rac_protocol::Uuid16;
+
+use crate::app::{Action, App, Modal, SessionAction, Snapshot};
+use crate::cli::Cli;
+
+/// Keeps one connection to RAS and reopens it after transport errors.
+struct Monitor {
+    cli: Cli,
+    addr: String,
+    client: Option<RacClient>,
+    cluster: Option<Uuid16>,
+    /// Cluster the current connection authenticated to.
+    authenticated: Option<Uuid16>,
+    /// Read on the first session action.
+    policy: Option<Policy>,
+}
+
+impl Monitor {
+    fn with_client<T>(
+        &mut self,
+        call: impl FnOnce(&mut RacClient, Uuid16, &Cli) -> Result<T>,
+    ) -> Result<T> {
+        if self.client.is_none() {
+            self.client = Some(RacClient::connect(&self.addr, self.cli.client_cfg())?);
+            self.authenticated = None;
+        }
+        let client = self.client.as_mut().expect("connected above");
+        let result = resolve_cluster(client, &mut self.cluster, &self.cli).and_then(|cluster| {
+            if self.authenticated != Some(cluster) {
+                cluster_auth_optional(
+                    client,
+                    cluster,
+                    self.cli.cluster_user.as_deref(),
+                    self.cli.cluster_pwd.as_deref(),
+                )?;
+                self.authenticated = Some(cluster);
+            }
+            call(client, cluster, &self.cli)
+        });
+        if matches!(
+            result,
+            Err(RacError::Io(_)
+                | RacError::Wire(_)
+                | RacError::Decode(_)
+                | RacError::DecodeMessage(_)
+                | RacError::UnexpectedMethod { .. })
+        ) {
+            self.client = None;
+        }
+        result
+    }
+
+    fn fetch(&mut self) -> Result<Snapshot> {
+        self.with_client(|client, cluster, _| {
+            Ok(Snapshot {
+                processes: process_list(client, cluster)?.records,
+                sessions: session_list(client, cluster)?.records,
+                locks: lock_list(client, cluster)?.records,
+                infobases: infobase_summary_list(client, cluster)?.summaries,
+            })
+        })
+    }
+
+    fn session(&mut self, session: Uuid16) -> Result<SessionRecord> {
+        self.with_client(|client, cluster, _| Ok(session_info(client, cluster, session)?.record))
+    }
+
+    fn apply(&mut self, action: SessionAction, session: Uuid16) -> Result<()> {
+        let policy = match self.policy.take() {
+            Some(policy) => policy,
+            None => match self.cli.policy.clone().or_else(default_policy_path) {
+                Some(path) => Policy::load(&path)?,
+                None => Policy::default(),
+            },
+        };
+        let result = self.with_client(|client, cluster, _| {
+            policy::enforce(client, &policy, |client| {
+                Ok(vec![session_target(
+                    client,
+                    action.command(),
+                    cluster,
+                    session,
+                )?])
+            })?;
+            let error_message = String::new();
+            match action {
+                SessionAction::Terminate => client.call_typed(SessionTerminateRpc {
+                    cluster,
+                    session,
+                    error_message,
+                })?,
+                SessionAction::Interrupt => {
+                    client.call_typed(SessionInterruptCurrentServerCallRpc {
+                        cluster,
+                        session,
+                        error_message,
+                    })?
+                }
+            };
+            Ok(())
+        });
+        self.policy = Some(policy);
+        result
+    }
+
+    fn close(self) {
+        if let Some(client) = self.client {
+            let _ = client.close();
+        }
+    }
+}
+
+fn resolve_cluster(
+    client: &mut RacClient,
+    cached: &mut Option<Uuid16>,
+    cli: &Cli,
+) -> Result<Uuid16> {
+    if let Some(cluster) = cached {
+        return Ok(*cluster);
+    }
+    let cluster = match &cli.cluster {
+        Some(input) => Resolver::default().cluster(client, input)?,
+        None => {
+            cluster_list(client)?
+                .first()
+                .ok_or_else(|| RacError::InvalidArgs("no clusters on this server".to_string()))?
+                .uuid
+        }
+    };
+    *cached = Some(cluster);
+    Ok(cluster)
+}
+
+fn main() {
+    let mut cli = Cli::parse();
+    if let Err(msg) = cli.apply_profile() {
+        Cli::command().error(ErrorKind::InvalidValue, msg).exit();
+    }
+    let Some(addr) = cli.addr.clone() else {
+        Cli::command()
+            .error(
+                ErrorKind::MissingRequiredArgument,
+                "no address: pass <ADDR> or use a profile with `addr`",
+            )
+            .exit();
+    };
+    let interval = Duration::from_secs_f64(cli.interval.max(0.2));
+    let title = match &cli.cluster {
+        Some(cluster) => format!("{addr} {cluster}"),
+        None => addr.clone(),
+    };
+    let mut monitor = Monitor {
+        cli,
+        addr,
+        client: None,
+        cluster: None,
+        authenticated: None,
+        policy: None,
+    };
+    // Report a wrong address or cluster before taking over the screen.
+    let mut app = App::default();
+    match monitor.fetch() {
+        Ok(snapshot) => app.update(snapshot),
+        Err(err) => {
+            eprintln!("Error: {err}");
+            process::exit(1);
+        }
+    }
+
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal, &mut monitor, &mut app, interval, &title);
+    ratatui::restore();
+    monitor.close();
+    if let Err(err) = result {
+        eprintln!("Error: {err}");
+        process::exit(1);
+    }
+}
+
+fn run(
+    terminal: &mut DefaultTerminal,
+    monitor: &mut Monitor,
+    app: &mut App,
+    interval: Duration,
+    title: &str,
+) -> io::Result<()> {
+    let mut next = Instant::now() + interval;
+    loop {
+        terminal.draw(|frame| ui::draw(frame, app, title))?;
+        if event::poll(next.saturating_duration_since(Instant::now()))? {
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match app.on_key(key.code) {
+                Action::None => {}
+                Action::Quit => return Ok(()),
+                Action::Refresh => next = Instant::now(),
+                Action::ShowSession(session) => match monitor.session(session) {
+                    Ok(record) => app.modal = Some(Modal::Details(Box::new(record))),
+                    Err(err) => app.status = format!("Error: {err}"),
+                },
+                Action::Run(action, session) => {
+                    app.status = match monitor.apply(action, session) {
+                        Ok(()) => action.done().to_string(),
+                        Err(err) => format!("Error: {err}"),
+                    };
+                    next = Instant::now();
+                }
+            }
+        }
+        if Instant::now() >= next {
+            match monitor.fetch() {
+                Ok(snapshot) => app.update(snapshot),
+                Err(err) => app.status = format!("Error: {err}"),
+            }
+            next = Instant::now() + interval;
+        }
+    }
+}
@@ -0,0 +1,265 @@
+//! Drawing of the dashboard; all data comes from [`App`].
+
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::style::{Modifier, Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Clear, Paragraph, Row, Table, TableState, Tabs};
+use ratatui::Frame;
+
+use rac_protocol::commands::SessionRecord;
+
+use crate::app::{human_bytes, App, Modal, Pane};
+
+const KEYS: &str = "1-4/Tab pane  j/k move  c/m/d/b sort  Enter info  t terminate  \
+                    i interrupt  r refresh  q quit";
+
+pub fn draw(frame: &mut Frame, app: &App, title: &str) {
+    let [tabs, body, footer] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    let titles = Pane::ALL
+        .iter()
+        .enumerate()
+        .map(|(index, pane)| format!("{} {}", index + 1, pane.title()));
+    frame.render_widget(
+        Tabs::new(titles)
+            .select(app.pane.index())
+            .highlight_style(Style::new().reversed()),
+        tabs,
+    );
+
+    let (header, widths, rows) = match app.pane {
+        Pane::Processes => processes(app),
+        Pane::Sessions => sessions(app),
+        Pane::Locks => locks(app),
+        Pane::Infobases => infobases(app),
+    };
+    let mut block_title = format!(" {title} ");
+    if app.pane == Pane::Sessions {
+        block_title.push_str(&format!("- sorted by {} ", app.sort.label()));
+    }
+    let table = Table::new(rows, widths)
+        .header(Row::new(header).add_modifier(Modifier::BOLD))
+        .block(Block::bordered().title(block_title))
+        .row_highlight_style(Style::new().reversed());
+    let mut state = TableState::default().with_selected(Some(app.selected(app.pane)));
+    frame.render_stateful_widget(table, body, &mut state);
+
+    let status = if app.status.is_empty() {
+        KEYS
+    } else {
+        &app.status
+    };
+    frame.render_widget(Paragraph::new(status).dim(), footer);
+
+    match &app.modal {
+        Some(Modal::Details(session)) => {
+            let lines = details(app, session);
+            let area = popup(frame.area(), 64, lines.len() as u16 + 2);
+            frame.render_widget(Clear, area);
+            frame.render_widget(
+                Paragraph::new(lines).block(Block::bordered().title(" Session (Esc to close) ")),
+                area,
+            );
+        }
+        Some(Modal::Confirm { action, label, .. }) => {
+            let text = format!("{} {label}? [y/n]", action.verb());
+            let area = popup(frame.area(), text.len() as u16 + 4, 3);
+            frame.render_widget(Clear, area);
+            frame.render_widget(
+                Paragraph::new(text).block(Block::bordered().title(" Confirm ").yellow()),
+                area,
+            );
+        }
+        None => {}
+    }
+}
+
+type Columns = (Vec<&'static str>, Vec<Constraint>, Vec<Row<'static>>);
+
+fn processes(app: &App) -> Columns {
+    let rows = app
+        .snapshot
+        .processes
+        .iter()
+        .map(|process| {
+            Row::new([
+                process.host.clone(),
+                process.port.to_string(),
+                process.pid.clone(),
+                human_bytes(u64::from(process.memory_size) * 1024),
+                format!("{:.3}", process.avg_call_time),
+                process.connections.to_string(),
+                if process.running { "yes" } else { "no" }.to_string(),
+            ])
+        })
+        .collect();
+    (
+        vec![
+            "Host", "Port", "PID", "Memory", "Avg call", "Conns", "Running",
+        ],
+        vec![
+            Constraint::Min(16),
+            Constraint::Length(6),
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Length(9),
+            Constraint::Length(6),
+            Constraint::Length(7),
+        ],
+        rows,
+    )
+}
+
+fn sessions(app: &App) -> Columns {
+    let rows = app
+        .sessions()
+        .into_iter()
+        .map(|session| {
+            Row::new([
+                session.session_id.to_string(),
+                session.user_name.clone(),
+                session.host.clone(),
+                session.app_id.clone(),
+                app.infobase_name(&session.infobase).to_string(),
+                session.cpu_time_current.to_string(),
+                human_bytes(session.memory_current),
+                session.duration_current_dbms.to_string(),
+                format!("{}/{}", session.blocked_by_ls, session.blocked_by_dbms),
+            ])
+        })
+        .collect();
+    (
+        vec![
+            "#",
+            "User",
+            "Host",
+            "App",
+            "Infobase",
+            "CPU ms",
+            "Memory",
+            "DBMS ms",
+            "Blk ls/db",
+        ],
+        vec![
+            Constraint::Length(6),
+            Constraint::Min(12),
+            Constraint::Min(10),
+            Constraint::Length(14),
+            Constraint::Min(10),
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Length(8),
+            Constraint::Length(9),
+        ],
+        rows,
+    )
+}
+
+fn locks(app: &App) -> Columns {
+    let rows = app
+        .snapshot
+        .locks
+        .iter()
+        .map(|lock| {
+            let session = app.session(&lock.session);
+            Row::new([
+                session.map_or("-".to_string(), |s| s.session_id.to_string()),
+                session.map_or(String::new(), |s| s.user_name.clone()),
+                session.map_or(String::new(), |s| {
+                    app.infobase_name(&s.infobase).to_string()
+                }),
+                lock.locked_at.clone(),
+                lock.descr.descr.clone(),
+            ])
+        })
+        .collect();
+    (
+        vec!["Session", "User", "Infobase", "Locked at", "Description"],
+        vec![
+            Constraint::Length(7),
+            Constraint::Length(16),
+            Constraint::Length(16),
+            Constraint::Length(19),
+            Constraint::Min(20),
+        ],
+        rows,
+    )
+}
+
+fn infobases(app: &App) -> Columns {
+    let rows = app
+        .infobase_counts()
+        .into_iter()
+        .map(|(name, sessions, locks)| {
+            Row::new([name.to_string(), sessions.to_string(), locks.to_string()])
+        })
+        .collect();
+    (
+        vec!["Infobase", "Sessions", "Locks"],
+        vec![
+            Constraint::Min(20),
+            Constraint::Length(8),
+            Constraint::Length(8),
+        ],
+        rows,
+    )
+}
+
+fn details(app: &App, session: &SessionRecord) -> Vec<Line<'static>> {
+    let fields = [
+        ("session", session.session_id.to_string()),
+        ("user", session.user_name.clone()),
+        ("infobase", app.infobase_name(&session.infobase).to_string()),
+        ("app", session.app_id.clone()),
+        ("host", session.host.clone()),
+        ("client ip", session.client_ip.clone()),
+        ("started at", session.started_at.clone()),
+        ("last active", session.last_active_at.clone()),
+        ("service", session.current_service_name.clone()),
+        (
+            "cpu ms",
+            format!(
+                "{} (total {})",
+                session.cpu_time_current, session.cpu_time_total
+            ),
+        ),
+        (
+            "memory",
+            format!(
+                "{} (total {})",
+                human_bytes(session.memory_current),
+                human_bytes(session.memory_total)
+            ),
+        ),
+        ("call ms", session.duration_current.to_string()),
+        ("dbms ms", session.duration_current_dbms.to_string()),
+        ("db proc", session.db_proc_info.clone()),
+        (
+            "blocked by",
+            format!(
+                "ls {}, dbms {}",
+                session.blocked_by_ls, session.blocked_by_dbms
+            ),
+        ),
+        ("hibernate", session.hibernate.to_string()),
+    ];
+    fields
+        .into_iter()
+        .map(|(name, value)| Line::from(format!("{name:>12}: {value}")))
+        .collect()
+}
+
+fn popup(area: Rect, width: u16, height: u16) -> Rect {
+    let [area] = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [area] = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .areas(area);
+    area
+}
@@ -65,7 +65,7 @@ impl SessionLogger {
         let mut state = self
             .state
             .lock()
-            .map_err(|_| io::Error::new(io::ErrorKind::Other, "logger mutex poisoned"))?;
+            .map_err(|_| io::Error::other("logger mutex poisoned"))?;
 
         let exchange_id = match direction {
             Direction::ClientToServer => {
@@ -94,7 +94,7 @@ impl SessionLogger {
         let mut state = self
             .state
             .lock()
-            .map_err(|_| io::Error::new(io::ErrorKind::Other, "logger mutex poisoned"))?;
+            .map_err(|_| io::Error::other("logger mutex poisoned"))?;
         state.event_id += 1;
         let event_id = state.event_id;
         drop(state);
@@ -395,10 +395,10 @@ fn run(args: Args) -> io::Result<()> {
 
     let bytes_c2s = t1
         .join()
-        .map_err(|_| io::Error::new(io::ErrorKind::Other, "c2s thread panicked"))??;
+        .map_err(|_| io::Error::other("c2s thread panicked"))??;
     let bytes_s2c = t2
         .join()
-        .map_err(|_| io::Error::new(io::ErrorKind::Other, "s2c thread panicked"))??;
+        .map_err(|_| io::Error::other("s2c thread panicked"))??;
 
     write_session_info(
         &logger,
@@ -8,6 +8,7 @@ except ModuleNotFoundError:  # Python < 3.11
     _TOML_AVAILABLE = False
 
 from .schema import (
+    MUTATIONS,
     FieldSpec,
     RecordSpec,
     RequestSpec,
@@ -133,6 +134,9 @@ def parse_schema_payload(
         if raw_version is None:
             raise ValueError(f"rpc {name} missing version")
         rpc_version = parse_version_range(str(raw_version))
+        mutation = spec.get("mutation")
+        if mutation not in MUTATIONS:
+            raise ValueError(f"rpc {name} mutation must be one of {', '.join(MUTATIONS)}")
         tests = []
         for raw in spec.get("tests", []):
             tests.append(
@@ -179,6 +183,7 @@ def parse_schema_payload(
                 request_inline=request_inline,
                 method_req=int(spec.get("method_req")),
                 method_resp=spec.get("method_resp"),
+                mutation=str(mutation),
                 requires_cluster_context=bool(spec.get("requires_cluster_context", False)),
                 requires_infobase_context=bool(spec.get("requires_infobase_context", False)),
                 tests=tests,
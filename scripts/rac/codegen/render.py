@@ -1,6 +1,6 @@
 from typing import Any, Dict, List, Optional
 
-from .schema import RecordSpec, RequestSpec, ResponseSpec, RpcSpec, Version
+from .schema import FieldSpec, RecordSpec, RequestSpec, ResponseSpec, RpcSpec, Version
 from .rust_types import (
     decode_expr,
     needs_datetime,
@@ -9,6 +9,7 @@ from .rust_types import (
     needs_uuid,
     request_encoded_len,
     request_encode_expr,
+    request_literal_bytes,
     request_rust_type,
     request_uses,
     rust_type,
@@ -21,6 +22,7 @@ def generate(
     responses: List[ResponseSpec],
     rpcs: List[RpcSpec],
     extra_uses: Optional[List[str]] = None,
+    object_name: Optional[str] = None,
 ) -> str:
     lines: List[str] = []
     uses = ["use crate::codec::RecordCursor;", "use crate::error::Result;"]
@@ -33,6 +35,7 @@ def generate(
         uses.insert(0, "use crate::Uuid16;")
     uses.append("use serde::Serialize;")
     if rpcs:
+        uses.append("use serde::Deserialize;")
         req_specs = collect_request_specs(rpcs, requests)
         extra_req_uses = request_uses(req_specs)
         for item in extra_req_uses:
@@ -162,7 +165,9 @@ def generate(
         lines.append("")
 
     if rpcs:
-        lines.extend(generate_rpc_metadata(rpcs))
+        lines.extend(generate_rpc_metadata(object_name, rpcs))
+        if object_name:
+            lines.extend(generate_command_table(object_name, rpcs, requests))
         lines.append("")
 
     return "\n".join(lines).rstrip() + "\n"
@@ -200,6 +205,14 @@ def render_version_guard(
     return f"{protocol_var} >= {start} && {protocol_var} < {end}"
 
 
+def render_version_reject(version_range: "VersionRange", protocol_var: str) -> str:
+    start = protocol_version_const(version_range.start)
+    if version_range.end is None:
+        return f"{protocol_var} < {start}"
+    end = protocol_version_const(version_range.end)
+    return f"{protocol_var} < {start} || {protocol_var} >= {end}"
+
+
 def emit_decode_statement(
     lines: List[str], var_name: str, expr: List[str], indent: str
 ) -> None:
@@ -361,7 +374,9 @@ def generate_response_structs(
                 f"        let record = parse_{snake_case(resp.name)}_body(body, protocol_version)?;"
             )
             lines.append("        Ok(Self {")
-            if field_spec is None:
+            if field_spec is None and field_name == "record":
+                lines.append("            record,")
+            elif field_spec is None:
                 lines.append(f"            {field_name}: record,")
             else:
                 lines.append(f"            {field_name}: record.{field_name},")
@@ -478,10 +493,11 @@ def generate_response_tests(responses: List[ResponseSpec]) -> List[str]:
     return lines
 
 
-def generate_rpc_metadata(rpcs: List[RpcSpec]) -> List[str]:
+def generate_rpc_metadata(object_name: Optional[str], rpcs: List[RpcSpec]) -> List[str]:
     lines: List[str] = []
     for rpc in rpcs:
         const_name = f"RPC_{snake_case(rpc.name).upper()}_META"
+        name = command_name(object_name, rpc.name) if object_name else snake_case(rpc.name)
         method_req = rpc_method_req_const(rpc.name)
         method_resp = (
             "None"
@@ -489,8 +505,10 @@ def generate_rpc_metadata(rpcs: List[RpcSpec]) -> List[str]:
             else f"Some({rpc_method_resp_const(rpc.name)})"
         )
         lines.append(f"pub const {const_name}: crate::rpc::Meta = crate::rpc::Meta {{")
+        lines.append(f"    name: \"{name}\",")
         lines.append(f"    method_req: {method_req},")
         lines.append(f"    method_resp: {method_resp},")
+        lines.append(f"    mutation: crate::rpc::Mutation::{rpc.mutation.capitalize()},")
         lines.append(f"    requires_cluster_context: {str(rpc.requires_cluster_context).lower()},")
         lines.append(f"    requires_infobase_context: {str(rpc.requires_infobase_context).lower()},")
         lines.append("};")
@@ -598,11 +616,7 @@ def generate_rpc_section(rpcs: List[RpcSpec], requests: List[RequestSpec]) -> Li
     emitted_requests = set()
 
     for rpc in rpcs:
-        req_spec = None
-        if rpc.request_inline is not None:
-            req_spec = rpc.request_inline
-        elif rpc.request:
-            req_spec = request_map.get(rpc.request)
+        req_spec = rpc_request_spec(rpc, request_map)
         if req_spec and rpc.request_inline is None and req_spec.name not in emitted_requests:
             lines.extend(render_request(req_spec))
             lines.append("")
@@ -610,16 +624,14 @@ def generate_rpc_section(rpcs: List[RpcSpec], requests: List[RequestSpec]) -> Li
         if not rpc.response:
             continue
         struct_name = f"{rpc.name}Rpc"
-        fields = []
-        if req_spec:
-            for field in req_spec.fields:
-                if field.skip or field.literal is not None:
-                    continue
-                fields.append((field.name, request_rust_type(field)))
+        fields = rpc_fields(req_spec)
 
+        lines.append("#[derive(Deserialize)]")
         if fields:
             lines.append(f"pub struct {struct_name} {{")
             for name, ty in fields:
+                if ty == "Uuid16":
+                    lines.append('    #[serde(deserialize_with = "crate::registry::uuid_arg")]')
                 lines.append(f"    pub {name}: {ty},")
             lines.append("}")
         else:
@@ -650,7 +662,7 @@ def generate_rpc_section(rpcs: List[RpcSpec], requests: List[RequestSpec]) -> Li
         )
         lines.append("        let protocol_version = _codec.protocol_version();")
         lines.append(
-            f"        if !({render_version_guard(rpc.version, None, 'protocol_version')}) {{"
+            f"        if {render_version_reject(rpc.version, 'protocol_version')} {{"
         )
         lines.append(
             f"            return Err(RacError::Unsupported(\"rpc {rpc.name} unsupported for protocol\"));"
@@ -695,6 +707,112 @@ def generate_rpc_section(rpcs: List[RpcSpec], requests: List[RequestSpec]) -> Li
     return lines
 
 
+def rpc_fields(req_spec: Optional[RequestSpec]) -> List[tuple]:
+    fields = []
+    if req_spec:
+        for field in req_spec.fields:
+            if field.skip or field.literal is not None:
+                continue
+            fields.append((field.name, request_rust_type(field)))
+    return fields
+
+
+def rpc_request_spec(rpc: RpcSpec, request_map: Dict[str, RequestSpec]) -> Optional[RequestSpec]:
+    if rpc.request_inline is not None:
+        return rpc.request_inline
+    if rpc.request:
+        return request_map.get(rpc.request)
+    return None
+
+
+ARG_KINDS = {
+    "Uuid16": "Uuid",
+    "String": "String",
+    "bool": "Bool",
+    "u8": "U8",
+    "u16": "U16",
+    "u32": "U32",
+    "u64": "U64",
+}
+
+
+WIRE_TYPES = {
+    "uuid": "Uuid",
+    "str8": "Str8",
+    "str_u14": "StrU14",
+    "u8": "U8",
+    "bool": "Bool",
+    "u16_be": "U16Be",
+    "u32_be": "U32Be",
+    "u64_be": "U64Be",
+}
+
+
+def wire_type(field: FieldSpec) -> str:
+    if field.type_name == "bytes_fixed":
+        length = len(request_literal_bytes(field)) if field.literal is not None else field.length
+        return f"Bytes({length})"
+    kind = WIRE_TYPES.get(field.type_name)
+    if kind is None:
+        raise ValueError(f"field {field.name}: unsupported wire type {field.type_name}")
+    return kind
+
+
+def command_name(object_name: str, rpc_name: str) -> str:
+    snake = snake_case(rpc_name)
+    prefix = f"{object_name}_"
+    if snake.startswith(prefix):
+        return f"{object_name}.{snake[len(prefix):]}"
+    return f"{object_name}.{snake}"
+
+
+def generate_command_table(
+    object_name: str, rpcs: List[RpcSpec], requests: List[RequestSpec]
+) -> List[str]:
+    request_map = {req.name: req for req in requests}
+    lines = ["pub const COMMANDS: &[crate::registry::CommandSpec] = &["]
+    for rpc in rpcs:
+        if not rpc.response:
+            continue
+        struct_name = f"{rpc.name}Rpc"
+        req_spec = rpc_request_spec(rpc, request_map)
+        fields = rpc_fields(req_spec)
+        lines.append("    crate::registry::CommandSpec {")
+        lines.append(f"        name: \"{command_name(object_name, rpc.name)}\",")
+        lines.append(f"        request: \"{struct_name}\",")
+        lines.append(f"        response: \"{rpc.response}\",")
+        if fields:
+            lines.append("        args: &[")
+            for name, ty in fields:
+                kind = ARG_KINDS.get(ty)
+                if kind is None:
+                    raise ValueError(f"rpc {rpc.name} field {name}: unsupported arg type {ty}")
+                lines.append(
+                    f"            crate::registry::ArgSpec {{ name: \"{name}\", "
+                    f"kind: crate::registry::ArgKind::{kind} }},"
+                )
+            lines.append("        ],")
+        else:
+            lines.append("        args: &[],")
+        wire = [field for field in req_spec.fields if not field.skip] if req_spec else []
+        if wire:
+            lines.append("        wire: &[")
+            for field in wire:
+                lines.append(
+                    f"            crate::registry::WireField {{ name: \"{field.name}\", "
+                    f"ty: crate::registry::WireType::{wire_type(field)} }},"
+                )
+            lines.append("        ],")
+        else:
+            lines.append("        wire: &[],")
+        lines.append(f"        meta: RPC_{snake_case(rpc.name).upper()}_META,")
+        lines.append(f"        validate: crate::registry::validate::<{struct_name}>,")
+        lines.append(f"        call: crate::registry::call::<{struct_name}>,")
+        lines.append("    },")
+    lines.append("];")
+    return lines
+
+
 def render_request(req: RequestSpec) -> List[str]:
     lines: List[str] = []
     derive = ", ".join(req.derives)
@@ -1,6 +1,9 @@
 from dataclasses import dataclass
 from typing import Any, Dict, List, Optional
 
+# Effect of an RPC on cluster state, see `rpc::Mutation`.
+MUTATIONS = ("read", "modify", "destructive")
+
 
 @dataclass(frozen=True, order=True)
 class Version:
@@ -75,6 +78,7 @@ class RpcSpec:
     request_inline: Optional[RequestSpec]
     method_req: int
     method_resp: Optional[int]
+    mutation: str
     requires_cluster_context: bool
     requires_infobase_context: bool
     tests: List[RpcTestSpec]
@@ -3,7 +3,7 @@ from __future__ import annotations
 
 import argparse
 import re
-from dataclasses import dataclass
+from dataclasses import dataclass, replace
 from pathlib import Path
 from typing import Any, Dict, List, Optional
 
@@ -75,6 +75,7 @@ class RecordSpec:
     list_specs: List[ListSpec]
     lines: List[LineSpec]
     label_align: Optional[int]
+    rac_lines: List[LineSpec]
 
 
 def snake_to_pascal(value: str) -> str:
@@ -196,6 +197,9 @@ def parse_records(
         else:
             raise ValueError(f"invalid label_align for {base}")
         lines = parse_lines(raw.get("lines", []) or [])
+        rac_lines = parse_lines(raw.get("rac", []) or [])
+        if any(line.call for line in rac_lines):
+            raise ValueError(f"record {base}: rac lines do not support call")
 
         list_specs: List[ListSpec] = []
         if raw.get("lists"):
@@ -222,6 +226,7 @@ def parse_records(
             list_specs=list_specs,
             lines=lines,
             label_align=label_align,
+            rac_lines=rac_lines,
         )
         apply_label_align(record)
         apply_default_formats(record, record_field_types)
@@ -428,7 +433,7 @@ def infer_format_name(
 
 
 def apply_default_formats(record: RecordSpec, record_field_types: Dict[str, Dict[str, str]]) -> None:
-    for line in record.lines:
+    for line in record.lines + record.rac_lines:
         if line.call or line.fmt or line.format_name:
             continue
         value = line.value or ""
@@ -476,6 +481,9 @@ def build_fmt_and_args(line: LineSpec, label_align: Optional[int]) -> tuple[str,
     elif format_name == "yes_no":
         fmt = "{}"
         args = [f"yes_no({value})"]
+    elif format_name == "bit":
+        fmt = "{}"
+        args = [f"u8::from({value})"]
     elif format_name == "quoted":
         fmt = "\"{}\""
         args = [value]
@@ -522,6 +530,45 @@ def emit_line(buf: List[str], line: LineSpec, indent: str, label_align: Optional
         buf.append(f"{indent}outln!(out, {fmt_literal}{args});")
 
 
+def rac_field_expr(line: LineSpec) -> str:
+    if line.fmt:
+        if ": " not in line.fmt:
+            raise ValueError(f"rac fmt needs a label: {line.fmt}")
+        label, fmt = line.fmt.split(": ", 1)
+        args_list = line.args
+    else:
+        label = line.label or ""
+        fmt, args_list = build_fmt_and_args(replace(line, label=None), None)
+    if fmt == "{}" and len(args_list) == 1:
+        value = f"{args_list[0]}.to_string()"
+    else:
+        args = ", " + ", ".join(args_list) if args_list else ""
+        value = f"format!({rust_string_literal(fmt)}{args})"
+    return f"({rust_string_literal(label)}, {value})"
+
+
+def emit_rac_fields(buf: List[str], fn_name: str, type_name: str, lines: List[LineSpec]) -> None:
+    leading = 0
+    while leading < len(lines) and not lines[leading].optional:
+        leading += 1
+    binding = "let mut fields" if leading < len(lines) else "let fields"
+    buf.append(f"fn {fn_name}(item: &{type_name}) -> RacFields {{")
+    buf.append(f"    {binding}: RacFields = vec![")
+    for line in lines[:leading]:
+        buf.append(f"        {rac_field_expr(line)},")
+    buf.append("    ];")
+    for line in lines[leading:]:
+        push = f"fields.push({rac_field_expr(line)});"
+        if line.optional:
+            buf.append(f"    if let Some({line.optional.var}) = {line.optional.expr} {{")
+            buf.append(f"        {push}")
+            buf.append("    }")
+        else:
+            buf.append(f"    {push}")
+    buf.append("    fields")
+    buf.append("}")
+
+
 def generate(records: List[RecordSpec], schema_path: Path) -> str:
     out: List[str] = []
     out.append("// @generated by scripts/rac/console_output_codegen.py. DO NOT EDIT.")
@@ -535,6 +582,13 @@ def generate(records: List[RecordSpec], schema_path: Path) -> str:
         render_fn = f"render_{base}_info"
         type_name = record.type_name
 
+        rac_fn = f"rac_{base}_fields"
+        if record.rac_lines:
+            emit_rac_fields(out, rac_fn, type_name, record.rac_lines)
+            out.append("")
+        if not record.lines:
+            continue
+
         out.append(f"pub struct {info_struct}<'a> {{")
         out.append(f"    item: &'a {type_name},")
         out.append("}")
@@ -550,6 +604,10 @@ def generate(records: List[RecordSpec], schema_path: Path) -> str:
         out.append("")
         out.append(f"impl Display for {info_struct}<'_> {{")
         out.append("    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {")
+        if record.rac_lines:
+            out.append("        if f.alternate() {")
+            out.append(f"            return write_rac_blocks(f, [{rac_fn}(self.item)]);")
+            out.append("        }")
         out.append("        let mut out = String::new();")
         out.append(f"        {render_fn}(&mut out, self.item);")
         out.append("        write_trimmed(f, &out)")
@@ -570,6 +628,10 @@ def generate(records: List[RecordSpec], schema_path: Path) -> str:
             out.append("")
             out.append(f"impl Display for {list_spec.struct_name}<'_> {{")
             out.append("    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {")
+            if record.rac_lines:
+                out.append("        if f.alternate() {")
+                out.append(f"            return write_rac_blocks(f, self.items.iter().map({rac_fn}));")
+                out.append("        }")
             if list_spec.style == "blocks":
                 out.append("        let mut out = String::new();")
                 out.append("        for (idx, item) in self.items.iter().enumerate() {")
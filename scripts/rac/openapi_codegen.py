@@ -0,0 +1,171 @@
+#!/usr/bin/env python3
+from __future__ import annotations
+
+import argparse
+from pathlib import Path
+from typing import Dict, List, Tuple
+
+from codegen.parse import parse_schema
+from codegen.schema import FieldSpec, RecordSpec, ResponseSpec
+
+
+ROOT = Path(__file__).resolve().parents[2]
+DEFAULT_SCHEMA_DIR = ROOT / "schemas" / "rac"
+DEFAULT_OUT = ROOT / "apps" / "rac_rest" / "src" / "openapi_generated.rs"
+
+STRING_TYPES = {
+    "str",
+    "str8",
+    "str8_opt",
+    "str8_default",
+    "str8_flagged",
+    "str_len_u8",
+    "str_len_u8_or_2c",
+    "str_u14",
+}
+DATETIME_TYPES = {"datetime_u64_be", "datetime_u64_be_opt", "datetime_u64_be_default"}
+UUID_TYPES = {"uuid", "uuid_opt", "uuid_default"}
+BOOL_TYPES = {
+    "bool",
+    "bool_default",
+    "bool_opt",
+    "u8_bool",
+    "u16_be_bool",
+    "u32_be_bool",
+}
+U8_TYPES = {"u8", "u8_opt"}
+U16_TYPES = {"u16_be", "u16_le"}
+U32_TYPES = {"u24_be", "u32_be", "u32_le", "u32_be_opt", "u32_be_default"}
+U64_TYPES = {"u64_be", "u64_be_opt", "u64_be_default"}
+
+
+def field_kind(record: str, field: FieldSpec) -> str:
+    t = field.type_name
+    if field.rust_type == "bool" or t in BOOL_TYPES:
+        return "FieldKind::Bool"
+    if t in UUID_TYPES:
+        return "FieldKind::Uuid"
+    if t in DATETIME_TYPES:
+        return "FieldKind::DateTime"
+    if t in STRING_TYPES:
+        return "FieldKind::String"
+    if t in U8_TYPES:
+        return "FieldKind::U8"
+    if t in U16_TYPES:
+        return "FieldKind::U16"
+    if t in U32_TYPES:
+        return "FieldKind::U32"
+    if t in U64_TYPES:
+        return "FieldKind::U64"
+    if t == "f64_be":
+        return "FieldKind::F64"
+    if t == "bytes_fixed" and field.length == 16:
+        # rac_rest renders every 16-byte array as a UUID string.
+        return "FieldKind::Uuid"
+    if t in {"bytes", "bytes_fixed"}:
+        return "FieldKind::Bytes"
+    if t == "list_str8_rest":
+        return "FieldKind::StringList"
+    if t == "lock_descr":
+        return 'FieldKind::Record("LockDescr")'
+    if t in {"record", "record_u8_first"}:
+        return f'FieldKind::Record("{field.item}")'
+    if t == "list_u8":
+        return f'FieldKind::List("{field.item}")'
+    raise ValueError(f"record {record} field {field.name}: unsupported type {t}")
+
+
+def load_schemas(schema_dir: Path) -> Tuple[List[RecordSpec], List[ResponseSpec]]:
+    records: List[RecordSpec] = []
+    responses: List[ResponseSpec] = []
+    for path in sorted(schema_dir.glob("*.toml")):
+        if path.name == "console_output.toml":
+            continue
+        file_records, _requests, _rpcs, file_responses = parse_schema(path)
+        records.extend(file_records)
+        responses.extend(file_responses)
+    return records, responses
+
+
+def generate(records: List[RecordSpec], responses: List[ResponseSpec], schema_dir: Path) -> str:
+    try:
+        source = schema_dir.relative_to(ROOT)
+    except ValueError:
+        source = schema_dir
+    out: List[str] = []
+    out.append("// @generated by scripts/rac/openapi_codegen.py. DO NOT EDIT.")
+    out.append(f"// source: {source.as_posix()}")
+    out.append("")
+    out.append("pub const RECORD_SCHEMAS: &[RecordSchema] = &[")
+    seen: Dict[str, None] = {}
+    for record in records:
+        if record.name in seen:
+            raise ValueError(f"duplicate record {record.name}")
+        seen[record.name] = None
+        out.append("    RecordSchema {")
+        out.append(f'        name: "{record.name}",')
+        out.append("        fields: &[")
+        for field in record.fields:
+            if field.skip:
+                continue
+            kind = field_kind(record.name, field)
+            optional = "true" if field.optional else "false"
+            out.append(
+                f'            FieldSchema {{ name: "{field.name}", kind: {kind}, optional: {optional} }},'
+            )
+        out.append("        ],")
+        out.append("    },")
+    out.append("];")
+    out.append("")
+    out.append("pub const RESPONSE_SCHEMAS: &[ResponseSchema] = &[")
+    record_map = {record.name: record for record in records}
+    for resp in responses:
+        body = resp.body
+        if not body.make_struct or not body.field_name or not body.item:
+            continue
+        if body.type_name == "list_u8":
+            kind = f'FieldKind::List("{body.item}")'
+        elif body.type_name == "record":
+            # Mirrors render.generate_response_structs: a record holding a field
+            # named like the response field is unwrapped to that field.
+            kind = f'FieldKind::Record("{body.item}")'
+            record = record_map.get(body.item)
+            if record:
+                for field in record.fields:
+                    if not field.skip and field.name == body.field_name:
+                        kind = field_kind(record.name, field)
+                        break
+        else:
+            raise ValueError(f"response {resp.name}: unsupported body type {body.type_name}")
+        out.append(
+            f'    ResponseSchema {{ name: "{resp.name}Resp", field: "{body.field_name}", kind: {kind} }},'
+        )
+    out.append("];")
+    return "\n".join(out) + "\n"
+
+
+def main() -> int:
+    parser = argparse.ArgumentParser(description="Generate rac_rest OpenAPI component schemas")
+    parser.add_argument(
+        "--schema-dir",
+        default=str(DEFAULT_SCHEMA_DIR),
+        help="Directory with RAC protocol schemas",
+    )
+    parser.add_argument("--out", default=str(DEFAULT_OUT), help="Output .rs file path")
+    args = parser.parse_args()
+
+    schema_dir = Path(args.schema_dir)
+    if not schema_dir.is_absolute():
+        schema_dir = (ROOT / schema_dir).resolve()
+    records, responses = load_schemas(schema_dir)
+
+    out_path = Path(args.out)
+    if not out_path.is_absolute():
+        out_path = (ROOT / out_path).resolve()
+    out_path.parent.mkdir(parents=True, exist_ok=True)
+    out_path.write_text(generate(records, responses, schema_dir))
+    return 0
+
+
+if __name__ == "__main__":
+    raise SystemExit(main())
@@ -21,7 +21,7 @@ def main() -> int:
 
     records, requests, rpcs, responses = parse_schema(schema_path)
     extra_uses = request_uses(requests) if requests else None
-    output = generate(records, requests, responses, rpcs, extra_uses)
+    output = generate(records, requests, responses, rpcs, extra_uses, schema_path.stem)
     if responses:
         output = output + "\n" + "\n".join(generate_response_tests(responses)).rstrip() + "\n"
 